@@ -0,0 +1,36 @@
+use std::collections::HashSet;
+
+#[derive(Debug, Default)]
+pub struct Difference<'a, 'b> {
+    first_only: Vec<&'a str>,
+    second_only: Vec<&'b str>,
+}
+
+pub fn find_difference<'a, 'b>(sentence1: &'a str, sentence2: &'b str) -> Difference<'a, 'b> {
+    let sentence_1_words: HashSet<&str> = sentence1.split(" ").collect();
+    let sentence_2_words: HashSet<&str> = sentence2.split(" ").collect();
+
+    let mut diff = Difference::default();
+
+    for word in &sentence_1_words {
+        if !sentence_2_words.contains(word) {
+            diff.first_only.push(word)
+        }
+    }
+
+    for word in &sentence_2_words {
+        if !sentence_1_words.contains(word) {
+            diff.second_only.push(word)
+        }
+    }
+
+    diff.first_only.sort();
+    diff.second_only.sort();
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
@@ -0,0 +1,223 @@
+//! This is the exhaustive engine on its own, so that it can be driven one
+//! frame at a time. `Matcher::match_string_exhaustive` runs an `Engine` to
+//! the end without stopping, but stepping through one by hand shows how the
+//! stack of frames stands in for the calls a recursive search would make.
+
+use super::{Arena, OutputData, OutputFrame, Stack, StackedFrame};
+use crate::{Matcher, MatcherToken};
+use alloc::vec::Vec;
+use require_lifetimes::require_lifetimes;
+
+/// This is a search for the best way of matching some tokens against the
+/// start of a candidate string, trying every option of every group.
+///
+/// The tokens are borrowed for `'tokens`, the pattern they were parsed from
+/// for `'pattern`, and the candidate for `'candidate`. Each of those can end
+/// at a different time, which is why they're kept apart.
+pub struct Engine<'tokens, 'pattern, 'candidate> {
+    tokens: &'tokens [MatcherToken<'pattern>],
+    candidate: &'candidate str,
+    stack: Stack,
+    arena: Arena<'tokens>,
+    root_output_data: OutputData,
+}
+
+/// This is what `Engine::stack` shows of a frame. Every frame is about a
+/// branch of the search, which is an option chosen for the group at
+/// `token_index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackFrame<'candidate> {
+    /// This branch hasn't been explored yet. `remaining` is what's left of
+    /// the candidate after the option.
+    Input {
+        token_index: usize,
+        chosen_option: &'candidate str,
+        remaining: &'candidate str,
+    },
+    /// This branch has matched `matched_tokens_count` tokens (starting with
+    /// the option), and is waiting for the branches of the next group to
+    /// finish. `best_option` is the option of whichever of those has done
+    /// best so far.
+    Output {
+        token_index: usize,
+        chosen_option: &'candidate str,
+        matched_tokens_count: usize,
+        best_option: Option<&'candidate str>,
+    },
+}
+
+#[require_lifetimes]
+impl<'tokens, 'pattern, 'candidate> Engine<'tokens, 'pattern, 'candidate> {
+    /// This starts a search of `candidate`: it matches the tokens before the
+    /// first group, and pushes a frame for every option of that group which
+    /// the rest of the candidate starts with. Nothing else is explored until
+    /// `step` or `run` is called.
+    pub fn new(
+        tokens: &'tokens [MatcherToken<'pattern>],
+        candidate: &'candidate str,
+    ) -> Engine<'tokens, 'pattern, 'candidate> {
+        Self::with_arena(tokens, candidate, Arena::new())
+    }
+
+    /// This does the same thing as `new`, but skips branches too short to be
+    /// complete matches once one has been found. `min_remaining_len` has, for
+    /// every token index, the fewest bytes that the tokens from that index
+    /// onwards can match.
+    pub(crate) fn with_pruning(
+        tokens: &'tokens [MatcherToken<'pattern>],
+        min_remaining_len: &'tokens [usize],
+        candidate: &'candidate str,
+    ) -> Engine<'tokens, 'pattern, 'candidate> {
+        Self::with_arena(tokens, candidate, Arena::with_pruning(min_remaining_len))
+    }
+
+    fn with_arena(
+        tokens: &'tokens [MatcherToken<'pattern>],
+        candidate: &'candidate str,
+        arena: Arena<'tokens>,
+    ) -> Engine<'tokens, 'pattern, 'candidate> {
+        let mut arena = arena;
+        let mut stack = Stack::default();
+        let root_output_data =
+            Matcher::process_root_input(tokens, candidate, &mut stack, &mut arena);
+
+        Engine {
+            tokens,
+            candidate,
+            stack,
+            arena,
+            root_output_data,
+        }
+    }
+
+    /// This pops the frame on top of the stack and processes it. An input
+    /// frame matches as many tokens as it can, then pushes an output frame
+    /// and an input frame for each option of the next group. An output frame
+    /// has had all of those finish, so it adds the best of them to its own
+    /// tokens and offers the result to the output it branched from.
+    ///
+    /// It returns false, without doing anything, once the stack is empty.
+    pub fn step<'a>(&'a mut self) -> bool {
+        Matcher::explore_step(
+            self.tokens,
+            self.candidate,
+            &mut self.stack,
+            &mut self.root_output_data,
+            &mut self.arena,
+            &mut (),
+        )
+    }
+
+    /// This steps until the stack is empty, and returns the best match: every
+    /// token that matched, and the text it matched.
+    pub fn run(mut self) -> Vec<(&'tokens MatcherToken<'pattern>, &'candidate str)> {
+        while self.step() {}
+        Matcher::finish_output(&mut self.root_output_data, &mut self.arena);
+
+        let tokens = self.tokens;
+        let mut matched_tokens = Vec::new();
+        self.arena.visit(
+            self.candidate,
+            self.root_output_data.segment_index,
+            &mut |index, text| {
+                matched_tokens.push((&tokens[index], text));
+            },
+        );
+        matched_tokens
+    }
+
+    /// This shows every frame on the stack, from the bottom up, so the last
+    /// one is what `step` processes next.
+    pub fn stack<'a>(&'a self) -> impl Iterator<Item = StackFrame<'candidate>> + 'a {
+        self.stack.frames().map(|frame| match frame {
+            StackedFrame::Input(input_data) => StackFrame::Input {
+                token_index: input_data.group_index,
+                chosen_option: &self.candidate[input_data.chosen_option.clone()],
+                remaining: &self.candidate[input_data.chosen_option.end..],
+            },
+            StackedFrame::Output(OutputFrame { output_data, .. }) => {
+                let (token_index, chosen_option) = self
+                    .arena
+                    .branch_of(self.candidate, output_data.segment_index);
+                StackFrame::Output {
+                    token_index,
+                    chosen_option,
+                    matched_tokens_count: output_data.matched_tokens_count,
+                    best_option: output_data
+                        .best_current_segment_index
+                        .map(|segment_index| self.arena.branch_of(self.candidate, segment_index).1),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Engine, StackFrame};
+    use crate::Matcher;
+
+    #[test]
+    fn running_an_engine_matches_exhaustively() {
+        let fixtures = [
+            ("abc(d|e|f).", "abcge"),
+            ("abc(d|e|f).", "abcd💪"),
+            ("(aba|abac).(aba|abac).", "abacabacd"),
+            ("(ab|a)(ba|b)(a|ab)c", "ababc"),
+            ("abc", "abd"),
+            ("", ""),
+        ];
+
+        for (match_string, candidate) in fixtures {
+            // Engines made straight from tokens don't prune anything, which
+            // mustn't change what they find.
+            let matcher = Matcher::new(match_string).unwrap();
+            let engine = Engine::new(&matcher.tokens, candidate);
+            let result = engine.run();
+
+            let mut other_matcher = Matcher::new(match_string).unwrap();
+            assert_eq!(
+                result,
+                other_matcher.match_string_exhaustive(candidate),
+                "{match_string} against {candidate}"
+            );
+        }
+    }
+
+    #[test]
+    fn stepping_through_an_engine() {
+        let matcher = Matcher::new("(aba|abac).").unwrap();
+        let mut engine = Engine::new(&matcher.tokens, "abac");
+
+        let mut stacks = vec![engine.stack().collect::<Vec<_>>()];
+        while engine.step() {
+            stacks.push(engine.stack().collect());
+        }
+        assert!(!engine.step());
+
+        let input = |chosen_option, remaining| StackFrame::Input {
+            token_index: 0,
+            chosen_option,
+            remaining,
+        };
+        let output = |chosen_option, matched_tokens_count| StackFrame::Output {
+            token_index: 0,
+            chosen_option,
+            matched_tokens_count,
+            best_option: None,
+        };
+        assert_eq!(
+            stacks,
+            vec![
+                vec![input("abac", ""), input("aba", "c")],
+                vec![input("abac", ""), output("aba", 2)],
+                vec![input("abac", "")],
+                vec![output("abac", 1)],
+                vec![],
+            ]
+        );
+
+        let result: Vec<&str> = engine.run().into_iter().map(|(_, text)| text).collect();
+        assert_eq!(result, vec!["aba", "c"]);
+    }
+}
@@ -0,0 +1,910 @@
+// Without the `std` feature, this only needs `alloc`, so it can be used on
+// targets without an operating system. The tests always have `std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use require_lifetimes::require_lifetimes;
+
+#[cfg(feature = "exhaustive")]
+mod exhaustive;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod highlight;
+#[cfg(feature = "json")]
+mod json;
+mod owned;
+mod set;
+
+#[cfg(feature = "exhaustive")]
+pub use exhaustive::engine;
+#[cfg(feature = "exhaustive")]
+pub use exhaustive::{
+    ExhaustiveSearch, ExploreOrder, ExploreStats, FrameLimitExceeded, MatchCandidate, MatchScoring,
+    TraceEvent,
+};
+
+#[cfg(feature = "scratch")]
+pub use exhaustive::ExhaustiveScratch;
+
+pub use highlight::highlight;
+pub use owned::{OwnedMatch, OwnedMatcherToken};
+pub use set::MatcherSet;
+
+/// This is what a pattern matched: each token that matched, in order, and
+/// the part of the string it matched, as `Matcher::match_string` returns.
+pub type MatchResult<'a, 'internal, 'b> = [(&'a MatcherToken<'internal>, &'b str)];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MatcherToken<'a> {
+    /// This is just text without anything special.
+    RawText(&'a str),
+    /// This is when text could be any one of multiple
+    /// strings. It looks like `(one|two|three)`, where
+    /// `one`, `two` or `three` are the allowed strings.
+    OneOfText(Box<[&'a str]>),
+    /// This is when you're happy to accept any single character.
+    /// It looks like `.`
+    WildCard,
+}
+
+impl<'a> MatcherToken<'a> {
+    /// This is the fewest bytes of the string that this token can match.
+    #[require_lifetimes]
+    pub fn min_match_len<'b>(&'b self) -> usize {
+        match self {
+            MatcherToken::RawText(text) => text.len(),
+            MatcherToken::OneOfText(options) => {
+                options.iter().map(|option| option.len()).min().unwrap_or(0)
+            }
+            MatcherToken::WildCard => 1,
+        }
+    }
+}
+
+const _: () = assert!(core::mem::size_of::<MatcherToken>() <= 24);
+
+/// This wraps the searcher for the pattern's leading literal, so that
+/// `Matcher` can keep deriving `PartialEq`.
+#[cfg(feature = "memchr")]
+#[derive(Debug)]
+struct Prefilter<'a>(memchr::memmem::Finder<'a>);
+
+#[cfg(feature = "memchr")]
+impl PartialEq for Prefilter<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.needle() == other.0.needle()
+    }
+}
+
+#[cfg(feature = "memchr")]
+impl Eq for Prefilter<'_> {}
+
+#[cfg(feature = "memchr")]
+impl Prefilter<'_> {
+    /// This finds the first place at or after `position` in `haystack` where
+    /// the leading literal occurs.
+    #[require_lifetimes]
+    fn find_from<'a, 'b>(&'b self, haystack: &'a str, position: usize) -> Option<usize> {
+        self.0
+            .find(&haystack.as_bytes()[position..])
+            .map(|offset| position + offset)
+    }
+}
+
+/// This is one step of a pattern that has no groups in it.
+#[derive(Debug, PartialEq, Eq)]
+enum LinearStep<'a> {
+    /// This text has to come next.
+    Literal(&'a str),
+    /// This many characters (of any kind) have to come next.
+    AnyChars(usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum CompiledForm<'a> {
+    /// The pattern has no groups, so there's only one way it could match,
+    /// and checking that takes a single pass over the candidate.
+    Linear(Box<[LinearStep<'a>]>),
+    /// The pattern has groups, so checking it may need to backtrack.
+    General,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Matcher<'a> {
+    /// This is the actual text of the matcher
+    text: &'a str,
+    /// This is a vector of the tokens inside the expression.
+    tokens: Vec<MatcherToken<'a>>,
+    /// This keeps track of the most tokens that this matcher has matched.
+    most_tokens_matched: usize,
+    /// This finds where the leading literal of the pattern (if any) occurs,
+    /// so that `find_iter` and `search_exhaustive` can skip over positions
+    /// that can't match.
+    #[cfg(feature = "memchr")]
+    prefilter: Option<Prefilter<'a>>,
+    /// This is a faster way to check the pattern, worked out when it's parsed.
+    compiled: CompiledForm<'a>,
+    /// This has, for every token index, the fewest bytes that the tokens from
+    /// that index onwards can match.
+    #[cfg(feature = "exhaustive")]
+    min_remaining_len: Box<[usize]>,
+}
+
+/// This is why a pattern didn't parse, as returned by `Matcher::parse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// This is the byte offset in the pattern where the problem is: the
+    /// start of an empty option, or the `(` of any other broken group.
+    pub offset: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A group has an option with nothing in it, like `(a|)`.
+    EmptyOption,
+    /// A group has only one option, like `(a)`.
+    OneOption,
+    /// A group has no `)` to close it, like `(a|b`.
+    UnclosedGroup,
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let problem = match self.kind {
+            ParseErrorKind::EmptyOption => "this option of a group is empty",
+            ParseErrorKind::OneOption => "this group has only one option",
+            ParseErrorKind::UnclosedGroup => "this group isn't closed",
+        };
+        write!(f, "{problem} (at byte {})", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// This is the iterator returned by `Matcher::find_iter`.
+pub struct FindIter<'a, 'internal, 'b> {
+    tokens: &'a [MatcherToken<'internal>],
+    most_tokens_matched: &'a mut usize,
+    #[cfg(feature = "memchr")]
+    prefilter: Option<&'a Prefilter<'internal>>,
+    haystack: &'b str,
+    position: usize,
+}
+
+#[require_lifetimes]
+impl<'internal> Matcher<'internal> {
+    // ANCHOR: matcher_new
+    /// This should take a string reference, and return
+    /// an `Matcher` which has parsed that reference.
+    pub fn new(text: &'internal str) -> Option<Matcher<'internal>> {
+        Self::parse(text).ok()
+    }
+    // ANCHOR_END: matcher_new
+
+    /// This is `new`, but when the pattern doesn't parse, it says why, and
+    /// where in the pattern the problem is.
+    pub fn parse(text: &'internal str) -> Result<Matcher<'internal>, ParseError> {
+        let mut unparsed_text = text;
+        let mut tokens = Vec::new();
+
+        while let Some(dot_paren_index) = Self::find_either(unparsed_text, b'.', b'(') {
+            let raw_text = &unparsed_text[..dot_paren_index];
+            if !raw_text.is_empty() {
+                tokens.push(MatcherToken::RawText(raw_text));
+            }
+
+            if unparsed_text.as_bytes()[dot_paren_index] == b'.' {
+                tokens.push(MatcherToken::WildCard);
+                unparsed_text = &unparsed_text[dot_paren_index + 1..];
+            } else {
+                let group_offset = text.len() - unparsed_text.len() + dot_paren_index;
+                unparsed_text = &unparsed_text[dot_paren_index + 1..];
+                let mut options = Vec::new();
+                let mut found_a_pipe = false;
+
+                loop {
+                    let Some(pipe_paren_index) = Self::find_either(unparsed_text, b'|', b')')
+                    else {
+                        return Err(ParseError {
+                            kind: ParseErrorKind::UnclosedGroup,
+                            offset: group_offset,
+                        });
+                    };
+                    let option = &unparsed_text[..pipe_paren_index];
+                    if option.is_empty() {
+                        return Err(ParseError {
+                            kind: ParseErrorKind::EmptyOption,
+                            offset: text.len() - unparsed_text.len(),
+                        });
+                    }
+                    options.push(option);
+                    let is_pipe = unparsed_text.as_bytes()[pipe_paren_index] == b'|';
+                    unparsed_text = &unparsed_text[pipe_paren_index + 1..];
+
+                    if is_pipe {
+                        found_a_pipe = true;
+                    } else if !found_a_pipe {
+                        return Err(ParseError {
+                            kind: ParseErrorKind::OneOption,
+                            offset: group_offset,
+                        });
+                    } else {
+                        break;
+                    }
+                }
+
+                tokens.push(MatcherToken::OneOfText(options.into_boxed_slice()));
+            }
+        }
+
+        if !unparsed_text.is_empty() || tokens.is_empty() {
+            tokens.push(MatcherToken::RawText(unparsed_text));
+        }
+
+        Ok(Self::from_tokens(text, tokens))
+    }
+
+    /// This builds a matcher for tokens that have already been parsed from
+    /// `text`. The parser never makes empty options, but nothing here relies
+    /// on that, so tests can use this to try tokens it wouldn't make.
+    fn from_tokens(
+        text: &'internal str,
+        tokens: Vec<MatcherToken<'internal>>,
+    ) -> Matcher<'internal> {
+        #[cfg(feature = "memchr")]
+        let prefilter = match tokens.first() {
+            Some(&MatcherToken::RawText(text)) if !text.is_empty() => {
+                Some(Prefilter(memchr::memmem::Finder::new(text)))
+            }
+            _ => None,
+        };
+
+        let compiled = Self::compile(&tokens);
+
+        #[cfg(feature = "exhaustive")]
+        let min_remaining_len = {
+            let mut min_remaining_len = vec![0; tokens.len() + 1];
+            for (index, token) in tokens.iter().enumerate().rev() {
+                min_remaining_len[index] = min_remaining_len[index + 1] + token.min_match_len();
+            }
+            min_remaining_len.into_boxed_slice()
+        };
+
+        Matcher {
+            text,
+            tokens,
+            most_tokens_matched: 0,
+            #[cfg(feature = "memchr")]
+            prefilter,
+            compiled,
+            #[cfg(feature = "exhaustive")]
+            min_remaining_len,
+        }
+    }
+
+    /// This finds the first place in `string` where either of two ASCII
+    /// characters occurs. Long patterns spend most of their parsing time in
+    /// here, which is why it's worth using memchr when it's available.
+    fn find_either<'a>(string: &'a str, first: u8, second: u8) -> Option<usize> {
+        #[cfg(feature = "memchr")]
+        {
+            memchr::memchr2(first, second, string.as_bytes())
+        }
+
+        #[cfg(not(feature = "memchr"))]
+        {
+            string.find([char::from(first), char::from(second)])
+        }
+    }
+
+    fn compile<'a>(tokens: &'a [MatcherToken<'internal>]) -> CompiledForm<'internal> {
+        let mut steps = Vec::new();
+
+        for token in tokens {
+            match (token, steps.last_mut()) {
+                (MatcherToken::RawText(text), _) => steps.push(LinearStep::Literal(text)),
+                (MatcherToken::WildCard, Some(LinearStep::AnyChars(count))) => *count += 1,
+                (MatcherToken::WildCard, _) => steps.push(LinearStep::AnyChars(1)),
+                (MatcherToken::OneOfText(_), _) => return CompiledForm::General,
+            }
+        }
+
+        CompiledForm::Linear(steps.into_boxed_slice())
+    }
+
+    /// This checks whether the whole of `string` matches the pattern, trying
+    /// every option of every group if it has to. Unlike `match_string`, it
+    /// doesn't count towards `most_tokens_matched`.
+    pub fn is_match<'a, 'b>(&'a self, string: &'b str) -> bool {
+        match &self.compiled {
+            CompiledForm::Linear(steps) => Self::matches_linear(steps, string, true),
+            CompiledForm::General => Self::matches_general(&self.tokens, string, true),
+        }
+    }
+
+    /// This checks whether the pattern matches the start of `string`; anything
+    /// after the match is ignored. Like `is_match`, it doesn't count towards
+    /// `most_tokens_matched`.
+    pub fn matches_prefix<'a, 'b>(&'a self, string: &'b str) -> bool {
+        match &self.compiled {
+            CompiledForm::Linear(steps) => Self::matches_linear(steps, string, false),
+            CompiledForm::General => Self::matches_general(&self.tokens, string, false),
+        }
+    }
+
+    /// This describes the tokens the pattern was parsed into, one per line,
+    /// numbered from 1, for seeing how a pattern was understood.
+    pub fn explain<'a>(&'a self) -> String {
+        self.tokens
+            .iter()
+            .enumerate()
+            .map(|(index, token)| format!("{}. {token:?}\n", index + 1))
+            .collect()
+    }
+
+    /// This writes the pattern in the syntax of the `regex` crate, for
+    /// checking it against that, or moving to it: the regex matches exactly
+    /// the strings that `is_match` says the pattern does. Raw text is escaped
+    /// the way `regex::escape` does it, each group becomes a non-capturing
+    /// alternation, like `(?:a|b)`, and it's anchored with `\A` and `\z`.
+    ///
+    /// The wildcard becomes `.`, which matches one character, like it does.
+    /// But without the `s` flag, `.` doesn't match `\n`, which the wildcard
+    /// does, so the regex starts with `(?s)`. Leave that out to have the
+    /// wildcard not match a newline.
+    pub fn to_regex_string<'a>(&'a self) -> String {
+        let mut regex = String::from(r"(?s)\A");
+        for token in &self.tokens {
+            match token {
+                MatcherToken::RawText(text) => Self::push_escaped(&mut regex, text),
+                MatcherToken::OneOfText(options) => {
+                    regex.push_str("(?:");
+                    for (index, option) in options.iter().enumerate() {
+                        if index > 0 {
+                            regex.push('|');
+                        }
+                        Self::push_escaped(&mut regex, option);
+                    }
+                    regex.push(')');
+                }
+                MatcherToken::WildCard => regex.push('.'),
+            }
+        }
+        regex.push_str(r"\z");
+        regex
+    }
+
+    /// This adds `text` to `regex`, with a `\` before each character that
+    /// means something in a regex, which are the ones `regex::escape`
+    /// escapes.
+    fn push_escaped<'a, 'b>(regex: &'a mut String, text: &'b str) {
+        for character in text.chars() {
+            if r"\.+*?()|[]{}^$#&-~".contains(character) {
+                regex.push('\\');
+            }
+            regex.push(character);
+        }
+    }
+
+    fn matches_linear<'a, 'b, 'c>(
+        steps: &'a [LinearStep<'b>],
+        string: &'c str,
+        must_match_everything: bool,
+    ) -> bool {
+        let mut string = string;
+
+        for step in steps {
+            match step {
+                LinearStep::Literal(text) => match string.strip_prefix(text) {
+                    Some(rest) => string = rest,
+                    None => return false,
+                },
+                LinearStep::AnyChars(count) => {
+                    let mut chars = string.chars();
+                    for _ in 0..*count {
+                        if chars.next().is_none() {
+                            return false;
+                        }
+                    }
+                    string = chars.as_str();
+                }
+            }
+        }
+
+        !must_match_everything || string.is_empty()
+    }
+
+    /// This tries every option of every group, but keeps only the offsets
+    /// that the tokens so far can stop at, so options that end in the same
+    /// place are carried on once, and nothing is ever tried twice.
+    fn matches_general<'a, 'b, 'c>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        must_match_everything: bool,
+    ) -> bool {
+        let mut stops = vec![0];
+        let mut next_stops = Vec::new();
+
+        for token in tokens {
+            for &offset in &stops {
+                let rest = &string[offset..];
+                match token {
+                    MatcherToken::RawText(text) => {
+                        if rest.starts_with(text) {
+                            next_stops.push(offset + text.len());
+                        }
+                    }
+                    MatcherToken::OneOfText(options) => {
+                        for option in options.iter() {
+                            if rest.starts_with(option) {
+                                next_stops.push(offset + option.len());
+                            }
+                        }
+                    }
+                    MatcherToken::WildCard => {
+                        if let Some(c) = rest.chars().next() {
+                            next_stops.push(offset + c.len_utf8());
+                        }
+                    }
+                }
+            }
+
+            if next_stops.is_empty() {
+                return false;
+            }
+            next_stops.sort_unstable();
+            next_stops.dedup();
+            core::mem::swap(&mut stops, &mut next_stops);
+            next_stops.clear();
+        }
+
+        !must_match_everything || stops.contains(&string.len())
+    }
+
+    // ANCHOR: match_string
+    /// This should take a string, and return a vector of tokens, and the corresponding part
+    /// of the given string. For examples, see the test cases below.
+    pub fn match_string<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> Vec<(&'a MatcherToken<'internal>, &'b str)> {
+        let matched_tokens = Self::match_tokens(&self.tokens, string);
+
+        if matched_tokens.len() > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens.len();
+        }
+
+        matched_tokens
+    }
+    // ANCHOR_END: match_string
+
+    /// This returns an iterator over the places in `haystack` where the whole
+    /// pattern matches, along with the byte offset they start at. Matches don't
+    /// overlap, and every attempt counts towards `most_tokens_matched`.
+    pub fn find_iter<'a, 'b>(&'a mut self, haystack: &'b str) -> FindIter<'a, 'internal, 'b> {
+        FindIter {
+            tokens: &self.tokens,
+            most_tokens_matched: &mut self.most_tokens_matched,
+            #[cfg(feature = "memchr")]
+            prefilter: self.prefilter.as_ref(),
+            haystack,
+            position: 0,
+        }
+    }
+
+    fn match_tokens<'a, 'b, 'c>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+    ) -> Vec<(&'a MatcherToken<'b>, &'c str)> {
+        let mut matched_tokens = Vec::new();
+        let mut string = string;
+
+        for token in tokens {
+            match token {
+                MatcherToken::RawText(text) => {
+                    if !Self::match_raw_text(text, token, &mut matched_tokens, &mut string) {
+                        break;
+                    }
+                }
+                MatcherToken::OneOfText(options) => {
+                    if !Self::match_one_of_text(options, token, &mut matched_tokens, &mut string) {
+                        break;
+                    }
+                }
+                MatcherToken::WildCard => {
+                    if !Self::match_wild_card(token, &mut matched_tokens, &mut string) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        matched_tokens
+    }
+
+    fn match_raw_text<'a, 'b, 'c, 'd, 'e, 'f>(
+        text: &'a str,
+        token: &'b MatcherToken<'c>,
+        matched_tokens: &'d mut Vec<(&'b MatcherToken<'c>, &'e str)>,
+        string: &'f mut &'e str,
+    ) -> bool {
+        if Self::starts_with_raw_text(string, text) {
+            matched_tokens.push((token, &string[..text.len()]));
+            *string = &string[text.len()..];
+            true
+        } else {
+            false
+        }
+    }
+
+    /// This checks whether `string` starts with `text`, the same as
+    /// `str::starts_with`, only quicker to rule out.
+    fn starts_with_raw_text<'a, 'b>(string: &'a str, text: &'b str) -> bool {
+        // Comparing the first and last bytes is cheap, and for long literals it
+        // rules out most positions before the full comparison has to run.
+        let could_match = match (text.as_bytes().first(), text.as_bytes().last()) {
+            (Some(first), Some(last)) => {
+                string.len() >= text.len()
+                    && string.as_bytes()[0] == *first
+                    && string.as_bytes()[text.len() - 1] == *last
+            }
+            _ => true,
+        };
+
+        could_match && string.starts_with(text)
+    }
+
+    fn match_one_of_text<'a, 'b, 'c, 'd, 'e, 'f>(
+        options: &'a [&'b str],
+        token: &'c MatcherToken<'b>,
+        matched_tokens: &'d mut Vec<(&'c MatcherToken<'b>, &'e str)>,
+        string: &'f mut &'e str,
+    ) -> bool {
+        if let Some(option) = options.iter().find(|&option| string.starts_with(option)) {
+            matched_tokens.push((token, &string[..option.len()]));
+            *string = &string[option.len()..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn match_wild_card<'a, 'b, 'c, 'd, 'e>(
+        token: &'a MatcherToken<'b>,
+        matched_tokens: &'c mut Vec<(&'a MatcherToken<'b>, &'d str)>,
+        string: &'e mut &'d str,
+    ) -> bool {
+        if let Some(c) = string.chars().next() {
+            let next_char_index = c.len_utf8();
+            matched_tokens.push((token, &string[..next_char_index]));
+            *string = &string[next_char_index..];
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<'a, 'internal, 'b> FindIter<'a, 'internal, 'b> {
+    #[require_lifetimes]
+    fn next_candidate_position<'c>(&'c self) -> Option<usize> {
+        #[cfg(feature = "memchr")]
+        if let Some(prefilter) = self.prefilter {
+            return prefilter.find_from(self.haystack, self.position);
+        }
+
+        Some(self.position)
+    }
+}
+
+impl<'a, 'internal, 'b> Iterator for FindIter<'a, 'internal, 'b> {
+    type Item = (usize, Vec<(&'a MatcherToken<'internal>, &'b str)>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position <= self.haystack.len() {
+            let start = self.next_candidate_position()?;
+            let matched_tokens = Matcher::match_tokens(self.tokens, &self.haystack[start..]);
+
+            if matched_tokens.len() > *self.most_tokens_matched {
+                *self.most_tokens_matched = matched_tokens.len();
+            }
+
+            let next_char_len = self.haystack[start..]
+                .chars()
+                .next()
+                .map_or(1, char::len_utf8);
+
+            if matched_tokens.len() == self.tokens.len() {
+                let matched_len: usize = matched_tokens.iter().map(|(_, text)| text.len()).sum();
+                self.position = start + matched_len.max(next_char_len);
+                return Some((start, matched_tokens));
+            }
+
+            self.position = start + next_char_len;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod shared_tests {
+    include!("../../shared_tests.rs");
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CompiledForm, LinearStep, Matcher, MatcherToken, ParseError, ParseErrorKind};
+
+    #[test]
+    fn parse_errors_say_where() {
+        for (pattern, kind, offset) in [
+            ("ab(|c)", ParseErrorKind::EmptyOption, 3),
+            ("ab(c||d)", ParseErrorKind::EmptyOption, 5),
+            ("(c|d|)", ParseErrorKind::EmptyOption, 5),
+            ("a.()", ParseErrorKind::EmptyOption, 3),
+            ("x(c|d)y(e)", ParseErrorKind::OneOption, 7),
+            ("x(c|d", ParseErrorKind::UnclosedGroup, 1),
+            ("x(c|d)(", ParseErrorKind::UnclosedGroup, 6),
+        ] {
+            assert_eq!(
+                Matcher::parse(pattern),
+                Err(ParseError { kind, offset }),
+                "{pattern}"
+            );
+            assert_eq!(Matcher::new(pattern), None, "{pattern}");
+        }
+        assert_eq!(
+            Matcher::parse("x(c|d)y(e)").unwrap_err().to_string(),
+            "this group has only one option (at byte 7)"
+        );
+        assert_eq!(
+            Matcher::parse("a(b|c)."),
+            Ok(Matcher::new("a(b|c).").unwrap())
+        );
+    }
+
+    #[test]
+    fn to_regex_string_escapes_and_anchors() {
+        assert_eq!(
+            Matcher::new("a+b.(c|d*|$)").unwrap().to_regex_string(),
+            r"(?s)\Aa\+b.(?:c|d\*|\$)\z"
+        );
+        assert_eq!(Matcher::new("").unwrap().to_regex_string(), r"(?s)\A\z");
+    }
+
+    #[test]
+    fn explain_lists_the_tokens() {
+        let matcher = Matcher::new("ab.(c|de)").unwrap();
+        assert_eq!(
+            matcher.explain(),
+            "1. RawText(\"ab\")\n2. WildCard\n3. OneOfText([\"c\", \"de\"])\n"
+        );
+    }
+
+    #[cfg(feature = "exhaustive")]
+    #[test]
+    fn simple_test_with_exhaustive_match() {
+        let match_string = "abc(d|e|f).".to_string();
+        let mut matcher = Matcher::new(&match_string).unwrap();
+
+        assert_eq!(matcher.most_tokens_matched, 0);
+
+        {
+            let candidate1 = "abcge".to_string();
+            let result = matcher.match_string_exhaustive(&candidate1);
+            assert_eq!(result, vec![(&MatcherToken::RawText("abc"), "abc"),]);
+            assert_eq!(matcher.most_tokens_matched, 1);
+        }
+
+        {
+            let candidate1 = "abcde".to_string();
+            let result = matcher.match_string_exhaustive(&candidate1);
+            assert_eq!(
+                result,
+                vec![
+                    (&MatcherToken::RawText("abc"), "abc"),
+                    (&MatcherToken::OneOfText(vec!["d", "e", "f"].into()), "d"),
+                    (&MatcherToken::WildCard, "e")
+                ]
+            );
+            assert_eq!(matcher.most_tokens_matched, 3);
+        }
+
+        {
+            let candidate1 = "abcd💪".to_string();
+            let result = matcher.match_string_exhaustive(&candidate1);
+            assert_eq!(
+                result,
+                vec![
+                    (&MatcherToken::RawText("abc"), "abc"),
+                    (&MatcherToken::OneOfText(vec!["d", "e", "f"].into()), "d"),
+                    (&MatcherToken::WildCard, "💪")
+                ]
+            );
+            assert_eq!(matcher.most_tokens_matched, 3);
+        }
+    }
+
+    #[cfg(feature = "exhaustive")]
+    #[test]
+    fn exhaustive_match_with_exhaustive_matcher() {
+        let match_string = "(aba|abac).(aba|abac).";
+        let mut matcher = Matcher::new(match_string).unwrap();
+
+        assert_eq!(matcher.most_tokens_matched, 0);
+
+        let candidate = "abacabacd";
+        let result = matcher.match_string_exhaustive(candidate);
+        assert_eq!(
+            result,
+            vec![
+                (&MatcherToken::OneOfText(vec!["aba", "abac"].into()), "aba"),
+                (&MatcherToken::WildCard, "c"),
+                (&MatcherToken::OneOfText(vec!["aba", "abac"].into()), "aba"),
+                (&MatcherToken::WildCard, "c")
+            ]
+        );
+        assert_eq!(matcher.most_tokens_matched, 4);
+    }
+
+    #[test]
+    fn is_match_and_matches_prefix() {
+        let matcher = Matcher::new("abc(d|e|f).").unwrap();
+        assert!(matcher.is_match("abcde"));
+        assert!(matcher.is_match("abcf💪"));
+        assert!(!matcher.is_match("abcdef"));
+        assert!(matcher.matches_prefix("abcdef"));
+        assert!(!matcher.matches_prefix("abcge"));
+
+        let matcher = Matcher::new("(aba|abac).(aba|abac).").unwrap();
+        assert!(matcher.is_match("abacabacd"));
+        assert!(matcher.is_match("abacabac"));
+        assert!(!matcher.is_match("abacab"));
+
+        // There are 2^30 ways of matching the groups, none of them followed
+        // by a "b", so this only finishes if they aren't tried one by one.
+        let match_string = format!("{}b", "(a|aa)".repeat(30));
+        let matcher = Matcher::new(&match_string).unwrap();
+        assert!(!matcher.is_match(&"a".repeat(60)));
+        assert!(!matcher.matches_prefix(&"a".repeat(60)));
+        assert!(matcher.is_match(&format!("{}b", "a".repeat(60))));
+    }
+
+    #[test]
+    fn linear_form_agrees_with_general_form() {
+        let patterns = ["abc...def", "a.b", "...", ".", "abc", "", "💪.💪"];
+        let candidates = [
+            "",
+            "a",
+            "ab",
+            "abc",
+            "axb",
+            "a💪b",
+            "abcxyzdef",
+            "abcxyzdefg",
+            "abc💪💪💪def",
+            "💪a💪",
+            "xyz",
+        ];
+
+        for pattern in patterns {
+            let matcher = Matcher::new(pattern).unwrap();
+            assert!(matches!(matcher.compiled, CompiledForm::Linear(_)));
+
+            for candidate in candidates {
+                assert_eq!(
+                    matcher.is_match(candidate),
+                    Matcher::matches_general(&matcher.tokens, candidate, true),
+                    "{pattern:?} against {candidate:?}"
+                );
+                assert_eq!(
+                    matcher.matches_prefix(candidate),
+                    Matcher::matches_general(&matcher.tokens, candidate, false),
+                    "{pattern:?} against {candidate:?}"
+                );
+            }
+        }
+
+        let matcher = Matcher::new("abc...def").unwrap();
+        assert_eq!(
+            matcher.compiled,
+            CompiledForm::Linear(
+                vec![
+                    LinearStep::Literal("abc"),
+                    LinearStep::AnyChars(3),
+                    LinearStep::Literal("def")
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            Matcher::new("a(b|c)").unwrap().compiled,
+            CompiledForm::General
+        );
+    }
+
+    #[test]
+    fn find_iter_positions() {
+        let literal = "needle".repeat(10);
+        let match_string = format!("{literal}.(x|y)");
+        let haystack = format!("hay{literal}💪xhay{literal}{literal}yx{literal}q");
+        let mut matcher = Matcher::new(&match_string).unwrap();
+
+        let mut expected = vec![];
+        let mut position = 0;
+        while position < haystack.len() {
+            let result = matcher.match_string(&haystack[position..]);
+            if result.len() == 3 {
+                expected.push(position);
+                position += result.iter().map(|(_, text)| text.len()).sum::<usize>();
+            } else {
+                position += haystack[position..].chars().next().unwrap().len_utf8();
+            }
+        }
+
+        let found: Vec<usize> = matcher
+            .find_iter(&haystack)
+            .map(|(start, _)| start)
+            .collect();
+        assert_eq!(found, expected);
+        assert_eq!(found, vec![3, 131]);
+        assert_eq!(matcher.most_tokens_matched, 3);
+    }
+
+    #[test]
+    fn find_iter_without_leading_literal() {
+        let mut matcher = Matcher::new("(ab|b)a").unwrap();
+        let haystack = "abababa";
+
+        let found: Vec<_> = matcher.find_iter(haystack).collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    0,
+                    vec![
+                        (&MatcherToken::OneOfText(vec!["ab", "b"].into()), "ab"),
+                        (&MatcherToken::RawText("a"), "a")
+                    ]
+                ),
+                (
+                    3,
+                    vec![
+                        (&MatcherToken::OneOfText(vec!["ab", "b"].into()), "b"),
+                        (&MatcherToken::RawText("a"), "a")
+                    ]
+                ),
+                (
+                    5,
+                    vec![
+                        (&MatcherToken::OneOfText(vec!["ab", "b"].into()), "b"),
+                        (&MatcherToken::RawText("a"), "a")
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_either_metacharacter() {
+        let options: Vec<String> = (0..10_000).map(|i| format!("option{i:04}")).collect();
+        let pattern = format!("start({}).end", options.join("|"));
+
+        for string in [&pattern, "", "no metacharacters", "ü(ß|.)", ")|"] {
+            for (first, second) in [(b'.', b'('), (b'|', b')')] {
+                assert_eq!(
+                    Matcher::find_either(string, first, second),
+                    string.find([char::from(first), char::from(second)])
+                );
+            }
+        }
+    }
+}
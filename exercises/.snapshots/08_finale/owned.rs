@@ -0,0 +1,223 @@
+//! A match result borrows the pattern and the candidate, so it can't outlive
+//! them, which makes it hard to keep, or to send to another thread or
+//! process. These are owned copies of the parts of one, which can be, and
+//! with the `serde` feature, they can be serialized too.
+
+use crate::{MatchResult, Matcher, MatcherToken};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use require_lifetimes::require_lifetimes;
+
+/// This is a `MatcherToken` that owns its text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub enum OwnedMatcherToken {
+    RawText(String),
+    OneOfText(Vec<String>),
+    WildCard,
+}
+
+/// This is what one token matched, with where in the candidate it was (in
+/// bytes), owning everything in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct OwnedMatch {
+    /// Which token of the pattern this is, counting from 0.
+    pub token_index: usize,
+    pub token: OwnedMatcherToken,
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[require_lifetimes]
+impl<'a, 'internal> From<&'a MatcherToken<'internal>> for OwnedMatcherToken {
+    fn from(token: &'a MatcherToken<'internal>) -> OwnedMatcherToken {
+        match token {
+            MatcherToken::RawText(text) => OwnedMatcherToken::RawText(text.to_string()),
+            MatcherToken::OneOfText(options) => OwnedMatcherToken::OneOfText(
+                options.iter().map(|option| option.to_string()).collect(),
+            ),
+            MatcherToken::WildCard => OwnedMatcherToken::WildCard,
+        }
+    }
+}
+
+#[require_lifetimes]
+impl<'a> From<&'a OwnedMatcherToken> for MatcherToken<'a> {
+    /// This borrows the text of an owned token, to match with again.
+    fn from(token: &'a OwnedMatcherToken) -> MatcherToken<'a> {
+        match token {
+            OwnedMatcherToken::RawText(text) => MatcherToken::RawText(text),
+            OwnedMatcherToken::OneOfText(options) => {
+                MatcherToken::OneOfText(options.iter().map(|option| option.as_str()).collect())
+            }
+            OwnedMatcherToken::WildCard => MatcherToken::WildCard,
+        }
+    }
+}
+
+/// Given a candidate, some text that should be part of it, and where to
+/// start looking for the text if it isn't, return where the text starts in
+/// the candidate.
+///
+/// Text that `match_string` returns is a slice of the candidate, so where it
+/// starts is just how far its pointer is past the candidate's. But if it's a
+/// slice of something else (a copy of the candidate, say), that's
+/// meaningless, so then this looks for the text after `from` instead, and if
+/// it isn't there either, says it's at `from`.
+#[require_lifetimes]
+fn offset_in<'candidate, 'text>(
+    candidate: &'candidate str,
+    text: &'text str,
+    from: usize,
+) -> usize {
+    let candidate_start = candidate.as_ptr() as usize;
+    let text_start = text.as_ptr() as usize;
+    if text_start >= candidate_start && text_start + text.len() <= candidate_start + candidate.len()
+    {
+        return text_start - candidate_start;
+    }
+    candidate
+        .get(from..)
+        .and_then(|rest| rest.find(text))
+        .map_or(from, |found| from + found)
+}
+
+#[require_lifetimes]
+impl<'internal> Matcher<'internal> {
+    /// This copies what this pattern matched in `candidate` into values that
+    /// own everything in them, with where in `candidate` each token's text
+    /// is, in bytes.
+    pub fn to_owned_result<'a, 'r, 'b, 'c>(
+        &'a self,
+        result: &'r MatchResult<'r, 'internal, 'b>,
+        candidate: &'c str,
+    ) -> Vec<OwnedMatch> {
+        let mut owned = Vec::new();
+        let mut end = 0;
+        for (token_index, (token, text)) in result.iter().enumerate() {
+            let start = offset_in(candidate, text, end);
+            end = start + text.len();
+            owned.push(OwnedMatch {
+                token_index,
+                token: OwnedMatcherToken::from(*token),
+                text: text.to_string(),
+                start,
+                end,
+            });
+        }
+        owned
+    }
+
+    /// This copies the tokens the pattern was parsed into, so that they can
+    /// be kept without keeping the pattern borrowed.
+    pub fn to_owned_tokens<'a>(&'a self) -> Vec<OwnedMatcherToken> {
+        self.tokens.iter().map(OwnedMatcherToken::from).collect()
+    }
+
+    /// This makes a matcher from tokens that `to_owned_tokens` copied out of
+    /// a matcher for `text`, without parsing `text` again.
+    pub fn from_owned_tokens(
+        text: &'internal str,
+        tokens: &'internal [OwnedMatcherToken],
+    ) -> Matcher<'internal> {
+        Self::from_tokens(text, tokens.iter().map(MatcherToken::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{offset_in, OwnedMatch, OwnedMatcherToken};
+    use crate::{Matcher, MatcherToken};
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn offsets_count_bytes_of_multibyte_candidates() {
+        let candidate = "é💪éz";
+        let mut matcher = Matcher::new("é.(é|e)").unwrap();
+        let other_matcher = Matcher::new("é.(é|e)").unwrap();
+        let result = matcher.match_string(candidate);
+        let owned = other_matcher.to_owned_result(&result, candidate);
+        assert_eq!(
+            owned,
+            [
+                OwnedMatch {
+                    token_index: 0,
+                    token: OwnedMatcherToken::RawText("é".to_string()),
+                    text: "é".to_string(),
+                    start: 0,
+                    end: 2,
+                },
+                OwnedMatch {
+                    token_index: 1,
+                    token: OwnedMatcherToken::WildCard,
+                    text: "💪".to_string(),
+                    start: 2,
+                    end: 6,
+                },
+                OwnedMatch {
+                    token_index: 2,
+                    token: OwnedMatcherToken::OneOfText(vec!["é".to_string(), "e".to_string()]),
+                    text: "é".to_string(),
+                    start: 6,
+                    end: 8,
+                },
+            ]
+        );
+        for matched in &owned {
+            assert_eq!(&candidate[matched.start..matched.end], matched.text);
+        }
+    }
+
+    #[test]
+    fn text_from_elsewhere_is_searched_for() {
+        let candidate = "abab";
+        // A copy isn't part of the candidate, so it has to be found.
+        let copy = "ab".to_string();
+        assert_eq!(offset_in(candidate, &candidate[2..], 0), 2);
+        assert_eq!(offset_in(candidate, &copy, 0), 0);
+        assert_eq!(offset_in(candidate, &copy, 1), 2);
+        assert_eq!(offset_in(candidate, "x", 3), 3);
+        assert_eq!(offset_in(candidate, "x", 9), 9);
+    }
+
+    #[test]
+    fn tokens_convert_both_ways() {
+        for token in [
+            MatcherToken::RawText("ab"),
+            MatcherToken::OneOfText(vec!["c", "de"].into_boxed_slice()),
+            MatcherToken::WildCard,
+        ] {
+            let owned = OwnedMatcherToken::from(&token);
+            assert_eq!(MatcherToken::from(&owned), token);
+        }
+    }
+
+    #[test]
+    fn matchers_can_be_made_again_from_owned_tokens() {
+        let pattern = "(Black|Bridge)(rock|stone).";
+        let tokens = Matcher::new(pattern).unwrap().to_owned_tokens();
+        let mut matcher = Matcher::from_owned_tokens(pattern, &tokens);
+        assert_eq!(matcher, Matcher::new(pattern).unwrap());
+        assert_eq!(
+            matcher.match_string("Bridgestone_Tyres"),
+            Matcher::new(pattern)
+                .unwrap()
+                .match_string("Bridgestone_Tyres")
+        );
+    }
+
+    #[test]
+    fn owned_matches_can_go_to_other_threads() {
+        fn sendable<T: Send + 'static>() {}
+        sendable::<OwnedMatch>();
+    }
+}
@@ -0,0 +1,138 @@
+//! This is a set of patterns, read from a file of them, one to a line, for
+//! checking a candidate against all of them at once.
+
+use crate::{Matcher, ParseError};
+use alloc::vec::Vec;
+use require_lifetimes::require_lifetimes;
+
+/// This is some patterns, each with the number of the line it was on, as
+/// `MatcherSet::from_lines` reads them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MatcherSet<'a> {
+    /// These are in the order of their lines.
+    matchers: Vec<(usize, Matcher<'a>)>,
+}
+
+#[require_lifetimes]
+impl<'internal> MatcherSet<'internal> {
+    /// This parses each line of `text` as a pattern, numbering the lines
+    /// from one. Blank lines, and lines that start with `#` (after any
+    /// spaces), are skipped. If a pattern doesn't parse, this says which line
+    /// it was on, and why.
+    pub fn from_lines(text: &'internal str) -> Result<MatcherSet<'internal>, (usize, ParseError)> {
+        let mut matchers = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let matcher = Matcher::parse(line).map_err(|error| (index + 1, error))?;
+            matchers.push((index + 1, matcher));
+        }
+        Ok(MatcherSet { matchers })
+    }
+
+    /// This is how many patterns there are.
+    pub fn len<'a>(&'a self) -> usize {
+        self.matchers.len()
+    }
+
+    pub fn is_empty<'a>(&'a self) -> bool {
+        self.matchers.is_empty()
+    }
+
+    /// This returns the line numbers of the patterns, in order.
+    pub fn lines<'a>(&'a self) -> Vec<usize> {
+        self.matchers.iter().map(|(line, _)| *line).collect()
+    }
+
+    /// This returns the pattern that was on `line`, if one was.
+    pub fn get<'a>(&'a self, line: usize) -> Option<&'a Matcher<'internal>> {
+        self.matchers
+            .iter()
+            .find(|(number, _)| *number == line)
+            .map(|(_, matcher)| matcher)
+    }
+
+    /// This is `get`, but for a pattern to match with (which needs it to be
+    /// mutable).
+    pub fn get_mut<'a>(&'a mut self, line: usize) -> Option<&'a mut Matcher<'internal>> {
+        self.matchers
+            .iter_mut()
+            .find(|(number, _)| *number == line)
+            .map(|(_, matcher)| matcher)
+    }
+
+    /// This returns the line numbers of the patterns that the whole of
+    /// `string` matches (see `Matcher::is_match`), in order.
+    pub fn matches<'a, 'b>(&'a self, string: &'b str) -> Vec<usize> {
+        self.matchers
+            .iter()
+            .filter(|(_, matcher)| matcher.is_match(string))
+            .map(|(line, _)| *line)
+            .collect()
+    }
+
+    /// This returns the line numbers of the patterns that match somewhere in
+    /// `haystack` (see `Matcher::find_iter`), in order.
+    pub fn matches_anywhere<'a, 'b>(&'a mut self, haystack: &'b str) -> Vec<usize> {
+        self.matchers
+            .iter_mut()
+            .filter_map(|(line, matcher)| matcher.find_iter(haystack).next().map(|_| *line))
+            .collect()
+    }
+}
+
+#[require_lifetimes]
+impl<'internal> From<Matcher<'internal>> for MatcherSet<'internal> {
+    /// This is a set of just `matcher`, as though it were on line 1.
+    fn from(matcher: Matcher<'internal>) -> MatcherSet<'internal> {
+        MatcherSet {
+            matchers: alloc::vec![(1, matcher)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MatcherSet;
+    use crate::{Matcher, ParseError, ParseErrorKind};
+
+    #[test]
+    fn skips_blank_lines_and_comments() {
+        let set =
+            MatcherSet::from_lines("# companies\n\nBlack.\n  # indented\n   \n(a|b)c\n").unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.lines(), [3, 6]);
+        assert_eq!(set.get(6), Some(&Matcher::new("(a|b)c").unwrap()));
+        assert_eq!(set.get(1), None);
+        assert!(MatcherSet::from_lines("# nothing\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn says_which_line_does_not_parse() {
+        assert_eq!(
+            MatcherSet::from_lines("a\r\n# b(\r\nc(d|e\r\n"),
+            Err((
+                3,
+                ParseError {
+                    kind: ParseErrorKind::UnclosedGroup,
+                    offset: 1
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn reports_every_pattern_that_matches() {
+        let mut set = MatcherSet::from_lines("ab.\n(a|x)bc\nb\nz").unwrap();
+        assert_eq!(set.matches("abc"), [1, 2]);
+        assert_eq!(set.matches("xbc"), [2]);
+        assert_eq!(set.matches_anywhere("zabc"), [1, 2, 3, 4]);
+        assert_eq!(set.matches_anywhere("qq"), [] as [usize; 0]);
+
+        let mut single = MatcherSet::from(Matcher::new("b").unwrap());
+        assert_eq!(single.matches_anywhere("abc"), [1]);
+        assert!(single.get_mut(1).is_some());
+    }
+}
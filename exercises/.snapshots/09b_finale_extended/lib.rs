@@ -0,0 +1,76 @@
+// The methods are for you to write, so their arguments aren't used yet.
+#![allow(unused_variables)]
+
+use require_lifetimes::require_lifetimes;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MatcherToken<'a> {
+    /// This is just text without anything special.
+    RawText(&'a str),
+    /// This is when text could be any one of multiple
+    /// strings. It looks like `(one|two|three)`, where
+    /// `one`, `two` or `three` are the allowed strings.
+    OneOfText(Box<[&'a str]>),
+    /// This is when you're happy to accept any single character.
+    /// It looks like `.`
+    WildCard,
+    // These two are new since the finale.
+    /// This is a token that can match once, or not at all. It looks like
+    /// the token, then `?`. After text, it's only the last character that's
+    /// optional: `colou?r` is `colo`, then an optional `u`, then `r`.
+    Optional(Box<MatcherToken<'a>>),
+    /// This is a token that can match any number of times, including none.
+    /// It looks like the token, then `*`, and after text it's only the last
+    /// character that's repeated, like `?`.
+    Repeated(Box<MatcherToken<'a>>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Matcher<'a> {
+    /// This is the actual text of the matcher
+    pub text: &'a str,
+    /// This is a vector of the tokens inside the expression.
+    pub tokens: Vec<MatcherToken<'a>>,
+    /// This keeps track of the most tokens that this matcher has matched.
+    pub most_tokens_matched: usize,
+}
+
+impl<'a> Matcher<'a> {
+    /// This should take a string reference, and return
+    /// an `Matcher` which has parsed that reference.
+    ///
+    /// It returns `None` if a `(` isn't closed, or a `?` or `*` doesn't
+    /// come straight after a token.
+    #[require_lifetimes]
+    pub fn new(text: &'a str) -> Option<Matcher<'a>> {
+        todo!()
+    }
+
+    /// This should take a string, and return a vector of tokens, and the
+    /// corresponding part of the given string: as many tokens as can be
+    /// matched from the start of the string, one after the other.
+    ///
+    /// Where there's more than one way to match that many, a `?` or `*`
+    /// takes as much as it can, and a `(...)` takes the first option it
+    /// can, earlier tokens first. An optional or repeated token that
+    /// matches nothing is still in the vector, with `""`.
+    #[require_lifetimes]
+    pub fn match_string<'b, 'c>(
+        &'b mut self,
+        string: &'c str,
+    ) -> Vec<(&'b MatcherToken<'a>, &'c str)> {
+        todo!()
+    }
+
+    /// This returns whether the whole of a string matches the whole
+    /// matcher.
+    #[require_lifetimes]
+    pub fn is_match<'b, 'c>(&'b self, string: &'c str) -> bool {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod shared_tests {
+    include!("../../shared_tests.rs");
+}
@@ -0,0 +1,64 @@
+use require_lifetimes::require_lifetimes;
+
+/// Like `Iterator`, but each item can borrow from the iterator itself, so
+/// it has to be finished with before the next one is asked for.
+///
+/// As it's handed out, `Item` has no lifetime, and neither does `next`.
+/// Give them one each, so `next` can lend out something that borrows
+/// `self`. `starter/missing_where.rs` shows what rustc says if `Item`
+/// is missing its `where` clause.
+#[require_lifetimes]
+pub trait LendingIterator {
+    /// What's lent out, borrowing the iterator for `'next`.
+    type Item<'next>
+    where
+        Self: 'next;
+
+    /// Lend out the next item, or return `None` if there aren't any more.
+    fn next<'next>(&'next mut self) -> Option<Self::Item<'next>>;
+}
+
+/// Every window of `size` elements in a slice, from the start, overlapping,
+/// and each one mutable.
+///
+/// This can't be an `Iterator`: see `starter/std_iterator.rs`.
+#[require_lifetimes]
+pub struct WindowsMut<'slice, T> {
+    slice: &'slice mut [T],
+    start: usize,
+    size: usize,
+}
+
+#[require_lifetimes]
+impl<'slice, T> WindowsMut<'slice, T> {
+    /// Given a slice and how big each window should be, return the windows
+    /// over it.
+    pub fn new(slice: &'slice mut [T], size: usize) -> WindowsMut<'slice, T> {
+        WindowsMut {
+            slice,
+            start: 0,
+            size,
+        }
+    }
+}
+
+#[require_lifetimes]
+impl<'slice, T> LendingIterator for WindowsMut<'slice, T> {
+    type Item<'next>
+        = &'next mut [T]
+    where
+        Self: 'next;
+
+    fn next<'next>(&'next mut self) -> Option<&'next mut [T]> {
+        // This borrows from `self`, not from `'slice`: the next window
+        // overlaps this one, so it mustn't be lent out while this one is.
+        let window = self.slice.get_mut(self.start..self.start + self.size)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
@@ -0,0 +1,23 @@
+// This is chapter 2's exercise as it's handed out, before any lifetimes
+// are written in (the bodies and the docs are in `exercise/src/lib.rs`).
+// It's without `#[require_lifetimes]`, so the errors are rustc's own, which
+// are the ones a new compiler could change. `tests/starters.rs` checks that
+// it still fails with them.
+
+pub fn identity(number: &i32) -> &i32 {
+    number
+}
+
+pub fn split(text: &str, delimiter: &str) -> Vec<&str> {
+    text.split(delimiter).collect()
+}
+
+pub fn only_if_greater_hard(number: &i32, greater_than: &i32, otherwise: &i32) -> &i32 {
+    if number > greater_than {
+        number
+    } else {
+        otherwise
+    }
+}
+
+fn main() {}
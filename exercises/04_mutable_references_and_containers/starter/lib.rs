@@ -0,0 +1,13 @@
+// This is chapter 4's exercise as it's handed out, with the vector's
+// lifetime written in but not `new`'s (the docs are in `exercise/src/lib.rs`).
+// It's without `#[require_lifetimes]`, so the errors are rustc's own, which
+// are the ones a new compiler could change. `tests/starters.rs` checks that
+// it still fails with them.
+
+pub fn vector_set<'a>(vector: &mut Vec<&'a str>, loc: usize, new: &str) {
+    if let Some(element) = vector.get_mut(loc) {
+        *element = new;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+#[test]
+fn main() {
+    let first_sentence = String::from("I hate the surf and the sand.");
+    let second_sentence = String::from("I love the surf and the sand.");
+
+    let first_only = {
+        let third_sentence = String::from("I love the snow and the sand.");
+        let diff = find_difference(&first_sentence, &third_sentence);
+        diff.first_only
+    };
+
+    assert_eq!(first_only, vec!["hate", "surf"]);
+
+    let second_only = {
+        let third_sentence = String::from("I love the snow and the sand.");
+        let diff = find_difference(&third_sentence, &second_sentence);
+        diff.second_only
+    };
+
+    assert_eq!(second_only, vec!["surf"]);
+}
@@ -35,26 +35,5 @@ pub fn find_difference<'fst, 'snd>(
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    #[test]
-    fn main() {
-        let first_sentence = String::from("I hate the surf and the sand.");
-        let second_sentence = String::from("I love the surf and the sand.");
-
-        let first_only = {
-            let third_sentence = String::from("I love the snow and the sand.");
-            let diff = find_difference(&first_sentence, &third_sentence);
-            diff.first_only
-        };
-
-        assert_eq!(first_only, vec!["hate", "surf"]);
-
-        let second_only = {
-            let third_sentence = String::from("I love the snow and the sand.");
-            let diff = find_difference(&third_sentence, &second_sentence);
-            diff.second_only
-        };
-
-        assert_eq!(second_only, vec!["surf"]);
-    }
+    include!("../../shared_tests.rs");
 }
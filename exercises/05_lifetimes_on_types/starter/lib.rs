@@ -0,0 +1,18 @@
+// This is chapter 5's exercise as it's handed out, before `Difference` has
+// any lifetimes (the rest is in `exercise/src/lib.rs`). `tests/starters.rs`
+// checks that it still fails with the errors rustc gives for it.
+
+#[derive(Debug, Default)]
+pub struct Difference {
+    first_only: Vec<&str>,
+    second_only: Vec<&str>,
+}
+
+pub fn find_difference(sentence1: &str, sentence2: &str) -> Difference {
+    let mut diff = Difference::default();
+    diff.first_only.extend(sentence1.split(' '));
+    diff.second_only.extend(sentence2.split(' '));
+    diff
+}
+
+fn main() {}
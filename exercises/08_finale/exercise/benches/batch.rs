@@ -0,0 +1,56 @@
+//! Matches 100k short candidates one after the other, with and without
+//! reusing an `ExhaustiveScratch` between them.
+//!
+//! Run with `cargo bench --package ex08 --bench batch --features scratch`.
+
+use ex08::{ExhaustiveScratch, Matcher};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const CANDIDATES: usize = 100_000;
+
+fn main() {
+    let pattern = format!("{}.", "(a|aa|b)".repeat(4));
+    let candidates: Vec<String> = (0..CANDIDATES)
+        .map(|i| {
+            (0..6)
+                .map(|bit| if i >> bit & 1 == 0 { 'a' } else { 'b' })
+                .collect()
+        })
+        .collect();
+
+    let mut matcher = Matcher::new(&pattern).unwrap();
+    let mut scratch = ExhaustiveScratch::default();
+
+    for candidate in &candidates[..64] {
+        let fresh = matcher.match_string_exhaustive(candidate).len();
+        let reused = matcher
+            .match_string_exhaustive_with_scratch(candidate, &mut scratch)
+            .len();
+        assert_eq!(fresh, reused);
+    }
+
+    let fresh = time(|| {
+        for candidate in &candidates {
+            black_box(matcher.match_string_exhaustive(black_box(candidate)).len());
+        }
+    });
+    let reused = time(|| {
+        for candidate in &candidates {
+            black_box(
+                matcher
+                    .match_string_exhaustive_with_scratch(black_box(candidate), &mut scratch)
+                    .len(),
+            );
+        }
+    });
+
+    println!("fresh:   {fresh:?} per batch");
+    println!("scratch: {reused:?} per batch");
+}
+
+fn time(f: impl FnOnce()) -> Duration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
@@ -0,0 +1,24 @@
+//! Runs the exhaustive engine on a pattern made of many small groups, where
+//! nearly all of the time is spent pushing and popping frames.
+//!
+//! Run with `cargo bench --package ex08 --bench frames`.
+
+use ex08::Matcher;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 5;
+
+fn main() {
+    let pattern = format!("{}b", "(a|aa)".repeat(16));
+    let candidate = "a".repeat(32);
+    let mut matcher = Matcher::new(&pattern).unwrap();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(matcher.match_string_exhaustive(black_box(&candidate)).len());
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+
+    println!("{elapsed:?} per exhaustive match");
+}
@@ -0,0 +1,41 @@
+//! Checks candidates against a pattern with no groups, once through
+//! `is_match` (which uses the linear form) and once by running
+//! `match_string` and looking at how many tokens matched.
+//!
+//! Run with `cargo bench --package ex08 --bench linear`.
+
+use ex08::Matcher;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: usize = 200_000;
+
+fn main() {
+    let pattern = "abc...def...ghi";
+    let candidates = [
+        "abcxyzdefxyzghi",
+        "abcxyzdefxyzghj",
+        "abd",
+        "abc💪💪💪def💪💪💪ghi",
+    ];
+
+    // Three literals and six wildcards.
+    let token_count = 9;
+    let mut matcher = Matcher::new(pattern).unwrap();
+
+    let start = Instant::now();
+    let mut matches = 0;
+    for i in 0..ITERATIONS {
+        let candidate = black_box(candidates[i % candidates.len()]);
+        matches += usize::from(matcher.is_match(candidate));
+    }
+    println!("is_match:     {:?} ({matches} matches)", start.elapsed());
+
+    let start = Instant::now();
+    let mut matches = 0;
+    for i in 0..ITERATIONS {
+        let candidate = black_box(candidates[i % candidates.len()]);
+        matches += usize::from(matcher.match_string(candidate).len() == token_count);
+    }
+    println!("match_string: {:?} ({matches} matches)", start.elapsed());
+}
@@ -0,0 +1,53 @@
+//! Compares the sequential and the rayon-backed exhaustive engines on a
+//! pattern whose first group has 64 options, all of which are viable.
+//!
+//! Run with `cargo bench --package ex08 --features parallel`.
+
+use ex08::Matcher;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 5;
+
+fn main() {
+    let first_group = (1..=64)
+        .map(|length| "a".repeat(length))
+        .collect::<Vec<_>>()
+        .join("|");
+    let pattern = format!("({first_group}){}b", "(a|aa|aaa|aaaa)".repeat(5));
+    let candidate = "a".repeat(200);
+
+    let mut sequential_matcher = Matcher::new(&pattern).unwrap();
+    let mut parallel_matcher = Matcher::new(&pattern).unwrap();
+
+    assert_eq!(
+        sequential_matcher.match_string_exhaustive(&candidate),
+        parallel_matcher.match_string_exhaustive_parallel(&candidate)
+    );
+
+    let sequential = time(|| {
+        black_box(
+            sequential_matcher
+                .match_string_exhaustive(black_box(&candidate))
+                .len(),
+        );
+    });
+    let parallel = time(|| {
+        black_box(
+            parallel_matcher
+                .match_string_exhaustive_parallel(black_box(&candidate))
+                .len(),
+        );
+    });
+
+    println!("sequential: {sequential:?} per iteration");
+    println!("parallel:   {parallel:?} per iteration");
+}
+
+fn time(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
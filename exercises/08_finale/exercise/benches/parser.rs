@@ -0,0 +1,25 @@
+//! Parses a 100 KB pattern whose group has 10k options, which is mostly
+//! spent scanning for the next metacharacter.
+//!
+//! Run with `cargo bench --package ex08 --bench parser`, and again with
+//! `--features memchr` to compare.
+
+use ex08::Matcher;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 20;
+
+fn main() {
+    let options: Vec<String> = (0..10_000).map(|i| format!("option{i:04}")).collect();
+    let pattern = format!("{}({}).", "x".repeat(10_000), options.join("|"));
+    assert!(pattern.len() >= 100_000);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(Matcher::new(black_box(&pattern)));
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+
+    println!("{elapsed:?} per parse");
+}
@@ -0,0 +1,40 @@
+//! Scans a 1 MB haystack for a pattern starting with a 100-byte literal.
+//!
+//! Run with `cargo bench --package ex08 --bench raw_text`, once with and
+//! once without `--features memchr`, to compare the two scanning strategies.
+
+use ex08::Matcher;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 5;
+
+fn main() {
+    let literal: String = (0..100).map(|i| (b'a' + (i % 26) as u8) as char).collect();
+    let pattern = format!("{literal}.(x|y)");
+
+    // A cheap deterministic generator, so that every run scans the same text.
+    let mut state: u32 = 12345;
+    let mut haystack = String::with_capacity(1 << 20);
+    while haystack.len() < 1 << 20 {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        haystack.push((b'a' + (state >> 16) as u8 % 26) as char);
+        if (state >> 8).is_multiple_of(50_000) {
+            haystack.push_str(&literal);
+            haystack.push_str("_x");
+        }
+    }
+
+    let mut matcher = Matcher::new(&pattern).unwrap();
+    let start = Instant::now();
+    let mut found = 0;
+    for _ in 0..ITERATIONS {
+        found = black_box(matcher.find_iter(black_box(&haystack)).count());
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+
+    println!(
+        "memchr: {}, {found} matches, {elapsed:?} per scan",
+        cfg!(feature = "memchr")
+    );
+}
@@ -0,0 +1,152 @@
+/* The finale matcher, for C. See src/ffi.rs. */
+
+#ifndef LK_MATCHER_H
+#define LK_MATCHER_H
+
+/* Written by `cargo xtask ffi-header`; don't change it by hand. */
+
+#include <stddef.h>
+
+/**
+ * `lk_matcher_match` matched the candidate (as far as it could).
+ */
+#define LK_OK 0
+
+/**
+ * A pointer was null, or a string wasn't UTF-8.
+ */
+#define LK_INVALID_ARGUMENT -1
+
+/**
+ * The matcher panicked, which is a bug.
+ */
+#define LK_PANICKED -2
+
+/**
+ * A group of the pattern has an option with nothing in it, like `(a|)`.
+ */
+#define LK_EMPTY_OPTION 1
+
+/**
+ * A group of the pattern has only one option, like `(a)`.
+ */
+#define LK_ONE_OPTION 2
+
+/**
+ * A group of the pattern has no `)` to close it, like `(a|b`.
+ */
+#define LK_UNCLOSED_GROUP 3
+
+/**
+ * A pattern, made by `lk_matcher_new`, and freed by `lk_matcher_free`.
+ */
+typedef struct LkMatcher LkMatcher;
+
+/**
+ * Why a pattern didn't parse, filled in by `lk_matcher_parse`.
+ */
+typedef struct LkParseError {
+  /**
+   * `LK_EMPTY_OPTION`, `LK_ONE_OPTION` or `LK_UNCLOSED_GROUP`.
+   */
+  int kind;
+  /**
+   * The byte offset in the pattern where the problem is.
+   */
+  size_t offset;
+} LkParseError;
+
+/**
+ * What one token matched, in bytes of the candidate.
+ */
+typedef struct LkTokenMatch {
+  size_t start;
+  size_t len;
+  /**
+   * Which token of the pattern it is, counting from 0.
+   */
+  size_t token_index;
+} LkTokenMatch;
+
+/**
+ * What a pattern matched, filled in by `lk_matcher_match`, and freed by
+ * `lk_match_result_free`.
+ */
+typedef struct LkMatchResult {
+  /**
+   * How many tokens the pattern has.
+   */
+  size_t token_count;
+  /**
+   * How many of them matched, which is how many `matches` there are. If
+   * it's `token_count`, the whole pattern matched.
+   */
+  size_t matched_count;
+  /**
+   * This is null if nothing matched.
+   */
+  struct LkTokenMatch *matches;
+} LkMatchResult;
+
+/**
+ * Given a pattern, as a C string, return it ready to match against, or null
+ * if it's null, isn't UTF-8 or doesn't parse.
+ *
+ * # Safety
+ *
+ * `pattern` has to be null, or point to a C string.
+ */
+struct LkMatcher *lk_matcher_new(const char *pattern);
+
+/**
+ * This is `lk_matcher_new`, but when the pattern doesn't parse, it also
+ * fills in `error` (unless it's null) with why, and where in the pattern the
+ * problem is. Otherwise, `error` is left alone.
+ *
+ * # Safety
+ *
+ * `pattern` has to be null, or point to a C string. `error` has to be null,
+ * or point to an `LkParseError` that can be written to.
+ */
+struct LkMatcher *lk_matcher_parse(const char *pattern, struct LkParseError *error);
+
+/**
+ * Free a matcher made by `lk_matcher_new`. It does nothing with null.
+ *
+ * # Safety
+ *
+ * `matcher` has to be null, or from `lk_matcher_new`, and not freed yet.
+ */
+void lk_matcher_free(struct LkMatcher *matcher);
+
+/**
+ * Match `candidate`, a C string, against `matcher`, greedily, from the
+ * start, and fill in `out` with what matched, returning `LK_OK`. Free `out`
+ * with `lk_match_result_free` afterwards. If a pointer is null, or
+ * `candidate` isn't UTF-8, it returns `LK_INVALID_ARGUMENT` instead, and if
+ * it panics, `LK_PANICKED`, and either way, `out` (if it isn't null) is
+ * left empty, which doesn't need freeing.
+ *
+ * # Safety
+ *
+ * `matcher` has to be null, or from `lk_matcher_new`, and not freed yet.
+ * `candidate` has to be null, or point to a C string. `out` has to be null,
+ * or point to an `LkMatchResult` that can be written to, and anything in it
+ * already is overwritten without being freed.
+ */
+int lk_matcher_match(const struct LkMatcher *matcher,
+                     const char *candidate,
+                     struct LkMatchResult *out);
+
+/**
+ * Free what `lk_matcher_match` put in `result`, and leave it empty. It does
+ * nothing with null, or with a result that's empty already.
+ *
+ * # Safety
+ *
+ * `result` has to be null, or point to an `LkMatchResult` that
+ * `lk_matcher_match` filled in.
+ */
+void lk_match_result_free(struct LkMatchResult *result);
+
+#endif  /* LK_MATCHER_H */
@@ -0,0 +1,323 @@
+//! A prompt for trying patterns out. The first line is the pattern, and
+//! each line after it is a candidate, which is matched greedily (with
+//! `match_string`), exhaustively (with `match_string_exhaustive`), or both,
+//! showing what each token matched under the candidate.
+//!
+//! Lines starting with `:` are commands: `:pattern P` changes the pattern,
+//! `:strategy greedy|exhaustive|both` changes how candidates are matched,
+//! and `:quit` stops.
+//!
+//! Run with `cargo run -- matcher-repl` from the root of the kata, or
+//! `cargo run --package ex08 --bin matcher-repl`.
+
+use ex08::{Matcher, MatcherToken};
+use require_lifetimes::require_lifetimes;
+use std::io::{BufRead, Write};
+
+/// How candidates are matched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Strategy {
+    Greedy,
+    Exhaustive,
+    Both,
+}
+
+/// What a line asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum Command<'line> {
+    /// Use this pattern from now on.
+    Pattern(&'line str),
+    Strategy(Strategy),
+    Quit,
+    /// Match this against the pattern (or make it the pattern, if there
+    /// isn't one yet).
+    Candidate(&'line str),
+}
+
+/// Given a line, without its line ending, return what it asks for.
+#[require_lifetimes]
+fn parse<'line>(line: &'line str) -> Result<Command<'line>, String> {
+    let Some(command) = line.strip_prefix(':') else {
+        return Ok(Command::Candidate(line));
+    };
+    let (name, argument) = command.split_once(' ').unwrap_or((command, ""));
+    match (name, argument) {
+        ("pattern", "") => Err("say which pattern, like `:pattern ab(c|d)`".to_string()),
+        ("pattern", pattern) => Ok(Command::Pattern(pattern)),
+        ("strategy", "greedy") => Ok(Command::Strategy(Strategy::Greedy)),
+        ("strategy", "exhaustive") => Ok(Command::Strategy(Strategy::Exhaustive)),
+        ("strategy", "both") => Ok(Command::Strategy(Strategy::Both)),
+        ("strategy", _) => Err("the strategies are greedy, exhaustive and both".to_string()),
+        ("quit", "") => Ok(Command::Quit),
+        _ => Err(format!(
+            "there's no `:{name}`; try :pattern, :strategy or :quit"
+        )),
+    }
+}
+
+/// Where a session is up to.
+#[derive(Debug, PartialEq, Eq)]
+struct Session {
+    /// The pattern candidates are matched against, once there is one.
+    pattern: Option<String>,
+    strategy: Strategy,
+    /// Whether it's been asked to stop.
+    done: bool,
+}
+
+impl Session {
+    fn new() -> Session {
+        Session {
+            pattern: None,
+            strategy: Strategy::Both,
+            done: false,
+        }
+    }
+
+    /// Given a line, do what it asks, and return what to print. A pattern
+    /// that doesn't parse is an error, and the one before it is kept.
+    #[require_lifetimes]
+    fn handle<'session, 'line>(&'session mut self, line: &'line str) -> Result<String, String> {
+        match parse(line)? {
+            Command::Candidate(pattern) if self.pattern.is_none() => self.set_pattern(pattern),
+            Command::Pattern(pattern) => self.set_pattern(pattern),
+            Command::Candidate(candidate) => {
+                let pattern = self.pattern.as_deref().unwrap_or_default();
+                Ok(try_candidate(pattern, candidate, self.strategy))
+            }
+            Command::Strategy(strategy) => {
+                self.strategy = strategy;
+                Ok(String::new())
+            }
+            Command::Quit => {
+                self.done = true;
+                Ok(String::new())
+            }
+        }
+    }
+
+    /// Given a pattern, use it from now on, and return how it was parsed. If
+    /// it doesn't parse, the error says why, with a caret under where.
+    #[require_lifetimes]
+    fn set_pattern<'session, 'pattern>(
+        &'session mut self,
+        pattern: &'pattern str,
+    ) -> Result<String, String> {
+        let explained = Matcher::parse(pattern)
+            .map_err(|error| {
+                format!(
+                    "`{pattern}` isn't a pattern: {error}\n    {pattern}\n    {}^",
+                    " ".repeat(pattern[..error.offset].chars().count())
+                )
+            })?
+            .explain();
+        self.pattern = Some(pattern.to_string());
+        Ok(explained)
+    }
+}
+
+/// Given a pattern, a candidate and how to match it, return what matched,
+/// and (when it's matched both ways) whether the two differ.
+#[require_lifetimes]
+fn try_candidate<'pattern, 'candidate>(
+    pattern: &'pattern str,
+    candidate: &'candidate str,
+    strategy: Strategy,
+) -> String {
+    // `set_pattern` has checked it parses already. Each match borrows its
+    // matcher for as long as the result is kept, so there's one for each.
+    let mut greedy_matcher = Matcher::new(pattern).expect("the pattern was checked");
+    let mut exhaustive_matcher = Matcher::new(pattern).expect("the pattern was checked");
+    let greedy = matches!(strategy, Strategy::Greedy | Strategy::Both)
+        .then(|| greedy_matcher.match_string(candidate));
+    let exhaustive = matches!(strategy, Strategy::Exhaustive | Strategy::Both)
+        .then(|| exhaustive_matcher.match_string_exhaustive(candidate));
+
+    let mut output = String::new();
+    if let Some(greedy) = &greedy {
+        output.push_str(&format!("greedy, {} tokens:\n", greedy.len()));
+        output.push_str(&render(candidate, greedy));
+    }
+    if let Some(exhaustive) = &exhaustive {
+        output.push_str(&format!("exhaustive, {} tokens:\n", exhaustive.len()));
+        output.push_str(&render(candidate, exhaustive));
+    }
+    if let (Some(greedy), Some(exhaustive)) = (&greedy, &exhaustive) {
+        output.push_str(if greedy == exhaustive {
+            "They're the same.\n"
+        } else {
+            "They differ.\n"
+        });
+    }
+    output
+}
+
+/// Given a candidate and what each token matched in it (from the start, one
+/// after another), return the candidate with each token's match marked
+/// under it, one per line.
+#[require_lifetimes]
+fn render<'candidate, 'matched, 'pattern>(
+    candidate: &'candidate str,
+    matched: &'matched [(&'matched MatcherToken<'pattern>, &'candidate str)],
+) -> String {
+    let mut output = format!("  {candidate}\n");
+    let mut column = 0;
+    for (token, text) in matched {
+        let width = text.chars().count();
+        output.push_str(&format!(
+            "  {}{}{} {token:?}\n",
+            " ".repeat(column),
+            "^".repeat(width),
+            " ".repeat(candidate.chars().count().saturating_sub(column + width)),
+        ));
+        column += width;
+    }
+    output
+}
+
+fn main() {
+    let mut session = Session::new();
+    println!("Type a pattern, then candidates to match against it, or :quit.");
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    while !session.done {
+        print!("> ");
+        let _ = std::io::stdout().flush();
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        match session.handle(&line) {
+            Ok(output) => print!("{output}"),
+            Err(error) => println!("error: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(parse(":pattern ab.c"), Ok(Command::Pattern("ab.c")));
+        // Everything after the first space is the pattern, spaces and all.
+        assert_eq!(parse(":pattern a b "), Ok(Command::Pattern("a b ")));
+        assert_eq!(
+            parse(":strategy greedy"),
+            Ok(Command::Strategy(Strategy::Greedy))
+        );
+        assert_eq!(
+            parse(":strategy exhaustive"),
+            Ok(Command::Strategy(Strategy::Exhaustive))
+        );
+        assert_eq!(
+            parse(":strategy both"),
+            Ok(Command::Strategy(Strategy::Both))
+        );
+        assert_eq!(parse(":quit"), Ok(Command::Quit));
+        assert_eq!(parse("abc"), Ok(Command::Candidate("abc")));
+        assert_eq!(parse(""), Ok(Command::Candidate("")));
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        assert!(parse(":pattern").is_err());
+        assert!(parse(":strategy").is_err());
+        assert!(parse(":strategy fastest").is_err());
+        assert!(parse(":quit now").is_err());
+        assert_eq!(
+            parse(":help"),
+            Err("there's no `:help`; try :pattern, :strategy or :quit".to_string())
+        );
+    }
+
+    #[test]
+    fn first_line_is_the_pattern() {
+        let mut session = Session::new();
+        assert_eq!(
+            session.handle("ab."),
+            Ok("1. RawText(\"ab\")\n2. WildCard\n".to_string())
+        );
+        assert_eq!(session.pattern.as_deref(), Some("ab."));
+
+        // After that, lines are candidates.
+        let output = session.handle("abc").unwrap();
+        assert!(output.starts_with("greedy, 2 tokens:\n"), "{output}");
+        assert_eq!(session.pattern.as_deref(), Some("ab."));
+    }
+
+    #[test]
+    fn bad_patterns_keep_the_old_one() {
+        let mut session = Session::new();
+        assert!(session.handle("(a|b").is_err());
+        assert_eq!(session.pattern, None);
+
+        session.handle(":pattern a.").unwrap();
+        assert_eq!(
+            session.handle(":pattern (a|"),
+            Err(
+                "`(a|` isn't a pattern: this group isn't closed (at byte 0)\n    (a|\n    ^"
+                    .to_string()
+            )
+        );
+        assert_eq!(session.pattern.as_deref(), Some("a."));
+        // The caret counts characters, not bytes.
+        assert_eq!(
+            session.handle(":pattern éb(c|"),
+            Err(
+                "`éb(c|` isn't a pattern: this group isn't closed (at byte 3)\n    éb(c|\n      ^"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn strategy_and_quit_change_the_session() {
+        let mut session = Session::new();
+        assert_eq!(session.strategy, Strategy::Both);
+        assert_eq!(session.handle(":strategy greedy"), Ok(String::new()));
+        assert_eq!(session.strategy, Strategy::Greedy);
+        session.handle(":pattern a").unwrap();
+        assert_eq!(
+            session.handle("ab").unwrap(),
+            "greedy, 1 tokens:\n  ab\n  ^  RawText(\"a\")\n"
+        );
+
+        assert!(!session.done);
+        session.handle(":quit").unwrap();
+        assert!(session.done);
+    }
+
+    #[test]
+    fn says_whether_greedy_and_exhaustive_differ() {
+        let mut session = Session::new();
+        session.handle(":pattern (a|ab)c").unwrap();
+        assert_eq!(
+            session.handle("abc").unwrap(),
+            concat!(
+                "greedy, 1 tokens:\n",
+                "  abc\n",
+                "  ^   OneOfText([\"a\", \"ab\"])\n",
+                "exhaustive, 2 tokens:\n",
+                "  abc\n",
+                "  ^^  OneOfText([\"a\", \"ab\"])\n",
+                "    ^ RawText(\"c\")\n",
+                "They differ.\n",
+            )
+        );
+        assert!(session
+            .handle("ac")
+            .unwrap()
+            .ends_with("They're the same.\n"));
+    }
+
+    #[test]
+    fn renders_by_character() {
+        let pattern = MatcherToken::WildCard;
+        let raw = MatcherToken::RawText("b");
+        assert_eq!(
+            render("💪bc", &[(&pattern, "💪"), (&raw, "b")]),
+            "  💪bc\n  ^   WildCard\n   ^  RawText(\"b\")\n"
+        );
+    }
+}
@@ -0,0 +1,642 @@
+//! `matchgrep PATTERN [FILE...]` prints each line (of the files, or of stdin
+//! if there aren't any) that the pattern matches somewhere in, the way
+//! `grep` does. `--full` only counts lines the whole of which match,
+//! `--invert` prints the lines that don't match instead, and `--count`
+//! prints how many lines there were, rather than the lines.
+//!
+//! `matchgrep -f PATTERNS [FILE...]` reads the patterns from a file instead,
+//! one to a line, as `MatcherSet::from_lines` does, and prints the lines that
+//! any of them match. If one doesn't parse, the error says which line of
+//! `PATTERNS` it was on.
+//!
+//! `--json` prints each line as a JSON object instead, one to a line, like
+//! `{"file":"a.txt","line_number":3,"line":"xabc","start_byte":1,"match":{...}}`,
+//! where `match` is what `Matcher::to_json` says matched from `start_byte`
+//! on (so the offsets in it count from there). `file` is `null` for stdin.
+//! Without `--full`, that's the first place the whole pattern matches, and
+//! `start_byte` and `match` are `null` for a line it doesn't match in (with
+//! `--invert`). With `--full`, it's the best match from the start of the
+//! line, even if it's not complete. With `-f`, there's also `"patterns"`,
+//! the line numbers of the patterns that matched, and `match` is for the
+//! first of them (or the first pattern, if none did).
+//!
+//! `--color=always` colours in what each token matched in the lines it
+//! prints, and dims the rest (see `ex08::highlight`), `--color=never`
+//! doesn't, and `--color=auto`, which is the default, does when stdout is a
+//! terminal, unless `NO_COLOR` is set. Lines printed by `--invert` have
+//! nothing to colour in, and `--json` and `--count` are never coloured. With
+//! `-f`, it's what the first pattern that matched matched that's coloured.
+//!
+//! It exits with 0 if any line was printed (or counted), 1 if none were, and
+//! 2 if a pattern doesn't parse, a file can't be read, or stdout can't be
+//! written to. If stdout is a pipe that's closed early (as with `| head`),
+//! it stops quietly instead, with 0, since it had found something to print.
+//! A line that isn't UTF-8 can't be matched, so it's skipped, with a
+//! warning.
+
+use ex08::{highlight, Matcher, MatcherSet, ParseError};
+use require_lifetimes::require_lifetimes;
+use std::io::{BufRead, ErrorKind, IsTerminal, Write};
+use std::process::exit;
+
+/// When to colour in what matched.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+enum Color {
+    /// Only when printing to a terminal.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// What to look for, and how.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Options<'args> {
+    /// There's either a pattern, or a file of them.
+    pattern: Option<&'args str>,
+    patterns_file: Option<&'args str>,
+    files: Vec<&'args str>,
+    /// Whether a line has to match as a whole, rather than anywhere in it.
+    full: bool,
+    count: bool,
+    invert: bool,
+    json: bool,
+    color: Color,
+}
+
+/// Given the arguments, without the program's name, return what they ask for.
+#[require_lifetimes]
+fn parse_args<'args>(args: &'args [String]) -> Result<Options<'args>, String> {
+    let mut options = Options::default();
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--full" => options.full = true,
+            "--count" => options.count = true,
+            "--invert" => options.invert = true,
+            "--json" => options.json = true,
+            "--color=auto" => options.color = Color::Auto,
+            "--color=always" => options.color = Color::Always,
+            "--color=never" => options.color = Color::Never,
+            "-f" => {
+                let file = args.next().ok_or("`-f` needs a file of patterns")?;
+                options.patterns_file = Some(file.as_str());
+            }
+            flag if flag.starts_with("--color") => {
+                return Err(format!(
+                    "`{flag}` should be `--color=auto`, `--color=always` or `--color=never`"
+                ))
+            }
+            flag if flag.starts_with("--") => return Err(format!("there's no `{flag}`")),
+            _ => positional.push(arg.as_str()),
+        }
+    }
+    // Like grep, with `-f` every other argument is a file to search.
+    let mut positional = positional.into_iter();
+    if options.patterns_file.is_none() {
+        options.pattern = Some(positional.next().ok_or("say what to look for")?);
+    }
+    options.files = positional.collect();
+    if options.json && options.count {
+        return Err("`--json` and `--count` don't go together".to_string());
+    }
+    Ok(options)
+}
+
+/// Given the options, and what's in the file of patterns (if they name one),
+/// return the patterns they ask for. If one doesn't parse, return where it
+/// was (to go in front of the error), the pattern, and why.
+#[require_lifetimes]
+fn parse_patterns<'options, 'p>(
+    options: &'options Options<'p>,
+    patterns: &'p str,
+) -> Result<MatcherSet<'p>, (String, &'p str, ParseError)> {
+    match (options.patterns_file, options.pattern) {
+        (Some(file), _) => MatcherSet::from_lines(patterns).map_err(|(line, error)| {
+            let pattern = patterns.lines().nth(line - 1).unwrap_or_default();
+            (format!("{file}:{line}: "), pattern, error)
+        }),
+        (None, pattern) => {
+            let pattern = pattern.unwrap_or_default();
+            Matcher::parse(pattern)
+                .map(MatcherSet::from)
+                .map_err(|error| (String::new(), pattern, error))
+        }
+    }
+}
+
+/// Given the patterns, the options and a line, return the line numbers of the
+/// patterns that match it.
+#[require_lifetimes]
+fn matching<'set, 'pattern, 'options, 'args, 'line>(
+    set: &'set mut MatcherSet<'pattern>,
+    options: &'options Options<'args>,
+    line: &'line str,
+) -> Vec<usize> {
+    if options.full {
+        set.matches(line)
+    } else {
+        set.matches_anywhere(line)
+    }
+}
+
+/// Given the patterns, the options and a line they select, return the line
+/// to print, with what the first pattern that matched matched coloured in if
+/// they say to. `Color::Auto` has to have been settled one way or the other
+/// already.
+#[require_lifetimes]
+fn print_line<'set, 'pattern, 'options, 'args, 'line>(
+    set: &'set mut MatcherSet<'pattern>,
+    options: &'options Options<'args>,
+    line: &'line str,
+) -> String {
+    let color = options.color == Color::Always;
+    if !color || options.invert {
+        return line.to_string();
+    }
+    let first = matching(set, options, line).first().copied();
+    let Some(matcher) = first.and_then(|first| set.get_mut(first)) else {
+        return line.to_string();
+    };
+    if options.full {
+        highlight(line, 0, &matcher.match_string_exhaustive(line), color)
+    } else {
+        match matcher.find_iter(line).next() {
+            Some((start, result)) => highlight(line, start, &result, color),
+            None => line.to_string(),
+        }
+    }
+}
+
+/// Given the patterns, another copy of them (to describe what the first
+/// matched, while that's still borrowing it), the options, a file's name
+/// (or none for stdin), a line and its number, return the line as JSON.
+#[require_lifetimes]
+fn json_line<'set, 'describer, 'pattern, 'options, 'args, 'file, 'line>(
+    set: &'set mut MatcherSet<'pattern>,
+    describer: &'describer MatcherSet<'pattern>,
+    options: &'options Options<'args>,
+    file: Option<&'file str>,
+    line: &'line str,
+    number: usize,
+) -> String {
+    let matched = matching(set, options, line);
+    let described = matched
+        .first()
+        .or(set.lines().first())
+        .copied()
+        .and_then(|pattern| Some((set.get_mut(pattern)?, describer.get(pattern)?)));
+    let found = described.and_then(|(matcher, describer)| {
+        let found = if options.full {
+            Some((0, matcher.match_string_exhaustive(line)))
+        } else {
+            matcher.find_iter(line).next()
+        };
+        found.map(|(start, result)| (start, describer.to_json(&result)))
+    });
+    let (start, matched_json) = match found {
+        Some((start, json)) => (start.to_string(), json),
+        None => ("null".to_string(), "null".to_string()),
+    };
+    let quote = |text| serde_json::to_string(text).expect("strings always serialize");
+    let patterns = if options.patterns_file.is_some() {
+        format!(
+            ",\"patterns\":{}",
+            serde_json::to_string(&matched).expect("numbers always serialize")
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        "{{\"file\":{},\"line_number\":{number},\"line\":{},\"start_byte\":{start}{patterns},\"match\":{matched_json}}}",
+        file.map_or("null".to_string(), quote),
+        quote(line),
+    )
+}
+
+/// Why a search stopped early.
+#[derive(Debug)]
+enum SearchError {
+    /// An input couldn't be read, which stops the search of just that one.
+    Read(String),
+    /// The output couldn't be written to, which stops everything.
+    Write(std::io::Error),
+}
+
+/// Given the patterns, another copy of them (for `--json`), the options,
+/// something to read lines from and its file's name (or none for stdin),
+/// print the lines it selects (or how many there were) to `out`, with
+/// `prefix` in front, and return how many there were. Lines that aren't
+/// UTF-8 are skipped, with a warning, but if the input can't be read, or
+/// `out` can't be written to, it stops there.
+#[require_lifetimes]
+fn search<'set, 'describer, 'pattern, 'options, 'args, 'file, 'prefix, 'out>(
+    set: &'set mut MatcherSet<'pattern>,
+    describer: &'describer MatcherSet<'pattern>,
+    options: &'options Options<'args>,
+    mut input: impl BufRead,
+    file: Option<&'file str>,
+    prefix: &'prefix str,
+    out: &'out mut impl Write,
+) -> Result<usize, SearchError> {
+    let name = file.unwrap_or("stdin");
+    let mut selected = 0;
+    let mut line = Vec::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        let read = input
+            .read_until(b'\n', &mut line)
+            .map_err(|error| SearchError::Read(format!("couldn't read {name}: {error}")))?;
+        if read == 0 {
+            break;
+        }
+        number += 1;
+
+        let bytes = line.strip_suffix(b"\n").unwrap_or(&line);
+        let Ok(text) = std::str::from_utf8(bytes) else {
+            eprintln!("matchgrep: {name}:{number}: skipping a line that isn't UTF-8");
+            continue;
+        };
+        if matching(set, options, text).is_empty() == options.invert {
+            selected += 1;
+            if options.json {
+                let json = json_line(set, describer, options, file, text, number);
+                writeln!(out, "{json}").map_err(SearchError::Write)?;
+            } else if !options.count {
+                let text = print_line(set, options, text);
+                writeln!(out, "{prefix}{text}").map_err(SearchError::Write)?;
+            }
+        }
+    }
+    if options.count {
+        writeln!(out, "{prefix}{selected}").map_err(SearchError::Write)?;
+    }
+    Ok(selected)
+}
+
+/// Given how searching one input went, add how many lines it selected to
+/// `selected`, or say what went wrong, and return the exit code to stop
+/// with, if there's no use searching the rest because nothing more can be
+/// printed. An input that can't be read only sets `failed`.
+#[require_lifetimes]
+fn tally<'selected, 'failed>(
+    found: Result<usize, SearchError>,
+    selected: &'selected mut usize,
+    failed: &'failed mut bool,
+) -> Option<i32> {
+    match found {
+        Ok(found) => *selected += found,
+        Err(SearchError::Read(error)) => {
+            eprintln!("matchgrep: {error}");
+            *failed = true;
+        }
+        // Whatever it's piped to has all it wants, and since something was
+        // being printed, something was selected.
+        Err(SearchError::Write(error)) if error.kind() == ErrorKind::BrokenPipe => return Some(0),
+        Err(SearchError::Write(error)) => {
+            eprintln!("matchgrep: couldn't write to stdout: {error}");
+            return Some(2);
+        }
+    }
+    None
+}
+
+/// Given the arguments, search the way they ask, and return the exit code.
+#[require_lifetimes]
+fn run<'args>(args: &'args [String]) -> i32 {
+    let mut options = match parse_args(args) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("matchgrep: {error}");
+            eprintln!(
+                "usage: matchgrep [--full] [--count] [--invert] [--json] [--color=WHEN] (PATTERN | -f PATTERNS) [FILE...]"
+            );
+            return 2;
+        }
+    };
+    let patterns = match options.patterns_file {
+        Some(file) => match std::fs::read_to_string(file) {
+            Ok(patterns) => patterns,
+            Err(error) => {
+                eprintln!("matchgrep: couldn't read {file}: {error}");
+                return 2;
+            }
+        },
+        None => String::new(),
+    };
+    let mut set = match parse_patterns(&options, &patterns) {
+        Ok(set) => set,
+        Err((place, pattern, error)) => {
+            eprintln!("matchgrep: {place}`{pattern}` isn't a pattern: {error}");
+            eprintln!("    {pattern}");
+            eprintln!(
+                "    {}^",
+                " ".repeat(pattern[..error.offset].chars().count())
+            );
+            return 2;
+        }
+    };
+    let describer = parse_patterns(&options, &patterns).expect("they're parsed already");
+
+    let stdout = std::io::stdout();
+    if options.color == Color::Auto {
+        options.color = if stdout.is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+            Color::Always
+        } else {
+            Color::Never
+        };
+    }
+    let mut out = stdout.lock();
+    let mut selected = 0;
+    let mut failed = false;
+    if options.files.is_empty() {
+        let stdin = std::io::stdin();
+        let found = search(
+            &mut set,
+            &describer,
+            &options,
+            stdin.lock(),
+            None,
+            "",
+            &mut out,
+        );
+        if let Some(code) = tally(found, &mut selected, &mut failed) {
+            return code;
+        }
+    }
+    for file in &options.files {
+        // Like grep, each line says which file it's from when there's more
+        // than one.
+        let prefix = if options.files.len() > 1 {
+            format!("{file}:")
+        } else {
+            String::new()
+        };
+        let found = std::fs::File::open(file)
+            .map_err(|error| SearchError::Read(format!("couldn't read {file}: {error}")))
+            .and_then(|input| {
+                let input = std::io::BufReader::new(input);
+                search(
+                    &mut set,
+                    &describer,
+                    &options,
+                    input,
+                    Some(file),
+                    &prefix,
+                    &mut out,
+                )
+            });
+        if let Some(code) = tally(found, &mut selected, &mut failed) {
+            return code;
+        }
+    }
+
+    if failed {
+        2
+    } else if selected > 0 {
+        0
+    } else {
+        1
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    exit(run(&args));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_the_arguments() {
+        let given = args(&["--count", "a.b", "one", "--invert", "two"]);
+        assert_eq!(
+            parse_args(&given),
+            Ok(Options {
+                pattern: Some("a.b"),
+                patterns_file: None,
+                files: vec!["one", "two"],
+                full: false,
+                count: true,
+                invert: true,
+                json: false,
+                color: Color::Auto,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--color=never", "a"])).map(|options| options.color),
+            Ok(Color::Never)
+        );
+        assert!(parse_args(&args(&["--color", "a"])).is_err());
+        assert!(parse_args(&args(&["--full"])).is_err());
+        assert!(parse_args(&args(&["a", "-f"])).is_err());
+        assert!(parse_args(&args(&["a", "--json", "--count"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["a", "--colour"])),
+            Err("there's no `--colour`".to_string())
+        );
+    }
+
+    #[test]
+    fn with_a_file_of_patterns_every_argument_is_a_file() {
+        let given = args(&["one", "-f", "patterns.txt", "two"]);
+        let options = parse_args(&given).unwrap();
+        assert_eq!(options.pattern, None);
+        assert_eq!(options.patterns_file, Some("patterns.txt"));
+        assert_eq!(options.files, ["one", "two"]);
+    }
+
+    #[test]
+    fn says_where_a_pattern_does_not_parse() {
+        let given = args(&["-f", "patterns.txt"]);
+        let options = parse_args(&given).unwrap();
+        let (place, pattern, error) = parse_patterns(&options, "a\n# b(\nc(d|e\n").unwrap_err();
+        assert_eq!(place, "patterns.txt:3: ");
+        assert_eq!(pattern, "c(d|e");
+        assert_eq!(error.offset, 1);
+
+        let given = args(&["c(d|e"]);
+        let options = parse_args(&given).unwrap();
+        let (place, pattern, _) = parse_patterns(&options, "").unwrap_err();
+        assert_eq!((place.as_str(), pattern), ("", "c(d|e"));
+    }
+
+    #[test]
+    fn searches_lines() {
+        let mut set = MatcherSet::from(Matcher::new("b(c|d)").unwrap());
+        let describer = MatcherSet::from(Matcher::new("b(c|d)").unwrap());
+        let given = args(&["b(c|d)"]);
+        let mut options = parse_args(&given).unwrap();
+        let input = "abc\nbd\nbe\n\u{1F4AA}bd".as_bytes();
+
+        let mut out = Vec::new();
+        let found = search(
+            &mut set,
+            &describer,
+            &options,
+            input,
+            Some("input"),
+            "",
+            &mut out,
+        );
+        assert_eq!(found.unwrap(), 3);
+        assert_eq!(String::from_utf8(out).unwrap(), "abc\nbd\n\u{1F4AA}bd\n");
+
+        options.full = true;
+        let mut out = Vec::new();
+        search(
+            &mut set,
+            &describer,
+            &options,
+            input,
+            Some("input"),
+            "x:",
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "x:bd\n");
+
+        options.invert = true;
+        options.count = true;
+        let mut out = Vec::new();
+        search(
+            &mut set,
+            &describer,
+            &options,
+            input,
+            Some("input"),
+            "",
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "3\n");
+    }
+
+    #[test]
+    fn write_errors_stop_the_search() {
+        /// This is stdout once whatever it was piped to has gone.
+        struct Closed;
+
+        impl Write for Closed {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut set = MatcherSet::from(Matcher::new("b").unwrap());
+        let describer = MatcherSet::from(Matcher::new("b").unwrap());
+        let given = args(&["b"]);
+        let options = parse_args(&given).unwrap();
+        let found = search(
+            &mut set,
+            &describer,
+            &options,
+            "b\nb\n".as_bytes(),
+            None,
+            "",
+            &mut Closed,
+        );
+        let Err(SearchError::Write(error)) = found else {
+            panic!("expected a write error, not {found:?}");
+        };
+        assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+
+        let (mut selected, mut failed) = (0, false);
+        let found = Err(SearchError::Write(error));
+        assert_eq!(tally(found, &mut selected, &mut failed), Some(0));
+        let found = Err(SearchError::Write(ErrorKind::StorageFull.into()));
+        assert_eq!(tally(found, &mut selected, &mut failed), Some(2));
+        let found = Err(SearchError::Read("couldn't read x".to_string()));
+        assert_eq!(tally(found, &mut selected, &mut failed), None);
+        assert!(failed);
+    }
+
+    #[test]
+    fn colours_in_what_matched() {
+        let mut set = MatcherSet::from(Matcher::new("b(c|d)").unwrap());
+        let given = args(&["--color=always", "b(c|d)"]);
+        let mut options = parse_args(&given).unwrap();
+
+        assert_eq!(
+            print_line(&mut set, &options, "abdx"),
+            "\x1b[2ma\x1b[0m\x1b[1;31mb\x1b[0m\x1b[1;32md\x1b[0m\x1b[2mx\x1b[0m"
+        );
+
+        // There's nothing to colour in a line that doesn't match.
+        options.invert = true;
+        assert_eq!(print_line(&mut set, &options, "ax"), "ax");
+
+        options.invert = false;
+        options.color = Color::Never;
+        assert_eq!(print_line(&mut set, &options, "abdx"), "abdx");
+    }
+
+    #[test]
+    fn lines_as_json() {
+        let mut set = MatcherSet::from(Matcher::new("b(c|d)").unwrap());
+        let describer = MatcherSet::from(Matcher::new("b(c|d)").unwrap());
+        let given = args(&["--json", "b(c|d)"]);
+        let mut options = parse_args(&given).unwrap();
+
+        assert_eq!(
+            json_line(&mut set, &describer, &options, None, "ab\"d", 7),
+            r#"{"file":null,"line_number":7,"line":"ab\"d","start_byte":null,"match":null}"#
+        );
+        assert_eq!(
+            json_line(&mut set, &describer, &options, Some("a.txt"), "abdbc", 1),
+            concat!(
+                r#"{"file":"a.txt","line_number":1,"line":"abdbc","start_byte":1,"match":"#,
+                r#"{"complete":true,"consumed_bytes":2,"tokens":["#,
+                r#"{"kind":"raw","pattern":"b","matched":"b","start_byte":0,"end_byte":1},"#,
+                r#"{"kind":"one_of","pattern":"(c|d)","matched":"d","start_byte":1,"end_byte":2}]}}"#,
+            )
+        );
+
+        // With `--full`, it's the best match from the start, complete or not.
+        options.full = true;
+        assert_eq!(
+            json_line(&mut set, &describer, &options, None, "bx", 2),
+            concat!(
+                r#"{"file":null,"line_number":2,"line":"bx","start_byte":0,"match":"#,
+                r#"{"complete":false,"consumed_bytes":1,"tokens":["#,
+                r#"{"kind":"raw","pattern":"b","matched":"b","start_byte":0,"end_byte":1}]}}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn json_says_which_patterns_matched() {
+        let patterns = "# two patterns\nb(c|d)\nd";
+        let mut set = MatcherSet::from_lines(patterns).unwrap();
+        let describer = MatcherSet::from_lines(patterns).unwrap();
+        let given = args(&["--json", "-f", "patterns.txt"]);
+        let options = parse_args(&given).unwrap();
+
+        assert_eq!(
+            json_line(&mut set, &describer, &options, None, "xd", 1),
+            concat!(
+                r#"{"file":null,"line_number":1,"line":"xd","start_byte":1,"patterns":[3],"match":"#,
+                r#"{"complete":true,"consumed_bytes":1,"tokens":["#,
+                r#"{"kind":"raw","pattern":"d","matched":"d","start_byte":0,"end_byte":1}]}}"#,
+            )
+        );
+        assert!(json_line(&mut set, &describer, &options, None, "abd", 1)
+            .contains(r#""start_byte":1,"patterns":[2,3],"#));
+        assert_eq!(
+            json_line(&mut set, &describer, &options, None, "x", 1),
+            r#"{"file":null,"line_number":1,"line":"x","start_byte":null,"patterns":[],"match":null}"#
+        );
+    }
+}
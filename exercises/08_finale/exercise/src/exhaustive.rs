@@ -0,0 +1,2919 @@
+//! This is the exhaustive engine, which tries every option of every group
+//! rather than settling for the first one that fits. It's behind the
+//! `exhaustive` feature, so that users who only need greedy matching don't
+//! have to build it.
+
+pub mod engine;
+
+use crate::{Matcher, MatcherToken};
+use alloc::borrow::ToOwned;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+use core::ops::Range;
+use require_lifetimes::require_lifetimes;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// This says which output a frame's result gets merged into.
+#[derive(Clone, Copy)]
+enum Parent {
+    /// This is the root output. It's kept to one side rather than on the
+    /// stack, so that finishing the search never depends on finding it there.
+    Root,
+    /// This is the output frame at the given index of the search's outputs.
+    Frame(usize),
+}
+
+/// This is a branch of the search that hasn't been explored yet: the option
+/// that `chosen_option` covers in the string, chosen for the group at
+/// `group_index`. The branch's own tokens are the ones after the group.
+///
+/// Frames only hold offsets into the string and indices into the tokens, so
+/// they don't borrow either, and a scratch can keep them between searches.
+struct InputData {
+    group_index: usize,
+    chosen_option: Range<usize>,
+    parent: Parent,
+    parent_segment_index: usize,
+}
+
+#[derive(Clone, Copy)]
+struct OutputData {
+    segment_index: usize,
+    matched_tokens_count: usize,
+    /// This counts the bytes of the string matched by the same tokens that
+    /// `matched_tokens_count` counts. Once the output is finished, that's how
+    /// far into the string its best branch got.
+    matched_bytes_count: usize,
+    best_current_segment_index: Option<usize>,
+    best_current_matched_tokens_count: usize,
+    best_current_matched_bytes_count: usize,
+    /// This is the last of the branches tied with the best one, which is
+    /// where the next tie gets added. It means nothing until there's a best.
+    last_tied_segment_index: usize,
+    is_complete_match: bool,
+}
+
+/// This is an output frame, waiting for its branches to finish so that it
+/// can merge the best of them into its parent. Outputs are kept apart from
+/// input frames, so a `Parent::Frame` can only ever name an output. Breadth
+/// first, an output's branches can finish long after it's been made, and in
+/// any order, so rather than waiting for them in place, it counts how many
+/// are still going.
+struct OutputFrame {
+    output_data: OutputData,
+    parent: Parent,
+    unfinished_branches: usize,
+}
+
+/// This is the stack of a depth-first search. Its input frames and output
+/// frames are kept apart, but they come and go as if they shared a stack:
+/// an output's branches are pushed right after it, and it's popped as soon
+/// as they've all finished.
+#[derive(Default)]
+struct Stack {
+    inputs: Vec<InputData>,
+    outputs: Vec<OutputFrame>,
+}
+
+/// This is a frame of a `Stack`, as `Stack::frames` shows it.
+enum StackedFrame<'a> {
+    Input(&'a InputData),
+    Output(&'a OutputFrame),
+}
+
+/// This is where `push_branches` puts an input frame for each option of a
+/// group. A stack gets them backwards, so that they're popped in the order
+/// their options are written; a queue gets them in that order.
+trait Branches {
+    /// This counts the frames held, for the frame limit.
+    fn frames(&self) -> usize;
+    fn push_all(&mut self, branches: impl DoubleEndedIterator<Item = InputData>);
+}
+
+impl Branches for Stack {
+    fn frames(&self) -> usize {
+        self.len()
+    }
+
+    fn push_all(&mut self, branches: impl DoubleEndedIterator<Item = InputData>) {
+        self.inputs.extend(branches.rev());
+    }
+}
+
+impl Branches for Vec<InputData> {
+    fn frames(&self) -> usize {
+        self.len()
+    }
+
+    fn push_all(&mut self, branches: impl DoubleEndedIterator<Item = InputData>) {
+        self.extend(branches.rev());
+    }
+}
+
+impl Branches for VecDeque<InputData> {
+    fn frames(&self) -> usize {
+        self.len()
+    }
+
+    fn push_all(&mut self, branches: impl DoubleEndedIterator<Item = InputData>) {
+        self.extend(branches);
+    }
+}
+
+// Frames are pushed and popped for every branch, so neither kind may be
+// bigger than the 88-byte `Frame` the search used to push, plus the one word
+// an output spends counting its unfinished branches.
+const _: () = assert!(core::mem::size_of::<InputData>() <= 96);
+const _: () = assert!(core::mem::size_of::<OutputFrame>() <= 96);
+
+/// This is a run of tokens matched by a single input frame, followed by the
+/// segment of whichever branch that frame ended up choosing.
+struct Segment {
+    matched_tokens: Range<usize>,
+    next: Option<usize>,
+    /// This is the segment of the frame whose branch this one is.
+    parent: Option<usize>,
+    /// This counts the tokens in this segment and in all the ones above it.
+    total_matched_tokens: usize,
+    /// This counts the bytes those tokens matched, which is also where in
+    /// the string this segment stopped.
+    total_matched_bytes: usize,
+    /// This is true when the frame didn't have any branches to explore.
+    is_leaf: bool,
+    /// This is the next segment that did exactly as well as this one, out of
+    /// the branches of the same frame.
+    next_tie: Option<usize>,
+}
+
+/// This is where the exhaustive engine keeps every token matched during one
+/// call. Frames only refer to it by index, so exploring a branch doesn't
+/// need to allocate anything of its own.
+struct Arena<'reference> {
+    /// This has the bytes of the string each matched token covers. Which
+    /// token that was isn't kept: every match starts at the first token and
+    /// covers consecutive tokens, so it's just how many came before.
+    matched_tokens: Vec<Range<usize>>,
+    segments: Vec<Segment>,
+    pruning: Option<Pruning<'reference>>,
+    scoring: MatchScoring,
+    frame_limit: Option<FrameLimit>,
+}
+
+/// This stops the exhaustive engine before its stack grows past a number of
+/// frames.
+struct FrameLimit {
+    max_frames: usize,
+    /// This is how many frames were on the stack when the search stopped,
+    /// if it had to.
+    frames_at_abort: Option<usize>,
+}
+
+/// This says what the exhaustive engine prefers when two branches are both
+/// complete matches, or both partial ones. A complete match always beats a
+/// partial one either way.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MatchScoring {
+    /// This prefers the branch that matched more tokens, which is what
+    /// `Matcher::match_string_exhaustive` does.
+    #[default]
+    MostTokens,
+    /// This prefers the branch that consumed more of the string.
+    MostBytes,
+}
+
+/// This lets the exhaustive engine skip branches that are too short to ever
+/// match completely. That's only safe once a complete match has been found:
+/// until then, one of those branches could still be the best partial match.
+struct Pruning<'reference> {
+    /// This has, for every token index, the fewest bytes that the tokens from
+    /// that index onwards can match.
+    min_remaining_len: &'reference [usize],
+    found_complete_match: bool,
+    branches_pruned: usize,
+}
+
+/// These count the work done by one call to
+/// `Matcher::match_string_exhaustive_with_stats`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExploreStats {
+    /// This counts every frame pushed onto the stack. The root of the search
+    /// is kept apart from the stack, so it isn't counted here (or below).
+    pub frames_pushed: usize,
+    /// This counts every frame popped off the stack.
+    pub frames_popped: usize,
+    /// This is the most frames the stack held at once.
+    pub peak_stack_len: usize,
+    /// This counts the branches that ran out of string (or hit a mismatch)
+    /// before reaching the end of the pattern.
+    pub branches_abandoned: usize,
+    /// This counts the branches that were never explored, because a complete
+    /// match had already been found and they were too short to be another.
+    pub branches_pruned: usize,
+}
+
+/// This is how the exhaustive engine reports what it's doing. The `()`
+/// recorder throws everything away, so that searches which didn't ask for
+/// stats don't pay for them.
+trait StatsRecorder {
+    fn record_push(&mut self, frames_pushed: usize, stack_len: usize);
+    fn record_pop(&mut self);
+    fn record_abandoned_branch(&mut self);
+    fn record_pruned_branches(&mut self, branches_pruned: usize);
+    /// This only calls `event` if the recorder keeps a trace, so that the
+    /// others never pay for building one. `event` can also decide there's
+    /// nothing worth recording.
+    fn record_event(&mut self, event: impl FnOnce() -> Option<TraceEvent>);
+    /// This is called with the whole stack, rendered by `Arena::dump_stack`,
+    /// every time the engine is about to pop a frame off it.
+    #[cfg(feature = "debug-internals")]
+    fn record_stack(&mut self, _dump: impl FnOnce() -> String) {}
+}
+
+impl StatsRecorder for () {
+    fn record_push(&mut self, _frames_pushed: usize, _stack_len: usize) {}
+    fn record_pop(&mut self) {}
+    fn record_abandoned_branch(&mut self) {}
+    fn record_pruned_branches(&mut self, _branches_pruned: usize) {}
+    fn record_event(&mut self, _event: impl FnOnce() -> Option<TraceEvent>) {}
+}
+
+impl StatsRecorder for ExploreStats {
+    fn record_push(&mut self, frames_pushed: usize, stack_len: usize) {
+        self.frames_pushed += frames_pushed;
+        self.peak_stack_len = self.peak_stack_len.max(stack_len);
+    }
+
+    fn record_pop(&mut self) {
+        self.frames_popped += 1;
+    }
+
+    fn record_abandoned_branch(&mut self) {
+        self.branches_abandoned += 1;
+    }
+
+    fn record_pruned_branches(&mut self, branches_pruned: usize) {
+        self.branches_pruned += branches_pruned;
+    }
+
+    fn record_event(&mut self, _event: impl FnOnce() -> Option<TraceEvent>) {}
+}
+
+impl StatsRecorder for Vec<TraceEvent> {
+    fn record_push(&mut self, _frames_pushed: usize, _stack_len: usize) {}
+    fn record_pop(&mut self) {}
+    fn record_abandoned_branch(&mut self) {}
+    fn record_pruned_branches(&mut self, _branches_pruned: usize) {}
+
+    fn record_event(&mut self, event: impl FnOnce() -> Option<TraceEvent>) {
+        self.extend(event());
+    }
+}
+
+/// This says which frames the exhaustive engine explores first. Both orders
+/// find the same best match; they only differ in how much work it takes and
+/// how many frames are held at once.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExploreOrder {
+    /// This follows each branch all the way down before trying the next one,
+    /// which is what `Matcher::match_string_exhaustive` does.
+    #[default]
+    DepthFirst,
+    /// This explores every branch one group at a time, so that a deep branch
+    /// that goes nowhere can't hold up its siblings.
+    BreadthFirst,
+}
+
+/// This keeps every dump of the stack it's given, in order.
+#[cfg(feature = "debug-internals")]
+struct StackDumps(Vec<String>);
+
+#[cfg(feature = "debug-internals")]
+impl StatsRecorder for StackDumps {
+    fn record_push(&mut self, _frames_pushed: usize, _stack_len: usize) {}
+    fn record_pop(&mut self) {}
+    fn record_abandoned_branch(&mut self) {}
+    fn record_pruned_branches(&mut self, _branches_pruned: usize) {}
+    fn record_event(&mut self, _event: impl FnOnce() -> Option<TraceEvent>) {}
+
+    fn record_stack(&mut self, dump: impl FnOnce() -> String) {
+        self.0.push(dump());
+    }
+}
+
+/// This is how much of the remaining string a `TraceEvent` keeps, in chars.
+const TRACE_PREVIEW_LEN: usize = 16;
+
+/// This is one step of the exhaustive engine, as returned by
+/// `Matcher::match_string_exhaustive_traced`.
+///
+/// Every event is about a branch of the search, which is an option chosen for
+/// the group at `token_index`. The events come in the order the engine takes
+/// those steps, which only depends on the pattern and the string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// An input frame for the branch was pushed onto the stack. `remaining`
+    /// is the start of what's left of the string after the option.
+    PushInput {
+        token_index: usize,
+        chosen_option: String,
+        remaining: String,
+    },
+    /// The branch's input frame was popped, the tokens after the group were
+    /// matched as far as they'd go, and an output frame was pushed to collect
+    /// the results of any groups further along. `remaining` is the start of
+    /// what those tokens left of the string.
+    PushOutput {
+        token_index: usize,
+        chosen_option: String,
+        remaining: String,
+    },
+    /// The branch's output frame was popped, with every branch after it
+    /// explored. `matched_tokens_count` counts the tokens in the best match
+    /// the branch led to, starting with the option.
+    PopOutput {
+        token_index: usize,
+        chosen_option: String,
+        matched_tokens_count: usize,
+    },
+    /// The branch that was just popped is now the best one for its group.
+    BranchChosen {
+        token_index: usize,
+        chosen_option: String,
+    },
+}
+
+impl TraceEvent {
+    fn preview(string: &str) -> String {
+        match string.char_indices().nth(TRACE_PREVIEW_LEN) {
+            Some((end, _)) => format!("{}...", &string[..end]),
+            None => string.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraceEvent::PushInput {
+                token_index,
+                chosen_option,
+                remaining,
+            } => write!(
+                f,
+                "push input    #{token_index} {chosen_option:?}, leaving {remaining:?}"
+            ),
+            TraceEvent::PushOutput {
+                token_index,
+                chosen_option,
+                remaining,
+            } => write!(
+                f,
+                "push output   #{token_index} {chosen_option:?}, leaving {remaining:?}"
+            ),
+            TraceEvent::PopOutput {
+                token_index,
+                chosen_option,
+                matched_tokens_count,
+            } => write!(
+                f,
+                "pop output    #{token_index} {chosen_option:?}, matching {matched_tokens_count} token{}",
+                if *matched_tokens_count == 1 { "" } else { "s" }
+            ),
+            TraceEvent::BranchChosen {
+                token_index,
+                chosen_option,
+            } => write!(f, "choose branch #{token_index} {chosen_option:?}"),
+        }
+    }
+}
+
+/// This holds on to the memory used by the exhaustive engine between calls to
+/// `Matcher::match_string_exhaustive_with_scratch`. It doesn't remember
+/// anything else, so one scratch can be shared by any number of matchers.
+#[cfg(feature = "scratch")]
+#[derive(Default)]
+pub struct ExhaustiveScratch {
+    stack: Stack,
+    matched_tokens: Vec<Range<usize>>,
+    segments: Vec<Segment>,
+}
+
+/// This is the error returned by `Matcher::match_string_exhaustive_bounded`
+/// when the search would have needed more frames than it was allowed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FrameLimitExceeded<'a, 'internal, 'b> {
+    /// This is the match that got furthest before the search was stopped. It
+    /// can even be complete, but there's no telling whether the rest of the
+    /// search would have found a better one.
+    pub best_partial_match: Vec<(&'a MatcherToken<'internal>, &'b str)>,
+    /// This is the byte offset in the string where `best_partial_match`
+    /// stopped.
+    pub stop_offset: usize,
+    /// This is how many frames were on the stack when the search stopped.
+    pub frames: usize,
+}
+
+impl fmt::Display for FrameLimitExceeded<'_, '_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the exhaustive search was stopped with {} frames on its stack",
+            self.frames
+        )
+    }
+}
+
+// Without `std`, the error only has `Display`.
+#[cfg(feature = "std")]
+impl std::error::Error for FrameLimitExceeded<'_, '_, '_> {}
+
+/// This is one way of matching a string, as yielded by `ExhaustiveSearch`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MatchCandidate<'a, 'internal, 'b> {
+    /// These are the tokens that matched, and the text each of them matched.
+    pub matched_tokens: Vec<(&'a MatcherToken<'internal>, &'b str)>,
+    /// This is the byte offset in the string where the match stopped, so
+    /// everything before it was matched and nothing after it was.
+    pub stop_offset: usize,
+}
+
+/// This is the iterator returned by `Matcher::exhaustive_search`.
+///
+/// It yields a `MatchCandidate` for every complete match, which all match
+/// every token, so they're ordered by the options they chose: the one that
+/// chose an earlier option (in the order the pattern writes them) for the
+/// first group where two differ comes first. That's how
+/// `match_string_exhaustive` breaks ties too, so the first one yielded is
+/// always what it returns.
+///
+/// The search is depth first, and stops at each complete match it finds,
+/// carrying on from there at the next call to `next`. So getting the first
+/// match explores no more than `match_string_exhaustive` would (often far
+/// less, since that has to rule out everything after it), and nothing at all
+/// is explored until the first call. Like that, it skips branches too short
+/// to be complete, once it's found a complete match. Partial matches are
+/// never yielded, but as they're explored, `most_tokens_matched` counts the
+/// tokens the furthest of them matched.
+pub struct ExhaustiveSearch<'a, 'internal, 'b> {
+    tokens: &'a [MatcherToken<'internal>],
+    min_remaining_len: &'a [usize],
+    most_tokens_matched: &'a mut usize,
+    string: &'b str,
+    /// This is the stack of branches still to explore, and the arena holding
+    /// the segments explored so far, once the search has started.
+    explored: Option<(Vec<InputData>, Arena<'a>)>,
+}
+
+#[require_lifetimes]
+impl<'internal> Matcher<'internal> {
+    /// This should try all possible combinations while attempting to find a match.
+    /// Even if the code is uglier, I chose to use a heap-allocated stack
+    /// rather than going with a recursive implementation so as to not be
+    /// limited by the thread stack.
+    ///
+    /// A complete match always beats a partial one, and otherwise more tokens
+    /// beat fewer. When the options of a group tie, the one written first in
+    /// the pattern wins.
+    ///
+    /// All it does is run an `engine::Engine` to the end.
+    pub fn match_string_exhaustive<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> Vec<(&'a MatcherToken<'internal>, &'b str)> {
+        let matched_tokens =
+            engine::Engine::with_pruning(&self.tokens, &self.min_remaining_len, string).run();
+
+        if matched_tokens.len() > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens.len();
+        }
+
+        matched_tokens
+    }
+
+    /// This does the same thing as `match_string_exhaustive`, but `scoring`
+    /// decides which of two complete (or two partial) matches is better. With
+    /// `MatchScoring::MostBytes`, it returns whichever match consumed the most
+    /// of `string`.
+    ///
+    /// That match can have fewer tokens than one it beat, but it's still the
+    /// most tokens any branch matched that counts towards
+    /// `most_tokens_matched`.
+    pub fn match_string_exhaustive_scored<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+        scoring: MatchScoring,
+    ) -> Vec<(&'a MatcherToken<'internal>, &'b str)> {
+        let tokens = &self.tokens;
+        let mut arena = Arena::with_pruning(&self.min_remaining_len);
+        arena.scoring = scoring;
+
+        let mut matched_tokens = Vec::new();
+        Self::visit_exhaustive(
+            tokens,
+            string,
+            &mut Stack::default(),
+            &mut arena,
+            &mut (),
+            &mut |index, text| {
+                matched_tokens.push((&tokens[index], text));
+            },
+        );
+
+        let most_tokens_matched = arena.segments[arena.furthest_segment()].total_matched_tokens;
+        if most_tokens_matched > self.most_tokens_matched {
+            self.most_tokens_matched = most_tokens_matched;
+        }
+
+        matched_tokens
+    }
+
+    /// This does the same thing as `match_string_exhaustive`, but explores
+    /// the branches of the search in the given `order`. The result is the same
+    /// either way.
+    pub fn match_string_exhaustive_with_order<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+        order: ExploreOrder,
+    ) -> Vec<(&'a MatcherToken<'internal>, &'b str)> {
+        let tokens = &self.tokens;
+        let mut arena = Arena::with_pruning(&self.min_remaining_len);
+        let root_segment_index = match order {
+            ExploreOrder::DepthFirst => {
+                Self::search(tokens, string, &mut Stack::default(), &mut arena, &mut ())
+            }
+            ExploreOrder::BreadthFirst => {
+                Self::search_breadth_first(tokens, string, &mut arena, &mut ())
+            }
+        };
+
+        let mut matched_tokens = Vec::new();
+        let matched_tokens_count = arena.visit(string, root_segment_index, &mut |index, text| {
+            matched_tokens.push((&tokens[index], text));
+        });
+
+        if matched_tokens_count > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens_count;
+        }
+
+        matched_tokens
+    }
+
+    /// This does the same thing as `match_string_exhaustive`, but also counts
+    /// how much work the search took.
+    pub fn match_string_exhaustive_with_stats<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> (Vec<(&'a MatcherToken<'internal>, &'b str)>, ExploreStats) {
+        let tokens = &self.tokens;
+        let mut matched_tokens = Vec::new();
+        let mut stats = ExploreStats::default();
+        let matched_tokens_count = Self::visit_exhaustive(
+            tokens,
+            string,
+            &mut Stack::default(),
+            &mut Arena::with_pruning(&self.min_remaining_len),
+            &mut stats,
+            &mut |index, text| {
+                matched_tokens.push((&tokens[index], text));
+            },
+        );
+
+        if matched_tokens_count > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens_count;
+        }
+
+        (matched_tokens, stats)
+    }
+
+    /// This does the same thing as `match_string_exhaustive`, but also
+    /// returns every step the search took along the way. It's meant for
+    /// showing how the stack of frames stands in for recursion; see
+    /// `TraceEvent` for what each step means.
+    pub fn match_string_exhaustive_traced<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> (Vec<(&'a MatcherToken<'internal>, &'b str)>, Vec<TraceEvent>) {
+        let tokens = &self.tokens;
+        let mut matched_tokens = Vec::new();
+        let mut trace = Vec::new();
+        let matched_tokens_count = Self::visit_exhaustive(
+            tokens,
+            string,
+            &mut Stack::default(),
+            &mut Arena::with_pruning(&self.min_remaining_len),
+            &mut trace,
+            &mut |index, text| {
+                matched_tokens.push((&tokens[index], text));
+            },
+        );
+
+        if matched_tokens_count > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens_count;
+        }
+
+        (matched_tokens, trace)
+    }
+
+    /// This does the same thing as `match_string_exhaustive`, but keeps the
+    /// engine's stack and arena in `scratch` afterwards rather than freeing
+    /// them. Passing the same scratch for every candidate in a batch means
+    /// their allocations only have to grow, instead of starting over each time.
+    #[cfg(feature = "scratch")]
+    pub fn match_string_exhaustive_with_scratch<'a, 'b, 'c>(
+        &'a mut self,
+        string: &'b str,
+        scratch: &'c mut ExhaustiveScratch,
+    ) -> Vec<(&'a MatcherToken<'internal>, &'b str)> {
+        let tokens = &self.tokens;
+        let mut stack = core::mem::take(&mut scratch.stack);
+        let mut arena = Arena::with_pruning(&self.min_remaining_len);
+        arena.matched_tokens = core::mem::take(&mut scratch.matched_tokens);
+        arena.segments = core::mem::take(&mut scratch.segments);
+
+        let mut matched_tokens = Vec::new();
+        let matched_tokens_count = Self::visit_exhaustive(
+            tokens,
+            string,
+            &mut stack,
+            &mut arena,
+            &mut (),
+            &mut |index, text| {
+                matched_tokens.push((&tokens[index], text));
+            },
+        );
+
+        // The search leaves the stack empty, but the segments it explored
+        // mean nothing to the next one.
+        arena.clear();
+        scratch.stack = stack;
+        scratch.matched_tokens = arena.matched_tokens;
+        scratch.segments = arena.segments;
+
+        if matched_tokens_count > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens_count;
+        }
+
+        matched_tokens
+    }
+
+    /// This runs the same search as `match_string_exhaustive`, but rather than
+    /// building a vector it calls `f` with the index of each token in the best
+    /// match and the text that token matched, in order.
+    pub fn match_exhaustive_visit<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+        mut f: impl FnMut(usize, &'b str),
+    ) {
+        let matched_tokens_count = Self::visit_exhaustive(
+            &self.tokens,
+            string,
+            &mut Stack::default(),
+            &mut Arena::with_pruning(&self.min_remaining_len),
+            &mut (),
+            &mut f,
+        );
+
+        if matched_tokens_count > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens_count;
+        }
+    }
+
+    /// This does the same thing as `match_string_exhaustive`, but also
+    /// renders the whole stack every time a frame is about to be popped off
+    /// it. It's only there so that tests can snapshot how the stack evolves;
+    /// nothing about the format is stable.
+    #[cfg(feature = "debug-internals")]
+    #[doc(hidden)]
+    pub fn match_string_exhaustive_stack_dumps<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> (Vec<(&'a MatcherToken<'internal>, &'b str)>, Vec<String>) {
+        let tokens = &self.tokens;
+        let mut matched_tokens = Vec::new();
+        let mut dumps = StackDumps(Vec::new());
+        let matched_tokens_count = Self::visit_exhaustive(
+            tokens,
+            string,
+            &mut Stack::default(),
+            &mut Arena::with_pruning(&self.min_remaining_len),
+            &mut dumps,
+            &mut |index, text| {
+                matched_tokens.push((&tokens[index], text));
+            },
+        );
+
+        if matched_tokens_count > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens_count;
+        }
+
+        (matched_tokens, dumps.0)
+    }
+
+    /// This does the same thing as `match_string_exhaustive`, but never lets
+    /// the engine's stack hold more than `max_frames` frames. If the search
+    /// can't be finished within that, it stops and returns the best match it
+    /// had found so far in the error instead.
+    ///
+    /// A group's options are only explored once there's room on the stack
+    /// for all of them, even if some can't match, so the limit can be hit a
+    /// little earlier than the search strictly needs.
+    pub fn match_string_exhaustive_bounded<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+        max_frames: usize,
+    ) -> Result<Vec<(&'a MatcherToken<'internal>, &'b str)>, FrameLimitExceeded<'a, 'internal, 'b>>
+    {
+        let tokens = &self.tokens;
+        let mut arena = Arena::with_pruning(&self.min_remaining_len);
+        arena.frame_limit = Some(FrameLimit {
+            max_frames,
+            frames_at_abort: None,
+        });
+
+        let root_segment_index =
+            Self::search(tokens, string, &mut Stack::default(), &mut arena, &mut ());
+
+        let frames_at_abort = arena
+            .frame_limit
+            .as_ref()
+            .and_then(|frame_limit| frame_limit.frames_at_abort);
+        let result = match frames_at_abort {
+            Some(frames) => {
+                let furthest_segment_index = arena.furthest_segment();
+                Err(FrameLimitExceeded {
+                    best_partial_match: arena.path_to(tokens, string, furthest_segment_index),
+                    stop_offset: arena.segments[furthest_segment_index].total_matched_bytes,
+                    frames,
+                })
+            }
+            None => {
+                let mut matched_tokens = Vec::new();
+                arena.visit(string, root_segment_index, &mut |index, text| {
+                    matched_tokens.push((&tokens[index], text));
+                });
+                Ok(matched_tokens)
+            }
+        };
+
+        let matched_tokens_count = match &result {
+            Ok(matched_tokens) => matched_tokens.len(),
+            Err(error) => error.best_partial_match.len(),
+        };
+        if matched_tokens_count > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens_count;
+        }
+
+        result
+    }
+
+    /// This finds the first place in `haystack` where the whole pattern
+    /// matches, trying every option of every group at each position. It
+    /// returns the byte offset the match starts at, and the best match there
+    /// (by the same rules as `match_string_exhaustive`). Every position tried
+    /// counts towards `most_tokens_matched`.
+    pub fn search_exhaustive<'a, 'b>(
+        &'a mut self,
+        haystack: &'b str,
+    ) -> Option<(usize, Vec<(&'a MatcherToken<'internal>, &'b str)>)> {
+        let tokens = &self.tokens;
+        let mut stack = Stack::default();
+        let mut arena = Arena::with_pruning(&self.min_remaining_len);
+        let mut matched_tokens = Vec::new();
+        let mut most_tokens_matched = self.most_tokens_matched;
+        let mut found = None;
+        let mut position = 0;
+
+        while position <= haystack.len() {
+            #[cfg(feature = "memchr")]
+            if let Some(prefilter) = &self.prefilter {
+                match prefilter.find_from(haystack, position) {
+                    Some(start) => position = start,
+                    None => break,
+                }
+            }
+
+            arena.clear();
+            matched_tokens.clear();
+            let string = &haystack[position..];
+            let root_segment_index = Self::search(tokens, string, &mut stack, &mut arena, &mut ());
+            let matched_tokens_count =
+                arena.visit(string, root_segment_index, &mut |index, text| {
+                    matched_tokens.push((&tokens[index], text));
+                });
+            most_tokens_matched = most_tokens_matched.max(matched_tokens_count);
+
+            if matched_tokens_count == tokens.len() {
+                found = Some((position, matched_tokens));
+                break;
+            }
+
+            position += haystack[position..]
+                .chars()
+                .next()
+                .map_or(1, char::len_utf8);
+        }
+
+        self.most_tokens_matched = most_tokens_matched;
+        found
+    }
+
+    /// This returns an `ExhaustiveSearch`, which yields every complete match
+    /// of `string`, best first, rather than only the best one.
+    pub fn exhaustive_search<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> ExhaustiveSearch<'a, 'internal, 'b> {
+        ExhaustiveSearch {
+            tokens: &self.tokens,
+            min_remaining_len: &self.min_remaining_len,
+            most_tokens_matched: &mut self.most_tokens_matched,
+            string,
+            explored: None,
+        }
+    }
+
+    /// This does the same search as `match_string_exhaustive`, but rather
+    /// than only the best match, it returns it along with every other match
+    /// that did exactly as well (so they're all complete, or all partial with
+    /// the same number of tokens).
+    ///
+    /// The first of them is always what `match_string_exhaustive` returns.
+    /// Wherever a group had tied options, the matches using each of them come
+    /// in the order the options are written in the pattern.
+    pub fn all_best_matches<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> Vec<Vec<(&'a MatcherToken<'internal>, &'b str)>> {
+        let mut arena = Arena::with_pruning(&self.min_remaining_len);
+        let root_segment_index = Self::search(
+            &self.tokens,
+            string,
+            &mut Stack::default(),
+            &mut arena,
+            &mut (),
+        );
+        let matches = arena.collect_all(&self.tokens, string, root_segment_index);
+
+        if let Some(best_match) = matches.first() {
+            if best_match.len() > self.most_tokens_matched {
+                self.most_tokens_matched = best_match.len();
+            }
+        }
+
+        matches
+    }
+
+    /// This counts the distinct complete matches of `string`, without building
+    /// any of them. Two matches only count separately if some token matched
+    /// different text in each, so a group that lists the same option twice
+    /// doesn't count it twice.
+    ///
+    /// Rather than following each branch to its end, this keeps one count
+    /// per offset that the tokens so far can stop at, and sums the counts of
+    /// every branch that reaches the same offset. That makes it quick even
+    /// for patterns with far more matches than could ever be listed. The
+    /// count saturates at `usize::MAX`.
+    pub fn count_complete_matches<'a, 'b>(&'a mut self, string: &'b str) -> usize {
+        // Each of these is an offset that some match of the tokens so far
+        // stops at, and how many distinct matches stop there.
+        let mut stops = vec![(0, 1)];
+        let mut next_stops: Vec<(usize, usize)> = Vec::new();
+        let mut matched_tokens_count = 0;
+
+        for token in self.tokens.iter() {
+            for &(offset, count) in &stops {
+                let rest = &string[offset..];
+                match token {
+                    MatcherToken::RawText(text) => {
+                        if rest.starts_with(text) {
+                            next_stops.push((offset + text.len(), count));
+                        }
+                    }
+                    MatcherToken::OneOfText(options) => {
+                        for (index, option) in options.iter().enumerate() {
+                            if rest.starts_with(option) && !options[..index].contains(option) {
+                                next_stops.push((offset + option.len(), count));
+                            }
+                        }
+                    }
+                    MatcherToken::WildCard => {
+                        if let Some(c) = rest.chars().next() {
+                            next_stops.push((offset + c.len_utf8(), count));
+                        }
+                    }
+                }
+            }
+
+            if next_stops.is_empty() {
+                break;
+            }
+            matched_tokens_count += 1;
+
+            next_stops.sort_unstable_by_key(|&(offset, _)| offset);
+            next_stops.dedup_by(|(offset, count), (kept_offset, kept_count)| {
+                let is_same_offset = offset == kept_offset;
+                if is_same_offset {
+                    *kept_count = kept_count.saturating_add(*count);
+                }
+                is_same_offset
+            });
+            core::mem::swap(&mut stops, &mut next_stops);
+            next_stops.clear();
+        }
+
+        if matched_tokens_count > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens_count;
+        }
+
+        if matched_tokens_count < self.tokens.len() {
+            return 0;
+        }
+        stops
+            .iter()
+            .fold(0, |total, &(_, count)| total.saturating_add(count))
+    }
+
+    /// This carries on a search for complete matches from where it stopped,
+    /// and returns the segment of the next one it finds, or `None` once
+    /// there's nothing left to explore. Nothing has to be merged into an
+    /// output to find complete matches, so `stack` only holds input frames,
+    /// and the parent the branches are given is never read.
+    fn next_complete_segment<'a, 'b, 'c, 'd, 'e, 'f>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        stack: &'d mut Vec<InputData>,
+        arena: &'e mut Arena<'f>,
+    ) -> Option<usize> {
+        while let Some(input_data) = stack.pop() {
+            let (output_data, group) = Self::match_segment(
+                tokens,
+                string,
+                Some((input_data.group_index, input_data.chosen_option)),
+                Some(input_data.parent_segment_index),
+                arena,
+            );
+            // A complete match has matched every token, so there's no group
+            // left to branch on.
+            if output_data.is_complete_match {
+                return Some(output_data.segment_index);
+            }
+            Self::push_branches(
+                string,
+                group,
+                input_data.parent,
+                output_data.segment_index,
+                stack,
+                arena,
+            );
+        }
+
+        None
+    }
+
+    /// This drives the exhaustive search, then walks the winning chain of
+    /// segments and reports every token in it to `f`. It returns how many
+    /// tokens were reported.
+    fn visit_exhaustive<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        stack: &'d mut Stack,
+        arena: &'e mut Arena<'f>,
+        stats: &'g mut impl StatsRecorder,
+        f: &'h mut impl FnMut(usize, &'c str),
+    ) -> usize {
+        let root_segment_index = Self::search(tokens, string, stack, arena, stats);
+        arena.visit(string, root_segment_index, f)
+    }
+
+    /// This runs the exhaustive search to the end, and returns the root's
+    /// segment, which the best match (and anything tied with it) starts from.
+    fn search<'a, 'b, 'c, 'd, 'e, 'f, 'g>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        stack: &'d mut Stack,
+        arena: &'e mut Arena<'f>,
+        stats: &'g mut impl StatsRecorder,
+    ) -> usize {
+        let mut root_output_data = Self::process_root_input(tokens, string, stack, arena);
+        stats.record_push(stack.len(), stack.len());
+        Self::record_pushed_inputs(string, &stack.inputs, stats);
+        if arena.segments[root_output_data.segment_index].is_leaf
+            && !root_output_data.is_complete_match
+        {
+            stats.record_abandoned_branch();
+        }
+
+        Self::explore(tokens, string, stack, &mut root_output_data, arena, stats);
+        Self::finish_output(&mut root_output_data, arena);
+
+        if let Some(pruning) = &arena.pruning {
+            stats.record_pruned_branches(pruning.branches_pruned);
+        }
+
+        root_output_data.segment_index
+    }
+
+    /// This does the same search as `search`, but takes input frames from the
+    /// front of a queue rather than the top of a stack, so that every branch
+    /// of a group is started before any of them goes further. Outputs are kept
+    /// apart from the queue until all of their branches have finished, and
+    /// are then merged into their parents straight away.
+    fn search_breadth_first<'a, 'b, 'c, 'd, 'e, 'f>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        arena: &'d mut Arena<'e>,
+        stats: &'f mut impl StatsRecorder,
+    ) -> usize {
+        let mut queue = VecDeque::new();
+        let mut root_output_data = Self::process_root_input(tokens, string, &mut queue, arena);
+        if arena.segments[root_output_data.segment_index].is_leaf
+            && !root_output_data.is_complete_match
+        {
+            stats.record_abandoned_branch();
+        }
+
+        let mut outputs = Vec::new();
+        let mut unfinished_outputs = 0;
+        stats.record_push(queue.len(), queue.len());
+
+        while let Some(input_data) = queue.pop_front() {
+            stats.record_pop();
+
+            let (output_data, group) = Self::match_segment(
+                tokens,
+                string,
+                Some((input_data.group_index, input_data.chosen_option)),
+                Some(input_data.parent_segment_index),
+                arena,
+            );
+
+            let output_index = outputs.len();
+            let segment_index = output_data.segment_index;
+            let queue_len = queue.len();
+            Self::push_branches(
+                string,
+                group,
+                Parent::Frame(output_index),
+                segment_index,
+                &mut queue,
+                arena,
+            );
+
+            if arena.segments[segment_index].is_leaf && !output_data.is_complete_match {
+                stats.record_abandoned_branch();
+            }
+
+            let unfinished_branches = queue.len() - queue_len;
+            outputs.push(OutputFrame {
+                output_data,
+                parent: input_data.parent,
+                unfinished_branches,
+            });
+            unfinished_outputs += 1;
+            stats.record_push(unfinished_branches + 1, queue.len() + unfinished_outputs);
+
+            if unfinished_branches == 0 {
+                unfinished_outputs -= Self::finish_breadth_first_output(
+                    output_index,
+                    &mut outputs,
+                    &mut root_output_data,
+                    arena,
+                    stats,
+                );
+            }
+        }
+
+        Self::finish_output(&mut root_output_data, arena);
+
+        if let Some(pruning) = &arena.pruning {
+            stats.record_pruned_branches(pruning.branches_pruned);
+        }
+
+        root_output_data.segment_index
+    }
+
+    /// This finishes the breadth-first output at `output_index`, which has no
+    /// unfinished branches left, and merges it into its parent. If that was
+    /// the parent's last unfinished branch, the parent gets finished too, and
+    /// so on up. It returns how many outputs were finished.
+    fn finish_breadth_first_output<'a, 'b, 'c, 'd, 'e>(
+        output_index: usize,
+        outputs: &'a mut [OutputFrame],
+        root_output_data: &'b mut OutputData,
+        arena: &'c mut Arena<'d>,
+        stats: &'e mut impl StatsRecorder,
+    ) -> usize {
+        let mut output_index = output_index;
+        let mut finished_outputs = 0;
+
+        loop {
+            stats.record_pop();
+            finished_outputs += 1;
+
+            let output = &outputs[output_index];
+            let (output_data, parent) = (output.output_data, output.parent);
+            match Self::process_output_frame(output_data, parent, outputs, root_output_data, arena)
+            {
+                Some(parent_index) if outputs[parent_index].unfinished_branches == 0 => {
+                    output_index = parent_index;
+                }
+                _ => return finished_outputs,
+            }
+        }
+    }
+
+    /// This processes frames until the stack is empty, merging the result of
+    /// every branch into its parent, and the results of the root's branches
+    /// into `root_output_data`.
+    fn explore<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        stack: &'d mut Stack,
+        root_output_data: &'e mut OutputData,
+        arena: &'f mut Arena<'g>,
+        stats: &'h mut impl StatsRecorder,
+    ) {
+        #[cfg(feature = "debug-internals")]
+        stats.record_stack(|| arena.dump_stack(string, stack));
+
+        while Self::explore_step(tokens, string, stack, root_output_data, arena, stats) {}
+    }
+
+    /// This pops the frame on top of the stack and processes it. It returns
+    /// false once there's nothing left to do, either because the stack is
+    /// empty or because the search hit its frame limit.
+    #[inline(always)]
+    fn explore_step<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        stack: &'d mut Stack,
+        root_output_data: &'e mut OutputData,
+        arena: &'f mut Arena<'g>,
+        stats: &'h mut impl StatsRecorder,
+    ) -> bool {
+        // An output's unfinished branches are the input frames on top of the
+        // stack, so once it has none, it's what's on top.
+        if let Some(output) = stack
+            .outputs
+            .pop_if(|output| output.unfinished_branches == 0)
+        {
+            stats.record_pop();
+
+            let (output_data, parent) = (output.output_data, output.parent);
+            let segment_index = output_data.segment_index;
+            stats.record_event(|| {
+                let (token_index, chosen_option) = arena.branch_of(string, segment_index);
+                Some(TraceEvent::PopOutput {
+                    token_index,
+                    chosen_option: chosen_option.to_owned(),
+                    matched_tokens_count: output_data.matched_tokens_count
+                        + output_data.best_current_matched_tokens_count,
+                })
+            });
+
+            let parent_index = Self::process_output_frame(
+                output_data,
+                parent,
+                &mut stack.outputs,
+                root_output_data,
+                arena,
+            );
+
+            stats.record_event(|| {
+                let best_current_segment_index = match parent_index {
+                    None => root_output_data.best_current_segment_index,
+                    Some(index) => stack.outputs[index].output_data.best_current_segment_index,
+                };
+                if best_current_segment_index != Some(segment_index) {
+                    return None;
+                }
+
+                let (token_index, chosen_option) = arena.branch_of(string, segment_index);
+                Some(TraceEvent::BranchChosen {
+                    token_index,
+                    chosen_option: chosen_option.to_owned(),
+                })
+            });
+        } else if let Some(input_data) = stack.inputs.pop() {
+            stats.record_pop();
+
+            let stack_len = stack.len();
+            let inputs_len = stack.inputs.len();
+            Self::process_input_frame(tokens, string, input_data, stack, arena);
+            stats.record_push(stack.len() - stack_len, stack.len());
+
+            if let Some(FrameLimit {
+                frames_at_abort: Some(_),
+                ..
+            }) = arena.frame_limit
+            {
+                return false;
+            }
+
+            let segment_index = arena.segments.len() - 1;
+            let segment = &arena.segments[segment_index];
+            if segment.is_leaf && segment.total_matched_tokens < tokens.len() {
+                stats.record_abandoned_branch();
+            }
+
+            stats.record_event(|| {
+                let (token_index, chosen_option) = arena.branch_of(string, segment_index);
+                Some(TraceEvent::PushOutput {
+                    token_index,
+                    chosen_option: chosen_option.to_owned(),
+                    remaining: TraceEvent::preview(&string[segment.total_matched_bytes..]),
+                })
+            });
+            Self::record_pushed_inputs(string, &stack.inputs[inputs_len..], stats);
+        } else {
+            return false;
+        }
+
+        #[cfg(feature = "debug-internals")]
+        stats.record_stack(|| arena.dump_stack(string, stack));
+
+        true
+    }
+
+    /// This does the same thing as `match_string_exhaustive`, but each option
+    /// of the first group is explored on its own rayon task. Those branches are
+    /// independent of each other, so the only shared step is merging them back
+    /// into the root output frame, which happens in the exact order the
+    /// sequential engine would have used so that both give identical results.
+    #[cfg(feature = "parallel")]
+    pub fn match_string_exhaustive_parallel<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> Vec<(&'a MatcherToken<'internal>, &'b str)> {
+        let tokens = &self.tokens;
+        let mut arena = Arena::with_pruning(&self.min_remaining_len);
+        let mut stack = Vec::new();
+        let mut root_output_data = Self::process_root_input(tokens, string, &mut stack, &mut arena);
+
+        let min_remaining_len = &self.min_remaining_len;
+        let root_segment = &arena.segments[root_output_data.segment_index];
+        let branch_results: Vec<_> = stack
+            .into_par_iter()
+            .map(|branch| {
+                Self::explore_branch(tokens, string, root_segment, branch, min_remaining_len)
+            })
+            .collect();
+
+        // The sequential engine pops the branch that was pushed last first.
+        for (matched_tokens, is_complete_match) in branch_results.into_iter().rev() {
+            let branch_output_data = OutputData {
+                segment_index: arena.segments.len(),
+                matched_tokens_count: matched_tokens.len(),
+                matched_bytes_count: matched_tokens.iter().map(Range::len).sum(),
+                best_current_segment_index: None,
+                best_current_matched_tokens_count: 0,
+                best_current_matched_bytes_count: 0,
+                last_tied_segment_index: 0,
+                is_complete_match,
+            };
+            arena.push_segment(&matched_tokens);
+            Self::merge_into_parent(
+                &mut root_output_data,
+                branch_output_data,
+                arena.scoring,
+                &mut arena.segments,
+            );
+        }
+
+        Self::finish_output(&mut root_output_data, &mut arena);
+
+        let mut matched_tokens = Vec::new();
+        arena.visit(
+            string,
+            root_output_data.segment_index,
+            &mut |index, text| {
+                matched_tokens.push((&tokens[index], text));
+            },
+        );
+        if matched_tokens.len() > self.most_tokens_matched {
+            self.most_tokens_matched = matched_tokens.len();
+        }
+
+        matched_tokens
+    }
+
+    /// Runs the sequential engine over a single branch of the root input frame,
+    /// returning where the branch's best tokens matched and whether they're a
+    /// complete match. The branch's real parent is the root output of another
+    /// search, so an empty stand-in takes its place and collects whatever the
+    /// branch produces. The stand-in's segment is the first one in the arena,
+    /// just like the root's segment is in the arena of the search it came
+    /// from, and it stops where the root's did.
+    #[cfg(feature = "parallel")]
+    fn explore_branch<'a, 'b, 'c, 'd>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        root_segment: &'d Segment,
+        branch: InputData,
+        min_remaining_len: &'a [usize],
+    ) -> (Vec<Range<usize>>, bool) {
+        let mut arena = Arena::with_pruning(min_remaining_len);
+        arena.segments.push(Segment {
+            matched_tokens: 0..0,
+            next: None,
+            parent: None,
+            total_matched_tokens: root_segment.total_matched_tokens,
+            total_matched_bytes: root_segment.total_matched_bytes,
+            is_leaf: false,
+            next_tie: None,
+        });
+
+        let mut stand_in_output_data = OutputData {
+            segment_index: 0,
+            matched_tokens_count: 0,
+            matched_bytes_count: 0,
+            best_current_segment_index: None,
+            best_current_matched_tokens_count: 0,
+            best_current_matched_bytes_count: 0,
+            last_tied_segment_index: 0,
+            is_complete_match: false,
+        };
+        Self::explore(
+            tokens,
+            string,
+            &mut Stack {
+                inputs: vec![branch],
+                outputs: Vec::new(),
+            },
+            &mut stand_in_output_data,
+            &mut arena,
+            &mut (),
+        );
+
+        let matched_tokens = stand_in_output_data
+            .best_current_segment_index
+            .map_or_else(Vec::new, |segment_index| arena.collect(segment_index));
+        (matched_tokens, stand_in_output_data.is_complete_match)
+    }
+
+    /// This records a `TraceEvent::PushInput` for every input frame in
+    /// `inputs`, which have just been pushed.
+    fn record_pushed_inputs<'a, 'b, 'c>(
+        string: &'a str,
+        inputs: &'b [InputData],
+        stats: &'c mut impl StatsRecorder,
+    ) {
+        for input_data in inputs {
+            stats.record_event(|| {
+                Some(TraceEvent::PushInput {
+                    token_index: input_data.group_index,
+                    chosen_option: string[input_data.chosen_option.clone()].to_owned(),
+                    remaining: TraceEvent::preview(&string[input_data.chosen_option.end..]),
+                })
+            });
+        }
+    }
+
+    fn match_one_of_text_exhaustive<'a, 'b>(
+        options: &'a [&'b str],
+        string: &'a str,
+    ) -> impl DoubleEndedIterator<Item = &'b str> + 'a {
+        options
+            .iter()
+            .copied()
+            .filter(|&option| string.starts_with(option))
+    }
+
+    // This and `push_branches` only exist so that the root and every other
+    // input frame can share them. They're on the hottest path there is, and
+    // leaving inlining up to the compiler made the frames benchmark about
+    // 25% slower.
+    /// This matches tokens, starting with the one after the chosen option (or
+    /// the first one, for the root), until one of them fails or a group is
+    /// reached, and records them in a new segment. It returns the output for
+    /// that segment, and the group it stopped at, if any.
+    #[inline(always)]
+    fn match_segment<'a, 'b, 'c, 'd, 'e>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        chosen_option: Option<(usize, Range<usize>)>,
+        parent_segment_index: Option<usize>,
+        arena: &'d mut Arena<'e>,
+    ) -> (OutputData, Option<(usize, &'a [&'b str])>) {
+        let segment_start = arena.matched_tokens.len();
+        let (mut token_index, start_offset, mut offset) = match chosen_option {
+            Some((group_index, option)) => {
+                let (start, end) = (option.start, option.end);
+                arena.matched_tokens.push(option);
+                (group_index + 1, start, end)
+            }
+            None => (0, 0, 0),
+        };
+
+        let mut group = None;
+
+        while let Some(token) = tokens.get(token_index) {
+            let rest = &string[offset..];
+            let matched_len = match token {
+                MatcherToken::RawText(text) => {
+                    Self::starts_with_raw_text(rest, text).then_some(text.len())
+                }
+                MatcherToken::OneOfText(options) => {
+                    group = Some((token_index, &options[..]));
+                    break;
+                }
+                MatcherToken::WildCard => rest.chars().next().map(char::len_utf8),
+            };
+            let Some(matched_len) = matched_len else {
+                break;
+            };
+            arena.matched_tokens.push(offset..offset + matched_len);
+            offset += matched_len;
+            token_index += 1;
+        }
+
+        let matched_tokens_count = arena.matched_tokens.len() - segment_start;
+        let matched_bytes_count = offset - start_offset;
+        let is_complete_match = token_index == tokens.len();
+        if let Some(pruning) = &mut arena.pruning {
+            if is_complete_match {
+                pruning.found_complete_match = true;
+            }
+        }
+
+        let segment_index = arena.segments.len();
+        arena.segments.push(Segment {
+            matched_tokens: segment_start..arena.matched_tokens.len(),
+            next: None,
+            parent: parent_segment_index,
+            total_matched_tokens: matched_tokens_count
+                + parent_segment_index.map_or(0, |i| arena.segments[i].total_matched_tokens),
+            total_matched_bytes: matched_bytes_count
+                + parent_segment_index.map_or(0, |i| arena.segments[i].total_matched_bytes),
+            is_leaf: true,
+            next_tie: None,
+        });
+
+        let output_data = OutputData {
+            segment_index,
+            matched_tokens_count,
+            matched_bytes_count,
+            best_current_segment_index: None,
+            best_current_matched_tokens_count: 0,
+            best_current_matched_bytes_count: 0,
+            last_tied_segment_index: 0,
+            is_complete_match,
+        };
+
+        (output_data, group)
+    }
+
+    /// This pushes an input frame for every option of `group` that the string
+    /// has where the segment at `segment_index` stopped, each of which will
+    /// merge its result into `parent`.
+    #[inline(always)]
+    fn push_branches<'a, 'b, 'c, 'd, 'e, 'f>(
+        string: &'a str,
+        group: Option<(usize, &'b [&'c str])>,
+        parent: Parent,
+        segment_index: usize,
+        branches: &'d mut impl Branches,
+        arena: &'e mut Arena<'f>,
+    ) {
+        let Some((group_index, options)) = group else {
+            return;
+        };
+
+        // The branches get explored (and finish) in the order their options
+        // are written, and an option only replaces the best one so far if it
+        // does strictly better, so when options tie, the first one written
+        // wins.
+        let pruning = &mut arena.pruning;
+        let segment = &mut arena.segments[segment_index];
+        let offset = segment.total_matched_bytes;
+        let rest = &string[offset..];
+        let options = Self::match_one_of_text_exhaustive(options, rest).filter(|option| {
+            let Some(pruning) = pruning.as_mut() else {
+                return true;
+            };
+            let is_pruned = pruning.found_complete_match
+                && rest.len() - option.len() < pruning.min_remaining_len[group_index + 1];
+            if is_pruned {
+                pruning.branches_pruned += 1;
+            }
+            !is_pruned
+        });
+
+        // A branch's tokens are the ones after the group, so it always starts
+        // further through the pattern than the frame pushing it. That's what
+        // keeps the search finite even when an option matches no text, and the
+        // branch starts where its frame stopped.
+        branches.push_all(options.map(|option| {
+            segment.is_leaf = false;
+            InputData {
+                group_index,
+                chosen_option: offset..offset + option.len(),
+                parent,
+                parent_segment_index: segment_index,
+            }
+        }));
+    }
+
+    /// This checks whether pushing a branch for every option of `group` onto
+    /// a stack of `stack_len` frames could break the arena's frame limit. If
+    /// it could, the search has to stop, and this records where it did.
+    #[inline(always)]
+    fn hits_frame_limit<'a, 'b, 'c, 'd>(
+        stack_len: usize,
+        group: Option<(usize, &'a [&'b str])>,
+        arena: &'c mut Arena<'d>,
+    ) -> bool {
+        if let (Some(frame_limit), Some((_, options))) = (&mut arena.frame_limit, group) {
+            if stack_len + options.len() > frame_limit.max_frames {
+                frame_limit.frames_at_abort = Some(stack_len);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// This starts a search: it matches the root's first segment, and pushes
+    /// its branches onto `branches`. The root's output is handed back rather
+    /// than pushed, for its branches to merge into.
+    fn process_root_input<'a, 'b, 'c, 'd, 'e, 'f>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        branches: &'d mut impl Branches,
+        arena: &'e mut Arena<'f>,
+    ) -> OutputData {
+        let (output_data, group) = Self::match_segment(tokens, string, None, None, arena);
+
+        if Self::hits_frame_limit(branches.frames(), group, arena) {
+            return output_data;
+        }
+
+        Self::push_branches(
+            string,
+            group,
+            Parent::Root,
+            output_data.segment_index,
+            branches,
+            arena,
+        );
+
+        output_data
+    }
+
+    fn process_input_frame<'a, 'b, 'c, 'd, 'e, 'f>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+        input_data: InputData,
+        stack: &'d mut Stack,
+        arena: &'e mut Arena<'f>,
+    ) {
+        let (output_data, group) = Self::match_segment(
+            tokens,
+            string,
+            Some((input_data.group_index, input_data.chosen_option)),
+            Some(input_data.parent_segment_index),
+            arena,
+        );
+
+        let segment_index = output_data.segment_index;
+        let output_index = stack.outputs.len();
+        stack.outputs.push(OutputFrame {
+            output_data,
+            parent: input_data.parent,
+            unfinished_branches: 0,
+        });
+
+        if Self::hits_frame_limit(stack.len(), group, arena) {
+            return;
+        }
+
+        let inputs_len = stack.inputs.len();
+        Self::push_branches(
+            string,
+            group,
+            Parent::Frame(output_index),
+            segment_index,
+            stack,
+            arena,
+        );
+        stack.outputs[output_index].unfinished_branches = stack.inputs.len() - inputs_len;
+    }
+
+    /// This adds the best of an output's branches to its own result.
+    fn finish_output<'a, 'b, 'c>(output_data: &'a mut OutputData, arena: &'b mut Arena<'c>) {
+        output_data.matched_tokens_count += output_data.best_current_matched_tokens_count;
+        output_data.matched_bytes_count += output_data.best_current_matched_bytes_count;
+        arena.segments[output_data.segment_index].next = output_data.best_current_segment_index;
+    }
+
+    /// This finishes an output whose branches have all finished, and merges
+    /// it into its parent, which is either the root or one of `outputs`. It
+    /// returns the index of the parent in `outputs`, if that's where it was.
+    fn process_output_frame<'a, 'b, 'c, 'd>(
+        mut output_data: OutputData,
+        parent: Parent,
+        outputs: &'a mut [OutputFrame],
+        root_output_data: &'b mut OutputData,
+        arena: &'c mut Arena<'d>,
+    ) -> Option<usize> {
+        Self::finish_output(&mut output_data, arena);
+
+        let (parent_output_data, parent_index) = match parent {
+            Parent::Root => (root_output_data, None),
+            Parent::Frame(index) => {
+                let parent_output = &mut outputs[index];
+                parent_output.unfinished_branches -= 1;
+                (&mut parent_output.output_data, Some(index))
+            }
+        };
+        Self::merge_into_parent(
+            parent_output_data,
+            output_data,
+            arena.scoring,
+            &mut arena.segments,
+        );
+
+        parent_index
+    }
+
+    /// This makes `output_data` the best branch of its parent if it did
+    /// better than the current best, or adds it to the branches tied with the
+    /// current best if it did exactly as well. How well a branch did is
+    /// measured in whatever `scoring` counts.
+    fn merge_into_parent<'a, 'b>(
+        parent_output_data: &'a mut OutputData,
+        output_data: OutputData,
+        scoring: MatchScoring,
+        segments: &'b mut [Segment],
+    ) {
+        let (score, best_current_score) = match scoring {
+            MatchScoring::MostTokens => (
+                output_data.matched_tokens_count,
+                parent_output_data.best_current_matched_tokens_count,
+            ),
+            MatchScoring::MostBytes => (
+                output_data.matched_bytes_count,
+                parent_output_data.best_current_matched_bytes_count,
+            ),
+        };
+        let has_best = parent_output_data.best_current_segment_index.is_some();
+
+        let is_tied = has_best
+            && output_data.is_complete_match == parent_output_data.is_complete_match
+            && score == best_current_score;
+
+        let is_better = if output_data.is_complete_match {
+            !parent_output_data.is_complete_match || score > best_current_score
+        } else {
+            // A branch can consume no bytes at all when it chose an empty
+            // option, so having no best yet has to be checked separately.
+            !parent_output_data.is_complete_match && (!has_best || score > best_current_score)
+        };
+
+        if is_tied {
+            // The branches of a frame get their segments in the order their
+            // options are written. Depth first, they also finish in that
+            // order, but breadth first, a branch can finish before one written
+            // ahead of it, which then has to take its place as the best.
+            match parent_output_data.best_current_segment_index {
+                Some(best_segment_index) if output_data.segment_index < best_segment_index => {
+                    segments[output_data.segment_index].next_tie = Some(best_segment_index);
+                    parent_output_data.best_current_matched_tokens_count =
+                        output_data.matched_tokens_count;
+                    parent_output_data.best_current_matched_bytes_count =
+                        output_data.matched_bytes_count;
+                    parent_output_data.best_current_segment_index = Some(output_data.segment_index);
+                }
+                _ => {
+                    segments[parent_output_data.last_tied_segment_index].next_tie =
+                        Some(output_data.segment_index);
+                    parent_output_data.last_tied_segment_index = output_data.segment_index;
+                }
+            }
+        } else if is_better {
+            parent_output_data.best_current_matched_tokens_count = output_data.matched_tokens_count;
+            parent_output_data.best_current_matched_bytes_count = output_data.matched_bytes_count;
+            parent_output_data.best_current_segment_index = Some(output_data.segment_index);
+            parent_output_data.last_tied_segment_index = output_data.segment_index;
+            parent_output_data.is_complete_match |= output_data.is_complete_match;
+        }
+    }
+}
+
+#[require_lifetimes]
+impl Stack {
+    /// This counts the frames on the stack, inputs and outputs alike.
+    fn len<'a>(&'a self) -> usize {
+        self.inputs.len() + self.outputs.len()
+    }
+
+    /// This shows every frame on the stack, from the bottom up, as if inputs
+    /// and outputs shared a stack: the root's branches come first, then each
+    /// output, followed by its branches that are still waiting. That's the
+    /// order they were pushed in, so the last one is processed next.
+    fn frames<'a>(&'a self) -> impl Iterator<Item = StackedFrame<'a>> + 'a {
+        let mut inputs = self.inputs.iter().peekable();
+        let mut outputs = self.outputs.iter();
+        let mut outputs_shown = 0;
+
+        core::iter::from_fn(move || {
+            let is_input_next = inputs
+                .peek()
+                .is_some_and(|input_data| match input_data.parent {
+                    Parent::Root => true,
+                    Parent::Frame(output_index) => output_index < outputs_shown,
+                });
+            if !is_input_next {
+                if let Some(output) = outputs.next() {
+                    outputs_shown += 1;
+                    return Some(StackedFrame::Output(output));
+                }
+            }
+            inputs.next().map(StackedFrame::Input)
+        })
+    }
+}
+
+#[require_lifetimes]
+impl<'reference> Arena<'reference> {
+    fn new() -> Self {
+        Arena {
+            matched_tokens: Vec::new(),
+            segments: Vec::new(),
+            pruning: None,
+            scoring: MatchScoring::MostTokens,
+            frame_limit: None,
+        }
+    }
+
+    /// This makes an arena for a search that skips branches which can't be
+    /// complete matches, once it has found one that is.
+    fn with_pruning(min_remaining_len: &'reference [usize]) -> Self {
+        Arena {
+            matched_tokens: Vec::new(),
+            segments: Vec::new(),
+            pruning: Some(Pruning {
+                min_remaining_len,
+                found_complete_match: false,
+                branches_pruned: 0,
+            }),
+            scoring: MatchScoring::MostTokens,
+            frame_limit: None,
+        }
+    }
+
+    /// This empties the arena for another search, keeping its allocations.
+    fn clear<'a>(&'a mut self) {
+        self.matched_tokens.clear();
+        self.segments.clear();
+        if let Some(pruning) = &mut self.pruning {
+            pruning.found_complete_match = false;
+            pruning.branches_pruned = 0;
+        }
+    }
+
+    /// This adds a segment holding a copy of `matched_tokens`, with nothing after it.
+    #[cfg(feature = "parallel")]
+    fn push_segment<'a, 'b>(&'b mut self, matched_tokens: &'a [Range<usize>]) {
+        let segment_start = self.matched_tokens.len();
+        self.matched_tokens.extend_from_slice(matched_tokens);
+        self.segments.push(Segment {
+            matched_tokens: segment_start..self.matched_tokens.len(),
+            next: None,
+            parent: None,
+            total_matched_tokens: matched_tokens.len(),
+            total_matched_bytes: matched_tokens.iter().map(Range::len).sum(),
+            is_leaf: false,
+            next_tie: None,
+        });
+    }
+
+    /// This renders every frame on `stack`, one per line from the bottom up,
+    /// for tests to snapshot.
+    #[cfg(feature = "debug-internals")]
+    fn dump_stack<'a, 'b, 'c>(&'a self, string: &'b str, stack: &'c Stack) -> String {
+        use core::fmt::Write;
+
+        let describe_tokens = |count: usize| match count {
+            1 => "1 token".to_owned(),
+            _ => format!("{count} tokens"),
+        };
+
+        let mut dump = String::new();
+        // Parents are described by where their output is in `stack.frames()`.
+        let mut output_positions = Vec::with_capacity(stack.outputs.len());
+        for (index, frame) in stack.frames().enumerate() {
+            let describe_parent = |parent: Parent| match parent {
+                Parent::Root => "root".to_owned(),
+                Parent::Frame(output_index) => format!("frame {}", output_positions[output_index]),
+            };
+
+            match frame {
+                StackedFrame::Input(input_data) => writeln!(
+                    dump,
+                    "{index}: input  into {}, trying #{} {:?}, leaving {:?}",
+                    describe_parent(input_data.parent),
+                    input_data.group_index,
+                    &string[input_data.chosen_option.clone()],
+                    TraceEvent::preview(&string[input_data.chosen_option.end..]),
+                ),
+                StackedFrame::Output(OutputFrame {
+                    output_data,
+                    parent,
+                    ..
+                }) => {
+                    let (token_index, chosen_option) =
+                        self.branch_of(string, output_data.segment_index);
+                    let best = match output_data.best_current_segment_index {
+                        Some(best_segment_index) => format!(
+                            "best so far {:?} with {} more{}",
+                            self.branch_of(string, best_segment_index).1,
+                            describe_tokens(output_data.best_current_matched_tokens_count),
+                            if output_data.is_complete_match {
+                                ", complete"
+                            } else {
+                                ""
+                            },
+                        ),
+                        None => "no best yet".to_owned(),
+                    };
+                    let line = writeln!(
+                        dump,
+                        "{index}: output into {}, chose #{token_index} {chosen_option:?}, matching {}, {best}",
+                        describe_parent(*parent),
+                        describe_tokens(output_data.matched_tokens_count),
+                    );
+                    output_positions.push(index);
+                    line
+                }
+            }
+            .unwrap();
+        }
+
+        if dump.is_empty() {
+            dump.push_str("(empty)\n");
+        }
+        dump
+    }
+
+    /// This returns the index of the token whose option the segment at
+    /// `segment_index` starts with, along with the text of that option.
+    fn branch_of<'a, 'b>(&'a self, string: &'b str, segment_index: usize) -> (usize, &'b str) {
+        let segment = &self.segments[segment_index];
+        let token_index = segment
+            .parent
+            .map_or(0, |parent| self.segments[parent].total_matched_tokens);
+        (
+            token_index,
+            &string[self.matched_tokens[segment.matched_tokens.start].clone()],
+        )
+    }
+
+    /// This returns the segment that the most tokens were matched on the way
+    /// to. Of those that tie, it's the one that was explored first.
+    fn furthest_segment<'a>(&'a self) -> usize {
+        let mut furthest_segment_index = 0;
+        for (segment_index, segment) in self.segments.iter().enumerate() {
+            if segment.total_matched_tokens
+                > self.segments[furthest_segment_index].total_matched_tokens
+            {
+                furthest_segment_index = segment_index;
+            }
+        }
+        furthest_segment_index
+    }
+
+    /// This adds the tokens of the segment at `segment_index` to the end of
+    /// `matched_tokens`, along with the text each of them matched. Those have
+    /// to be every token before the segment's, in order.
+    fn extend_with_segment<'a, 'b, 'c, 'd, 'e>(
+        &'a self,
+        tokens: &'b [MatcherToken<'c>],
+        string: &'d str,
+        segment_index: usize,
+        matched_tokens: &'e mut Vec<(&'b MatcherToken<'c>, &'d str)>,
+    ) {
+        let range = self.segments[segment_index].matched_tokens.clone();
+        let first_token_index = matched_tokens.len();
+        matched_tokens.extend(
+            self.matched_tokens[range]
+                .iter()
+                .enumerate()
+                .map(|(index, text)| (&tokens[first_token_index + index], &string[text.clone()])),
+        );
+    }
+
+    /// This rebuilds the tokens matched on the way to the segment at
+    /// `segment_index`, by following its parents back up to the root.
+    fn path_to<'a, 'b, 'c, 'd>(
+        &'a self,
+        tokens: &'b [MatcherToken<'c>],
+        string: &'d str,
+        segment_index: usize,
+    ) -> Vec<(&'b MatcherToken<'c>, &'d str)> {
+        let mut segment_indices = vec![];
+        let mut next_segment_index = Some(segment_index);
+
+        while let Some(segment_index) = next_segment_index {
+            segment_indices.push(segment_index);
+            next_segment_index = self.segments[segment_index].parent;
+        }
+
+        let mut matched_tokens =
+            Vec::with_capacity(self.segments[segment_index].total_matched_tokens);
+        for &segment_index in segment_indices.iter().rev() {
+            self.extend_with_segment(tokens, string, segment_index, &mut matched_tokens);
+        }
+
+        matched_tokens
+    }
+
+    /// This follows the chain of segments starting at `segment_index`, and
+    /// calls `f` with every token's text along the way. Every match starts at
+    /// the first token and covers consecutive tokens, so a token's index is
+    /// just how many came before it. It returns how many tokens there were.
+    fn visit<'a, 'b, 'c>(
+        &'a self,
+        string: &'b str,
+        segment_index: usize,
+        f: &'c mut impl FnMut(usize, &'b str),
+    ) -> usize {
+        let mut index = 0;
+        let mut next_segment_index = Some(segment_index);
+
+        while let Some(segment_index) = next_segment_index {
+            let segment = &self.segments[segment_index];
+            for text in &self.matched_tokens[segment.matched_tokens.clone()] {
+                f(index, &string[text.clone()]);
+                index += 1;
+            }
+            next_segment_index = segment.next;
+        }
+
+        index
+    }
+
+    /// This copies out every chain of segments starting at `segment_index`,
+    /// following each of the tied branches wherever there were some.
+    fn collect_all<'a, 'b, 'c, 'd>(
+        &'a self,
+        tokens: &'b [MatcherToken<'c>],
+        string: &'d str,
+        segment_index: usize,
+    ) -> Vec<Vec<(&'b MatcherToken<'c>, &'d str)>> {
+        let mut matches = Vec::new();
+        // These are matches that still need finishing, from the segment after
+        // them. They're popped in the order they have to be finished in.
+        let mut unfinished = vec![(Vec::new(), Some(segment_index))];
+
+        while let Some((mut matched_tokens, mut next_segment_index)) = unfinished.pop() {
+            while let Some(segment_index) = next_segment_index {
+                self.extend_with_segment(tokens, string, segment_index, &mut matched_tokens);
+                next_segment_index = self.segments[segment_index].next;
+
+                let ties_start = unfinished.len();
+                let mut next_tie = next_segment_index.and_then(|i| self.segments[i].next_tie);
+                while let Some(tie) = next_tie {
+                    unfinished.push((matched_tokens.clone(), Some(tie)));
+                    next_tie = self.segments[tie].next_tie;
+                }
+                unfinished[ties_start..].reverse();
+            }
+
+            matches.push(matched_tokens);
+        }
+
+        matches
+    }
+
+    /// This follows the chain of segments starting at `segment_index`, and
+    /// copies out where in the string every token along the way matched.
+    #[cfg(feature = "parallel")]
+    fn collect<'a>(&'a self, segment_index: usize) -> Vec<Range<usize>> {
+        let mut matched_tokens = Vec::new();
+        let mut next_segment_index = Some(segment_index);
+
+        while let Some(segment_index) = next_segment_index {
+            let segment = &self.segments[segment_index];
+            matched_tokens.extend_from_slice(&self.matched_tokens[segment.matched_tokens.clone()]);
+            next_segment_index = segment.next;
+        }
+
+        matched_tokens
+    }
+}
+
+impl<'a, 'internal, 'b> Iterator for ExhaustiveSearch<'a, 'internal, 'b> {
+    type Item = MatchCandidate<'a, 'internal, 'b>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (tokens, string) = (self.tokens, self.string);
+        let (segment_index, arena) = match &mut self.explored {
+            Some((stack, arena)) => (
+                Matcher::next_complete_segment(tokens, string, stack, arena),
+                arena,
+            ),
+            None => {
+                let mut stack = Vec::new();
+                let mut arena = Arena::with_pruning(self.min_remaining_len);
+                let root_output_data =
+                    Matcher::process_root_input(tokens, string, &mut stack, &mut arena);
+                let (stack, arena) = self.explored.insert((stack, arena));
+                // A root that matched every token has no branches.
+                let segment_index = if root_output_data.is_complete_match {
+                    Some(root_output_data.segment_index)
+                } else {
+                    Matcher::next_complete_segment(tokens, string, stack, arena)
+                };
+                (segment_index, arena)
+            }
+        };
+
+        // Nothing's pruned until there's a complete match, so until then,
+        // the furthest partial match is among the segments explored.
+        let furthest = match segment_index {
+            Some(_) => self.tokens.len(),
+            None => arena
+                .segments
+                .iter()
+                .map(|segment| segment.total_matched_tokens)
+                .max()
+                .unwrap_or(0),
+        };
+        if furthest > *self.most_tokens_matched {
+            *self.most_tokens_matched = furthest;
+        }
+
+        let segment_index = segment_index?;
+        Some(MatchCandidate {
+            matched_tokens: arena.path_to(tokens, string, segment_index),
+            stop_offset: arena.segments[segment_index].total_matched_bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Arena, ExploreOrder, ExploreStats, FrameLimitExceeded, MatchCandidate, MatchScoring, Stack,
+        TraceEvent,
+    };
+    use crate::{Matcher, MatcherToken};
+
+    #[test]
+    fn exhaustive_visit_agrees_with_collected_results() {
+        let fixtures = [
+            ("abc(d|e|f).", "abcge", vec!["abc"]),
+            ("abc(d|e|f).", "abcde", vec!["abc", "d", "e"]),
+            ("abc(d|e|f).", "abcd💪", vec!["abc", "d", "💪"]),
+            (
+                "(aba|abac).(aba|abac).",
+                "abacabacd",
+                vec!["aba", "c", "aba", "c"],
+            ),
+            ("a.c", "", vec![]),
+            // These all end with a group, or with a branch failing on its
+            // last token, which branches used to get wrong about whether
+            // they had matched completely.
+            ("(aba|abac).", "abac", vec!["aba", "c"]),
+            ("(x|a)(x|a)", "aa", vec!["a", "a"]),
+            ("(a|b).(c|d)", "bxd", vec!["b", "x", "d"]),
+            ("(ab|a)(ba|b)(a|ab)c", "ababc", vec!["a", "b", "ab", "c"]),
+            ("(x|xy|xyz)(yz|z|?)!", "xyz?!", vec!["xyz", "?", "!"]),
+        ];
+
+        for (match_string, candidate, expected) in fixtures {
+            let mut matcher = Matcher::new(match_string).unwrap();
+            let collected: Vec<&str> = matcher
+                .match_string_exhaustive(candidate)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+
+            let mut visited = vec![];
+            matcher.match_exhaustive_visit(candidate, |index, text| visited.push((index, text)));
+
+            assert_eq!(
+                collected, expected,
+                "{match_string:?} against {candidate:?}"
+            );
+            assert_eq!(
+                visited,
+                expected.into_iter().enumerate().collect::<Vec<_>>(),
+                "{match_string:?} against {candidate:?}"
+            );
+            assert_eq!(matcher.most_tokens_matched, collected.len());
+        }
+    }
+
+    #[test]
+    fn exhaustive_stats() {
+        let fixtures = [
+            ("(a|b)c", "ac", (2, 2, 1, 0, 0)),
+            ("(a|ab)c", "abd", (4, 4, 2, 2, 0)),
+            ("(aba|abac).(aba|abac).", "abacabacd", (8, 8, 4, 1, 0)),
+            ("abc", "abd", (0, 0, 0, 1, 0)),
+            ("(a|aa)(a|aa)(a|aa)(a|aa)b", "aaaab", (14, 14, 7, 3, 3)),
+        ];
+
+        for (match_string, candidate, expected) in fixtures {
+            let (frames_pushed, frames_popped, peak_stack_len, branches_abandoned, branches_pruned) =
+                expected;
+            let mut matcher = Matcher::new(match_string).unwrap();
+            let mut other_matcher = Matcher::new(match_string).unwrap();
+
+            let (result, stats) = matcher.match_string_exhaustive_with_stats(candidate);
+            assert_eq!(result, other_matcher.match_string_exhaustive(candidate));
+            assert_eq!(
+                stats,
+                ExploreStats {
+                    frames_pushed,
+                    frames_popped,
+                    peak_stack_len,
+                    branches_abandoned,
+                    branches_pruned,
+                },
+                "{match_string} against {candidate}"
+            );
+        }
+    }
+
+    #[test]
+    fn breadth_first_finds_the_same_matches() {
+        let wide_group = (1..=8)
+            .map(|length| "a".repeat(length))
+            .collect::<Vec<_>>()
+            .join("|");
+        // Both orders do the same work, but breadth first has to hold on to
+        // more frames at once when groups have lots of options.
+        let fixtures = [
+            ("abc(d|e|f).", "abcde", (2, 2, 1, 0, 0), (2, 2, 1, 0, 0)),
+            ("(a|ab)c", "abd", (4, 4, 2, 2, 0), (4, 4, 2, 2, 0)),
+            (
+                "(aba|abac).(aba|abac).",
+                "abacabacd",
+                (8, 8, 4, 1, 0),
+                (8, 8, 4, 1, 0),
+            ),
+            ("(a|ab)(b|c)x.", "abc", (8, 8, 3, 2, 0), (8, 8, 4, 2, 0)),
+            (
+                &format!("({wide_group})({wide_group})b"),
+                "aaaaaaaaab",
+                (88, 88, 16, 28, 0),
+                (88, 88, 44, 28, 0),
+            ),
+        ];
+
+        for (match_string, candidate, depth_first, breadth_first) in fixtures {
+            let mut matcher = Matcher::new(match_string).unwrap();
+            let mut other_matcher = Matcher::new(match_string).unwrap();
+            let expected = other_matcher.match_string_exhaustive(candidate);
+            assert_eq!(
+                matcher.match_string_exhaustive_with_order(candidate, ExploreOrder::BreadthFirst),
+                expected,
+                "{match_string} against {candidate}"
+            );
+            assert_eq!(
+                matcher.match_string_exhaustive_with_order(candidate, ExploreOrder::DepthFirst),
+                expected,
+                "{match_string} against {candidate}"
+            );
+
+            for (stats, order) in [
+                (depth_first, ExploreOrder::DepthFirst),
+                (breadth_first, ExploreOrder::BreadthFirst),
+            ] {
+                let mut recorded = ExploreStats::default();
+                let mut arena = Arena::with_pruning(&matcher.min_remaining_len);
+                match order {
+                    ExploreOrder::DepthFirst => Matcher::search(
+                        &matcher.tokens,
+                        candidate,
+                        &mut Stack::default(),
+                        &mut arena,
+                        &mut recorded,
+                    ),
+                    ExploreOrder::BreadthFirst => Matcher::search_breadth_first(
+                        &matcher.tokens,
+                        candidate,
+                        &mut arena,
+                        &mut recorded,
+                    ),
+                };
+
+                let (
+                    frames_pushed,
+                    frames_popped,
+                    peak_stack_len,
+                    branches_abandoned,
+                    branches_pruned,
+                ) = stats;
+                assert_eq!(
+                    recorded,
+                    ExploreStats {
+                        frames_pushed,
+                        frames_popped,
+                        peak_stack_len,
+                        branches_abandoned,
+                        branches_pruned,
+                    },
+                    "{match_string} against {candidate}, {order:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn empty_options_still_terminate() {
+        let mut tokens: Vec<_> = (0..12)
+            .map(|_| MatcherToken::OneOfText(vec!["", "a"].into()))
+            .collect();
+        tokens.push(MatcherToken::RawText("b"));
+        let mut matcher = Matcher::from_tokens("(|a)(|a)(|a)...b", tokens);
+
+        // Every one of the 2^12 ways of choosing options gets as far as the
+        // "b", but only the ones choosing "a" exactly twice match it.
+        let (result, stats) = matcher.match_string_exhaustive_with_stats("aab");
+        let texts: Vec<&str> = result.into_iter().map(|(_, text)| text).collect();
+        let mut expected = vec![""; 10];
+        expected.extend(["a", "a", "b"]);
+        assert_eq!(texts, expected);
+        assert_eq!(matcher.most_tokens_matched, 13);
+        assert!(stats.frames_pushed < 2 * 2usize.pow(13));
+
+        for order in [ExploreOrder::DepthFirst, ExploreOrder::BreadthFirst] {
+            let result: Vec<&str> = matcher
+                .match_string_exhaustive_with_order("aab", order)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+            assert_eq!(result, texts, "{order:?}");
+        }
+        assert_eq!(matcher.all_best_matches("aab").len(), 66);
+
+        // Scoring by bytes, the branches that consume nothing have to be
+        // told apart from there being no best branch yet.
+        let texts: Vec<&str> = matcher
+            .match_string_exhaustive_scored("ac", MatchScoring::MostBytes)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        let mut expected = vec![""; 11];
+        expected.push("a");
+        assert_eq!(texts, expected);
+
+        // An empty option that's the only one still gets explored.
+        let mut matcher = Matcher::from_tokens(
+            "()b",
+            vec![
+                MatcherToken::OneOfText(vec![""].into()),
+                MatcherToken::RawText("b"),
+            ],
+        );
+        let result = matcher.match_string_exhaustive("b");
+        assert_eq!(
+            result,
+            vec![
+                (&MatcherToken::OneOfText(vec![""].into()), ""),
+                (&MatcherToken::RawText("b"), "b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pruning_keeps_the_best_partial_match() {
+        // Every branch here is too short to match completely, but since no
+        // complete match exists they mustn't be pruned: the best partial
+        // match is at the end of one of them.
+        let mut matcher = Matcher::new("(ab|a)(c|b).xyz").unwrap();
+        let (result, stats) = matcher.match_string_exhaustive_with_stats("abc");
+        assert_eq!(
+            result,
+            vec![
+                (&MatcherToken::OneOfText(vec!["ab", "a"].into()), "a"),
+                (&MatcherToken::OneOfText(vec!["c", "b"].into()), "b"),
+                (&MatcherToken::WildCard, "c"),
+            ]
+        );
+        assert_eq!(stats.branches_pruned, 0);
+    }
+
+    #[test]
+    fn searches_that_never_branch() {
+        let fixtures = [
+            // The first token fails straight away.
+            ("abc", "xbc", vec![]),
+            // The pattern starts with a group that no option of matches.
+            ("(a|b)c", "xc", vec![]),
+            ("(a|b)c", "", vec![]),
+            ("abc", "", vec![]),
+            ("", "", vec![(MatcherToken::RawText(""), "")]),
+            ("", "abc", vec![(MatcherToken::RawText(""), "")]),
+        ];
+
+        for (match_string, candidate, expected) in fixtures {
+            let mut matcher = Matcher::new(match_string).unwrap();
+            let expected: Vec<_> = expected
+                .iter()
+                .map(|(token, text)| (token, *text))
+                .collect();
+
+            assert_eq!(matcher.match_string_exhaustive(candidate), expected);
+            // Only the empty pattern matches completely, and a search only
+            // yields complete matches.
+            let complete = match_string.is_empty();
+            assert_eq!(
+                matcher
+                    .exhaustive_search(candidate)
+                    .next()
+                    .map(|candidate| candidate.matched_tokens),
+                complete.then_some(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn ties_go_to_the_first_option() {
+        let fixtures = [
+            ("(a|ab)(bc|c)!", "abc!", vec!["a", "bc", "!"]),
+            ("(ab|a)(bc|c)!", "abc!", vec!["ab", "c", "!"]),
+            ("(ab|a)(c|bc)!", "abc!", vec!["ab", "c", "!"]),
+            ("(a|ab)(c|bc)!", "abc!", vec!["a", "bc", "!"]),
+            // Neither option leads to a complete match, but both get as far
+            // as two tokens.
+            ("(a|ab)(b|c)x.", "abc", vec!["a", "b"]),
+            ("(ab|a)(b|c)x.", "abc", vec!["ab", "c"]),
+        ];
+
+        for (match_string, candidate, expected) in fixtures {
+            let mut matcher = Matcher::new(match_string).unwrap();
+            let result: Vec<&str> = matcher
+                .match_string_exhaustive(candidate)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+            assert_eq!(result, expected, "{match_string} against {candidate}");
+        }
+    }
+
+    #[test]
+    fn scoring_by_bytes_prefers_longer_matches() {
+        let fixtures = [
+            // Every option leads to a complete match, and those always have the
+            // same number of tokens.
+            ("(a|ab).", "abc", vec!["a", "b"], vec!["ab", "c"]),
+            ("(ab|a).", "abc", vec!["ab", "c"], vec!["ab", "c"]),
+            ("(a|abc|ab).", "abcd", vec!["a", "b"], vec!["abc", "d"]),
+            // Neither option matches completely. The first gets further
+            // through the pattern, but the second gets further through the
+            // string.
+            ("(a|abcd)b.y.", "abcd", vec!["a", "b", "c"], vec!["abcd"]),
+            (
+                "(a|abcd)b.y.",
+                "abcx",
+                vec!["a", "b", "c"],
+                vec!["a", "b", "c"],
+            ),
+        ];
+
+        for (match_string, candidate, most_tokens, most_bytes) in fixtures {
+            let mut matcher = Matcher::new(match_string).unwrap();
+            let mut other_matcher = Matcher::new(match_string).unwrap();
+
+            let by_tokens: Vec<&str> = matcher
+                .match_string_exhaustive_scored(candidate, MatchScoring::MostTokens)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+            let by_bytes: Vec<&str> = matcher
+                .match_string_exhaustive_scored(candidate, MatchScoring::MostBytes)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+
+            assert_eq!(by_tokens, most_tokens, "{match_string} against {candidate}");
+            assert_eq!(by_bytes, most_bytes, "{match_string} against {candidate}");
+            assert_eq!(
+                by_tokens.len(),
+                other_matcher.match_string_exhaustive(candidate).len()
+            );
+        }
+    }
+
+    #[test]
+    fn exhaustive_trace() {
+        let mut matcher = Matcher::new("(aba|abac).").unwrap();
+        let mut other_matcher = Matcher::new("(aba|abac).").unwrap();
+
+        let (result, trace) = matcher.match_string_exhaustive_traced("abac");
+        assert_eq!(result, other_matcher.match_string_exhaustive("abac"));
+
+        let trace: Vec<String> = trace.iter().map(TraceEvent::to_string).collect();
+        assert_eq!(
+            trace.join("\n"),
+            r#"push input    #0 "abac", leaving ""
+push input    #0 "aba", leaving "c"
+push output   #0 "aba", leaving ""
+pop output    #0 "aba", matching 2 tokens
+choose branch #0 "aba"
+push output   #0 "abac", leaving ""
+pop output    #0 "abac", matching 1 token"#
+        );
+
+        let mut matcher = Matcher::new("a(b|bc)(c|d)!").unwrap();
+        let (_, trace) = matcher.match_string_exhaustive_traced("abcd!abcdefghijklmnop");
+        assert_eq!(
+            trace[..2],
+            [
+                TraceEvent::PushInput {
+                    token_index: 1,
+                    chosen_option: "bc".to_owned(),
+                    remaining: "d!abcdefghijklmn...".to_owned(),
+                },
+                TraceEvent::PushInput {
+                    token_index: 1,
+                    chosen_option: "b".to_owned(),
+                    remaining: "cd!abcdefghijklm...".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            trace.last(),
+            Some(&TraceEvent::BranchChosen {
+                token_index: 1,
+                chosen_option: "bc".to_owned(),
+            })
+        );
+        assert!(trace.contains(&TraceEvent::BranchChosen {
+            token_index: 2,
+            chosen_option: "d".to_owned(),
+        }));
+    }
+
+    #[test]
+    fn frame_limit_stops_pathological_searches() {
+        // Nothing here can match completely, so nothing gets pruned, and
+        // every one of the 2^20 ways of choosing options would be explored.
+        let match_string = format!("{}b", "(a|aa)".repeat(20));
+        let candidate = "a".repeat(40);
+        let mut matcher = Matcher::new(&match_string).unwrap();
+        let group = MatcherToken::OneOfText(vec!["a", "aa"].into());
+
+        assert_eq!(
+            matcher.match_string_exhaustive_bounded(&candidate, 8),
+            Err(FrameLimitExceeded {
+                best_partial_match: vec![(&group, "a"); 4],
+                stop_offset: 4,
+                frames: 8,
+            })
+        );
+        assert_eq!(matcher.most_tokens_matched, 4);
+
+        // The limit covers the root's branches too.
+        let mut matcher = Matcher::new("(a|b|c)d").unwrap();
+        assert_eq!(
+            matcher.match_string_exhaustive_bounded("ad", 2),
+            Err(FrameLimitExceeded {
+                best_partial_match: vec![],
+                stop_offset: 0,
+                frames: 0,
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn frame_limit_is_an_error() {
+        let mut matcher = Matcher::new("(a|b|c)d").unwrap();
+        let error: Box<dyn std::error::Error> = matcher
+            .match_string_exhaustive_bounded("ad", 2)
+            .unwrap_err()
+            .into();
+        assert_eq!(
+            error.to_string(),
+            "the exhaustive search was stopped with 0 frames on its stack"
+        );
+    }
+
+    #[test]
+    fn frame_limit_allows_searches_that_fit() {
+        let match_string = format!("{}b", "(a|aa)".repeat(8));
+        let candidate = "a".repeat(16);
+        let mut matcher = Matcher::new(&match_string).unwrap();
+        let mut other_matcher = Matcher::new(&match_string).unwrap();
+
+        let (expected, stats) = other_matcher.match_string_exhaustive_with_stats(&candidate);
+        assert_eq!(
+            matcher.match_string_exhaustive_bounded(&candidate, stats.peak_stack_len),
+            Ok(expected)
+        );
+        assert_eq!(
+            matcher.most_tokens_matched,
+            other_matcher.most_tokens_matched
+        );
+    }
+
+    #[test]
+    fn search_exhaustive_finds_matches_anywhere() {
+        let group = MatcherToken::OneOfText(vec!["b", "bc"].into());
+        let mut matcher = Matcher::new("a(b|bc)d.e").unwrap();
+
+        // Searching from the start gets four tokens in before failing, but
+        // the only complete match starts further along.
+        assert_eq!(
+            matcher.search_exhaustive("abcd!xabd?e"),
+            Some((
+                6,
+                vec![
+                    (&MatcherToken::RawText("a"), "a"),
+                    (&group, "b"),
+                    (&MatcherToken::RawText("d"), "d"),
+                    (&MatcherToken::WildCard, "?"),
+                    (&MatcherToken::RawText("e"), "e"),
+                ]
+            ))
+        );
+        assert_eq!(matcher.most_tokens_matched, 5);
+
+        // The earliest match wins, even though a later one matches more text.
+        assert_eq!(
+            matcher
+                .search_exhaustive("💪abd!e abcd!e")
+                .map(|(start, matched_tokens)| (start, matched_tokens.len())),
+            Some((4, 5))
+        );
+
+        // Greedy matching would take "b" and fail, so this needs every
+        // option to be tried at each position.
+        let mut matcher = Matcher::new("(b|bc)d").unwrap();
+        assert_eq!(
+            matcher
+                .search_exhaustive("xbxbcd")
+                .map(|(start, matched_tokens)| (start, matched_tokens[0].1)),
+            Some((3, "bc"))
+        );
+
+        let mut matcher = Matcher::new("a(b|bc)d.e").unwrap();
+        assert_eq!(matcher.search_exhaustive("abcd!xabd?"), None);
+        assert_eq!(matcher.most_tokens_matched, 4);
+        assert_eq!(matcher.search_exhaustive(""), None);
+    }
+
+    #[test]
+    fn most_tokens_matched_counts_the_furthest_branch() {
+        let fixtures = [
+            // A shallow branch that fails on its last token is not complete,
+            // so it mustn't beat a deeper one that matches more tokens.
+            ("(aba|abac).", "abac"),
+            ("(ab|a)xy", "abx"),
+            ("(a|ab)(bx|x)yz.", "abxy"),
+            ("(a|abcd)b.y.", "abcd"),
+            ("(a|ab)(b|c)x.", "abc"),
+            ("(x|a)(x|a)", "aa"),
+            ("(ab|a)(ba|b)(a|ab)c", "ababd"),
+            ("abc", "abd"),
+        ];
+
+        for (match_string, candidate) in fixtures {
+            // A search run to the end counts how far every branch it explored
+            // got, rather than merging them the way the engine does.
+            let mut matcher = Matcher::new(match_string).unwrap();
+            matcher.exhaustive_search(candidate).for_each(drop);
+            let furthest = matcher.most_tokens_matched;
+
+            for scoring in [MatchScoring::MostTokens, MatchScoring::MostBytes] {
+                let mut matcher = Matcher::new(match_string).unwrap();
+                matcher.match_string_exhaustive_scored(candidate, scoring);
+                assert_eq!(
+                    matcher.most_tokens_matched, furthest,
+                    "{match_string} against {candidate} with {scoring:?}"
+                );
+            }
+
+            let mut matcher = Matcher::new(match_string).unwrap();
+            assert_eq!(
+                matcher.match_string_exhaustive(candidate).len(),
+                furthest,
+                "{match_string} against {candidate}"
+            );
+            assert_eq!(matcher.most_tokens_matched, furthest);
+        }
+    }
+
+    #[test]
+    fn stop_offsets_agree_with_matched_text() {
+        let fixtures = [
+            ("abc(d|e|f).", "abcd💪"),
+            ("abc(d|e|f).", "abcge"),
+            ("(aba|abac).(aba|abac).", "abacabacd"),
+            ("(a|abcd)b.y.", "abcd"),
+            ("(ab|a)(ba|b)(a|ab)c", "ababa"),
+            ("a..(💪|b)", "a💪💪b"),
+            ("abc", ""),
+        ];
+
+        for (match_string, candidate) in fixtures {
+            let mut matcher = Matcher::new(match_string).unwrap();
+            for match_candidate in matcher.exhaustive_search(candidate) {
+                // Matches start at the beginning of the string and don't
+                // skip anything, so they stop after the text they matched.
+                let matched_text: String = match_candidate
+                    .matched_tokens
+                    .iter()
+                    .map(|(_, text)| *text)
+                    .collect();
+                assert_eq!(
+                    &candidate[..match_candidate.stop_offset],
+                    matched_text,
+                    "{match_string} against {candidate}"
+                );
+            }
+
+            let mut matcher = Matcher::new(match_string).unwrap();
+            if let Err(error) = matcher.match_string_exhaustive_bounded(candidate, 1) {
+                let matched_len: usize = error
+                    .best_partial_match
+                    .iter()
+                    .map(|(_, text)| text.len())
+                    .sum();
+                assert_eq!(error.stop_offset, matched_len);
+            }
+        }
+    }
+
+    #[test]
+    fn all_best_matches_keeps_ties() {
+        let first_group = MatcherToken::OneOfText(vec!["x", "xy"].into());
+        let second_group = MatcherToken::OneOfText(vec!["yz", "z"].into());
+        let third_group = MatcherToken::OneOfText(vec!["1", "12"].into());
+        let fourth_group = MatcherToken::OneOfText(vec!["23", "3"].into());
+        let end = MatcherToken::RawText("!");
+
+        let mut matcher = Matcher::new("(x|xy)(yz|z)(1|12)(23|3)!").unwrap();
+        let mut other_matcher = Matcher::new("(x|xy)(yz|z)(1|12)(23|3)!").unwrap();
+        let matches = matcher.all_best_matches("xyz123!");
+        assert_eq!(
+            matches,
+            vec![
+                vec![
+                    (&first_group, "x"),
+                    (&second_group, "yz"),
+                    (&third_group, "1"),
+                    (&fourth_group, "23"),
+                    (&end, "!")
+                ],
+                vec![
+                    (&first_group, "x"),
+                    (&second_group, "yz"),
+                    (&third_group, "12"),
+                    (&fourth_group, "3"),
+                    (&end, "!")
+                ],
+                vec![
+                    (&first_group, "xy"),
+                    (&second_group, "z"),
+                    (&third_group, "1"),
+                    (&fourth_group, "23"),
+                    (&end, "!")
+                ],
+                vec![
+                    (&first_group, "xy"),
+                    (&second_group, "z"),
+                    (&third_group, "12"),
+                    (&fourth_group, "3"),
+                    (&end, "!")
+                ],
+            ]
+        );
+        assert_eq!(matches[0], other_matcher.match_string_exhaustive("xyz123!"));
+        assert_eq!(matcher.most_tokens_matched, 5);
+
+        // Branches that did worse than the best aren't included, even when
+        // some of them are tied with each other.
+        let first_group = MatcherToken::OneOfText(vec!["x", "xy", "xyz"].into());
+        let second_group = MatcherToken::OneOfText(vec!["yz", "z", "?"].into());
+        let mut matcher = Matcher::new("(x|xy|xyz)(yz|z|?)!.").unwrap();
+        assert_eq!(
+            matcher.all_best_matches("xyz?!a"),
+            vec![vec![
+                (&first_group, "xyz"),
+                (&second_group, "?"),
+                (&MatcherToken::RawText("!"), "!"),
+                (&MatcherToken::WildCard, "a")
+            ]]
+        );
+        assert_eq!(
+            matcher.all_best_matches("xyz!a"),
+            vec![
+                vec![
+                    (&first_group, "x"),
+                    (&second_group, "yz"),
+                    (&MatcherToken::RawText("!"), "!"),
+                    (&MatcherToken::WildCard, "a")
+                ],
+                vec![
+                    (&first_group, "xy"),
+                    (&second_group, "z"),
+                    (&MatcherToken::RawText("!"), "!"),
+                    (&MatcherToken::WildCard, "a")
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn count_complete_matches_counts_every_way_of_matching() {
+        let mut matcher = Matcher::new("(a|aa)(a|aa)").unwrap();
+        assert_eq!(matcher.count_complete_matches("aaaa"), 4);
+        assert_eq!(matcher.count_complete_matches("aaa"), 3);
+        assert_eq!(matcher.count_complete_matches("aa"), 1);
+        assert_eq!(matcher.count_complete_matches("a"), 0);
+        assert_eq!(matcher.most_tokens_matched, 2);
+
+        let mut matcher = Matcher::new("(x|xy|xyz)(yz|z|?)!.").unwrap();
+        assert_eq!(matcher.count_complete_matches("xyz!a"), 2);
+        assert_eq!(matcher.count_complete_matches("xyz?!a"), 1);
+        assert_eq!(matcher.count_complete_matches("xyz!"), 0);
+
+        // The same text chosen twice is still only one match.
+        let mut matcher = Matcher::new("(a|b|a)(a|b|a)c").unwrap();
+        assert_eq!(matcher.count_complete_matches("abc"), 1);
+
+        // Nothing is ever listed, so this doesn't take 2^20 steps.
+        let match_string = "(a|aa)".repeat(20);
+        let mut matcher = Matcher::new(&match_string).unwrap();
+        assert_eq!(matcher.count_complete_matches(&"a".repeat(40)), 1 << 20);
+
+        let mut matcher = Matcher::new("").unwrap();
+        assert_eq!(matcher.count_complete_matches("abc"), 1);
+    }
+
+    #[test]
+    fn count_complete_matches_agrees_with_all_best_matches() {
+        for (match_string, candidate) in [
+            ("(x|xy)(yz|z)(1|12)(23|3)!", "xyz123!"),
+            ("(aba|abac).(aba|abac).", "abacabacd"),
+            ("(a|ab|b)(b|ba|c)(a|aa|c)", "abaac"),
+            ("(a|ab).(b|c)", "abbc"),
+        ] {
+            let mut matcher = Matcher::new(match_string).unwrap();
+            let mut other_matcher = Matcher::new(match_string).unwrap();
+            let count = matcher.count_complete_matches(candidate);
+            assert_eq!(
+                count,
+                other_matcher.all_best_matches(candidate).len(),
+                "{match_string:?} against {candidate:?}"
+            );
+            assert!(count > 0, "{match_string:?} against {candidate:?}");
+        }
+    }
+
+    #[test]
+    fn exhaustive_search_yields_every_complete_match() {
+        let match_string = "(aba|abac).(aba|abac).";
+        let mut matcher = Matcher::new(match_string).unwrap();
+        let group = MatcherToken::OneOfText(vec!["aba", "abac"].into());
+
+        // Choosing "abac" for the first group only gets as far as "abaca",
+        // which isn't complete, so it isn't yielded.
+        let candidates: Vec<_> = matcher.exhaustive_search("abacabacd").collect();
+        assert_eq!(
+            candidates,
+            vec![
+                MatchCandidate {
+                    matched_tokens: vec![
+                        (&group, "aba"),
+                        (&MatcherToken::WildCard, "c"),
+                        (&group, "aba"),
+                        (&MatcherToken::WildCard, "c")
+                    ],
+                    stop_offset: 8,
+                },
+                MatchCandidate {
+                    matched_tokens: vec![
+                        (&group, "aba"),
+                        (&MatcherToken::WildCard, "c"),
+                        (&group, "abac"),
+                        (&MatcherToken::WildCard, "d")
+                    ],
+                    stop_offset: 9,
+                },
+            ]
+        );
+        assert_eq!(matcher.most_tokens_matched, 4);
+
+        let mut other_matcher = Matcher::new(match_string).unwrap();
+        let first = matcher.exhaustive_search("abacabacd").next().unwrap();
+        assert_eq!(
+            first.matched_tokens,
+            other_matcher.match_string_exhaustive("abacabacd")
+        );
+
+        // Where a later group's options are the ones that differ, its first
+        // option still comes first.
+        let mut matcher = Matcher::new("(a|ab)(bc|c)").unwrap();
+        let chosen: Vec<Vec<&str>> = matcher
+            .exhaustive_search("abc")
+            .map(|candidate| {
+                candidate
+                    .matched_tokens
+                    .iter()
+                    .map(|(_, text)| *text)
+                    .collect()
+            })
+            .collect();
+        assert_eq!(chosen, vec![vec!["a", "bc"], vec!["ab", "c"]]);
+
+        let mut matcher = Matcher::new("abc(d|e|f).").unwrap();
+        assert_eq!(matcher.exhaustive_search("abcge").next(), None);
+        assert_eq!(matcher.most_tokens_matched, 1);
+    }
+
+    #[test]
+    fn exhaustive_search_stops_at_each_complete_match() {
+        // Every way of choosing 40 options here is complete, which is far too
+        // many to explore, so this only finishes if the search stops at each.
+        let match_string = "(a|aa)".repeat(40);
+        let candidate = "a".repeat(80);
+        let mut matcher = Matcher::new(&match_string).unwrap();
+        let mut search = matcher.exhaustive_search(&candidate);
+
+        let first = search.next().unwrap();
+        assert!(first.matched_tokens.iter().all(|(_, text)| *text == "a"));
+        assert_eq!(first.stop_offset, 40);
+        // Next, the last group takes its other option.
+        let second = search.next().unwrap();
+        assert_eq!(second.matched_tokens.last().unwrap().1, "aa");
+        assert_eq!(second.stop_offset, 41);
+    }
+
+    #[test]
+    fn deeply_nested_exhaustive_match() {
+        let match_string = format!("{}b", "(x|a)".repeat(2000));
+        let mut matcher = Matcher::new(&match_string).unwrap();
+
+        let candidate = format!("{}b", "a".repeat(2000));
+        let result = matcher.match_string_exhaustive(&candidate);
+        assert_eq!(result.len(), 2001);
+        assert!(result[..2000].iter().all(|&(_, text)| text == "a"));
+        assert_eq!(matcher.most_tokens_matched, 2001);
+
+        let match_string = format!("{}.", "(a|aa)".repeat(10));
+        let mut matcher = Matcher::new(&match_string).unwrap();
+
+        let candidate = "a".repeat(21);
+        let result = matcher.match_string_exhaustive(&candidate);
+        assert_eq!(result.len(), 11);
+        // Every option leads to a complete match, so the first one wins.
+        assert!(result.iter().all(|&(_, text)| text == "a"));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_exhaustive_matches_sequential() {
+        let wide_group = (1..=64)
+            .map(|length| "a".repeat(length))
+            .collect::<Vec<_>>()
+            .join("|");
+        let wide_pattern = format!("({wide_group})(a|aa|aaa).b");
+        let patterns = [
+            "abc(d|e|f).",
+            "(aba|abac).(aba|abac).",
+            "(ab|a)(ba|b)(a|ab)",
+            wide_pattern.as_str(),
+        ];
+        let long_candidate = "a".repeat(80);
+        let candidates = [
+            "abcge",
+            "abcde",
+            "abcd💪",
+            "abacabacd",
+            "ababa",
+            "aabab",
+            "",
+            long_candidate.as_str(),
+        ];
+
+        for pattern in patterns {
+            for candidate in candidates {
+                let mut sequential_matcher = Matcher::new(pattern).unwrap();
+                let mut parallel_matcher = Matcher::new(pattern).unwrap();
+
+                assert_eq!(
+                    sequential_matcher.match_string_exhaustive(candidate),
+                    parallel_matcher.match_string_exhaustive_parallel(candidate),
+                    "pattern {pattern:?} against {candidate:?}"
+                );
+                assert_eq!(
+                    sequential_matcher.most_tokens_matched,
+                    parallel_matcher.most_tokens_matched
+                );
+            }
+        }
+    }
+}
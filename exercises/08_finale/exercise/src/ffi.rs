@@ -0,0 +1,387 @@
+//! This lets C call the matcher. It's behind the `ffi` feature; build it as a
+//! library C can link against with
+//! `cargo rustc --package ex08 --lib --features ffi --crate-type cdylib`,
+//! and include `include/lk_matcher.h`, which `cargo xtask ffi-header` writes
+//! from this file with cbindgen.
+//!
+//! Like `matcher-wasm`, an `LkMatcher` owns its pattern and the tokens it was
+//! parsed into, and makes a `Matcher` borrowing them for each match, since C
+//! can't keep track of a borrow.
+//! Every function here checks its pointers for null, and catches any panic,
+//! rather than let it unwind into C.
+
+use crate::{Matcher, OwnedMatcherToken, ParseErrorKind};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ffi::{c_char, c_int, CStr};
+use core::ptr;
+use require_lifetimes::require_lifetimes;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// `lk_matcher_match` matched the candidate (as far as it could).
+pub const LK_OK: c_int = 0;
+/// A pointer was null, or a string wasn't UTF-8.
+pub const LK_INVALID_ARGUMENT: c_int = -1;
+/// The matcher panicked, which is a bug.
+pub const LK_PANICKED: c_int = -2;
+
+/// A group of the pattern has an option with nothing in it, like `(a|)`.
+pub const LK_EMPTY_OPTION: c_int = 1;
+/// A group of the pattern has only one option, like `(a)`.
+pub const LK_ONE_OPTION: c_int = 2;
+/// A group of the pattern has no `)` to close it, like `(a|b`.
+pub const LK_UNCLOSED_GROUP: c_int = 3;
+
+/// A pattern, made by `lk_matcher_new`, and freed by `lk_matcher_free`.
+pub struct LkMatcher {
+    pattern: String,
+    tokens: Vec<OwnedMatcherToken>,
+}
+
+/// Why a pattern didn't parse, filled in by `lk_matcher_parse`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LkParseError {
+    /// `LK_EMPTY_OPTION`, `LK_ONE_OPTION` or `LK_UNCLOSED_GROUP`.
+    pub kind: c_int,
+    /// The byte offset in the pattern where the problem is.
+    pub offset: usize,
+}
+
+/// What one token matched, in bytes of the candidate.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LkTokenMatch {
+    pub start: usize,
+    pub len: usize,
+    /// Which token of the pattern it is, counting from 0.
+    pub token_index: usize,
+}
+
+/// What a pattern matched, filled in by `lk_matcher_match`, and freed by
+/// `lk_match_result_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct LkMatchResult {
+    /// How many tokens the pattern has.
+    pub token_count: usize,
+    /// How many of them matched, which is how many `matches` there are. If
+    /// it's `token_count`, the whole pattern matched.
+    pub matched_count: usize,
+    /// This is null if nothing matched.
+    pub matches: *mut LkTokenMatch,
+}
+
+impl LkMatchResult {
+    const EMPTY: LkMatchResult = LkMatchResult {
+        token_count: 0,
+        matched_count: 0,
+        matches: ptr::null_mut(),
+    };
+}
+
+/// Given a C string, return it, if it isn't null, and is UTF-8.
+///
+/// # Safety
+///
+/// `string` has to be null, or point to a C string that lives for `'a`.
+#[require_lifetimes]
+unsafe fn to_str<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+    CStr::from_ptr(string).to_str().ok()
+}
+
+/// Given a matcher and a candidate, return how many tokens the pattern has,
+/// and what each that matched matched, greedily, from the start.
+#[require_lifetimes]
+fn matches<'matcher, 'candidate>(
+    matcher: &'matcher LkMatcher,
+    candidate: &'candidate str,
+) -> (usize, Vec<LkTokenMatch>) {
+    let mut matcher = Matcher::from_owned_tokens(&matcher.pattern, &matcher.tokens);
+    let token_count = matcher.tokens.len();
+    let mut start = 0;
+    let mut matches = Vec::new();
+    for (token_index, (_, text)) in matcher.match_string(candidate).iter().enumerate() {
+        matches.push(LkTokenMatch {
+            start,
+            len: text.len(),
+            token_index,
+        });
+        start += text.len();
+    }
+    (token_count, matches)
+}
+
+/// Given a pattern, as a C string, return it ready to match against, or null
+/// if it's null, isn't UTF-8 or doesn't parse.
+///
+/// # Safety
+///
+/// `pattern` has to be null, or point to a C string.
+#[no_mangle]
+pub unsafe extern "C" fn lk_matcher_new(pattern: *const c_char) -> *mut LkMatcher {
+    lk_matcher_parse(pattern, ptr::null_mut())
+}
+
+/// This is `lk_matcher_new`, but when the pattern doesn't parse, it also
+/// fills in `error` (unless it's null) with why, and where in the pattern the
+/// problem is. Otherwise, `error` is left alone.
+///
+/// # Safety
+///
+/// `pattern` has to be null, or point to a C string. `error` has to be null,
+/// or point to an `LkParseError` that can be written to.
+#[no_mangle]
+pub unsafe extern "C" fn lk_matcher_parse(
+    pattern: *const c_char,
+    error: *mut LkParseError,
+) -> *mut LkMatcher {
+    let Some(pattern) = to_str(pattern) else {
+        return ptr::null_mut();
+    };
+    let Ok(parsed) = catch_unwind(|| {
+        Matcher::parse(pattern).map(|matcher| LkMatcher {
+            pattern: pattern.to_string(),
+            tokens: matcher.to_owned_tokens(),
+        })
+    }) else {
+        return ptr::null_mut();
+    };
+    match parsed {
+        Ok(matcher) => Box::into_raw(Box::new(matcher)),
+        Err(parse_error) => {
+            if !error.is_null() {
+                let kind = match parse_error.kind {
+                    ParseErrorKind::EmptyOption => LK_EMPTY_OPTION,
+                    ParseErrorKind::OneOption => LK_ONE_OPTION,
+                    ParseErrorKind::UnclosedGroup => LK_UNCLOSED_GROUP,
+                };
+                error.write(LkParseError {
+                    kind,
+                    offset: parse_error.offset,
+                });
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Free a matcher made by `lk_matcher_new`. It does nothing with null.
+///
+/// # Safety
+///
+/// `matcher` has to be null, or from `lk_matcher_new`, and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn lk_matcher_free(matcher: *mut LkMatcher) {
+    if !matcher.is_null() {
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(matcher))));
+    }
+}
+
+/// Match `candidate`, a C string, against `matcher`, greedily, from the
+/// start, and fill in `out` with what matched, returning `LK_OK`. Free `out`
+/// with `lk_match_result_free` afterwards. If a pointer is null, or
+/// `candidate` isn't UTF-8, it returns `LK_INVALID_ARGUMENT` instead, and if
+/// it panics, `LK_PANICKED`, and either way, `out` (if it isn't null) is
+/// left empty, which doesn't need freeing.
+///
+/// # Safety
+///
+/// `matcher` has to be null, or from `lk_matcher_new`, and not freed yet.
+/// `candidate` has to be null, or point to a C string. `out` has to be null,
+/// or point to an `LkMatchResult` that can be written to, and anything in it
+/// already is overwritten without being freed.
+#[no_mangle]
+pub unsafe extern "C" fn lk_matcher_match(
+    matcher: *const LkMatcher,
+    candidate: *const c_char,
+    out: *mut LkMatchResult,
+) -> c_int {
+    if out.is_null() {
+        return LK_INVALID_ARGUMENT;
+    }
+    out.write(LkMatchResult::EMPTY);
+    let (Some(matcher), Some(candidate)) = (matcher.as_ref(), to_str(candidate)) else {
+        return LK_INVALID_ARGUMENT;
+    };
+    let Ok((token_count, matched)) = catch_unwind(|| matches(matcher, candidate)) else {
+        return LK_PANICKED;
+    };
+
+    let matched_count = matched.len();
+    let matches = if matched.is_empty() {
+        ptr::null_mut()
+    } else {
+        Box::into_raw(matched.into_boxed_slice()).cast::<LkTokenMatch>()
+    };
+    out.write(LkMatchResult {
+        token_count,
+        matched_count,
+        matches,
+    });
+    LK_OK
+}
+
+/// Free what `lk_matcher_match` put in `result`, and leave it empty. It does
+/// nothing with null, or with a result that's empty already.
+///
+/// # Safety
+///
+/// `result` has to be null, or point to an `LkMatchResult` that
+/// `lk_matcher_match` filled in.
+#[no_mangle]
+pub unsafe extern "C" fn lk_match_result_free(result: *mut LkMatchResult) {
+    let Some(result) = result.as_mut() else {
+        return;
+    };
+    if !result.matches.is_null() {
+        let matches = ptr::slice_from_raw_parts_mut(result.matches, result.matched_count);
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(matches))));
+    }
+    *result = LkMatchResult::EMPTY;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::CString;
+
+    /// Given a pattern and a candidate, match them through the C functions,
+    /// and return what `lk_matcher_match` returned, how many tokens there
+    /// are, and what matched.
+    fn match_through_c(pattern: &str, candidate: &str) -> (c_int, usize, Vec<LkTokenMatch>) {
+        let pattern = CString::new(pattern).unwrap();
+        let candidate = CString::new(candidate).unwrap();
+        unsafe {
+            let matcher = lk_matcher_new(pattern.as_ptr());
+            assert!(!matcher.is_null());
+            let mut result = LkMatchResult::EMPTY;
+            let status = lk_matcher_match(matcher, candidate.as_ptr(), &mut result);
+            let matches = if result.matches.is_null() {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(result.matches, result.matched_count).to_vec()
+            };
+            let token_count = result.token_count;
+            lk_match_result_free(&mut result);
+            assert!(result.matches.is_null());
+            lk_matcher_free(matcher);
+            (status, token_count, matches)
+        }
+    }
+
+    #[test]
+    fn matches_through_c() {
+        let (status, token_count, matches) = match_through_c("(a|é).c", "é💪cd");
+        assert_eq!((status, token_count), (LK_OK, 3));
+        assert_eq!(
+            matches,
+            [
+                LkTokenMatch {
+                    start: 0,
+                    len: 2,
+                    token_index: 0
+                },
+                LkTokenMatch {
+                    start: 2,
+                    len: 4,
+                    token_index: 1
+                },
+                LkTokenMatch {
+                    start: 6,
+                    len: 1,
+                    token_index: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn partial_matches_and_no_matches() {
+        let (status, token_count, matches) = match_through_c("ab.d", "abc");
+        assert_eq!((status, token_count, matches.len()), (LK_OK, 3, 2));
+        let (status, token_count, matches) = match_through_c("ab", "x");
+        assert_eq!((status, token_count), (LK_OK, 1));
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn patterns_that_do_not_parse_are_null() {
+        let broken = CString::new("a(b|").unwrap();
+        let not_utf8 = CString::new(vec![b'a', 0xff]).unwrap();
+        unsafe {
+            assert!(lk_matcher_new(broken.as_ptr()).is_null());
+            assert!(lk_matcher_new(not_utf8.as_ptr()).is_null());
+            assert!(lk_matcher_new(ptr::null()).is_null());
+        }
+    }
+
+    #[test]
+    fn parse_says_why_a_pattern_does_not_parse() {
+        let broken = CString::new("ab(c|").unwrap();
+        let empty_option = CString::new("a(b|)").unwrap();
+        let fine = CString::new("a(b|c)").unwrap();
+        unsafe {
+            let mut error = LkParseError { kind: 0, offset: 0 };
+            assert!(lk_matcher_parse(broken.as_ptr(), &mut error).is_null());
+            assert_eq!(
+                error,
+                LkParseError {
+                    kind: LK_UNCLOSED_GROUP,
+                    offset: 2
+                }
+            );
+            assert!(lk_matcher_parse(empty_option.as_ptr(), &mut error).is_null());
+            assert_eq!(
+                error,
+                LkParseError {
+                    kind: LK_EMPTY_OPTION,
+                    offset: 4
+                }
+            );
+            assert!(lk_matcher_parse(broken.as_ptr(), ptr::null_mut()).is_null());
+
+            let mut error = LkParseError { kind: 0, offset: 0 };
+            let matcher = lk_matcher_parse(fine.as_ptr(), &mut error);
+            assert!(!matcher.is_null());
+            assert_eq!(error, LkParseError { kind: 0, offset: 0 });
+            lk_matcher_free(matcher);
+        }
+    }
+
+    #[test]
+    fn null_is_safe_everywhere() {
+        let pattern = CString::new("a").unwrap();
+        let candidate = CString::new("a").unwrap();
+        let not_utf8 = CString::new(vec![0xff]).unwrap();
+        unsafe {
+            let matcher = lk_matcher_new(pattern.as_ptr());
+            let mut result = LkMatchResult::EMPTY;
+            assert_eq!(
+                lk_matcher_match(ptr::null(), candidate.as_ptr(), &mut result),
+                LK_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                lk_matcher_match(matcher, ptr::null(), &mut result),
+                LK_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                lk_matcher_match(matcher, not_utf8.as_ptr(), &mut result),
+                LK_INVALID_ARGUMENT
+            );
+            assert!(result.matches.is_null());
+            assert_eq!(
+                lk_matcher_match(matcher, candidate.as_ptr(), ptr::null_mut()),
+                LK_INVALID_ARGUMENT
+            );
+            lk_match_result_free(ptr::null_mut());
+            lk_match_result_free(&mut result);
+            lk_matcher_free(ptr::null_mut());
+            lk_matcher_free(matcher);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! This shows what a pattern matched in a line, for a terminal: each token's
+//! part of the line in its own colour, and the parts that weren't matched
+//! dimmed.
+
+use crate::MatchResult;
+use alloc::string::String;
+use require_lifetimes::require_lifetimes;
+
+/// The colours the tokens' parts cycle through: bold red, green, yellow,
+/// blue, magenta and cyan.
+const PALETTE: [&str; 6] = [
+    "\x1b[1;31m",
+    "\x1b[1;32m",
+    "\x1b[1;33m",
+    "\x1b[1;34m",
+    "\x1b[1;35m",
+    "\x1b[1;36m",
+];
+
+const DIM: &str = "\x1b[2m";
+
+const RESET: &str = "\x1b[0m";
+
+/// Given a line, where in it a match starts (in bytes), what matched there,
+/// and whether to use colour, return the line with the matched tokens
+/// coloured in, and the rest dimmed. Without colour, it's the line as it is.
+///
+/// The escapes only go between tokens, so they never split a character. If
+/// `result` isn't from `line`, at `start`, whatever doesn't fit is left
+/// uncoloured.
+#[require_lifetimes]
+pub fn highlight<'line, 'r, 'internal, 'b>(
+    line: &'line str,
+    start: usize,
+    result: &'r MatchResult<'r, 'internal, 'b>,
+    color: bool,
+) -> String {
+    if !color {
+        return String::from(line);
+    }
+
+    let mut highlighted = String::new();
+    let Some(before) = line.get(..start) else {
+        return String::from(line);
+    };
+    push_colored(&mut highlighted, DIM, before);
+
+    let mut position = start;
+    for (index, (_, matched)) in result.iter().enumerate() {
+        let end = position + matched.len();
+        match line.get(position..end) {
+            Some(part) if part == *matched => {
+                push_colored(&mut highlighted, PALETTE[index % PALETTE.len()], part);
+                position = end;
+            }
+            _ => break,
+        }
+    }
+
+    push_colored(&mut highlighted, DIM, &line[position..]);
+    highlighted
+}
+
+/// Given some text, add it to `highlighted` in a colour, unless there's none
+/// of it.
+#[require_lifetimes]
+fn push_colored<'h, 'text>(highlighted: &'h mut String, color: &'static str, text: &'text str) {
+    if !text.is_empty() {
+        highlighted.push_str(color);
+        highlighted.push_str(text);
+        highlighted.push_str(RESET);
+    }
+}
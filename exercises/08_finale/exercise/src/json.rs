@@ -0,0 +1,67 @@
+//! This writes out what a pattern matched as JSON, for other tools to read.
+//! It's behind the `json` feature, so that users who don't need it don't
+//! have to build serde, and it needs `std`.
+//!
+//! The schema is stable: `tests/json_snapshots.rs` has a snapshot of each
+//! kind of token, and a change to any of them is a change to the schema.
+//! Offsets and lengths are in bytes of UTF-8, which their names say, since
+//! that's not what every language counts in.
+
+use crate::{MatchResult, Matcher, MatcherToken};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use require_lifetimes::require_lifetimes;
+use serde_derive::Serialize;
+
+#[derive(Serialize)]
+struct Json<'a> {
+    /// Whether every token of the pattern matched.
+    complete: bool,
+    consumed_bytes: usize,
+    tokens: Vec<JsonToken<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonToken<'a> {
+    kind: &'static str,
+    /// The part of the pattern this token was parsed from.
+    pattern: String,
+    matched: &'a str,
+    start_byte: usize,
+    end_byte: usize,
+}
+
+#[require_lifetimes]
+impl<'internal> Matcher<'internal> {
+    /// This describes what this pattern matched (from the start of a
+    /// string) as JSON, like
+    /// `{"complete":true,"consumed_bytes":4,"tokens":[{"kind":"raw","pattern":"ab","matched":"ab","start_byte":0,"end_byte":2},...]}`.
+    /// Each token's `kind` is `raw`, `one_of` or `wildcard`.
+    pub fn to_json<'a, 'r, 'b>(&'a self, result: &'r MatchResult<'r, 'internal, 'b>) -> String {
+        let mut consumed_bytes = 0;
+        let mut tokens = Vec::new();
+        for (token, matched) in result {
+            let (kind, pattern) = match token {
+                MatcherToken::RawText(text) => ("raw", String::from(*text)),
+                MatcherToken::OneOfText(options) => ("one_of", format!("({})", options.join("|"))),
+                MatcherToken::WildCard => ("wildcard", String::from(".")),
+            };
+            tokens.push(JsonToken {
+                kind,
+                pattern,
+                matched,
+                start_byte: consumed_bytes,
+                end_byte: consumed_bytes + matched.len(),
+            });
+            consumed_bytes += matched.len();
+        }
+
+        let json = Json {
+            complete: result.len() == self.tokens.len(),
+            consumed_bytes,
+            tokens,
+        };
+        serde_json::to_string(&json).expect("these always serialize")
+    }
+}
@@ -0,0 +1,111 @@
+//! These tests count how many heap allocations the matcher makes, using a
+//! global allocator that keeps a separate count for every thread (so tests
+//! running in parallel don't see each other's allocations).
+//!
+//! Each bound leaves a little room above what the code needed when it was
+//! written (noted next to it), so that it doesn't trip on small changes, but
+//! an extra allocation for every token or every frame won't fit. If a change
+//! really does need to allocate more, run the failing test to see the new
+//! count (it's in the assertion message), move the bound and the note to
+//! match, and say why in the commit message.
+
+use ex08::Matcher;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn parsing_allocations() {
+    let (matcher, allocations) = count_allocations(|| Matcher::new("abc(d|e|f).").unwrap());
+    drop(matcher);
+    // This needed 5.
+    assert!(allocations <= 8, "{allocations} allocations");
+}
+
+#[test]
+fn greedy_match_allocations() {
+    let mut matcher = Matcher::new("abc(d|e|f).").unwrap();
+
+    let (result, allocations) = count_allocations(|| matcher.match_string("abcde").len());
+    assert_eq!(result, 3);
+    // This needed 1.
+    assert!(allocations <= 2, "{allocations} allocations");
+}
+
+#[cfg(feature = "exhaustive")]
+#[test]
+fn exhaustive_match_allocations() {
+    let mut matcher = Matcher::new("(aba|abac).(aba|abac).").unwrap();
+
+    let (result, allocations) =
+        count_allocations(|| matcher.match_string_exhaustive("abacabacd").len());
+    assert_eq!(result, 4);
+    // This needed 6.
+    assert!(allocations <= 8, "{allocations} allocations");
+}
+
+#[cfg(feature = "exhaustive")]
+#[test]
+fn exhaustive_allocations_do_not_grow_with_frames() {
+    let pattern = format!("{}b", "(a|aa)".repeat(12));
+    let candidate = "a".repeat(24);
+    let mut matcher = Matcher::new(&pattern).unwrap();
+
+    // Exploring this explores thousands of frames, but only the frame stack
+    // and the arena should ever need to grow. This needed 31.
+    let (result, allocations) =
+        count_allocations(|| matcher.match_string_exhaustive(&candidate).len());
+    assert_eq!(result, 12);
+    assert!(allocations <= 64, "{allocations} allocations");
+}
+
+#[cfg(feature = "scratch")]
+#[test]
+fn scratch_is_reused_between_candidates() {
+    let pattern = format!("{}b", "(a|aa)".repeat(12));
+    let candidate = "a".repeat(24);
+    let mut matcher = Matcher::new(&pattern).unwrap();
+    let mut scratch = ex08::ExhaustiveScratch::default();
+
+    matcher.match_string_exhaustive_with_scratch(&candidate, &mut scratch);
+
+    // Once the scratch has grown, the only allocations left are the ones
+    // for the result itself. This needed 3.
+    let (result, allocations) = count_allocations(|| {
+        matcher
+            .match_string_exhaustive_with_scratch(&candidate, &mut scratch)
+            .len()
+    });
+    assert_eq!(result, 12);
+    assert!(allocations <= 6, "{allocations} allocations");
+}
@@ -0,0 +1,43 @@
+//! These tests snapshot the exhaustive engine's whole stack every time it's
+//! about to pop a frame, so that a change to the order the engine explores
+//! things in shows up as a diff of the stacks, rather than only as a
+//! different result (or none at all).
+//!
+//! The dumps need the `debug-internals` feature, so run these with
+//! `cargo test --package ex08 --features debug-internals --test frame_snapshots`.
+//! When a change is meant to alter the stacks, the failing tests leave a
+//! `.snap.new` file next to each snapshot in `tests/snapshots`. Install
+//! `cargo-insta` (`cargo install cargo-insta`), then run
+//! `cargo insta review` to look over each diff and accept or reject it.
+//! Accepted snapshots replace the old ones, and go in the same commit as the
+//! change that caused them. Nothing about the format of the dumps is stable,
+//! so reformatting them is fine too, as long as the snapshots are updated
+//! alongside.
+
+#![cfg(feature = "debug-internals")]
+
+use ex08::Matcher;
+
+fn render_stack_dumps(match_string: &str, candidate: &str) -> String {
+    let mut matcher = Matcher::new(match_string).unwrap();
+    let mut other_matcher = Matcher::new(match_string).unwrap();
+
+    let (result, dumps) = matcher.match_string_exhaustive_stack_dumps(candidate);
+    assert_eq!(result, other_matcher.match_string_exhaustive(candidate));
+
+    let mut rendered = format!("{match_string:?} against {candidate:?}\n");
+    for (iteration, dump) in dumps.iter().enumerate() {
+        rendered.push_str(&format!("\n--- before pop {iteration} ---\n{dump}"));
+    }
+    rendered
+}
+
+#[test]
+fn stack_of_a_single_group() {
+    insta::assert_snapshot!(render_stack_dumps("(aba|abac).", "abac"));
+}
+
+#[test]
+fn stack_of_two_groups_with_a_tie() {
+    insta::assert_snapshot!(render_stack_dumps("(a|ab)(b|c)x.", "abc"));
+}
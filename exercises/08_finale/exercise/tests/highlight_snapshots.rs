@@ -0,0 +1,49 @@
+//! These pin down what `highlight` prints, with colour and without. The
+//! escape character is written as `\e` in the snapshots, so they're readable.
+
+use ex08::{highlight, Matcher};
+
+fn render(pattern: &str, line: &str, color: bool) -> String {
+    let mut matcher = Matcher::new(pattern).unwrap();
+    let (start, result) = matcher.find_iter(line).next().unwrap_or((0, Vec::new()));
+    highlight(line, start, &result, color).replace('\x1b', "\\e")
+}
+
+#[test]
+fn tokens_in_colour() {
+    insta::assert_snapshot!(render(
+        "(Black|Bridge)(rock|stone|water).company",
+        "the Bridgewater company, of course",
+        true
+    ));
+}
+
+#[test]
+fn tokens_without_colour() {
+    insta::assert_snapshot!(render(
+        "(Black|Bridge)(rock|stone|water).company",
+        "the Bridgewater company, of course",
+        false
+    ));
+}
+
+#[test]
+fn palette_cycles() {
+    insta::assert_snapshot!(render("a(b|c).d(e|f).gh", "abxdfyghi", true));
+}
+
+#[test]
+fn multibyte_characters_in_colour() {
+    // The wildcard matches all four bytes of the `💪`, and nothing's split.
+    insta::assert_snapshot!(render("é.(é|e)", "aé💪éz", true));
+}
+
+#[test]
+fn multibyte_characters_without_colour() {
+    insta::assert_snapshot!(render("é.(é|e)", "aé💪éz", false));
+}
+
+#[test]
+fn no_match_is_all_dimmed() {
+    insta::assert_snapshot!(render("xyz", "abc", true));
+}
@@ -0,0 +1,40 @@
+//! These pin down the JSON that `Matcher::to_json` writes, which other tools
+//! read, so its schema can't change by accident. A change that's meant to
+//! change it shows up as a diff of these snapshots, to be accepted with
+//! `cargo insta review` (see `frame_snapshots.rs`), and said so in the
+//! change's description.
+
+#![cfg(feature = "json")]
+
+use ex08::Matcher;
+
+fn render(pattern: &str, candidate: &str) -> String {
+    let mut matcher = Matcher::new(pattern).unwrap();
+    let other_matcher = Matcher::new(pattern).unwrap();
+    let result = matcher.match_string(candidate);
+    other_matcher.to_json(&result)
+}
+
+#[test]
+fn complete_match_of_every_kind_of_token() {
+    insta::assert_snapshot!(render("ab(c|de).f", "abdexf"));
+}
+
+#[test]
+fn partial_match() {
+    insta::assert_snapshot!(render(
+        "(Black|Bridge)(rock|stone).company",
+        "Bridgestone_Tyres"
+    ));
+}
+
+#[test]
+fn no_match() {
+    insta::assert_snapshot!(render("abc", "xyz"));
+}
+
+#[test]
+fn offsets_are_in_bytes() {
+    // The wildcard matches four bytes, and the `é`s are two each.
+    insta::assert_snapshot!(render("(é|e).é", "é💪é"));
+}
@@ -0,0 +1,35 @@
+//! These read the pattern files in `tests/fixtures/patterns` into a
+//! `MatcherSet`.
+
+use ex08::{MatcherSet, ParseError, ParseErrorKind};
+
+const OVERLAPPING: &str = include_str!("fixtures/patterns/overlapping.txt");
+const BROKEN: &str = include_str!("fixtures/patterns/broken.txt");
+
+#[test]
+fn says_which_line_is_broken() {
+    assert_eq!(
+        MatcherSet::from_lines(BROKEN),
+        Err((
+            7,
+            ParseError {
+                kind: ParseErrorKind::UnclosedGroup,
+                offset: 5,
+            }
+        ))
+    );
+}
+
+#[test]
+fn overlapping_patterns_all_match() {
+    let mut set = MatcherSet::from_lines(OVERLAPPING).unwrap();
+    assert_eq!(set.lines(), [3, 4, 5, 7]);
+
+    assert_eq!(set.matches_anywhere("Blackrock_company"), [3, 5]);
+    assert_eq!(set.matches_anywhere("Bridgestone_Tyres"), [4]);
+    assert_eq!(set.matches_anywhere("the Bridgewater company"), [3, 4, 5]);
+    assert!(set.matches_anywhere("something else").is_empty());
+
+    assert_eq!(set.matches("Blackrock_company"), [3]);
+    assert_eq!(set.matches("company"), [5]);
+}
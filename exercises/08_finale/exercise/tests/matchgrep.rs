@@ -0,0 +1,239 @@
+//! These run `matchgrep` over the files in `tests/fixtures/matchgrep` (with
+//! patterns from `tests/fixtures/patterns`), and check what it prints, and
+//! how it exits.
+
+#![cfg(all(feature = "exhaustive", feature = "json"))]
+
+use assert_cmd::Command;
+
+const COMPANIES: &str = "tests/fixtures/matchgrep/companies.txt";
+const INVALID_UTF8: &str = "tests/fixtures/matchgrep/invalid_utf8.txt";
+const OVERLAPPING: &str = "tests/fixtures/patterns/overlapping.txt";
+const BROKEN: &str = "tests/fixtures/patterns/broken.txt";
+
+fn matchgrep() -> Command {
+    Command::cargo_bin("matchgrep").unwrap()
+}
+
+#[test]
+fn prints_lines_that_match_anywhere() {
+    matchgrep()
+        .args(["(Black|Bridge)(rock|water).company", COMPANIES])
+        .assert()
+        .code(0)
+        .stdout("Blackrock_company\nthe Bridgewater company\n");
+}
+
+#[test]
+fn full_lines_only() {
+    matchgrep()
+        .args(["--full", "(Black|Bridge)(rock|water).company", COMPANIES])
+        .assert()
+        .code(0)
+        .stdout("Blackrock_company\n");
+}
+
+#[test]
+fn counts_and_inverts() {
+    matchgrep()
+        .args(["--count", "Bridge", COMPANIES])
+        .assert()
+        .code(0)
+        .stdout("2\n");
+    matchgrep()
+        .args(["--invert", "Bridge", COMPANIES])
+        .assert()
+        .code(0)
+        .stdout("Blackrock_company\nsomething else\n");
+}
+
+#[test]
+fn reads_stdin_without_files() {
+    matchgrep()
+        .arg("b.d")
+        .write_stdin("abcd\nbd\nbxd")
+        .assert()
+        .code(0)
+        .stdout("abcd\nbxd\n");
+}
+
+#[test]
+fn names_files_when_there_are_several() {
+    matchgrep()
+        .args(["--count", "company", COMPANIES, INVALID_UTF8])
+        .assert()
+        .code(0)
+        .stdout(format!("{COMPANIES}:2\n{INVALID_UTF8}:2\n"));
+}
+
+#[test]
+fn prints_json() {
+    matchgrep()
+        .args(["--json", "Bridge(stone|water)", COMPANIES])
+        .assert()
+        .code(0)
+        .stdout(concat!(
+            r#"{"file":"tests/fixtures/matchgrep/companies.txt","line_number":2,"#,
+            r#""line":"Bridgestone_Tyres","start_byte":0,"match":{"complete":true,"#,
+            r#""consumed_bytes":11,"tokens":[{"kind":"raw","pattern":"Bridge","matched":"Bridge","#,
+            r#""start_byte":0,"end_byte":6},{"kind":"one_of","pattern":"(stone|water)","#,
+            r#""matched":"stone","start_byte":6,"end_byte":11}]}}"#,
+            "\n",
+            r#"{"file":"tests/fixtures/matchgrep/companies.txt","line_number":3,"#,
+            r#""line":"the Bridgewater company","start_byte":4,"match":{"complete":true,"#,
+            r#""consumed_bytes":11,"tokens":[{"kind":"raw","pattern":"Bridge","matched":"Bridge","#,
+            r#""start_byte":0,"end_byte":6},{"kind":"one_of","pattern":"(stone|water)","#,
+            r#""matched":"water","start_byte":6,"end_byte":11}]}}"#,
+            "\n",
+        ));
+}
+
+#[test]
+fn no_match_exits_with_1() {
+    matchgrep()
+        .args(["Tyres.company", COMPANIES])
+        .assert()
+        .code(1)
+        .stdout("");
+    // Counting nothing still says so.
+    matchgrep()
+        .args(["--count", "Tyres.company", COMPANIES])
+        .assert()
+        .code(1)
+        .stdout("0\n");
+}
+
+#[test]
+fn skips_lines_that_are_not_utf8() {
+    matchgrep()
+        .args(["line", INVALID_UTF8])
+        .assert()
+        .code(0)
+        .stdout("last line\n")
+        .stderr(format!(
+            "matchgrep: {INVALID_UTF8}:2: skipping a line that isn't UTF-8\n"
+        ));
+}
+
+#[test]
+fn bad_patterns_exit_with_2() {
+    matchgrep()
+        .args(["ab(c|d", COMPANIES])
+        .assert()
+        .code(2)
+        .stdout("")
+        .stderr("matchgrep: `ab(c|d` isn't a pattern: this group isn't closed (at byte 2)\n    ab(c|d\n      ^\n");
+}
+
+#[test]
+fn unreadable_files_exit_with_2() {
+    matchgrep()
+        .args(["company", "tests/fixtures/matchgrep/missing.txt", COMPANIES])
+        .assert()
+        .code(2)
+        .stdout(format!(
+            "{COMPANIES}:Blackrock_company\n{COMPANIES}:the Bridgewater company\n"
+        ));
+}
+
+#[test]
+fn colours_only_when_asked() {
+    matchgrep()
+        .args(["--color=always", "Bridge(stone|water)", COMPANIES])
+        .assert()
+        .code(0)
+        .stdout(concat!(
+            "\x1b[1;31mBridge\x1b[0m\x1b[1;32mstone\x1b[0m\x1b[2m_Tyres\x1b[0m\n",
+            "\x1b[2mthe \x1b[0m\x1b[1;31mBridge\x1b[0m\x1b[1;32mwater\x1b[0m\x1b[2m company\x1b[0m\n",
+        ));
+    // Being run by a test, stdout isn't a terminal, so `auto` is plain.
+    matchgrep()
+        .args(["--color=auto", "Bridge(stone|water)", COMPANIES])
+        .assert()
+        .code(0)
+        .stdout("Bridgestone_Tyres\nthe Bridgewater company\n");
+}
+
+#[test]
+fn reads_patterns_from_a_file() {
+    matchgrep()
+        .args(["-f", OVERLAPPING, COMPANIES])
+        .assert()
+        .code(0)
+        .stdout("Blackrock_company\nBridgestone_Tyres\nthe Bridgewater company\n");
+    // A line that several patterns match is still only printed once.
+    matchgrep()
+        .args(["--count", "-f", OVERLAPPING, COMPANIES])
+        .assert()
+        .code(0)
+        .stdout("3\n");
+}
+
+#[test]
+fn json_says_which_patterns_matched() {
+    let output = matchgrep()
+        .args(["--json", "-f", OVERLAPPING, COMPANIES])
+        .assert()
+        .code(0)
+        .get_output()
+        .stdout
+        .clone();
+    let patterns: Vec<&str> = std::str::from_utf8(&output)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let start = line.find(r#""patterns":"#).unwrap();
+            let end = start + line[start..].find(']').unwrap() + 1;
+            &line[start..end]
+        })
+        .collect();
+    assert_eq!(
+        patterns,
+        [
+            r#""patterns":[3,5]"#,
+            r#""patterns":[4]"#,
+            r#""patterns":[3,4,5]"#
+        ]
+    );
+}
+
+#[test]
+fn broken_pattern_files_say_which_line() {
+    matchgrep()
+        .args(["-f", BROKEN, COMPANIES])
+        .assert()
+        .code(2)
+        .stdout("")
+        .stderr(format!(
+            "matchgrep: {BROKEN}:7: `water(s|` isn't a pattern: this group isn't closed (at byte 5)\n    water(s|\n         ^\n"
+        ));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn full_disks_are_errors() {
+    let full = std::fs::File::create("/dev/full").unwrap();
+    let output = std::process::Command::new(assert_cmd::cargo::cargo_bin("matchgrep"))
+        .args(["Bridge", COMPANIES])
+        .stdout(full)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("matchgrep: couldn't write to stdout: "));
+}
+
+#[test]
+fn closed_pipes_stop_quietly() {
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("matchgrep"))
+        .args(["Bridge", COMPANIES])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Whether or not it's written anything yet, it's found something to.
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "");
+}
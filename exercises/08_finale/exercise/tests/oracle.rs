@@ -0,0 +1,205 @@
+//! These tests compare the exhaustive engine against a reference
+//! implementation, which tries every combination of options recursively.
+//! That takes exponential time, but it's short enough to check by eye, so
+//! whenever the two disagree it's much easier to tell which one is wrong.
+//!
+//! Every pattern made of up to four of `PIECES` (with at most three groups)
+//! gets matched against every string of up to `SHORT_CANDIDATE_LEN`
+//! characters, and some longer ones, all over the letters `a`, `b` and `c`.
+
+#![cfg(feature = "exhaustive")]
+
+use ex08::{ExploreOrder, MatchScoring, Matcher};
+
+const PIECES: [&str; 6] = ["(a|ab|b)", "(b|ba|c)", "(a|aa|c)", "a", "c", "."];
+
+const SHORT_CANDIDATE_LEN: usize = 5;
+
+const LONG_CANDIDATES: usize = 100;
+
+const LONG_CANDIDATE_LEN: usize = 10;
+
+/// This is a token of the reference implementation's own parse of a pattern.
+enum Token<'a> {
+    Literal(String),
+    Options(Vec<&'a str>),
+    AnyChar,
+}
+
+/// This is the most direct way of parsing the patterns the tests build: it
+/// doesn't have to reject anything, since they're all valid.
+fn parse(pattern: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = pattern;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '.' => {
+                tokens.push(Token::AnyChar);
+                rest = &rest[1..];
+            }
+            '(' => {
+                let end = rest.find(')').unwrap();
+                tokens.push(Token::Options(rest[1..end].split('|').collect()));
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                if let Some(Token::Literal(text)) = tokens.last_mut() {
+                    text.push(c);
+                } else {
+                    tokens.push(Token::Literal(c.to_string()));
+                }
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    tokens
+}
+
+/// This is how the reference implementation ranks a match: complete matches
+/// come first, then whatever `scoring` counts.
+fn rank(matched: &(Vec<&str>, bool), scoring: MatchScoring) -> (bool, usize) {
+    let (texts, is_complete) = matched;
+    let score = match scoring {
+        MatchScoring::MostTokens => texts.len(),
+        MatchScoring::MostBytes => texts.iter().map(|text| text.len()).sum(),
+    };
+    (*is_complete, score)
+}
+
+/// This returns the text matched by each token of the best match of
+/// `tokens` against the start of `string`, and whether it's complete. Of
+/// the options of a group that rank the same, the first one written wins.
+fn best_match<'a>(
+    tokens: &[Token<'_>],
+    string: &'a str,
+    scoring: MatchScoring,
+) -> (Vec<&'a str>, bool) {
+    let Some(token) = tokens.first() else {
+        return (vec![], true);
+    };
+
+    let prefixes: Vec<usize> = match token {
+        Token::Literal(text) => {
+            Vec::from_iter(string.starts_with(text.as_str()).then_some(text.len()))
+        }
+        Token::Options(options) => options
+            .iter()
+            .filter(|option| string.starts_with(**option))
+            .map(|option| option.len())
+            .collect(),
+        Token::AnyChar => Vec::from_iter(string.chars().next().map(char::len_utf8)),
+    };
+
+    let mut best: Option<(Vec<&str>, bool)> = None;
+    for len in prefixes {
+        let (rest, is_complete) = best_match(&tokens[1..], &string[len..], scoring);
+        let candidate = ([vec![&string[..len]], rest].concat(), is_complete);
+
+        if best
+            .as_ref()
+            .is_none_or(|best| rank(&candidate, scoring) > rank(best, scoring))
+        {
+            best = Some(candidate);
+        }
+    }
+
+    best.unwrap_or((vec![], false))
+}
+
+fn patterns() -> Vec<String> {
+    let mut patterns: Vec<Vec<&str>> = vec![vec![]];
+    let mut all_patterns = Vec::new();
+
+    for _ in 0..4 {
+        patterns = patterns
+            .iter()
+            .flat_map(|pattern| {
+                PIECES
+                    .iter()
+                    .map(move |piece| [pattern.as_slice(), &[*piece]].concat())
+            })
+            .filter(|pattern| {
+                pattern
+                    .iter()
+                    .filter(|piece| piece.starts_with('('))
+                    .count()
+                    <= 3
+            })
+            .collect();
+        all_patterns.extend(patterns.iter().map(|pattern| pattern.concat()));
+    }
+
+    all_patterns
+}
+
+fn candidates() -> Vec<String> {
+    let mut candidates = vec![String::new()];
+    let mut shorter = vec![String::new()];
+
+    for _ in 0..SHORT_CANDIDATE_LEN {
+        shorter = shorter
+            .iter()
+            .flat_map(|candidate| ['a', 'b', 'c'].map(|c| format!("{candidate}{c}")))
+            .collect();
+        candidates.extend(shorter.iter().cloned());
+    }
+
+    // These only need to be spread out, not unpredictable, so a fixed
+    // xorshift sequence will do.
+    let mut state: u32 = 0x9e37_79b9;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as usize
+    };
+    for _ in 0..LONG_CANDIDATES {
+        let len = SHORT_CANDIDATE_LEN + 1 + next() % (LONG_CANDIDATE_LEN - SHORT_CANDIDATE_LEN);
+        candidates.push((0..len).map(|_| ['a', 'b', 'c'][next() % 3]).collect());
+    }
+
+    candidates
+}
+
+#[test]
+fn exhaustive_engine_agrees_with_oracle() {
+    let candidates = candidates();
+
+    for pattern in patterns() {
+        let tokens = parse(&pattern);
+        let mut matcher = Matcher::new(&pattern).unwrap();
+
+        for candidate in &candidates {
+            let (expected, _) = best_match(&tokens, candidate, MatchScoring::MostTokens);
+            let result: Vec<&str> = matcher
+                .match_string_exhaustive(candidate)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+            assert_eq!(result, expected, "{pattern:?} against {candidate:?}");
+
+            let result: Vec<&str> = matcher
+                .match_string_exhaustive_with_order(candidate, ExploreOrder::BreadthFirst)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+            assert_eq!(
+                result, expected,
+                "{pattern:?} against {candidate:?}, breadth first"
+            );
+
+            let (expected, _) = best_match(&tokens, candidate, MatchScoring::MostBytes);
+            let result: Vec<&str> = matcher
+                .match_string_exhaustive_scored(candidate, MatchScoring::MostBytes)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+            assert_eq!(
+                result, expected,
+                "{pattern:?} against {candidate:?}, scoring by bytes"
+            );
+        }
+    }
+}
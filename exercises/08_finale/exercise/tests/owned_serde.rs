@@ -0,0 +1,47 @@
+//! These check that owned match results survive being serialized and read
+//! back, which is what they're for.
+
+#![cfg(feature = "serde")]
+
+use ex08::{Matcher, OwnedMatch};
+
+fn owned(pattern: &str, candidate: &str) -> Vec<OwnedMatch> {
+    let mut matcher = Matcher::new(pattern).unwrap();
+    let other_matcher = Matcher::new(pattern).unwrap();
+    let result = matcher.match_string(candidate);
+    other_matcher.to_owned_result(&result, candidate)
+}
+
+#[test]
+fn round_trips_through_json() {
+    for (pattern, candidate) in [
+        ("ab(c|de).f", "abdexf"),
+        ("(Black|Bridge)(rock|stone).company", "Bridgestone_Tyres"),
+        ("(é|e).é", "é💪é"),
+        ("abc", "xyz"),
+    ] {
+        let owned = owned(pattern, candidate);
+        let json = serde_json::to_string(&owned).unwrap();
+        let read: Vec<OwnedMatch> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, owned, "{pattern} against {candidate}");
+    }
+}
+
+#[test]
+fn can_be_sent_after_the_candidate_is_gone() {
+    let owned = {
+        let candidate = String::from("xxé💪é");
+        owned("(é|e).é", &candidate[2..])
+    };
+    let json = std::thread::spawn(move || serde_json::to_string(&owned).unwrap())
+        .join()
+        .unwrap();
+    assert_eq!(
+        json,
+        concat!(
+            r#"[{"token_index":0,"token":{"OneOfText":["é","e"]},"text":"é","start":0,"end":2},"#,
+            r#"{"token_index":1,"token":"WildCard","text":"💪","start":2,"end":6},"#,
+            r#"{"token_index":2,"token":{"RawText":"é"},"text":"é","start":6,"end":8}]"#,
+        )
+    );
+}
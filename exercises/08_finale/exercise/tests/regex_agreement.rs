@@ -0,0 +1,79 @@
+//! These check that the regex `Matcher::to_regex_string` writes matches the
+//! same candidates the matcher does, by compiling it with the `regex` crate.
+
+use ex08::Matcher;
+use regex::Regex;
+
+/// Given a pattern and some candidates, check the regex for the pattern
+/// agrees with `is_match` about every one.
+fn assert_agrees(pattern: &str, candidates: &[&str]) {
+    let matcher = Matcher::new(pattern).unwrap();
+    let regex = Regex::new(&matcher.to_regex_string()).unwrap();
+    for candidate in candidates {
+        assert_eq!(
+            regex.is_match(candidate),
+            matcher.is_match(candidate),
+            "{pattern} (as {regex}) against {candidate:?}"
+        );
+    }
+}
+
+#[test]
+fn groups_and_wildcards() {
+    assert_agrees(
+        "(Black|Bridge)(rock|stone|water).company",
+        &[
+            "Blackrock_company",
+            "Bridgewater company",
+            "Bridgestone_Tyres",
+            "the Blackrock_company",
+            "Blackrock_company!",
+            "Blackrock\ncompany",
+            "Blackrockcompany",
+            "",
+        ],
+    );
+    // Options that are prefixes of each other still take the right one.
+    assert_agrees("(a|ab)(c|bcd)", &["ac", "abc", "abcd", "abbcd", "ab"]);
+}
+
+#[test]
+fn raw_text_full_of_metacharacters() {
+    let raw = r"^$+*?[]{}\|#&-~";
+    assert_agrees(
+        raw,
+        &[raw, "", "^$", &format!("{raw}x"), &format!("x{raw}")],
+    );
+    assert_agrees(
+        r"a\d.(+|?|[x]|\w)$",
+        &[
+            r"a\d.+$",
+            r"a\dz?$",
+            r"a\d [x]$",
+            r"a\d\w$",
+            "a1x+$",
+            r"a\d.w$",
+            r"a\d++",
+        ],
+    );
+    // A `)` or `|` outside a group is just text.
+    assert_agrees("a)b|c", &["a)b|c", "ab", "c"]);
+}
+
+#[test]
+fn wildcards_match_one_character_of_any_size() {
+    assert_agrees(
+        "é.(é|💪)",
+        &["ééé", "é💪💪", "éxé", "é\né", "é💪", "éé", "é", "é😀😀é"],
+    );
+    assert_agrees("..", &["ab", "💪\n", "a", "abc", ""]);
+}
+
+#[test]
+fn escaping_matches_regex_escape() {
+    let matcher = Matcher::new(r"x^$+*?[]{}\|#&-~é").unwrap();
+    assert_eq!(
+        matcher.to_regex_string(),
+        format!(r"(?s)\A{}\z", regex::escape(r"x^$+*?[]{}\|#&-~é"))
+    );
+}
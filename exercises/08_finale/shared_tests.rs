@@ -0,0 +1,93 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `shared_tests` module, so they run exactly the same
+// tests. `cargo xtask shared-tests` checks that they both still do.
+
+use super::{Matcher, MatcherToken};
+
+#[test]
+fn simple_test() {
+    let match_string = "abc(d|e|f).".to_string();
+    let mut matcher = Matcher::new(&match_string).unwrap();
+
+    assert_eq!(matcher.most_tokens_matched, 0);
+
+    {
+        let candidate1 = "abcge".to_string();
+        let result = matcher.match_string(&candidate1);
+        assert_eq!(result, vec![(&MatcherToken::RawText("abc"), "abc"),]);
+        assert_eq!(matcher.most_tokens_matched, 1);
+    }
+
+    {
+        let candidate1 = "abcde".to_string();
+        let result = matcher.match_string(&candidate1);
+        assert_eq!(
+            result,
+            vec![
+                (&MatcherToken::RawText("abc"), "abc"),
+                (&MatcherToken::OneOfText(vec!["d", "e", "f"].into()), "d"),
+                (&MatcherToken::WildCard, "e")
+            ]
+        );
+        assert_eq!(matcher.most_tokens_matched, 3);
+    }
+
+    {
+        let candidate1 = "abcd💪".to_string();
+        let result = matcher.match_string(&candidate1);
+        assert_eq!(
+            result,
+            vec![
+                (&MatcherToken::RawText("abc"), "abc"),
+                (&MatcherToken::OneOfText(vec!["d", "e", "f"].into()), "d"),
+                (&MatcherToken::WildCard, "💪")
+            ]
+        );
+        assert_eq!(matcher.most_tokens_matched, 3);
+    }
+}
+
+#[test]
+fn exhaustive_match() {
+    let match_string = "(aba|abac).(aba|abac).";
+    let mut matcher = Matcher::new(match_string).unwrap();
+
+    assert_eq!(matcher.most_tokens_matched, 0);
+
+    let candidate = "abacabacd";
+    let result = matcher.match_string(candidate);
+    assert_eq!(
+        result,
+        vec![
+            (&MatcherToken::OneOfText(vec!["aba", "abac"].into()), "aba"),
+            (&MatcherToken::WildCard, "c"),
+            (&MatcherToken::OneOfText(vec!["aba", "abac"].into()), "aba"),
+            (&MatcherToken::WildCard, "c")
+        ]
+    );
+    assert_eq!(matcher.most_tokens_matched, 4);
+}
+
+#[test]
+fn parses_long_option_lists() {
+    let options: Vec<String> = (0..10_000).map(|i| format!("option{i:04}")).collect();
+    let pattern = format!("start({}).end", options.join("|"));
+    let matcher = Matcher::new(&pattern).unwrap();
+
+    assert_eq!(
+        matcher.tokens,
+        vec![
+            MatcherToken::RawText("start"),
+            MatcherToken::OneOfText(options.iter().map(String::as_str).collect()),
+            MatcherToken::WildCard,
+            MatcherToken::RawText("end"),
+        ]
+    );
+}
+
+#[test]
+fn broken_matcher() {
+    let match_string = "abc(d|e|f.".to_string();
+    let matcher = Matcher::new(&match_string);
+    assert_eq!(matcher, None);
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use require_lifetimes::require_lifetimes;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -7,7 +9,7 @@ enum MatcherToken<'a> {
     /// This is when text could be any one of multiple
     /// strings. It looks like `(one|two|three)`, where
     /// `one`, `two` or `three` are the allowed strings.
-    OneOfText(Vec<&'a str>),
+    OneOfText(Box<[&'a str]>),
     /// This is when you're happy to accept any single character.
     /// It looks like `.`
     WildCard,
@@ -116,42 +118,6 @@ fn main() {
 }
 
 #[cfg(test)]
-mod test {
-    use super::{Matcher, MatcherToken};
-    #[test]
-    fn simple_test() {
-        let match_string = "abc(d|e|f).".to_string();
-        let mut matcher = Matcher::new(&match_string).unwrap();
-
-        assert_eq!(matcher.most_tokens_matched, 0);
-
-        {
-            let candidate1 = "abcge".to_string();
-            let result = matcher.match_string(&candidate1);
-            assert_eq!(result, vec![(&MatcherToken::RawText("abc"), "abc"),]);
-            assert_eq!(matcher.most_tokens_matched, 1);
-        }
-
-        {
-            // Change 'e' to '💪' if you want to test unicode.
-            let candidate1 = "abcde".to_string();
-            let result = matcher.match_string(&candidate1);
-            assert_eq!(
-                result,
-                vec![
-                    (&MatcherToken::RawText("abc"), "abc"),
-                    (&MatcherToken::OneOfText(vec!["d", "e", "f"]), "d"),
-                    (&MatcherToken::WildCard, "e") // or '💪'
-                ]
-            );
-            assert_eq!(matcher.most_tokens_matched, 3);
-        }
-    }
-
-    #[test]
-    fn broken_matcher() {
-        let match_string = "abc(d|e|f.".to_string();
-        let matcher = Matcher::new(&match_string);
-        assert_eq!(matcher, None);
-    }
+mod shared_tests {
+    include!("../../shared_tests.rs");
 }
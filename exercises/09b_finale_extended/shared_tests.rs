@@ -0,0 +1,180 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `shared_tests` module, so they run exactly the same
+// tests. `cargo xtask shared-tests` checks that they both still do.
+
+use super::{Matcher, MatcherToken};
+
+fn raw(text: &str) -> MatcherToken<'_> {
+    MatcherToken::RawText(text)
+}
+
+fn optional(token: MatcherToken<'_>) -> MatcherToken<'_> {
+    MatcherToken::Optional(Box::new(token))
+}
+
+fn repeated(token: MatcherToken<'_>) -> MatcherToken<'_> {
+    MatcherToken::Repeated(Box::new(token))
+}
+
+#[test]
+fn parses_the_finale_tokens() {
+    let matcher = Matcher::new("hello.(town|world)").unwrap();
+    assert_eq!(
+        matcher.tokens,
+        [
+            raw("hello"),
+            MatcherToken::WildCard,
+            MatcherToken::OneOfText(vec!["town", "world"].into()),
+        ]
+    );
+    assert_eq!(matcher.text, "hello.(town|world)");
+    assert_eq!(Matcher::new("(unclosed"), None);
+}
+
+#[test]
+fn parses_optional_and_repeated_tokens() {
+    let matcher = Matcher::new("colou?r.*(s|es)?").unwrap();
+    assert_eq!(
+        matcher.tokens,
+        [
+            raw("colo"),
+            optional(raw("u")),
+            raw("r"),
+            repeated(MatcherToken::WildCard),
+            optional(MatcherToken::OneOfText(vec!["s", "es"].into())),
+        ]
+    );
+    assert_eq!(Matcher::new("a*").unwrap().tokens, [repeated(raw("a"))]);
+}
+
+#[test]
+fn refuses_modifiers_without_a_token() {
+    assert_eq!(Matcher::new("?a"), None);
+    assert_eq!(Matcher::new("*"), None);
+    assert_eq!(Matcher::new("a?*"), None);
+    assert_eq!(Matcher::new("a**"), None);
+}
+
+#[test]
+fn optional_tokens_match_once_or_not_at_all() {
+    let mut matcher = Matcher::new("colou?r").unwrap();
+    assert_eq!(
+        matcher.match_string("colour"),
+        [
+            (&raw("colo"), "colo"),
+            (&optional(raw("u")), "u"),
+            (&raw("r"), "r")
+        ]
+    );
+    assert_eq!(
+        matcher.match_string("color"),
+        [
+            (&raw("colo"), "colo"),
+            (&optional(raw("u")), ""),
+            (&raw("r"), "r")
+        ]
+    );
+    assert_eq!(
+        matcher.match_string("colouur"),
+        [(&raw("colo"), "colo"), (&optional(raw("u")), "u")]
+    );
+    assert_eq!(matcher.most_tokens_matched, 3);
+}
+
+#[test]
+fn repeated_tokens_match_any_number_of_times() {
+    let mut matcher = Matcher::new("ab*c").unwrap();
+    assert_eq!(
+        matcher.match_string("abbbc"),
+        [
+            (&raw("a"), "a"),
+            (&repeated(raw("b")), "bbb"),
+            (&raw("c"), "c")
+        ]
+    );
+    assert_eq!(
+        matcher.match_string("ac"),
+        [
+            (&raw("a"), "a"),
+            (&repeated(raw("b")), ""),
+            (&raw("c"), "c")
+        ]
+    );
+    assert!(matcher.is_match("abbc"));
+    assert!(matcher.is_match("ac"));
+    assert!(!matcher.is_match("abbcd"));
+    assert!(!matcher.is_match("abd"));
+}
+
+#[test]
+fn repeats_give_back_what_later_tokens_need() {
+    // The `.*` could take everything, but then `x` wouldn't match.
+    let mut matcher = Matcher::new("a.*x").unwrap();
+    assert_eq!(
+        matcher.match_string("abxcx"),
+        [
+            (&raw("a"), "a"),
+            (&repeated(MatcherToken::WildCard), "bxc"),
+            (&raw("x"), "x")
+        ]
+    );
+    // With nothing after it to match, it takes everything.
+    assert_eq!(
+        matcher.match_string("abc"),
+        [(&raw("a"), "a"), (&repeated(MatcherToken::WildCard), "bc")]
+    );
+}
+
+#[test]
+fn options_are_tried_until_the_rest_matches() {
+    let mut matcher = Matcher::new("(a|ab)*c").unwrap();
+    let options = MatcherToken::OneOfText(vec!["a", "ab"].into());
+    assert_eq!(
+        matcher.match_string("aababc"),
+        [(&repeated(options), "aabab"), (&raw("c"), "c")]
+    );
+
+    // `a` is tried first, but only `ab` lets `c` match.
+    let mut matcher = Matcher::new("(a|ab)?c").unwrap();
+    let options = MatcherToken::OneOfText(vec!["a", "ab"].into());
+    assert_eq!(
+        matcher.match_string("abc"),
+        [(&optional(options), "ab"), (&raw("c"), "c")]
+    );
+    assert!(matcher.is_match("c"));
+}
+
+#[test]
+fn repeats_of_options_that_match_nothing_finish() {
+    let mut matcher = Matcher::new("(|a)*b").unwrap();
+    assert!(matcher.is_match("aab"));
+    assert!(matcher.is_match("b"));
+    let options = MatcherToken::OneOfText(vec!["", "a"].into());
+    assert_eq!(matcher.match_string("aac"), [(&repeated(options), "aa")]);
+}
+
+#[test]
+fn handles_utf8() {
+    let mut matcher = Matcher::new("café?.*💪").unwrap();
+    assert_eq!(
+        matcher.tokens,
+        [
+            raw("caf"),
+            optional(raw("é")),
+            repeated(MatcherToken::WildCard),
+            raw("💪"),
+        ]
+    );
+    assert_eq!(
+        matcher.match_string("café 日本 💪"),
+        [
+            (&raw("caf"), "caf"),
+            (&optional(raw("é")), "é"),
+            (&repeated(MatcherToken::WildCard), " 日本 "),
+            (&raw("💪"), "💪"),
+        ]
+    );
+    assert!(matcher.is_match("caf💪"));
+    assert!(Matcher::new("日*本").unwrap().is_match("日日日本"));
+    assert!(!Matcher::new("日*本").unwrap().is_match("日日日"));
+}
@@ -0,0 +1,207 @@
+use require_lifetimes::require_lifetimes;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MatcherToken<'a> {
+    /// This is just text without anything special.
+    RawText(&'a str),
+    /// This is when text could be any one of multiple
+    /// strings. It looks like `(one|two|three)`, where
+    /// `one`, `two` or `three` are the allowed strings.
+    OneOfText(Box<[&'a str]>),
+    /// This is when you're happy to accept any single character.
+    /// It looks like `.`
+    WildCard,
+    // The exercise starts out as the finale's solution, with these two
+    // tokens added, and everything else as it was.
+    /// This is a token that can match once, or not at all. It looks like
+    /// the token, then `?`. After text, it's only the last character that's
+    /// optional: `colou?r` is `colo`, then an optional `u`, then `r`.
+    Optional(Box<MatcherToken<'a>>),
+    /// This is a token that can match any number of times, including none.
+    /// It looks like the token, then `*`, and after text it's only the last
+    /// character that's repeated, like `?`.
+    Repeated(Box<MatcherToken<'a>>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Matcher<'a> {
+    /// This is the actual text of the matcher
+    pub text: &'a str,
+    /// This is a vector of the tokens inside the expression.
+    pub tokens: Vec<MatcherToken<'a>>,
+    /// This keeps track of the most tokens that this matcher has matched.
+    pub most_tokens_matched: usize,
+}
+
+impl<'a> Matcher<'a> {
+    /// This should take a string reference, and return
+    /// an `Matcher` which has parsed that reference.
+    ///
+    /// It returns `None` if a `(` isn't closed, or a `?` or `*` doesn't
+    /// come straight after a token.
+    #[require_lifetimes]
+    pub fn new(text: &'a str) -> Option<Matcher<'a>> {
+        let mut tokens: Vec<MatcherToken> = vec![];
+        let mut text_left = text;
+        while let Some(first) = text_left.chars().next() {
+            if first == '?' || first == '*' {
+                let token = match tokens.pop()? {
+                    MatcherToken::Optional(_) | MatcherToken::Repeated(_) => return None,
+                    MatcherToken::RawText(raw) => {
+                        // Only the last character is optional, or repeated.
+                        let last = raw.char_indices().next_back()?.0;
+                        if last > 0 {
+                            tokens.push(MatcherToken::RawText(&raw[..last]));
+                        }
+                        MatcherToken::RawText(&raw[last..])
+                    }
+                    token => token,
+                };
+                tokens.push(if first == '?' {
+                    MatcherToken::Optional(Box::new(token))
+                } else {
+                    MatcherToken::Repeated(Box::new(token))
+                });
+                text_left = &text_left[1..];
+            } else if first == '.' {
+                tokens.push(MatcherToken::WildCard);
+                text_left = &text_left[1..];
+            } else if first == '(' {
+                let first_close = text_left.find(')')?;
+                let (options, leftover) = text_left.split_at(first_close);
+                tokens.push(MatcherToken::OneOfText(options[1..].split('|').collect()));
+                text_left = &leftover[1..];
+            } else {
+                let first_token = text_left
+                    .find(['.', '(', '?', '*'])
+                    .unwrap_or(text_left.len());
+                tokens.push(MatcherToken::RawText(&text_left[..first_token]));
+                text_left = &text_left[first_token..];
+            }
+        }
+
+        Some(Matcher {
+            text,
+            tokens,
+            most_tokens_matched: 0,
+        })
+    }
+
+    /// This should take a string, and return a vector of tokens, and the
+    /// corresponding part of the given string: as many tokens as can be
+    /// matched from the start of the string, one after the other.
+    ///
+    /// Where there's more than one way to match that many, a `?` or `*`
+    /// takes as much as it can, and a `(...)` takes the first option it
+    /// can, earlier tokens first. An optional or repeated token that
+    /// matches nothing is still in the vector, with `""`.
+    #[require_lifetimes]
+    pub fn match_string<'b, 'c>(
+        &'b mut self,
+        string: &'c str,
+    ) -> Vec<(&'b MatcherToken<'a>, &'c str)> {
+        let lengths = longest_match(&self.tokens, string);
+        let mut string_left = string;
+        let mut answer = vec![];
+        for (token, length) in self.tokens.iter().zip(lengths) {
+            answer.push((token, &string_left[..length]));
+            string_left = &string_left[length..];
+        }
+        self.most_tokens_matched = self.most_tokens_matched.max(answer.len());
+        answer
+    }
+
+    /// This returns whether the whole of a string matches the whole
+    /// matcher.
+    #[require_lifetimes]
+    pub fn is_match<'b, 'c>(&'b self, string: &'c str) -> bool {
+        full_match(&self.tokens, string).is_some()
+    }
+}
+
+/// Given some tokens and a string, return how many bytes each of the most
+/// tokens that can be matched from the start of it match, preferring the
+/// ways `match_string` says it does.
+#[require_lifetimes]
+fn longest_match<'a, 'b, 'c>(tokens: &'b [MatcherToken<'a>], string: &'c str) -> Vec<usize> {
+    let Some((token, rest)) = tokens.split_first() else {
+        return vec![];
+    };
+    let mut best = vec![];
+    for length in lengths(token, string) {
+        let mut lengths = vec![length];
+        lengths.extend(longest_match(rest, &string[length..]));
+        if lengths.len() > best.len() {
+            best = lengths;
+        }
+        if best.len() == tokens.len() {
+            break;
+        }
+    }
+    best
+}
+
+/// Given some tokens and a string, return how many bytes each token
+/// matches, if they can match all of it between them.
+#[require_lifetimes]
+fn full_match<'a, 'b, 'c>(tokens: &'b [MatcherToken<'a>], string: &'c str) -> Option<Vec<usize>> {
+    let Some((token, rest)) = tokens.split_first() else {
+        return string.is_empty().then(Vec::new);
+    };
+    lengths(token, string).into_iter().find_map(|length| {
+        let mut lengths = full_match(rest, &string[length..])?;
+        lengths.insert(0, length);
+        Some(lengths)
+    })
+}
+
+/// Given a token and a string, return every number of bytes from the start
+/// of it that the token can match, in the order they should be tried.
+#[require_lifetimes]
+fn lengths<'a, 'b, 'c>(token: &'b MatcherToken<'a>, string: &'c str) -> Vec<usize> {
+    match token {
+        MatcherToken::RawText(text) => {
+            let matches = string.starts_with(text);
+            matches.then_some(text.len()).into_iter().collect()
+        }
+        MatcherToken::OneOfText(options) => options
+            .iter()
+            .filter(|option| string.starts_with(*option))
+            .map(|option| option.len())
+            .collect(),
+        MatcherToken::WildCard => string
+            .chars()
+            .next()
+            .map(char::len_utf8)
+            .into_iter()
+            .collect(),
+        MatcherToken::Optional(token) => {
+            let mut lengths = lengths(token, string);
+            lengths.push(0);
+            lengths.dedup();
+            lengths
+        }
+        MatcherToken::Repeated(token) => {
+            // Every length that some number of repeats can reach, longest
+            // first. A repeat that matches nothing gets nowhere, so it's
+            // left out, or this would never finish.
+            let mut reached = vec![0];
+            let mut unexplored = vec![0];
+            while let Some(start) = unexplored.pop() {
+                for length in lengths(token, &string[start..]) {
+                    if length > 0 && !reached.contains(&(start + length)) {
+                        reached.push(start + length);
+                        unexplored.push(start + length);
+                    }
+                }
+            }
+            reached.sort_unstable_by(|a, b| b.cmp(a));
+            reached
+        }
+    }
+}
+
+#[cfg(test)]
+mod shared_tests {
+    include!("../../shared_tests.rs");
+}
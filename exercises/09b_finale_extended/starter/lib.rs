@@ -0,0 +1,39 @@
+// This is the finale's way of matching, with `?` and `*` added to the tokens
+// and nothing else changed (the exercise is in `exercise/src/lib.rs`). It
+// goes through the tokens once, greedily, and has nothing to say about the
+// new ones, so it doesn't compile. `tests/starters.rs` checks that it still
+// fails with the errors rustc gives for it.
+
+pub enum MatcherToken<'a> {
+    RawText(&'a str),
+    OneOfText(Box<[&'a str]>),
+    WildCard,
+    Optional(Box<MatcherToken<'a>>),
+    Repeated(Box<MatcherToken<'a>>),
+}
+
+pub fn match_string<'a, 'b, 'c>(
+    tokens: &'b [MatcherToken<'a>],
+    string: &'c str,
+) -> Vec<(&'b MatcherToken<'a>, &'c str)> {
+    let mut string_left = string;
+    let mut answer = vec![];
+    for token in tokens {
+        let length = match token {
+            MatcherToken::RawText(text) => string_left.starts_with(text).then_some(text.len()),
+            MatcherToken::OneOfText(options) => options
+                .iter()
+                .find(|option| string_left.starts_with(*option))
+                .map(|option| option.len()),
+            MatcherToken::WildCard => string_left.chars().next().map(char::len_utf8),
+        };
+        let Some(length) = length else {
+            break;
+        };
+        answer.push((token, &string_left[..length]));
+        string_left = &string_left[length..];
+    }
+    answer
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+#[test]
+fn longer_returns_either() {
+    let forever: &'static str = "forever";
+    let brief = String::from("brief");
+    assert_eq!(longer(forever, &brief), "forever");
+
+    let longest = String::from("longest of all");
+    assert_eq!(longer(forever, &longest), "longest of all");
+    assert_eq!(longer("tie", &String::from("TIE")), "tie");
+}
+
+#[test]
+fn shortest_word_takes_shorter_lived_words() {
+    let words: Vec<String> = ["variance", "is", "subtyping"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    assert_eq!(shortest_word("nothing", &words), "is");
+    assert_eq!(shortest_word("a", &words), "a");
+    assert_eq!(shortest_word("nothing", &[]), "nothing");
+}
+
+#[test]
+fn shortest_keeps_the_shortest() {
+    let shortest = Shortest::new("placeholder");
+    let first = String::from("cell");
+    shortest.offer(&first);
+    let second = String::from("invariant");
+    shortest.offer(&second);
+    assert_eq!(shortest.get(), "cell");
+    assert_eq!(shorter(shortest.get(), "mut"), "mut");
+}
+
+#[test]
+fn shortest_offered_takes_shorter_lived_words() {
+    let words: Vec<String> = ["covariant", "in", "T"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    assert_eq!(shortest_offered("nothing", &words), "T");
+    assert_eq!(shortest_offered("", &words), "");
+    assert_eq!(shortest_offered("nothing", &[]), "nothing");
+}
@@ -0,0 +1,85 @@
+use require_lifetimes::require_lifetimes;
+use std::cell::Cell;
+
+/// Given `first`, which lives for `'long`, and `second`, which only lives
+/// for `'short`, return whichever of them is longer (or `first`, if they're
+/// the same length).
+#[require_lifetimes]
+pub fn longer<'short, 'long: 'short>(first: &'long str, second: &'short str) -> &'short str {
+    // `&'long str` is a subtype of `&'short str`, so `first` can be returned
+    // as it is.
+    if second.len() > first.len() {
+        second
+    } else {
+        first
+    }
+}
+
+/// Given the shortest word so far and another word, return whichever of
+/// them is shorter (or `best`, if they're the same length).
+#[require_lifetimes]
+pub fn shorter<'word>(best: &'word str, word: &'word str) -> &'word str {
+    if word.len() < best.len() {
+        word
+    } else {
+        best
+    }
+}
+
+/// Given a word that lives for the whole program, and some more that
+/// don't, return the shortest of all of them.
+#[require_lifetimes]
+pub fn shortest_word<'words>(default: &'static str, words: &'words [String]) -> &'words str {
+    // `best` starts out as `default`, shortened to `&'words str`. Nothing
+    // is written through a `&mut` to it, so it can be.
+    let mut best = default;
+    for word in words {
+        best = shorter(best, word);
+    }
+    best
+}
+
+/// This keeps the shortest word it's been offered. It's in a `Cell`, so
+/// offering one only needs `&self`.
+#[require_lifetimes]
+pub struct Shortest<'word> {
+    best: Cell<&'word str>,
+}
+
+#[require_lifetimes]
+impl<'word> Shortest<'word> {
+    /// Given the first word, return a `Shortest` that has only seen it.
+    pub fn new(first: &'word str) -> Shortest<'word> {
+        Shortest {
+            best: Cell::new(first),
+        }
+    }
+
+    /// Given a word, keep it if it's shorter than the shortest so far.
+    pub fn offer<'shortest>(&'shortest self, word: &'word str) {
+        self.best.set(shorter(self.best.get(), word));
+    }
+
+    /// Return the shortest word so far.
+    pub fn get<'shortest>(&'shortest self) -> &'word str {
+        self.best.get()
+    }
+}
+
+/// Given a word that lives for the whole program, and some more that
+/// don't, offer them all to a `Shortest`, and return the shortest.
+#[require_lifetimes]
+pub fn shortest_offered<'words>(default: &'static str, words: &'words [String]) -> &'words str {
+    // A `Cell<&'word str>` can't be shortened once it's made, so this one
+    // is made for the words' lifetime from the start.
+    let shortest: Shortest<'words> = Shortest::new(default);
+    for word in words {
+        shortest.offer(word);
+    }
+    shortest.get()
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
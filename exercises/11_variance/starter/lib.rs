@@ -0,0 +1,54 @@
+// This is the wrong way to go about chapter 11's exercise: writing a word
+// that lives for `'short` somewhere that holds a `&'long str`, through a
+// `&mut` and through a `Cell` (the rest is in `exercise/src/lib.rs`).
+// Neither can be fixed by writing more lifetimes in; the exercise does it
+// another way. `tests/starters.rs` checks that this still fails with the
+// errors rustc gives for it.
+
+use std::cell::Cell;
+
+pub fn keep_shorter<'short, 'long: 'short>(best: &mut &'long str, word: &'short str) {
+    if word.len() < best.len() {
+        *best = word;
+    }
+}
+
+pub fn shortest_word<'words>(default: &'static str, words: &'words [String]) -> &'words str {
+    let mut best = default;
+    for word in words {
+        keep_shorter(&mut best, word);
+    }
+    best
+}
+
+pub struct Shortest<'word> {
+    best: Cell<&'word str>,
+}
+
+impl<'word> Shortest<'word> {
+    pub fn new(first: &'word str) -> Shortest<'word> {
+        Shortest {
+            best: Cell::new(first),
+        }
+    }
+
+    pub fn offer(&self, word: &'word str) {
+        if word.len() < self.best.get().len() {
+            self.best.set(word);
+        }
+    }
+
+    pub fn get(&self) -> &'word str {
+        self.best.get()
+    }
+}
+
+pub fn shortest_offered<'words>(default: &'static str, words: &'words [String]) -> &'words str {
+    let shortest: Shortest<'static> = Shortest::new(default);
+    for word in words {
+        shortest.offer(word);
+    }
+    shortest.get()
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use require_lifetimes::require_lifetimes;
+
+/// Given two strings, which can live for different lengths of time, and a
+/// function that measures a string, return what it says about each of them.
+///
+/// The bound on `F` starts out as `F: Fn(&'??? str) -> usize`. Neither `'a`
+/// nor `'b` can go in place of `'???`, since `f` is given both strings:
+/// `starter/both.rs` shows what happens if you try.
+#[require_lifetimes]
+pub fn apply_to_both<'a, 'b, F>(a: &'a str, b: &'b str, f: F) -> (usize, usize)
+where
+    F: for<'any> Fn(&'any str) -> usize,
+{
+    (f(a), f(b))
+}
+
+/// Given a string and a function that measures a string, return what it
+/// says about the string, and about the string in capitals.
+///
+/// This also starts out as `F: Fn(&'??? str) -> usize`. With `'text` in
+/// place of `'???`, the capitals would have to outlive the function they're
+/// made in: see `starter/shouted.rs`.
+#[require_lifetimes]
+pub fn apply_to_shouted<'text, F>(text: &'text str, f: F) -> (usize, usize)
+where
+    F: for<'any> Fn(&'any str) -> usize,
+{
+    let shouted = text.to_uppercase();
+    (f(text), f(&shouted))
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
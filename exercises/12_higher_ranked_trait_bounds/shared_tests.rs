@@ -0,0 +1,39 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+fn vowels(text: &str) -> usize {
+    text.chars().filter(|c| "aeiouAEIOU".contains(*c)).count()
+}
+
+#[test]
+fn both_can_live_for_different_lengths_of_time() {
+    let forever: &'static str = "forever";
+    let (first, second) = {
+        let brief = String::from("brief");
+        apply_to_both(forever, &brief, str::len)
+    };
+    assert_eq!((first, second), (7, 5));
+    assert_eq!(apply_to_both("lifetime", "kata", vowels), (4, 2));
+}
+
+#[test]
+fn closures_can_be_passed() {
+    let capitals = |text: &str| text.chars().filter(char::is_ascii_uppercase).count();
+    assert_eq!(apply_to_both("For", "ALL", capitals), (1, 3));
+    let owned = String::from("Lifetimes");
+    assert_eq!(apply_to_both(&owned, &owned[..4], capitals), (1, 1));
+}
+
+#[test]
+fn shouted_is_measured_too() {
+    let capitals = |text: &str| text.chars().filter(char::is_ascii_uppercase).count();
+    assert_eq!(apply_to_shouted("quiet", capitals), (0, 5));
+    assert_eq!(apply_to_shouted("Hello", vowels), (2, 2));
+    let text = String::from("straße");
+    // Some letters turn into more than one in capitals.
+    let letters = |text: &str| text.chars().count();
+    assert_eq!(apply_to_shouted(&text, letters), (6, 7));
+}
@@ -0,0 +1,29 @@
+use require_lifetimes::require_lifetimes;
+
+/// Given two strings, which can live for different lengths of time, and a
+/// function that measures a string, return what it says about each of them.
+#[require_lifetimes]
+pub fn apply_to_both<'a, 'b, F>(a: &'a str, b: &'b str, f: F) -> (usize, usize)
+where
+    F: for<'x> Fn(&'x str) -> usize,
+{
+    (f(a), f(b))
+}
+
+/// Given a string and a function that measures a string, return what it
+/// says about the string, and about the string in capitals.
+#[require_lifetimes]
+pub fn apply_to_shouted<'text, F>(text: &'text str, f: F) -> (usize, usize)
+where
+    F: for<'x> Fn(&'x str) -> usize,
+{
+    // The capitals are only there until the end of this function, so `f`
+    // has to take a string that lives for any length of time at all.
+    let shouted = text.to_uppercase();
+    (f(text), f(&shouted))
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
@@ -0,0 +1,13 @@
+// This is chapter 12's `apply_to_both` with `'a` in place of the `'???` in
+// its bound, which is the wrong way to go about it (the rest is in
+// `exercise/src/lib.rs`). `tests/starters.rs` checks that it still fails
+// with the errors rustc gives for it.
+
+pub fn apply_to_both<'a, 'b, F>(a: &'a str, b: &'b str, f: F) -> (usize, usize)
+where
+    F: Fn(&'a str) -> usize,
+{
+    (f(a), f(b))
+}
+
+fn main() {}
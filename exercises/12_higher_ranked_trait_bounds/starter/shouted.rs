@@ -0,0 +1,14 @@
+// This is chapter 12's `apply_to_shouted` with `'text` in place of the
+// `'???` in its bound, which is the wrong way to go about it (the rest is in
+// `exercise/src/lib.rs`). `tests/starters.rs` checks that it still fails
+// with the errors rustc gives for it.
+
+pub fn apply_to_shouted<'text, F>(text: &'text str, f: F) -> (usize, usize)
+where
+    F: Fn(&'text str) -> usize,
+{
+    let shouted = text.to_uppercase();
+    (f(text), f(&shouted))
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+fn words(text: &str) -> Vec<String> {
+    text.split(' ').map(str::to_string).collect()
+}
+
+#[test]
+fn moved_counts_every_word() {
+    assert_eq!(
+        total_length_moved(words("borrowed value does not live")),
+        24
+    );
+    assert_eq!(total_length_moved(Vec::new()), 0);
+}
+
+#[test]
+fn scoped_finds_the_longest_in_either_half() {
+    let first_half = words("borrowed value does not live long enough");
+    assert_eq!(longest_scoped(&first_half), "borrowed");
+    let second_half = words("a thread may outlive the function");
+    assert_eq!(longest_scoped(&second_half), "function");
+    // The first of the longest wins, even if it's in the first half.
+    assert_eq!(longest_scoped(&words("four five nine")), "four");
+    assert_eq!(longest_scoped(&words("one")), "one");
+    assert_eq!(longest_scoped(&[]), "");
+}
+
+#[test]
+fn scoped_borrows_outlive_the_threads() {
+    let longest = {
+        let text = words("static bounds and threads");
+        longest_scoped(&text).to_string()
+    };
+    assert_eq!(longest, "threads");
+}
+
+#[test]
+fn shared_counts_every_word() {
+    let shared = Arc::new(words("move it scope it or share it"));
+    assert_eq!(total_length_shared(Arc::clone(&shared)), 22);
+    // The words are still there afterwards, for anyone else with them.
+    assert_eq!(shared.len(), 7);
+    assert_eq!(total_length_shared(Arc::new(Vec::new())), 0);
+}
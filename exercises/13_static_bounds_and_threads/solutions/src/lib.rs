@@ -0,0 +1,68 @@
+use require_lifetimes::require_lifetimes;
+use std::sync::Arc;
+use std::thread;
+
+/// Given some words, return how long they are altogether, counted on
+/// another thread. The words are moved to that thread, so it owns them.
+#[require_lifetimes]
+pub fn total_length_moved(words: Vec<String>) -> usize {
+    let counter = thread::spawn(move || words.iter().map(String::len).sum());
+    counter.join().unwrap()
+}
+
+/// Given some words, return the longest (or the first of the longest, if
+/// there's a tie, or `""` if there aren't any), looking through each half
+/// of them on a thread of its own.
+#[require_lifetimes]
+pub fn longest_scoped<'words>(words: &'words [String]) -> &'words str {
+    let (left, right) = words.split_at(words.len() / 2);
+    // The scope waits for its threads before it returns, so they can borrow
+    // `words`, and even hand back references into it.
+    let (left, right) = thread::scope(|scope| {
+        let left = scope.spawn(|| longest(left));
+        let right = scope.spawn(|| longest(right));
+        (left.join().unwrap(), right.join().unwrap())
+    });
+    if right.len() > left.len() {
+        right
+    } else {
+        left
+    }
+}
+
+/// Given some words, shared with whoever else has them, return how long
+/// they are altogether, counting each half of them on a thread of its own.
+#[require_lifetimes]
+pub fn total_length_shared(words: Arc<Vec<String>>) -> usize {
+    let half = words.len() / 2;
+    let counters: Vec<_> = [0..half, half..words.len()]
+        .into_iter()
+        .map(|range| {
+            // Each thread gets its own `Arc`, which it owns, so it can keep
+            // the words for as long as it likes.
+            let words = Arc::clone(&words);
+            thread::spawn(move || words[range].iter().map(String::len).sum::<usize>())
+        })
+        .collect();
+    counters
+        .into_iter()
+        .map(|counter| counter.join().unwrap())
+        .sum()
+}
+
+/// Given some words, return the longest, or the first of the longest.
+#[require_lifetimes]
+fn longest<'words>(words: &'words [String]) -> &'words str {
+    words.iter().fold("", |longest, word| {
+        if word.len() > longest.len() {
+            word
+        } else {
+            longest
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
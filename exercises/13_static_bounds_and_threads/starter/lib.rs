@@ -0,0 +1,13 @@
+// This is chapter 13's exercise as it's handed out: it hands a borrowed
+// slice to `thread::spawn` (the rest is in `exercise/src/lib.rs`).
+// `tests/starters.rs` checks that it still fails with the errors rustc
+// gives for it.
+
+use std::thread;
+
+pub fn total_length(words: &[String]) -> usize {
+    let counter = thread::spawn(|| words.iter().map(String::len).sum());
+    counter.join().unwrap()
+}
+
+fn main() {}
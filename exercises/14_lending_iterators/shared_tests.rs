@@ -0,0 +1,45 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+#[test]
+fn windows_overlap() {
+    let mut numbers = [1, 2, 3, 4];
+    let mut windows = WindowsMut::new(&mut numbers, 2);
+    assert_eq!(windows.next(), Some(&mut [1, 2][..]));
+    assert_eq!(windows.next(), Some(&mut [2, 3][..]));
+    assert_eq!(windows.next(), Some(&mut [3, 4][..]));
+    assert_eq!(windows.next(), None);
+    assert_eq!(windows.next(), None);
+}
+
+#[test]
+fn windows_can_be_changed() {
+    let mut numbers = [1, 2, 3, 4];
+    let mut windows = WindowsMut::new(&mut numbers, 2);
+    // Each window sees what the one before it did.
+    while let Some(window) = windows.next() {
+        window[1] += window[0];
+    }
+    assert_eq!(numbers, [1, 3, 6, 10]);
+}
+
+#[test]
+fn windows_can_be_too_big() {
+    let mut words = ["lending", "iterator"];
+    let mut windows = WindowsMut::new(&mut words, 3);
+    assert_eq!(windows.next(), None);
+}
+
+#[test]
+fn windows_can_be_the_whole_slice() {
+    let mut text: Vec<char> = "abcde".chars().collect();
+    let mut windows = WindowsMut::new(&mut text, 5);
+    if let Some(window) = windows.next() {
+        window.reverse();
+    }
+    assert_eq!(windows.next(), None);
+    assert_eq!(text.into_iter().collect::<String>(), "edcba");
+}
@@ -0,0 +1,44 @@
+// This uses chapter 14's `WindowsMut` (the rest is in `exercise/src/lib.rs`)
+// to hold on to one window while asking for the next, which a lending
+// iterator doesn't allow: the two overlap. `tests/starters.rs` checks that
+// it still fails with the errors rustc gives for it.
+
+pub trait LendingIterator {
+    type Item<'next>
+    where
+        Self: 'next;
+
+    fn next<'next>(&'next mut self) -> Option<Self::Item<'next>>;
+}
+
+pub struct WindowsMut<'slice, T> {
+    slice: &'slice mut [T],
+    start: usize,
+    size: usize,
+}
+
+impl<'slice, T> LendingIterator for WindowsMut<'slice, T> {
+    type Item<'next>
+        = &'next mut [T]
+    where
+        Self: 'next;
+
+    fn next<'next>(&'next mut self) -> Option<&'next mut [T]> {
+        let window = self.slice.get_mut(self.start..self.start + self.size)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+fn main() {
+    let mut numbers = [1, 2, 3];
+    let mut windows = WindowsMut {
+        slice: &mut numbers,
+        start: 0,
+        size: 2,
+    };
+    let first = windows.next().unwrap();
+    let second = windows.next().unwrap();
+    first[1] = 0;
+    second[0] = 1;
+}
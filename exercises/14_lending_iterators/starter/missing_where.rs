@@ -0,0 +1,11 @@
+// This is chapter 14's `LendingIterator` without `where Self: 'next` on
+// its `Item` (the rest is in `exercise/src/lib.rs`). `tests/starters.rs`
+// checks that it still fails with the errors rustc gives for it.
+
+pub trait LendingIterator {
+    type Item<'next>;
+
+    fn next<'next>(&'next mut self) -> Option<Self::Item<'next>>;
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+// This is chapter 14's `WindowsMut` as an `Iterator` from the standard
+// library, which it can't be: each window has to live for `'slice`, but
+// can only borrow the iterator for as long as `next` does (the rest is in
+// `exercise/src/lib.rs`). `tests/starters.rs` checks that it still fails
+// with the errors rustc gives for it.
+
+pub struct WindowsMut<'slice, T> {
+    slice: &'slice mut [T],
+    start: usize,
+    size: usize,
+}
+
+impl<'slice, T> Iterator for WindowsMut<'slice, T> {
+    type Item = &'slice mut [T];
+
+    fn next(&mut self) -> Option<&'slice mut [T]> {
+        let window = self.slice.get_mut(self.start..self.start + self.size)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+#[test]
+fn splits_on_whitespace() {
+    let mut parser = Parser::new("  a (b|c)\t*d \n");
+    assert_eq!(parser.next_token(), Some("a"));
+    assert_eq!(parser.next_token(), Some("(b|c)"));
+    assert_eq!(parser.next_token(), Some("*d"));
+    assert_eq!(parser.next_token(), None);
+    assert_eq!(parser.next_token(), None);
+    assert_eq!(Parser::new("   ").next_token(), None);
+}
+
+#[test]
+fn tokens_outlive_the_parser() {
+    let text = String::from("drop check");
+    let tokens = {
+        let mut parser = Parser::new(&text);
+        [parser.next_token(), parser.next_token()]
+    };
+    assert_eq!(tokens, [Some("drop"), Some("check")]);
+}
+
+#[test]
+fn raw_tokens_point_into_the_text() {
+    let text = String::from("phantom data");
+    let (phantom, data) = {
+        let mut parser = Parser::new(&text);
+        let tokens = (parser.next_raw().unwrap(), parser.next_raw().unwrap());
+        assert!(parser.next_raw().is_none());
+        tokens
+    };
+
+    assert_eq!(phantom.as_str(), "phantom");
+    assert_eq!((data.as_str(), data.len()), ("data", 4));
+    assert!(!data.is_empty());
+    assert!(std::ptr::eq(data.as_str(), &text[8..]));
+    assert!(RawToken::new("").is_empty());
+}
@@ -0,0 +1,85 @@
+use require_lifetimes::require_lifetimes;
+use std::marker::PhantomData;
+
+/// This splits some text into tokens, separated by whitespace. Every token
+/// it returns borrows from the text, not from the parser, so the tokens
+/// can outlive it (but not the text).
+#[require_lifetimes]
+pub struct Parser<'input> {
+    input: &'input str,
+    position: usize,
+}
+
+#[require_lifetimes]
+impl<'input> Parser<'input> {
+    /// Given some text, return a parser at the start of it.
+    pub fn new(input: &'input str) -> Parser<'input> {
+        Parser { input, position: 0 }
+    }
+
+    /// Return the next token, or `None` if there aren't any more.
+    pub fn next_token<'parser>(&'parser mut self) -> Option<&'input str> {
+        let rest = &self.input[self.position..];
+        let start = self.position + (rest.len() - rest.trim_start().len());
+        let rest = &self.input[start..];
+        let end = start + rest.find(char::is_whitespace).unwrap_or(rest.len());
+        self.position = end;
+        (start < end).then(|| &self.input[start..end])
+    }
+
+    /// Return the next token as a `RawToken`, or `None` if there aren't any
+    /// more.
+    pub fn next_raw<'parser>(&'parser mut self) -> Option<RawToken<'input>> {
+        self.next_token().map(RawToken::new)
+    }
+}
+
+/// A token, kept as a pointer and a length rather than a `&str`. Nothing in
+/// that says what it points into, so the `PhantomData` is there to borrow
+/// the text for `'input`, like a `&'input str` would: without it, a
+/// `RawToken` could outlive the text it points into.
+#[require_lifetimes]
+#[derive(Clone, Copy, Debug)]
+pub struct RawToken<'input> {
+    pointer: *const u8,
+    len: usize,
+    text: PhantomData<&'input ()>,
+}
+
+#[require_lifetimes]
+impl<'input> RawToken<'input> {
+    /// Given a token, return it as a `RawToken`, borrowing what it's in.
+    pub fn new(token: &'input str) -> RawToken<'input> {
+        RawToken {
+            pointer: token.as_ptr(),
+            len: token.len(),
+            text: PhantomData,
+        }
+    }
+
+    /// Return how long the token is, in bytes.
+    pub fn len<'token>(&'token self) -> usize {
+        self.len
+    }
+
+    /// Return whether the token is empty.
+    pub fn is_empty<'token>(&'token self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the token, as it was in the text.
+    pub fn as_str<'token>(&'token self) -> &'input str {
+        // SAFETY: `pointer` and `len` came from a `&'input str` in `new`,
+        // and the `PhantomData` means this can't outlive `'input`, so
+        // they're still a valid `str`.
+        unsafe {
+            let bytes = std::slice::from_raw_parts(self.pointer, self.len);
+            std::str::from_utf8_unchecked(bytes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
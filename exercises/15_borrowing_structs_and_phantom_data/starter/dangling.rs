@@ -0,0 +1,26 @@
+// This is chapter 15's `Parser` (the rest is in `exercise/src/lib.rs`),
+// used after the text it borrows is gone. `tests/starters.rs` checks that
+// it still fails with the errors rustc gives for it.
+
+pub struct Parser<'input> {
+    input: &'input str,
+}
+
+impl<'input> Parser<'input> {
+    pub fn new(input: &'input str) -> Parser<'input> {
+        Parser { input }
+    }
+
+    pub fn next_token<'parser>(&'parser mut self) -> Option<&'input str> {
+        self.input.split_whitespace().next()
+    }
+}
+
+fn main() {
+    let mut parser;
+    {
+        let text = String::from("dangling reference");
+        parser = Parser::new(&text);
+    }
+    println!("{:?}", parser.next_token());
+}
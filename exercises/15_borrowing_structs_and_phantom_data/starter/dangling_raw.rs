@@ -0,0 +1,31 @@
+// This is chapter 15's `RawToken` (the rest is in `exercise/src/lib.rs`),
+// used after the text it points into is gone. Its `PhantomData` is what
+// stops that compiling. `tests/starters.rs` checks that it still fails
+// with the errors rustc gives for it.
+
+use std::marker::PhantomData;
+
+pub struct RawToken<'input> {
+    pointer: *const u8,
+    len: usize,
+    text: PhantomData<&'input ()>,
+}
+
+impl<'input> RawToken<'input> {
+    pub fn new(token: &'input str) -> RawToken<'input> {
+        RawToken {
+            pointer: token.as_ptr(),
+            len: token.len(),
+            text: PhantomData,
+        }
+    }
+}
+
+fn main() {
+    let token;
+    {
+        let text = String::from("dangling pointer");
+        token = RawToken::new(&text);
+    }
+    println!("{:?} {}", token.pointer, token.len);
+}
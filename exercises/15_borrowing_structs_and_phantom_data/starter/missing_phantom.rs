@@ -0,0 +1,11 @@
+// This is chapter 15's `RawToken` (the rest is in `exercise/src/lib.rs`),
+// with a lifetime but without the `PhantomData` that uses it.
+// `tests/starters.rs` checks that it still fails with the errors rustc
+// gives for it.
+
+pub struct RawToken<'input> {
+    pointer: *const u8,
+    len: usize,
+}
+
+fn main() {}
@@ -0,0 +1,91 @@
+use require_lifetimes::require_lifetimes;
+
+/// This finds any of a pattern's alternatives, which are separated by `|`,
+/// in some text. It borrows the pattern, like the finale's `Matcher` does.
+#[require_lifetimes]
+pub struct Matcher<'pattern> {
+    alternatives: Vec<&'pattern str>,
+}
+
+#[require_lifetimes]
+impl<'pattern> Matcher<'pattern> {
+    /// Given a pattern, return a matcher for it.
+    pub fn new(pattern: &'pattern str) -> Matcher<'pattern> {
+        Matcher {
+            alternatives: pattern.split('|').collect(),
+        }
+    }
+
+    /// Return the pattern's alternatives.
+    pub fn alternatives<'matcher>(&'matcher self) -> &'matcher [&'pattern str] {
+        &self.alternatives
+    }
+
+    /// Given some text, return the first part of it that's one of the
+    /// alternatives (the longest, if more than one starts there), or `None`
+    /// if none of them are in it.
+    pub fn find<'matcher, 'text>(&'matcher self, text: &'text str) -> Option<&'text str> {
+        (0..=text.len())
+            .filter(|start| text.is_char_boundary(*start))
+            .find_map(|start| {
+                let rest = &text[start..];
+                let longest = self
+                    .alternatives
+                    .iter()
+                    .filter(|alternative| rest.starts_with(*alternative))
+                    .max_by_key(|alternative| alternative.len())?;
+                Some(&rest[..longest.len()])
+            })
+    }
+}
+
+/// A pattern that owns its text, so it can be kept and moved around.
+///
+/// It starts out as `Search` in `starter/returned.rs`, keeping a `Matcher`
+/// next to the pattern it borrows, which doesn't compile. Keep only the
+/// pattern, and make a `Matcher` whenever one's needed.
+pub struct OwnedMatcher {
+    pattern: String,
+}
+
+#[require_lifetimes]
+impl OwnedMatcher {
+    /// Given a pattern, return an `OwnedMatcher` for it.
+    pub fn new(pattern: String) -> OwnedMatcher {
+        OwnedMatcher { pattern }
+    }
+
+    /// Return the pattern.
+    pub fn pattern<'owned>(&'owned self) -> &'owned str {
+        &self.pattern
+    }
+
+    /// Return a matcher for the pattern, borrowing it from this.
+    pub fn matcher<'owned>(&'owned self) -> Matcher<'owned> {
+        Matcher::new(&self.pattern)
+    }
+
+    /// Given some text, return what `Matcher::find` does.
+    pub fn find<'owned, 'text>(&'owned self, text: &'text str) -> Option<&'text str> {
+        self.matcher().find(text)
+    }
+}
+
+/// Given a pattern, make a matcher for it, and return what `f` does with
+/// it.
+///
+/// Work out what `F`'s bound has to be: `f` is given a matcher that
+/// borrows `pattern`, which is only there until this returns.
+#[require_lifetimes]
+pub fn with_matcher<R, F>(pattern: String, f: F) -> R
+where
+    F: for<'matcher, 'pattern> FnOnce(&'matcher Matcher<'pattern>) -> R,
+{
+    let matcher = Matcher::new(&pattern);
+    f(&matcher)
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
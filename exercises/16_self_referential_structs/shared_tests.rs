@@ -0,0 +1,42 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+#[test]
+fn matcher_finds_the_first_alternative() {
+    let matcher = Matcher::new("self|referential|ref");
+    assert_eq!(matcher.alternatives(), ["self", "referential", "ref"]);
+    assert_eq!(matcher.find("a referential struct"), Some("referential"));
+    assert_eq!(matcher.find("itself"), Some("self"));
+    assert_eq!(matcher.find("a reference"), Some("ref"));
+    assert_eq!(matcher.find("nothing"), None);
+}
+
+#[test]
+fn owned_matcher_can_be_moved() {
+    let owned = {
+        let pattern = String::from("owned|own");
+        OwnedMatcher::new(pattern)
+    };
+    let matchers = [owned];
+    assert_eq!(matchers[0].pattern(), "owned|own");
+    assert_eq!(matchers[0].find("disowned"), Some("owned"));
+    assert_eq!(matchers[0].matcher().alternatives(), ["owned", "own"]);
+    assert_eq!(matchers[0].find("nothing"), None);
+}
+
+#[test]
+fn with_matcher_lends_it_out() {
+    let text = String::from("a closure borrows");
+    let found = with_matcher(String::from("borrow|closure"), |matcher| {
+        matcher.find(&text)
+    });
+    assert_eq!(found, Some("closure"));
+
+    let count = with_matcher(String::from("a|b|c"), |matcher| {
+        matcher.alternatives().len()
+    });
+    assert_eq!(count, 3);
+}
@@ -0,0 +1,33 @@
+// This is the second thing everyone tries in chapter 16: making the
+// struct first, then pointing its `Matcher` at its own pattern (the rest
+// is in `exercise/src/lib.rs`). That much compiles, but the struct is
+// borrowed by itself for as long as it's around, so it can't be moved.
+// `tests/starters.rs` checks that it still fails with the errors rustc
+// gives for it.
+
+pub struct Matcher<'pattern> {
+    alternatives: Vec<&'pattern str>,
+}
+
+impl<'pattern> Matcher<'pattern> {
+    pub fn new(pattern: &'pattern str) -> Matcher<'pattern> {
+        Matcher {
+            alternatives: pattern.split('|').collect(),
+        }
+    }
+}
+
+pub struct Search<'pattern> {
+    pattern: String,
+    matcher: Option<Matcher<'pattern>>,
+}
+
+fn main() {
+    let mut search = Search {
+        pattern: String::from("self|referential"),
+        matcher: None,
+    };
+    search.matcher = Some(Matcher::new(&search.pattern));
+    let moved = search;
+    println!("{}", moved.pattern);
+}
@@ -0,0 +1,28 @@
+// This is the first thing everyone tries in chapter 16: returning a
+// pattern along with a `Matcher` that borrows it (the rest is in
+// `exercise/src/lib.rs`). `tests/starters.rs` checks that it still fails
+// with the errors rustc gives for it.
+
+pub struct Matcher<'pattern> {
+    alternatives: Vec<&'pattern str>,
+}
+
+impl<'pattern> Matcher<'pattern> {
+    pub fn new(pattern: &'pattern str) -> Matcher<'pattern> {
+        Matcher {
+            alternatives: pattern.split('|').collect(),
+        }
+    }
+}
+
+pub struct Search<'pattern> {
+    pattern: String,
+    matcher: Matcher<'pattern>,
+}
+
+pub fn search<'pattern>(pattern: String) -> Search<'pattern> {
+    let matcher = Matcher::new(&pattern);
+    Search { pattern, matcher }
+}
+
+fn main() {}
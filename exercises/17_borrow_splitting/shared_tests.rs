@@ -0,0 +1,49 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+#[test]
+fn types_at_the_cursor() {
+    let mut editor = Editor::new("world");
+    editor.type_text("hello, ");
+    assert_eq!(editor.text(), "hello, world");
+    assert_eq!(editor.cursor(), 7);
+    assert_eq!(editor.stats(), Stats { edits: 1, typed: 7 });
+}
+
+#[test]
+fn moves_the_cursor() {
+    let mut editor = Editor::new("añb");
+    assert!(!editor.move_left());
+    assert!(editor.move_right());
+    assert!(editor.move_right());
+    assert_eq!(editor.cursor(), 2);
+    editor.type_text("!");
+    assert!(editor.move_right());
+    assert!(!editor.move_right());
+    assert!(editor.move_left());
+    editor.type_text("?");
+    assert_eq!(editor.text(), "añ!?b");
+    assert_eq!(editor.cursor(), 4);
+}
+
+#[test]
+fn splits_into_halves() {
+    let mut editor = Editor::new("split");
+    editor.move_right();
+    let (left, right) = editor.halves_mut();
+    std::mem::swap(left, right);
+    assert_eq!(editor.text(), "plits");
+    assert_eq!(editor.cursor(), 4);
+}
+
+#[test]
+fn copies_the_lines_after_the_cursor() {
+    let mut editor = Editor::new("one\ntwo");
+    editor.copy_right_lines();
+    assert_eq!(editor.text(), "one\ntwo\none\ntwo");
+    assert_eq!(editor.cursor(), 8);
+    assert_eq!(editor.stats(), Stats { edits: 4, typed: 8 });
+}
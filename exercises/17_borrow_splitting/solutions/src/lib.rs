@@ -0,0 +1,112 @@
+use require_lifetimes::require_lifetimes;
+
+/// What's been done to an `Editor`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// How many times text has been typed.
+    pub edits: usize,
+    /// How many characters have been typed, altogether.
+    pub typed: usize,
+}
+
+#[require_lifetimes]
+impl Stats {
+    /// Given some text that's been typed, count it.
+    pub fn record<'stats, 'text>(&'stats mut self, text: &'text str) {
+        self.edits += 1;
+        self.typed += text.chars().count();
+    }
+}
+
+/// Some text with a cursor in it: `left` is what's before the cursor, and
+/// `right` is what's after it.
+#[derive(Debug, Default)]
+pub struct Editor {
+    left: String,
+    right: String,
+    stats: Stats,
+}
+
+#[require_lifetimes]
+impl Editor {
+    /// Given some text, return an editor with the cursor at its start.
+    pub fn new<'text>(text: &'text str) -> Editor {
+        Editor {
+            right: text.to_string(),
+            ..Editor::default()
+        }
+    }
+
+    /// Return all the text.
+    pub fn text<'editor>(&'editor self) -> String {
+        format!("{}{}", self.left, self.right)
+    }
+
+    /// Return where the cursor is, in characters from the start.
+    pub fn cursor<'editor>(&'editor self) -> usize {
+        self.left.chars().count()
+    }
+
+    /// Return what's been done to the editor.
+    pub fn stats<'editor>(&'editor self) -> Stats {
+        self.stats
+    }
+
+    /// Return what's before the cursor and what's after it, both mutably.
+    /// They're different fields, so they can be borrowed at once, but only
+    /// a function that can see both of them can tell.
+    pub fn halves_mut<'editor>(&'editor mut self) -> (&'editor mut String, &'editor mut String) {
+        (&mut self.left, &mut self.right)
+    }
+
+    /// Given some text, type it in at the cursor.
+    pub fn type_text<'editor, 'text>(&'editor mut self, text: &'text str) {
+        // Borrowing the fields, rather than `self`, lets both be borrowed.
+        let left = &mut self.left;
+        let stats = &mut self.stats;
+        left.push_str(text);
+        stats.record(text);
+    }
+
+    /// Move the cursor one character to the left, and return whether it
+    /// could be.
+    pub fn move_left<'editor>(&'editor mut self) -> bool {
+        let (left, right) = self.halves_mut();
+        match left.pop() {
+            Some(c) => {
+                right.insert(0, c);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move the cursor one character to the right, and return whether it
+    /// could be.
+    pub fn move_right<'editor>(&'editor mut self) -> bool {
+        let (left, right) = self.halves_mut();
+        if right.is_empty() {
+            return false;
+        }
+        left.push(right.remove(0));
+        true
+    }
+
+    /// Type every line after the cursor in again, each ending in a
+    /// newline, at the cursor.
+    pub fn copy_right_lines<'editor>(&'editor mut self) {
+        // `type_text` would borrow all of `self`, while `right` is being
+        // read, so this does what it does with the fields it needs.
+        for line in self.right.lines() {
+            for text in [line, "\n"] {
+                self.left.push_str(text);
+                self.stats.record(text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
@@ -0,0 +1,60 @@
+// This is chapter 17's `Editor` as it's handed out, borrowing its fields
+// through helper methods that take `&mut self` (the rest is in
+// `exercise/src/lib.rs`). `tests/starters.rs` checks that it still fails
+// with the errors rustc gives for it.
+
+#[derive(Default)]
+pub struct Stats {
+    pub edits: usize,
+    pub typed: usize,
+}
+
+#[derive(Default)]
+pub struct Editor {
+    left: String,
+    right: String,
+    stats: Stats,
+}
+
+impl Editor {
+    fn left_mut(&mut self) -> &mut String {
+        &mut self.left
+    }
+
+    fn right_mut(&mut self) -> &mut String {
+        &mut self.right
+    }
+
+    fn stats_mut(&mut self) -> &mut Stats {
+        &mut self.stats
+    }
+
+    pub fn type_text(&mut self, text: &str) {
+        let left = self.left_mut();
+        let stats = self.stats_mut();
+        left.push_str(text);
+        stats.edits += 1;
+        stats.typed += text.chars().count();
+    }
+
+    pub fn move_left(&mut self) -> bool {
+        let left = self.left_mut();
+        let right = self.right_mut();
+        match left.pop() {
+            Some(c) => {
+                right.insert(0, c);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn copy_right_lines(&mut self) {
+        for line in self.right.lines() {
+            self.type_text(line);
+            self.type_text("\n");
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+// These tests are shared by the exercise and its solution: both include
+// this file in their `tests` module, so they run exactly the same tests.
+// `cargo xtask shared-tests` checks that they both still do.
+
+use super::*;
+
+#[test]
+fn validators_check_the_input() {
+    assert_eq!(MaxLength(3).validate("abc"), None);
+    assert_eq!(
+        MaxLength(3).validate("abcd"),
+        Some("4 characters is more than 3".to_string())
+    );
+    assert_eq!(Forbidden { word: "bad" }.validate("good"), None);
+    let choices = ["red".to_string(), "green".to_string()];
+    assert_eq!(
+        OneOf { choices: &choices }.validate("blue"),
+        Some("`blue` isn't one of red, green".to_string())
+    );
+}
+
+#[test]
+fn registry_runs_every_validator() {
+    let config = String::from("secret\nadmin,guest");
+    let mut lines = config.lines();
+    let forbidden = lines.next().unwrap();
+    let choices: Vec<String> = lines.next().unwrap().split(',').map(String::from).collect();
+
+    let mut registry = Registry::new();
+    assert!(registry.is_empty());
+    registry.add(Forbidden { word: forbidden });
+    registry.add(OneOf { choices: &choices });
+    registry.add(MaxLength(5));
+    assert_eq!(registry.len(), 3);
+
+    assert_eq!(registry.validate("guest"), Vec::<String>::new());
+    assert_eq!(
+        registry.validate("secret"),
+        [
+            "`secret` isn't allowed",
+            "`secret` isn't one of admin, guest",
+            "6 characters is more than 5",
+        ]
+    );
+}
+
+#[test]
+fn registry_outlives_what_it_validates() {
+    let word = String::from("drop");
+    let registry = {
+        let mut registry = Registry::default();
+        registry.add(Forbidden { word: &word });
+        registry
+    };
+    let input = String::from("dropped");
+    assert_eq!(registry.validate(&input), ["`drop` isn't allowed"]);
+}
@@ -0,0 +1,104 @@
+use require_lifetimes::require_lifetimes;
+
+/// Something that checks some input.
+#[require_lifetimes]
+pub trait Validator {
+    /// Given some input, return what's wrong with it, or `None` if nothing
+    /// is.
+    fn validate<'validator, 'input>(&'validator self, input: &'input str) -> Option<String>;
+}
+
+/// This checks the input isn't longer than a number of characters. It owns
+/// everything it needs, so it'd live for `'static`.
+pub struct MaxLength(pub usize);
+
+#[require_lifetimes]
+impl Validator for MaxLength {
+    fn validate<'validator, 'input>(&'validator self, input: &'input str) -> Option<String> {
+        let length = input.chars().count();
+        (length > self.0).then(|| format!("{length} characters is more than {}", self.0))
+    }
+}
+
+/// This checks the input doesn't have a word in it. The word is borrowed
+/// from the configuration, so this only lives as long as that does.
+#[require_lifetimes]
+pub struct Forbidden<'config> {
+    pub word: &'config str,
+}
+
+#[require_lifetimes]
+impl<'config> Validator for Forbidden<'config> {
+    fn validate<'validator, 'input>(&'validator self, input: &'input str) -> Option<String> {
+        input
+            .contains(self.word)
+            .then(|| format!("`{}` isn't allowed", self.word))
+    }
+}
+
+/// This checks the input is one of some choices, borrowed from the
+/// configuration.
+#[require_lifetimes]
+pub struct OneOf<'config> {
+    pub choices: &'config [String],
+}
+
+#[require_lifetimes]
+impl<'config> Validator for OneOf<'config> {
+    fn validate<'validator, 'input>(&'validator self, input: &'input str) -> Option<String> {
+        (!self.choices.iter().any(|choice| choice == input))
+            .then(|| format!("`{input}` isn't one of {}", self.choices.join(", ")))
+    }
+}
+
+/// Some validators, which can be of different types, and can borrow
+/// anything that lives for `'config`.
+#[require_lifetimes]
+#[derive(Default)]
+pub struct Registry<'config> {
+    // Without `+ 'config`, a `Box<dyn Validator>` is a
+    // `Box<dyn Validator + 'static>`, which can't borrow anything.
+    validators: Vec<Box<dyn Validator + 'config>>,
+}
+
+#[require_lifetimes]
+impl<'config> Registry<'config> {
+    /// Return a registry without any validators.
+    pub fn new() -> Registry<'config> {
+        Registry {
+            validators: Vec::new(),
+        }
+    }
+
+    /// Given a validator, add it.
+    pub fn add<'registry, V>(&'registry mut self, validator: V)
+    where
+        V: Validator + 'config,
+    {
+        self.validators.push(Box::new(validator));
+    }
+
+    /// Return how many validators there are.
+    pub fn len<'registry>(&'registry self) -> usize {
+        self.validators.len()
+    }
+
+    /// Return whether there aren't any validators.
+    pub fn is_empty<'registry>(&'registry self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Given some input, return what every validator says is wrong with it,
+    /// in the order they were added.
+    pub fn validate<'registry, 'input>(&'registry self, input: &'input str) -> Vec<String> {
+        self.validators
+            .iter()
+            .filter_map(|validator| validator.validate(input))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../../shared_tests.rs");
+}
@@ -0,0 +1,20 @@
+// This is chapter 18's `Registry` with `+ 'config` on its trait objects,
+// but not on what `add` takes (the rest is in `exercise/src/lib.rs`).
+// `tests/starters.rs` checks that it still fails with the errors rustc
+// gives for it.
+
+pub trait Validator {
+    fn validate(&self, input: &str) -> Option<String>;
+}
+
+pub struct Registry<'config> {
+    validators: Vec<Box<dyn Validator + 'config>>,
+}
+
+impl<'config> Registry<'config> {
+    pub fn add<V: Validator>(&mut self, validator: V) {
+        self.validators.push(Box::new(validator));
+    }
+}
+
+fn main() {}
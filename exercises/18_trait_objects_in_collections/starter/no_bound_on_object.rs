@@ -0,0 +1,21 @@
+// This is chapter 18's `Registry` as it's handed out, with no lifetime on
+// its trait objects, so they default to `'static` (the rest is in
+// `exercise/src/lib.rs`). `tests/starters.rs` checks that it still fails
+// with the errors rustc gives for it.
+
+pub trait Validator {
+    fn validate(&self, input: &str) -> Option<String>;
+}
+
+pub struct Registry<'config> {
+    validators: Vec<Box<dyn Validator>>,
+    config: &'config str,
+}
+
+impl<'config> Registry<'config> {
+    pub fn add<V: Validator + 'config>(&mut self, validator: V) {
+        self.validators.push(Box::new(validator));
+    }
+}
+
+fn main() {}
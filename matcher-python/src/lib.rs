@@ -0,0 +1,176 @@
+//! The finale's `Matcher`, for Python, for workshops where people would
+//! rather script in it. It's all behind the `python` feature; build and
+//! install it into a virtualenv with `maturin develop` (in this directory),
+//! and then, from Python:
+//!
+//! ```python
+//! from lifetimekata_matcher import Matcher
+//! matcher = Matcher("(Black|Bridge)(rock|stone).")
+//! matcher.match("Bridgestone_Tyres")
+//! # [('(Black|Bridge)', 'Bridge'), ('(rock|stone)', 'stone'), ('.', '_')]
+//! ```
+//!
+//! A `Matcher` borrows the pattern it was made from, but Python can't hold on
+//! to a borrow, just like JavaScript can't (see `matcher-wasm`), so the
+//! Python `Matcher` owns the pattern and the tokens it was parsed into, and
+//! makes a `Matcher` borrowing them for as long as each match takes, without
+//! parsing the pattern again.
+
+#![cfg(feature = "python")]
+
+use ex08::{MatchResult, Matcher, MatcherToken, OwnedMatcherToken};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// A pattern that's been checked to parse, for matching strings against.
+#[pyclass(name = "Matcher", module = "lifetimekata_matcher", frozen)]
+pub struct PyMatcher {
+    pattern: String,
+    tokens: Vec<OwnedMatcherToken>,
+}
+
+/// Given what a pattern matched, return each token, as it was written in the
+/// pattern, along with the text it matched.
+fn pairs(result: &MatchResult) -> Vec<(String, String)> {
+    result
+        .iter()
+        .map(|(token, text)| {
+            let token = match token {
+                MatcherToken::RawText(text) => text.to_string(),
+                MatcherToken::OneOfText(options) => format!("({})", options.join("|")),
+                MatcherToken::WildCard => ".".to_string(),
+            };
+            (token, text.to_string())
+        })
+        .collect()
+}
+
+#[pymethods]
+impl PyMatcher {
+    /// Raises `ValueError` if the pattern doesn't parse.
+    #[new]
+    fn new(pattern: &str) -> PyResult<Self> {
+        let matcher = Matcher::parse(pattern).map_err(|error| {
+            PyValueError::new_err(format!("`{pattern}` isn't a pattern: {error}"))
+        })?;
+        Ok(PyMatcher {
+            pattern: pattern.to_string(),
+            tokens: matcher.to_owned_tokens(),
+        })
+    }
+
+    #[getter]
+    fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Return what each token matched in `candidate`, greedily, from the
+    /// start, as a list of `(token, text)` tuples.
+    #[pyo3(name = "match")]
+    fn match_candidate(&self, candidate: &str) -> Vec<(String, String)> {
+        pairs(&self.matcher().match_string(candidate))
+    }
+
+    /// Return the best match from the start of `candidate`, trying every
+    /// option of every group, as `match` does.
+    fn match_exhaustive(&self, candidate: &str) -> Vec<(String, String)> {
+        pairs(&self.matcher().match_string_exhaustive(candidate))
+    }
+
+    /// Return whether the whole of `candidate` matches the whole pattern.
+    fn is_complete(&self, candidate: &str) -> bool {
+        self.matcher().is_match(candidate)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Matcher({:?})", self.pattern)
+    }
+}
+
+impl PyMatcher {
+    /// Return a `Matcher` for the pattern, made from the tokens it was
+    /// parsed into.
+    fn matcher(&self) -> Matcher<'_> {
+        Matcher::from_owned_tokens(&self.pattern, &self.tokens)
+    }
+}
+
+/// The module Python imports.
+#[pymodule]
+fn lifetimekata_matcher(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyMatcher>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    /// Given some Python, run it with this module imported as `lk`, and
+    /// return what it evaluates to.
+    fn eval<T: for<'py> FromPyObjectOwned<'py>>(code: &str) -> PyResult<T> {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "lifetimekata_matcher")?;
+            lifetimekata_matcher(&module)?;
+            let globals = PyDict::new(py);
+            globals.set_item("lk", module)?;
+            let code = std::ffi::CString::new(code).unwrap();
+            py.eval(&code, Some(&globals), None)?
+                .extract()
+                .map_err(Into::into)
+        })
+    }
+
+    #[test]
+    fn matches_from_python() {
+        let matched: Vec<(String, String)> =
+            eval("lk.Matcher('(Black|Bridge)(rock|stone).').match('Bridgestone_Tyres')").unwrap();
+        assert_eq!(
+            matched,
+            [
+                ("(Black|Bridge)".to_string(), "Bridge".to_string()),
+                ("(rock|stone)".to_string(), "stone".to_string()),
+                (".".to_string(), "_".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn exhaustive_matches_try_every_option() {
+        let greedy: Vec<(String, String)> = eval("lk.Matcher('(a|ab)c').match('abc')").unwrap();
+        assert_eq!(greedy, [("(a|ab)".to_string(), "a".to_string())]);
+        let exhaustive: Vec<(String, String)> =
+            eval("lk.Matcher('(a|ab)c').match_exhaustive('abc')").unwrap();
+        assert_eq!(
+            exhaustive,
+            [
+                ("(a|ab)".to_string(), "ab".to_string()),
+                ("c".to_string(), "c".to_string()),
+            ]
+        );
+        assert!(eval::<bool>("lk.Matcher('(a|ab)c').is_complete('abc')").unwrap());
+        assert!(!eval::<bool>("lk.Matcher('(a|ab)c').is_complete('abcd')").unwrap());
+    }
+
+    #[test]
+    fn bad_patterns_raise_value_error() {
+        let error = eval::<Option<String>>("lk.Matcher('ab(c|d')").unwrap_err();
+        Python::attach(|py| {
+            assert!(error.is_instance_of::<PyValueError>(py));
+            assert_eq!(
+                error.value(py).to_string(),
+                "`ab(c|d` isn't a pattern: this group isn't closed (at byte 2)"
+            );
+        });
+    }
+
+    #[test]
+    fn repr_shows_the_pattern() {
+        assert_eq!(
+            eval::<String>("repr(lk.Matcher('a.b'))").unwrap(),
+            "Matcher(\"a.b\")"
+        );
+        assert_eq!(eval::<String>("lk.Matcher('a.b').pattern").unwrap(), "a.b");
+    }
+}
@@ -0,0 +1,37 @@
+"""These run the matcher as Python sees it, after `maturin develop`, with
+`pytest tests`."""
+
+import pytest
+
+from lifetimekata_matcher import Matcher
+
+
+def test_matches_greedily_from_the_start():
+    matcher = Matcher("(Black|Bridge)(rock|stone).")
+    assert matcher.match("Bridgestone_Tyres") == [
+        ("(Black|Bridge)", "Bridge"),
+        ("(rock|stone)", "stone"),
+        (".", "_"),
+    ]
+    assert matcher.match("something else") == []
+
+
+def test_exhaustive_matches_try_every_option():
+    matcher = Matcher("(a|ab)c")
+    assert matcher.match("abc") == [("(a|ab)", "a")]
+    assert matcher.match_exhaustive("abc") == [("(a|ab)", "ab"), ("c", "c")]
+
+
+def test_is_complete_needs_the_whole_candidate():
+    matcher = Matcher("(a|ab)c")
+    assert matcher.is_complete("abc")
+    assert not matcher.is_complete("abcd")
+
+
+def test_bad_patterns_raise_value_error():
+    with pytest.raises(ValueError, match="this group isn't closed"):
+        Matcher("ab(c|d")
+
+
+def test_multibyte_text_comes_back_whole():
+    assert Matcher("é.").match("é💪") == [("é", "é"), (".", "💪")]
@@ -0,0 +1,180 @@
+//! The finale's `Matcher`, for JavaScript, so the book can have a demo of it
+//! running in the page. Build it with `wasm-pack build --target web
+//! matcher-wasm`, and then, from JavaScript:
+//!
+//! ```js
+//! const matcher = parse("(Black|Bridge)(rock|stone).");
+//! matcher.match("Bridgestone_Tyres");
+//! // [{tokenIndex: 0, tokenKind: "OneOfText", text: "Bridge", start: 0, end: 6}, ...]
+//! ```
+//!
+//! A `Matcher` borrows the pattern it was made from, but JavaScript can't
+//! hold on to a borrow: whatever it's given has to own everything in it. A
+//! struct can't own the pattern and a `Matcher` borrowing it at once (that's
+//! chapter 16), so `JsMatcher` owns the pattern and the tokens it was parsed
+//! into, and makes a `Matcher` borrowing them for as long as each match
+//! takes, without parsing the pattern again.
+
+use ex08::{Matcher, MatcherToken, OwnedMatcherToken};
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+/// A pattern that's been checked to parse, for matching strings against.
+#[wasm_bindgen]
+pub struct JsMatcher {
+    pattern: String,
+    tokens: Vec<OwnedMatcherToken>,
+}
+
+/// Given a pattern, return it ready to match against, or why it doesn't
+/// parse.
+#[wasm_bindgen]
+pub fn parse(pattern: &str) -> Result<JsMatcher, String> {
+    match Matcher::parse(pattern) {
+        Ok(matcher) => Ok(JsMatcher {
+            pattern: pattern.to_string(),
+            tokens: matcher.to_owned_tokens(),
+        }),
+        Err(error) => Err(format!("`{pattern}` isn't a pattern: {error}")),
+    }
+}
+
+#[wasm_bindgen]
+impl JsMatcher {
+    /// Given a candidate, return what each token matched in it, from the
+    /// start, as an array of `{tokenIndex, tokenKind, text, start, end}`.
+    /// `start` and `end` count UTF-16 code units, the way JavaScript's
+    /// strings do, so `candidate.slice(start, end)` is `text`.
+    #[wasm_bindgen(js_name = "match")]
+    pub fn match_candidate(&self, candidate: &str) -> JsValue {
+        let array = Array::new();
+        for matched in self.matches(candidate) {
+            array.push(&matched.to_js());
+        }
+        array.into()
+    }
+
+    /// Return the pattern, as it was parsed.
+    #[wasm_bindgen(getter)]
+    pub fn pattern(&self) -> String {
+        self.pattern.clone()
+    }
+}
+
+/// What one token matched.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Match {
+    /// Which token it is, counting from 0.
+    pub token_index: usize,
+    /// What kind of token it is: `RawText`, `OneOfText` or `WildCard`.
+    pub token_kind: &'static str,
+    pub text: String,
+    /// Where `text` starts and ends in the candidate, in UTF-16 code units.
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Match {
+    fn to_js(&self) -> JsValue {
+        let object = Object::new();
+        for (key, value) in [
+            ("tokenIndex", JsValue::from(self.token_index)),
+            ("tokenKind", JsValue::from(self.token_kind)),
+            ("text", JsValue::from(self.text.as_str())),
+            ("start", JsValue::from(self.start)),
+            ("end", JsValue::from(self.end)),
+        ] {
+            // Setting a property on a plain object can't fail.
+            let _ = Reflect::set(&object, &JsValue::from(key), &value);
+        }
+        object.into()
+    }
+}
+
+impl JsMatcher {
+    /// Given a candidate, return what each token of the pattern matched in
+    /// it, greedily, from the start.
+    pub fn matches(&self, candidate: &str) -> Vec<Match> {
+        let mut matcher = Matcher::from_owned_tokens(&self.pattern, &self.tokens);
+        let mut start = 0;
+        let mut matches = Vec::new();
+        for (token_index, (token, text)) in matcher.match_string(candidate).into_iter().enumerate()
+        {
+            let end = start + text.encode_utf16().count();
+            matches.push(Match {
+                token_index,
+                token_kind: kind(token),
+                text: text.to_string(),
+                start,
+                end,
+            });
+            start = end;
+        }
+        matches
+    }
+}
+
+/// Given a token, return the name of its kind.
+fn kind(token: &MatcherToken) -> &'static str {
+    match token {
+        MatcherToken::RawText(_) => "RawText",
+        MatcherToken::OneOfText(_) => "OneOfText",
+        MatcherToken::WildCard => "WildCard",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn offsets_count_utf16() {
+        assert_eq!(
+            parse("(a|💪).c").unwrap().matches("💪bcd"),
+            [
+                Match {
+                    token_index: 0,
+                    token_kind: "OneOfText",
+                    text: "💪".to_string(),
+                    start: 0,
+                    end: 2,
+                },
+                Match {
+                    token_index: 1,
+                    token_kind: "WildCard",
+                    text: "b".to_string(),
+                    start: 2,
+                    end: 3,
+                },
+                Match {
+                    token_index: 2,
+                    token_kind: "RawText",
+                    text: "c".to_string(),
+                    start: 3,
+                    end: 4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn partial_matches_stop_early() {
+        let matched = parse("(Black|Bridge)(rock|stone|water).company")
+            .unwrap()
+            .matches("Bridgestone_Tyres");
+        let texts: Vec<&str> = matched.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, ["Bridge", "stone", "_"]);
+    }
+
+    #[test]
+    fn parse_checks_the_pattern() {
+        assert_eq!(
+            parse("a.").map(|matcher| matcher.pattern()),
+            Ok("a.".to_string())
+        );
+        assert_eq!(
+            parse("(a|").err(),
+            Some("`(a|` isn't a pattern: this group isn't closed (at byte 0)".to_string())
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! These run the matcher as JavaScript sees it, with
+//! `wasm-pack test --node matcher-wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, Reflect};
+use matcher_wasm::parse;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Given what `match` returned, return each match's fields, in order.
+fn fields(matched: JsValue) -> Vec<(f64, String, String, f64, f64)> {
+    let get = |object: &JsValue, key: &str| Reflect::get(object, &JsValue::from(key)).unwrap();
+    Array::from(&matched)
+        .iter()
+        .map(|object| {
+            (
+                get(&object, "tokenIndex").as_f64().unwrap(),
+                get(&object, "tokenKind").as_string().unwrap(),
+                get(&object, "text").as_string().unwrap(),
+                get(&object, "start").as_f64().unwrap(),
+                get(&object, "end").as_f64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn complete_match() {
+    let matcher = parse("(Black|Bridge)(rock|stone).company").unwrap();
+    assert_eq!(
+        fields(matcher.match_candidate("Blackrock💪company")),
+        [
+            (0.0, "OneOfText".to_string(), "Black".to_string(), 0.0, 5.0),
+            (1.0, "OneOfText".to_string(), "rock".to_string(), 5.0, 9.0),
+            (2.0, "WildCard".to_string(), "💪".to_string(), 9.0, 11.0),
+            (
+                3.0,
+                "RawText".to_string(),
+                "company".to_string(),
+                11.0,
+                18.0
+            ),
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn partial_match() {
+    let matcher = parse("(Black|Bridge)(rock|stone).company").unwrap();
+    assert_eq!(
+        fields(matcher.match_candidate("BlackBridge")),
+        [(0.0, "OneOfText".to_string(), "Black".to_string(), 0.0, 5.0)]
+    );
+    assert!(parse("(Black|").is_err());
+}
@@ -0,0 +1,119 @@
+//! This takes the part of a file between anchors, which are comments like
+//! `// ANCHOR: name` and `// ANCHOR_END: name`, the way mdbook's own
+//! `{{#include}}` does.
+
+/// Which end of an anchored part a marker is.
+#[derive(Debug, PartialEq, Eq)]
+enum Marker<'line> {
+    Start(&'line str),
+    End(&'line str),
+}
+
+/// Given a line, return the anchor it marks, if it's a marker.
+fn marker(line: &str) -> Option<Marker<'_>> {
+    let comment = line.trim().strip_prefix("//")?.trim_start();
+    match comment.strip_prefix("ANCHOR:") {
+        Some(name) => Some(Marker::Start(name.trim())),
+        None => Some(Marker::End(comment.strip_prefix("ANCHOR_END:")?.trim())),
+    }
+}
+
+/// Given a file's contents, return all of it but the markers.
+pub fn whole(source: &str) -> String {
+    let lines: Vec<&str> = source
+        .lines()
+        .filter(|line| marker(line).is_none())
+        .collect();
+    lines.join("\n")
+}
+
+/// Given a file's contents and the name of an anchor in it, return the lines
+/// between its markers, leaving out any other markers among them, and the
+/// indentation they all share.
+pub fn anchored(source: &str, name: &str) -> Result<String, String> {
+    let mut lines = source.lines();
+    if !lines.any(|line| marker(line) == Some(Marker::Start(name))) {
+        return Err(format!("there's no `// ANCHOR: {name}`"));
+    }
+
+    let mut anchored = Vec::new();
+    for line in lines {
+        match marker(line) {
+            Some(Marker::End(end)) if end == name => return Ok(dedent(&anchored)),
+            Some(_) => {}
+            None => anchored.push(line),
+        }
+    }
+    Err(format!("there's no `// ANCHOR_END: {name}`"))
+}
+
+/// Given some lines, take the indentation they all share off the start of
+/// them, and join them up. Blank lines don't count.
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: &str = "\
+impl Matcher {
+    // ANCHOR: new
+    pub fn new() -> Matcher {
+        // ANCHOR: body
+        Matcher
+
+        // ANCHOR_END: body
+    }
+    // ANCHOR_END: new
+}
+";
+
+    #[test]
+    fn takes_the_anchored_lines() {
+        assert_eq!(
+            anchored(SOURCE, "new").unwrap(),
+            "pub fn new() -> Matcher {\n    Matcher\n\n}"
+        );
+        assert_eq!(anchored(SOURCE, "body").unwrap(), "Matcher\n");
+    }
+
+    #[test]
+    fn whole_file_leaves_out_markers() {
+        assert_eq!(
+            whole(SOURCE),
+            "impl Matcher {\n    pub fn new() -> Matcher {\n        Matcher\n\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn missing_anchors_are_errors() {
+        assert_eq!(
+            anchored(SOURCE, "match_string"),
+            Err("there's no `// ANCHOR: match_string`".to_string())
+        );
+        // `new` is only a prefix of `new_with`, so it's not that anchor.
+        assert!(anchored("// ANCHOR: new_with\n// ANCHOR_END: new_with\n", "new").is_err());
+        assert_eq!(
+            anchored("// ANCHOR: new\nfn new() {}\n", "new"),
+            Err("there's no `// ANCHOR_END: new`".to_string())
+        );
+    }
+
+    #[test]
+    fn markers_can_be_spaced_out() {
+        let source = "fn a() {}\n//ANCHOR:  b\nfn b() {}\n  //  ANCHOR_END: b  \n";
+        assert_eq!(anchored(source, "b").unwrap(), "fn b() {}");
+    }
+}
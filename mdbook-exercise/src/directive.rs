@@ -0,0 +1,171 @@
+//! This finds the `{{#exercise PATH}}` and `{{#exercise PATH anchor=NAME}}`
+//! directives in a chapter, and replaces each with what's in that file now
+//! (or the part of it between the anchors called `NAME`). As with mdbook's
+//! own directives, a `\` before one leaves it as it is, without the `\`.
+
+use crate::anchor;
+use std::path::Path;
+
+/// How a directive starts.
+const START: &str = "{{#exercise";
+
+/// How a directive ends.
+const END: &str = "}}";
+
+/// What a directive asks for.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Directive<'chapter> {
+    /// The file, from the directory of chapters.
+    pub path: &'chapter str,
+    /// The anchor to take, or `None` for the whole file.
+    pub anchor: Option<&'chapter str>,
+}
+
+/// Given what's between `{{#exercise` and `}}`, return what it asks for.
+pub fn parse(arguments: &str) -> Result<Directive<'_>, String> {
+    let mut words = arguments.split_whitespace();
+    let Some(path) = words.next() else {
+        return Err("`{{#exercise}}` needs a path".to_string());
+    };
+    let mut anchor = None;
+    for word in words {
+        match word.strip_prefix("anchor=") {
+            Some(name) if !name.is_empty() && anchor.is_none() => anchor = Some(name),
+            Some(_) if anchor.is_some() => {
+                return Err(format!("`{{{{#exercise {path}}}}}` has two anchors"))
+            }
+            _ => return Err(format!("`{{{{#exercise {path}}}}}` doesn't know `{word}`")),
+        }
+    }
+    Ok(Directive { path, anchor })
+}
+
+/// Given a chapter's contents and the directory of chapters, return it with
+/// each directive replaced by what it asks for.
+pub fn expand(content: &str, exercises: &Path) -> Result<String, String> {
+    let mut expanded = String::new();
+    let mut rest = content;
+    while let Some(start) = rest.find(START) {
+        let after = &rest[start + START.len()..];
+        if let Some(before) = rest[..start].strip_suffix('\\') {
+            expanded.push_str(before);
+            expanded.push_str(START);
+            rest = after;
+            continue;
+        }
+        expanded.push_str(&rest[..start]);
+        // Something like `{{#exercises}}` isn't this directive.
+        if !after.starts_with(char::is_whitespace) && !after.starts_with(END) {
+            expanded.push_str(START);
+            rest = after;
+            continue;
+        }
+        let Some(end) = after.find(END) else {
+            return Err(format!("`{START}` has no `{END}` after it"));
+        };
+        expanded.push_str(&contents(&parse(&after[..end])?, exercises)?);
+        rest = &after[end + END.len()..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Given a directive and the directory of chapters, return what it asks for.
+fn contents(directive: &Directive<'_>, exercises: &Path) -> Result<String, String> {
+    let path = exercises.join(directive.path);
+    let source = std::fs::read_to_string(&path)
+        .map_err(|error| format!("couldn't read {}: {error}", path.display()))?;
+    match directive.anchor {
+        Some(name) => anchor::anchored(&source, name)
+            .map_err(|error| format!("in {}, {error}", path.display())),
+        None => Ok(anchor::whole(&source)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_a_path() {
+        assert_eq!(
+            parse(" 08_finale/exercise/src/lib.rs "),
+            Ok(Directive {
+                path: "08_finale/exercise/src/lib.rs",
+                anchor: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_an_anchor() {
+        assert_eq!(
+            parse(" 08_finale/exercise/src/lib.rs  anchor=matcher_new"),
+            Ok(Directive {
+                path: "08_finale/exercise/src/lib.rs",
+                anchor: Some("matcher_new"),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_what_it_doesn_t_know() {
+        assert_eq!(parse(""), Err("`{{#exercise}}` needs a path".to_string()));
+        assert_eq!(
+            parse(" lib.rs anchor=a anchor=b"),
+            Err("`{{#exercise lib.rs}}` has two anchors".to_string())
+        );
+        assert_eq!(
+            parse(" lib.rs anchor="),
+            Err("`{{#exercise lib.rs}}` doesn't know `anchor=`".to_string())
+        );
+        assert_eq!(
+            parse(" lib.rs lines=1:3"),
+            Err("`{{#exercise lib.rs}}` doesn't know `lines=1:3`".to_string())
+        );
+    }
+
+    #[test]
+    fn expands_directives() {
+        let exercises =
+            std::env::temp_dir().join(format!("mdbook-exercise-expand-{}", std::process::id()));
+        std::fs::create_dir_all(&exercises).unwrap();
+        std::fs::write(
+            exercises.join("lib.rs"),
+            "// ANCHOR: answer\nfn answer() -> u32 {\n    42\n}\n// ANCHOR_END: answer\n",
+        )
+        .unwrap();
+
+        let chapter = "Before\n\n```rust\n{{#exercise lib.rs anchor=answer}}\n```\n\nAfter\n";
+        assert_eq!(
+            expand(chapter, &exercises).unwrap(),
+            "Before\n\n```rust\nfn answer() -> u32 {\n    42\n}\n```\n\nAfter\n"
+        );
+        assert_eq!(
+            expand("{{#exercise lib.rs}}", &exercises).unwrap(),
+            "fn answer() -> u32 {\n    42\n}"
+        );
+        // Anything else in `{{` and `}}` is left for other preprocessors.
+        assert_eq!(
+            expand("{{#include lib.rs}}", &exercises).unwrap(),
+            "{{#include lib.rs}}"
+        );
+        assert_eq!(
+            expand("{{#exercises lib.rs}}", &exercises).unwrap(),
+            "{{#exercises lib.rs}}"
+        );
+
+        assert_eq!(
+            expand("`\\{{#exercise lib.rs}}`", &exercises).unwrap(),
+            "`{{#exercise lib.rs}}`"
+        );
+
+        let missing = expand("{{#exercise lib.rs anchor=question}}", &exercises).unwrap_err();
+        assert!(
+            missing.ends_with("there's no `// ANCHOR: question`"),
+            "{missing}"
+        );
+        assert!(expand("{{#exercise missing.rs}}", &exercises).is_err());
+        assert!(expand("{{#exercise lib.rs", &exercises).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! An mdbook preprocessor, so the book can quote the exercises as they are,
+//! rather than copies that drift from them. It replaces
+//! `{{#exercise 08_finale/exercise/src/lib.rs anchor=matcher_new}}` with
+//! the lines of that file between `// ANCHOR: matcher_new` and
+//! `// ANCHOR_END: matcher_new`, and fails the build if they aren't there.
+//!
+//! mdbook runs it as `mdbook-exercise supports RENDERER`, to ask whether it
+//! works with that renderer (it works with all of them), and then with the
+//! book as JSON on stdin, to be written back out to stdout.
+
+mod anchor;
+mod directive;
+
+use serde_json::Value;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+/// Where the chapters are, from the book's root, unless
+/// `[preprocessor.exercise]` in `book.toml` sets `exercises`.
+const EXERCISES: &str = "../exercises";
+
+fn main() {
+    if std::env::args().nth(1).as_deref() == Some("supports") {
+        return;
+    }
+    if let Err(message) = run() {
+        eprintln!("error: {message}");
+        exit(1);
+    }
+}
+
+/// Read the context and the book from stdin, and write the book out again,
+/// with every directive in it expanded.
+fn run() -> Result<(), String> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|error| format!("couldn't read the book: {error}"))?;
+    let (context, mut book): (Value, Value) = serde_json::from_str(&input)
+        .map_err(|error| format!("couldn't parse the book: {error}"))?;
+
+    expand_all(&mut book, &exercises(&context))?;
+    serde_json::to_writer(std::io::stdout(), &book)
+        .map_err(|error| format!("couldn't write the book: {error}"))
+}
+
+/// Given the context mdbook passes in, return the directory of chapters.
+fn exercises(context: &Value) -> PathBuf {
+    let root = Path::new(context["root"].as_str().unwrap_or("."));
+    let exercises = context["config"]["preprocessor"]["exercise"]["exercises"]
+        .as_str()
+        .unwrap_or(EXERCISES);
+    root.join(exercises)
+}
+
+/// Given some of the book (all of it, to start with) and the directory of
+/// chapters, expand the directives in every chapter in it, including the
+/// ones nested in other chapters.
+fn expand_all(book: &mut Value, exercises: &Path) -> Result<(), String> {
+    match book {
+        Value::Object(fields) => {
+            if let Some(chapter) = fields.get_mut("Chapter") {
+                let name = chapter["name"].as_str().unwrap_or("a chapter").to_string();
+                if let Some(Value::String(content)) = chapter.get_mut("content") {
+                    *content = directive::expand(content, exercises)
+                        .map_err(|error| format!("in {name}: {error}"))?;
+                }
+            }
+            fields
+                .values_mut()
+                .try_for_each(|value| expand_all(value, exercises))
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .try_for_each(|value| expand_all(value, exercises)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Return the workspace's root, which is the directory above this crate.
+    fn workspace_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("mdbook-exercise is in the workspace")
+            .to_path_buf()
+    }
+
+    #[test]
+    fn expands_nested_chapters() {
+        let exercises = workspace_root().join("exercises");
+        let mut book = serde_json::json!({
+            "sections": [
+                {"Chapter": {
+                    "name": "Outer",
+                    "content": "no directives",
+                    "sub_items": [
+                        {"Chapter": {
+                            "name": "Inner",
+                            "content": "{{#exercise 08_finale/exercise/src/lib.rs anchor=matcher_new}}",
+                            "sub_items": [],
+                        }},
+                    ],
+                }},
+                "Separator",
+            ],
+        });
+        expand_all(&mut book, &exercises).unwrap();
+        let inner = book["sections"][0]["Chapter"]["sub_items"][0]["Chapter"]["content"]
+            .as_str()
+            .unwrap();
+        assert!(
+            inner.contains("pub fn new(text: &'internal str)"),
+            "{inner}"
+        );
+        assert_eq!(book["sections"][0]["Chapter"]["content"], "no directives");
+
+        let mut book = serde_json::json!({
+            "sections": [{"Chapter": {
+                "name": "Broken",
+                "content": "{{#exercise 08_finale/exercise/src/lib.rs anchor=missing}}",
+                "sub_items": [],
+            }}],
+        });
+        let error = expand_all(&mut book, &exercises).unwrap_err();
+        assert!(error.starts_with("in Broken: in "), "{error}");
+    }
+
+    #[test]
+    fn finds_the_exercises_from_the_book() {
+        let context = serde_json::json!({"root": "/kata/book", "config": {}});
+        assert_eq!(exercises(&context), Path::new("/kata/book/../exercises"));
+        let context = serde_json::json!({
+            "root": "/kata/book",
+            "config": {"preprocessor": {"exercise": {"exercises": "chapters"}}},
+        });
+        assert_eq!(exercises(&context), Path::new("/kata/book/chapters"));
+    }
+
+    /// Every directive in the book has to expand, so a renamed file or a
+    /// removed anchor is caught here, and not only when the book is built.
+    #[test]
+    fn book_expands() {
+        let root = workspace_root();
+        let chapters = std::fs::read_dir(root.join("book/src")).unwrap();
+        for chapter in chapters {
+            let path = chapter.unwrap().path();
+            if path.extension().is_some_and(|extension| extension == "md") {
+                let content = std::fs::read_to_string(&path).unwrap();
+                if let Err(error) = directive::expand(&content, &root.join("exercises")) {
+                    panic!("{}: {error}", path.display());
+                }
+            }
+        }
+    }
+}
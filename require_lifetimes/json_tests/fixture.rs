@@ -0,0 +1,11 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+pub fn first(text: &str) -> &str {
+    text
+}
+
+#[require_lifetimes]
+pub fn unused<'a, 'b>(text: &'a str) -> &'a str {
+    text
+}
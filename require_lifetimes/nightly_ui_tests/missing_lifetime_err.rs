@@ -0,0 +1,13 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn identity(a: &i32) -> &i32 {
+    a
+}
+
+#[require_lifetimes]
+fn first<'a, 'b>(text: &'a str) -> &'a str {
+    text
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+struct Pair<'a, 'b> {
+    first: &'a str,
+    second: &str,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(warn)]
+fn identity(a: &i32) -> &i32 {
+    a
+}
+
+fn main() {
+    assert_eq!(identity(&1), &1);
+}
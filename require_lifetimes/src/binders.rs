@@ -0,0 +1,56 @@
+use syn::visit::Visit;
+
+/// This keeps track of the lifetimes declared by the `for<...>`s around the
+/// part of a type being visited, like `'x` in `for<'x> Fn(&'x str)`. Those
+/// belong to the bound they're on, not to the item being checked.
+#[derive(Default)]
+pub struct Binders {
+    names: Vec<String>,
+}
+
+impl Binders {
+    /// Given the `for<...>` being visited (if there is one), add its
+    /// lifetimes, and return what to pass to `leave` once it's been visited.
+    pub fn enter(&mut self, lifetimes: Option<&syn::BoundLifetimes>) -> usize {
+        let depth = self.names.len();
+        self.names.extend(
+            lifetimes
+                .into_iter()
+                .flat_map(|lifetimes| &lifetimes.lifetimes)
+                .map(|definition| definition.lifetime.ident.to_string()),
+        );
+        depth
+    }
+
+    /// Given what `enter` returned, forget the lifetimes it added.
+    pub fn leave(&mut self, depth: usize) {
+        self.names.truncate(depth);
+    }
+
+    /// Given a lifetime, return whether a `for<...>` around it declares it.
+    pub fn contains(&self, lifetime: &syn::Lifetime) -> bool {
+        self.names.contains(&lifetime.ident.to_string())
+    }
+}
+
+/// Given a function's signature, return the name of every lifetime that a
+/// `for<...>` anywhere in it declares.
+pub fn binder_names(signature: &syn::Signature) -> Vec<String> {
+    let mut names = BinderNames::default();
+    names.visit_signature(signature);
+    names.0
+}
+
+#[derive(Default)]
+struct BinderNames(Vec<String>);
+
+impl<'ast> Visit<'ast> for BinderNames {
+    fn visit_bound_lifetimes(&mut self, lifetimes: &'ast syn::BoundLifetimes) {
+        self.0.extend(
+            lifetimes
+                .lifetimes
+                .iter()
+                .map(|definition| definition.lifetime.ident.to_string()),
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::binders::Binders;
+use crate::report::{Kind, Violation};
+use syn::visit::Visit;
+
+/// Given a function's signature, the generics of the `impl` or `trait` it's
+/// in (if any), and how to refer to it, return an error for every pair of
+/// lifetimes its return type needs to outlive each other but that isn't
+/// written as a bound.
+///
+/// This only looks at references and `impl Trait + 'a` in the return type,
+/// and only at bounds written on the function or the item it's in, so it's
+/// a syntactic check rather than the compiler's own.
+pub fn bound_errors(
+    signature: &syn::Signature,
+    outer_generics: Option<&syn::Generics>,
+    location: &str,
+) -> Vec<Violation> {
+    let mut declared = Vec::new();
+    for generics in outer_generics.into_iter().chain([&signature.generics]) {
+        for definition in generics.lifetimes() {
+            for bound in &definition.bounds {
+                declared.push((
+                    definition.lifetime.ident.to_string(),
+                    bound.ident.to_string(),
+                ));
+            }
+        }
+        for predicate in generics
+            .where_clause
+            .iter()
+            .flat_map(|clause| &clause.predicates)
+        {
+            if let syn::WherePredicate::Lifetime(predicate) = predicate {
+                for bound in &predicate.bounds {
+                    declared.push((
+                        predicate.lifetime.ident.to_string(),
+                        bound.ident.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut finder = CouplingFinder {
+        declared,
+        errors: Vec::new(),
+        location,
+        binders: Binders::default(),
+    };
+    if let syn::ReturnType::Type(_, output) = &signature.output {
+        finder.visit_type(output);
+    }
+    finder.errors
+}
+
+/// This finds the places in a return type where one lifetime has to outlive
+/// another, and records an error for each one that isn't declared.
+struct CouplingFinder<'a> {
+    /// Every bound that's written, as `(longer, shorter)`.
+    declared: Vec<(String, String)>,
+    errors: Vec<Violation>,
+    location: &'a str,
+    /// A reference whose lifetime a `for<...>` declares is only there for
+    /// as long as the bound it's in holds, so it isn't checked.
+    binders: Binders,
+}
+
+impl CouplingFinder<'_> {
+    /// Given two lifetimes, return whether the written bounds say the first
+    /// outlives the second.
+    fn outlives(&self, longer: &str, shorter: &str) -> bool {
+        let mut reached = vec![longer];
+        let mut index = 0;
+        while let Some(&lifetime) = reached.get(index) {
+            if lifetime == shorter || lifetime == "static" {
+                return true;
+            }
+            for (from, to) in &self.declared {
+                if from == lifetime && !reached.contains(&to.as_str()) {
+                    reached.push(to);
+                }
+            }
+            index += 1;
+        }
+        false
+    }
+
+    /// Given a part of the return type that needs every lifetime in
+    /// `inner` to outlive `shorter`, record an error for each that isn't
+    /// declared to.
+    fn require<'b>(
+        &mut self,
+        node: &dyn quote::ToTokens,
+        shorter: &syn::Lifetime,
+        inner: impl IntoIterator<Item = &'b syn::Lifetime>,
+    ) {
+        let shorter_name = shorter.ident.to_string();
+        if shorter_name == "_" || shorter_name == "static" {
+            return;
+        }
+
+        let mut reported = Vec::new();
+        for longer in inner {
+            let longer_name = longer.ident.to_string();
+            if longer_name == "_" || reported.contains(&longer_name) {
+                continue;
+            }
+            if !self.outlives(&longer_name, &shorter_name) {
+                self.errors.push(Violation::new(
+                    Kind::MissingBound,
+                    node,
+                    format!(
+                        "In {}, the return type needs `{longer}` to outlive `{shorter}`. This \
+                         exercise wants that written down, rather than left for the compiler \
+                         to work out: add `{longer}: {shorter}` to the generics or a where \
+                         clause.",
+                        self.location
+                    ),
+                ));
+            }
+            reported.push(longer_name);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for CouplingFinder<'_> {
+    fn visit_type_reference(&mut self, reference: &'ast syn::TypeReference) {
+        if let Some(shorter) = reference
+            .lifetime
+            .as_ref()
+            .filter(|lifetime| !self.binders.contains(lifetime))
+        {
+            let mut inner = LifetimeCollector::default();
+            inner.visit_type(&reference.elem);
+            self.require(reference, shorter, &inner.lifetimes);
+        }
+
+        syn::visit::visit_type_reference(self, reference);
+    }
+
+    fn visit_type_impl_trait(&mut self, impl_trait: &'ast syn::TypeImplTrait) {
+        let mut inner = LifetimeCollector::default();
+        for bound in &impl_trait.bounds {
+            if let syn::TypeParamBound::Trait(bound) = bound {
+                inner.visit_trait_bound(bound);
+            }
+        }
+        for bound in &impl_trait.bounds {
+            if let syn::TypeParamBound::Lifetime(shorter) = bound {
+                self.require(impl_trait, shorter, &inner.lifetimes);
+            }
+        }
+
+        syn::visit::visit_type_impl_trait(self, impl_trait);
+    }
+
+    fn visit_trait_bound(&mut self, bound: &'ast syn::TraitBound) {
+        let depth = self.binders.enter(bound.lifetimes.as_ref());
+        syn::visit::visit_trait_bound(self, bound);
+        self.binders.leave(depth);
+    }
+
+    fn visit_type_bare_fn(&mut self, bare_fn: &'ast syn::TypeBareFn) {
+        let depth = self.binders.enter(bare_fn.lifetimes.as_ref());
+        syn::visit::visit_type_bare_fn(self, bare_fn);
+        self.binders.leave(depth);
+    }
+}
+
+/// This collects every lifetime in what it visits, except the ones a
+/// `for<...>` in it declares.
+#[derive(Default)]
+pub struct LifetimeCollector {
+    pub lifetimes: Vec<syn::Lifetime>,
+    binders: Binders,
+}
+
+impl<'ast> Visit<'ast> for LifetimeCollector {
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        if !self.binders.contains(lifetime) {
+            self.lifetimes.push(lifetime.clone());
+        }
+    }
+
+    fn visit_bound_lifetimes(&mut self, _: &'ast syn::BoundLifetimes) {}
+
+    fn visit_trait_bound(&mut self, bound: &'ast syn::TraitBound) {
+        let depth = self.binders.enter(bound.lifetimes.as_ref());
+        syn::visit::visit_trait_bound(self, bound);
+        self.binders.leave(depth);
+    }
+
+    fn visit_type_bare_fn(&mut self, bare_fn: &'ast syn::TypeBareFn) {
+        let depth = self.binders.enter(bare_fn.lifetimes.as_ref());
+        syn::visit::visit_type_bare_fn(self, bare_fn);
+        self.binders.leave(depth);
+    }
+
+    fn visit_predicate_type(&mut self, predicate: &'ast syn::PredicateType) {
+        let depth = self.binders.enter(predicate.lifetimes.as_ref());
+        syn::visit::visit_predicate_type(self, predicate);
+        self.binders.leave(depth);
+    }
+}
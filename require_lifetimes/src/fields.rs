@@ -0,0 +1,166 @@
+use crate::binders::Binders;
+use crate::report::{Findings, Kind, Violation};
+use syn::visit::Visit;
+
+/// Given a struct or enum's name and generics, and every variant it has
+/// (with its name, if it's an enum's), find an error for every lifetime
+/// in its fields that's missing or isn't one of its parameters, and for
+/// every lifetime parameter that no field uses. Fields can only borrow for
+/// `'static` if `allow_static` is set.
+pub fn definition_errors<'a>(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    variants: impl IntoIterator<Item = (Option<&'a syn::Ident>, &'a syn::Fields)>,
+    allow_static: bool,
+) -> Findings {
+    let mut visitor = FieldVisitor::new(generics, allow_static, "a field");
+    for (variant, fields) in variants {
+        visitor.location = match variant {
+            Some(variant) => format!("`{name}::{variant}`"),
+            None => format!("`{name}`"),
+        };
+        for field in fields {
+            visitor.visit_type(&field.ty);
+        }
+    }
+
+    visitor.finish(name, generics, "no field uses")
+}
+
+/// Given a type alias, check the type it stands for the same way as a
+/// struct's fields. The alias's lifetimes can't be seen from where it's
+/// used, so this is the only place they get checked.
+pub fn alias_errors(alias: &syn::ItemType, allow_static: bool) -> Findings {
+    let mut visitor = FieldVisitor::new(&alias.generics, allow_static, "the aliased type");
+    visitor.location = format!("`{}`", alias.ident);
+    visitor.visit_type(&alias.ty);
+
+    visitor.finish(
+        &alias.ident,
+        &alias.generics,
+        "the aliased type doesn't use",
+    )
+}
+
+/// This checks every lifetime in the types of a definition's fields, or
+/// in the type an alias stands for.
+struct FieldVisitor {
+    /// The type's lifetime parameters.
+    declared: Vec<String>,
+    /// The lifetimes introduced by the `for<'x>`s around the field being
+    /// visited, which can be used too.
+    binders: Binders,
+    used: Vec<String>,
+    allow_static: bool,
+    /// How the errors should refer to what's being checked, like `a field`.
+    part: &'static str,
+    /// How the errors should refer to the variant being checked.
+    location: String,
+    errors: Vec<Violation>,
+}
+
+impl FieldVisitor {
+    fn new(generics: &syn::Generics, allow_static: bool, part: &'static str) -> FieldVisitor {
+        FieldVisitor {
+            declared: generics
+                .lifetimes()
+                .map(|definition| definition.lifetime.ident.to_string())
+                .collect(),
+            binders: Binders::default(),
+            used: Vec::new(),
+            allow_static,
+            part,
+            location: String::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Given the type's name and generics, and how to say that nothing
+    /// uses a lifetime (like `no field uses`), add an error for every
+    /// lifetime parameter that wasn't used, and return all the errors.
+    fn finish(mut self, name: &syn::Ident, generics: &syn::Generics, unused: &str) -> Findings {
+        for definition in generics.lifetimes() {
+            let lifetime = &definition.lifetime;
+            if !self.used.contains(&lifetime.ident.to_string()) {
+                self.errors.push(Violation::new(
+                    Kind::UnusedLifetime,
+                    lifetime,
+                    format!(
+                        "In `{name}`, {unused} the lifetime parameter `{lifetime}`, so it can \
+                         be removed."
+                    ),
+                ));
+            }
+        }
+
+        Findings::for_type(name, self.errors)
+    }
+}
+
+impl<'ast> Visit<'ast> for FieldVisitor {
+    fn visit_type_reference(&mut self, reference: &'ast syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            self.errors.push(Violation::new(
+                Kind::MissingLifetime,
+                reference,
+                format!(
+                    "In {}, every reference in {} needs a lifetime, and it has to be one of \
+                     the type's lifetime parameters.",
+                    self.location, self.part
+                ),
+            ));
+        }
+
+        syn::visit::visit_type_reference(self, reference);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        if self.binders.contains(lifetime) {
+            return;
+        }
+        let name = lifetime.ident.to_string();
+        if name == "static" {
+            if !self.allow_static {
+                self.errors.push(Violation::new(
+                    Kind::StaticField,
+                    lifetime,
+                    format!(
+                        "In {}, {} can't borrow for `'static`: this exercise wants the type \
+                         to take a lifetime parameter instead. Pass `allow_static` to allow it.",
+                        self.location, self.part
+                    ),
+                ));
+            }
+        } else if self.declared.contains(&name) {
+            self.used.push(name);
+        } else {
+            self.errors.push(Violation::new(
+                Kind::UndeclaredLifetime,
+                lifetime,
+                format!(
+                    "In {}, `{lifetime}` isn't one of the type's lifetime parameters. Every \
+                     lifetime {} uses has to be declared in the `<...>` after the type's name.",
+                    self.location, self.part
+                ),
+            ));
+        }
+    }
+
+    fn visit_bound_lifetimes(&mut self, _: &'ast syn::BoundLifetimes) {}
+
+    fn visit_trait_bound(&mut self, bound: &'ast syn::TraitBound) {
+        let depth = self.binders.enter(bound.lifetimes.as_ref());
+        syn::visit::visit_trait_bound(self, bound);
+        self.binders.leave(depth);
+    }
+
+    // References in function types can leave their lifetimes out, since
+    // those belong to each call rather than to the type.
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments(
+        &mut self,
+        _: &'ast syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
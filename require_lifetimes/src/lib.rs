@@ -0,0 +1,1006 @@
+#![cfg_attr(feature = "nightly-diagnostics", feature(proc_macro_diagnostic))]
+
+mod binders;
+mod bounds;
+mod fields;
+mod module;
+mod names;
+mod nested;
+mod report;
+mod suggestion;
+mod type_visitor;
+mod unused;
+#[cfg(not(feature = "nightly-diagnostics"))]
+mod warning;
+use bounds::bound_errors;
+use fields::{alias_errors, definition_errors};
+use module::annotate_module_items;
+use names::short_name_errors;
+use nested::NestedItems;
+use report::{Findings, Kind};
+use suggestion::{elision_note, suggestion_error};
+use type_visitor::TypeVisitor;
+use unused::unused_lifetime_errors;
+
+use quote::ToTokens;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
+use syn::{ForeignItem, ImplItem, Item, TraitItem};
+
+/// This attribute allows you to require that a function have
+/// all lifetimes in its input and output be present.
+/// A missing lifetime will cause a compiler error.
+///
+/// ## Examples
+///
+///
+/// This code compiles, as all required lifetimes are present:
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// #[require_lifetimes]
+/// fn identity<'a>(a: &'a i32) -> &'a i32 {
+///     a
+/// }
+/// ```
+///
+/// This code, however doesn't. Note that if the lifetimes
+/// aren't there, by default this macro outputs the exact same code.
+/// This means that the user may get further errors based on that
+/// code. To force the macro to output nothing, provide `!` as
+/// an argument to the macro invocation.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+/// // This fails to compile, even though lifetime elision
+/// // means it should be fine.
+///
+/// #[require_lifetimes(!)]
+/// fn identity(a: &i32) -> &i32 {
+///     a
+/// }
+/// ```
+///
+/// In some circumstances, you may want to replace the item
+/// that's missing lifetimes. To do that, you can put code
+/// in brackets after the macro.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+/// // This fails to compile, and will print an error for
+/// // both the missing lifetimes, and for the `compile_error!`
+/// // macro.
+///
+/// #[require_lifetimes(compile_error!("identity function was removed."))]
+/// fn identity(a: &i32) -> &i32 {
+///     a
+/// }
+/// ```
+///
+/// Functions defined inside a function's body, and the methods of `impl`
+/// blocks there, are checked too, unless they have their own
+/// `#[require_lifetimes]` (which could be `#[require_lifetimes(skip)]`). To
+/// only check the outer function, pass `skip_nested`.
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// #[require_lifetimes(skip_nested)]
+/// fn trimmed<'a>(text: &'a str) -> &'a str {
+///     fn helper(text: &str) -> &str {
+///         text.trim()
+///     }
+///     helper(text)
+/// }
+/// ```
+///
+/// An `async fn` is checked the same way. Only the references written in
+/// its signature need lifetimes, not the future it returns, since that
+/// captures them without ever naming a lifetime.
+///
+/// An `unsafe fn`, `const fn` or `extern "C" fn` is checked the same way
+/// too, and comes out with its qualifiers as they were. The attribute can
+/// also go on an `extern` block, which checks every function declared in
+/// it. A C-style variadic `...` there is left alone.
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// #[require_lifetimes]
+/// extern "C" {
+///     fn printf(format: *const std::ffi::c_char, ...) -> std::ffi::c_int;
+///     fn first<'a>(values: &'a [i32; 2]) -> &'a i32;
+/// }
+/// ```
+///
+/// The attribute can also go on an `impl` block, which checks every method
+/// in it the same way. A receiver that borrows `self`, whether it's written
+/// `&self`, `&mut self` or `self: Pin<&mut Self>`, needs a lifetime like any
+/// other reference. The `impl`'s own lifetimes can be used in its methods
+/// as they are, and `Self` (or `Self::Assoc`) needs nothing more written,
+/// since its lifetimes are written in the `impl` header (or wherever the
+/// associated type is defined). A method can opt out with
+/// `#[require_lifetimes(skip)]`, and a method with its own
+/// `#[require_lifetimes]` is left to that one.
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// struct Wrapper(i32);
+///
+/// #[require_lifetimes]
+/// impl Wrapper {
+///     fn pick<'a, 'b>(&'a self, other: &'b i32) -> &'a i32 {
+///         &self.0
+///     }
+///
+///     #[require_lifetimes(skip)]
+///     fn elided(&self, other: &i32) -> &i32 {
+///         &self.0
+///     }
+/// }
+/// ```
+///
+/// On a `trait`, every method is checked, whether or not it has a default
+/// body. Associated types and consts are left alone.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// // This fails to compile, since `&self` needs a lifetime.
+/// #[require_lifetimes]
+/// trait Named {
+///     fn name<'a>(&self) -> &'a str;
+/// }
+/// ```
+///
+/// If a function takes references, every `dyn Trait` in its signature has
+/// to say how long it lives, as in `Box<dyn Trait + 'a>` or
+/// `&'a (dyn Trait + 'static)`. Otherwise it's `'static` in a `Box`, and the
+/// reference's lifetime behind one, which is easy to miss.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// // This fails to compile, since the `dyn Fn` needs a bound.
+/// #[require_lifetimes]
+/// fn store<'a>(
+///     callbacks: &'a mut Vec<Box<dyn for<'x> Fn(&'x str) -> bool>>,
+///     callback: Box<dyn for<'x> Fn(&'x str) -> bool>,
+/// ) {
+///     callbacks.push(callback);
+/// }
+/// ```
+///
+/// References inside a function pointer or an `Fn` trait, like
+/// `fn(&str) -> &str` or `impl Fn(&str) -> &str`, can leave their lifetimes
+/// out. Those lifetimes belong to each call of the function that's passed
+/// in, not to the function being checked, so they don't change what its
+/// signature borrows. Writing them out with `for<'x>` is fine too.
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// #[require_lifetimes]
+/// fn apply<'a>(function: impl Fn(&str) -> &str, text: &'a str) -> &'a str {
+///     function(text)
+/// }
+/// ```
+///
+/// Writing `'_` on a reference is always an error, but elsewhere it's
+/// allowed, so `Token<'_>` or `impl Trait + '_` get through. To reject
+/// those too, pass `deny_wildcard`.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// struct Token<'a>(&'a str);
+///
+/// #[require_lifetimes(deny_wildcard)]
+/// fn text<'a>(token: Token<'_>) -> usize {
+///     token.0.len()
+/// }
+/// ```
+///
+/// Writing `'static` is allowed, since some exercises need it. Where it'd
+/// be a way around working out the right lifetime, pass `deny_static` to
+/// reject it in the parameters and return type. (It's still allowed in
+/// bounds, as in `T: 'static`.)
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// #[require_lifetimes(deny_static)]
+/// fn first_word(text: &'static str) -> &'static str {
+///     text.split(' ').next().unwrap()
+/// }
+/// ```
+///
+/// With `descriptive_names`, a lifetime parameter can't be named with a
+/// single letter, like `'a`, wherever it's declared in the item (on a
+/// function, an `impl` or `trait`, or a type). That's for later exercises,
+/// where a name like `'text` says what a lifetime borrows from. `'static`
+/// and `'_` aren't declared, so they're not affected.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// // This fails to compile, since `'a` doesn't say what it's for.
+/// #[require_lifetimes(descriptive_names)]
+/// fn first_word<'a>(text: &'a str) -> &'a str {
+///     text.split(' ').next().unwrap()
+/// }
+/// ```
+///
+/// A parameter that doesn't need its lifetimes named (say, a scratch buffer
+/// that has nothing to do with the lesson) can be marked `#[elided]`. It's
+/// left unchecked, and the mark is removed before the compiler sees it. The
+/// rest of the parameters, and the return type, are still checked.
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// #[require_lifetimes]
+/// fn first_word<'a>(text: &'a str, #[elided] scratch: &mut Vec<usize>) -> &'a str {
+///     scratch.clear();
+///     text.split(' ').next().unwrap()
+/// }
+/// ```
+///
+/// A lifetime parameter that isn't used by any parameter, the return type,
+/// or a bound (so `'b` in `<'a, 'b: 'a>` counts as used) is an error too,
+/// since it can be removed.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// // This fails to compile, since nothing uses `'b`.
+/// #[require_lifetimes]
+/// fn identity<'a, 'b>(a: &'a i32) -> &'a i32 {
+///     a
+/// }
+/// ```
+///
+/// With `require_bounds`, a return type that only works because one lifetime
+/// outlives another needs that written down, as in `where 'b: 'a`, rather
+/// than left for the compiler to work out. Only two shapes of return type
+/// are looked at: a reference `&'a T`, where every other lifetime in `T` has
+/// to outlive `'a`, and an `impl Trait + 'a`, where every other lifetime in
+/// the trait has to. Bounds count if they're written on the function (or
+/// the `impl` or `trait` it's in), directly or through a chain like
+/// `'c: 'b, 'b: 'a`. Bounds that come from a type's own definition, or
+/// lifetimes hidden behind a type alias, aren't seen, so this can ask for a
+/// bound the compiler wouldn't, and can miss one it would.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// struct Token<'a>(&'a str);
+///
+/// // This fails to compile, since it needs `where 'b: 'a`.
+/// #[require_lifetimes(require_bounds)]
+/// fn first<'a, 'b>(tokens: &'a [Token<'b>]) -> &'a Token<'b> {
+///     &tokens[0]
+/// }
+/// ```
+///
+/// On a `struct` or `enum`, every lifetime in its fields has to be one of
+/// its lifetime parameters (or `'static`, with `allow_static`), and every
+/// lifetime parameter has to be used by some field.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// // This fails to compile, since no field uses `'b`.
+/// #[require_lifetimes]
+/// struct Pair<'a, 'b> {
+///     first: &'a str,
+///     second: &'a str,
+/// }
+/// ```
+///
+/// A type alias is checked the same way, with the type it stands for in
+/// place of the fields. Where an alias is used, the macro can't see what's
+/// in it: a signature that takes a `Pair` when it's `type Pair<'a> = ...`
+/// leaves out a lifetime without it showing. So aliases have to be checked
+/// where they're defined, and used with their lifetimes written out, as in
+/// `Pair<'a>`.
+///
+/// ```rust,compile_fail
+/// use require_lifetimes::require_lifetimes;
+///
+/// // This fails to compile, since nothing uses `'b`.
+/// #[require_lifetimes]
+/// type Pair<'a, 'b> = (&'a str, &'a str);
+/// ```
+///
+/// On a module written out in place, the attribute is put on every function,
+/// `impl`, `trait`, `extern` block and module in it that doesn't have its
+/// own, so `#[require_lifetimes(skip)]` opts one out. Structs, enums and type
+/// aliases are only checked with `include_types`. (Rust doesn't allow
+/// `#![require_lifetimes]` inside a module yet, or the attribute on
+/// `mod name;`.)
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// #[require_lifetimes]
+/// mod text {
+///     pub fn first<'a>(text: &'a str) -> Option<&'a str> {
+///         text.split(' ').next()
+///     }
+///
+///     #[require_lifetimes(skip)]
+///     pub fn last(text: &str) -> Option<&str> {
+///         text.split(' ').last()
+///     }
+/// }
+/// ```
+///
+/// Building with `--cfg require_lifetimes_disabled` (in `RUSTFLAGS`, say)
+/// turns the attribute off everywhere, so code that it would reject
+/// compiles as it would without it. That's for trying other tools on code
+/// that isn't finished yet. The attribute can also end up on an item twice
+/// (when another macro re-emits it), in which case it's only checked once.
+///
+/// Attribute macros run from the top down, so with others on the same item,
+/// this one is best put first: below them, it checks whatever they rewrote
+/// the signature into. If the item isn't Rust until a macro below this one
+/// has run (like a function without a body that the macro fills in), it's
+/// passed on unchecked, with a warning.
+///
+/// With `warn`, everything that would be an error is a warning instead
+/// (a deprecation warning, since that's the one a proc-macro can cause on
+/// stable), so the code still compiles. That's handy for seeing everything
+/// that needs fixing in a file before turning the errors on.
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// // This compiles, with warnings about `identity`'s missing lifetimes.
+/// #[require_lifetimes(warn)]
+/// fn identity(a: &i32) -> &i32 {
+///     a
+/// }
+/// ```
+///
+/// With `verbose`, each function also gets a note walking through how the
+/// elision rules read its signature: which inputs get lifetimes of their
+/// own, and which one the output gets, or why no rule gives it one. On
+/// stable the note is a deprecation warning too.
+///
+/// ```rust
+/// use require_lifetimes::require_lifetimes;
+///
+/// // This compiles, with a note that `text` and the output both get `'a`.
+/// #[require_lifetimes(verbose, warn)]
+/// fn first_word(text: &str) -> &str {
+///     text.split(' ').next().unwrap_or("")
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn require_lifetimes(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(expand(attr.into(), item.into()))
+}
+
+/// Given the attribute's arguments and the item it's on, return what it
+/// expands to. This is the attribute on `proc_macro2`'s tokens, which,
+/// unlike `proc_macro`'s, can be made outside of a macro, so tests can see
+/// exactly what comes out.
+fn expand(
+    attr: proc_macro2::TokenStream,
+    mut item: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let attr_span = attr.span();
+    if cfg!(require_lifetimes_disabled) {
+        return pass_through(item);
+    }
+    if attr.to_string() == "skip" {
+        return pass_through(item);
+    }
+    let options = parse_options(&attr).unwrap_or_default();
+    let replacement = parse_error_case_from_attribute(attr.clone());
+    // An item with nothing to report and no `#[elided]` to remove is handed
+    // back as it came, since re-emitting a large one from its syntax tree
+    // takes a good part of the time spent on it.
+    let Ok(mut ast) = syn::parse2::<Item>(item.clone()) else {
+        return unread(item, attr_span);
+    };
+    if strip_repeated_attributes(&mut ast, &attr) {
+        item = ast.to_token_stream();
+    }
+    match ast {
+        Item::Fn(mut function) => {
+            let mut checked = vec![check_signature(&function.sig, &options, None, None)];
+            let mut changed = strip_elided_attributes(&mut function.sig);
+            if !options.skip_nested {
+                let mut nested = NestedItems {
+                    options: &options,
+                    checked: Vec::new(),
+                    stripped: false,
+                };
+                nested.visit_block_mut(&mut function.block);
+                checked.extend(nested.checked);
+                changed |= nested.stripped;
+            }
+            // The function might be a method, where a `const _` can't follow
+            // it, so the notes (like warnings) go at the start of its body.
+            let notes: proc_macro2::TokenStream =
+                checked.iter().map(Findings::report_note).collect();
+            if !notes.is_empty() {
+                function
+                    .block
+                    .stmts
+                    .insert(0, syn::Stmt::Item(Item::Verbatim(notes)));
+                changed = true;
+            }
+            if checked.iter().all(Findings::is_empty) {
+                if !changed {
+                    return item;
+                }
+                return function.to_token_stream();
+            }
+
+            let reports = checked
+                .iter()
+                .map(|findings| findings.report(&options))
+                .collect();
+            if options.warn {
+                function
+                    .block
+                    .stmts
+                    .insert(0, syn::Stmt::Item(Item::Verbatim(reports)));
+                return function.to_token_stream();
+            }
+
+            let mut new_function_stream = replacement.unwrap_or_else(|| function.to_token_stream());
+            new_function_stream.extend(reports);
+            new_function_stream
+        }
+        Item::Impl(mut block) => {
+            let mut reports = proc_macro2::TokenStream::new();
+            let mut changed = false;
+            if options.descriptive_names {
+                let name = syn::Ident::new("impl", block.impl_token.span);
+                let errors = short_name_errors(&block.generics, "this `impl`");
+                let findings = Findings::for_type(&name, errors);
+                changed |= !findings.is_empty();
+                reports.extend(findings.report(&options));
+            }
+            for impl_item in &mut block.items {
+                let ImplItem::Method(method) = impl_item else {
+                    continue;
+                };
+                // A method with its own attribute gets checked (or skipped)
+                // when that attribute is expanded.
+                if method.attrs.iter().any(is_require_lifetimes_attribute) {
+                    continue;
+                }
+
+                let findings = check_signature(&method.sig, &options, None, Some(&block.generics));
+                changed |= strip_elided_attributes(&mut method.sig);
+                let note = findings.report_note();
+                changed |= !note.is_empty();
+                reports.extend(note);
+                if findings.is_empty() {
+                    continue;
+                }
+                changed = true;
+                if let Some(replacement) = &replacement {
+                    *impl_item = ImplItem::Verbatim(replacement.clone());
+                }
+                reports.extend(findings.report(&options));
+            }
+
+            if !changed {
+                return item;
+            }
+            let mut new_impl_stream = block.to_token_stream();
+            new_impl_stream.extend(reports);
+            new_impl_stream
+        }
+        Item::Trait(mut definition) => {
+            let mut reports = proc_macro2::TokenStream::new();
+            let mut changed = false;
+            if options.descriptive_names {
+                let location = format!("the trait `{}`", definition.ident);
+                let errors = short_name_errors(&definition.generics, &location);
+                let findings = Findings::for_type(&definition.ident, errors);
+                changed |= !findings.is_empty();
+                reports.extend(findings.report(&options));
+            }
+            for trait_item in &mut definition.items {
+                let TraitItem::Method(method) = trait_item else {
+                    continue;
+                };
+                if method.attrs.iter().any(is_require_lifetimes_attribute) {
+                    continue;
+                }
+
+                let trait_method = (definition.ident.to_string(), method.sig.ident.to_string());
+                let findings = check_signature(
+                    &method.sig,
+                    &options,
+                    Some(trait_method),
+                    Some(&definition.generics),
+                );
+                changed |= strip_elided_attributes(&mut method.sig);
+                let note = findings.report_note();
+                changed |= !note.is_empty();
+                reports.extend(note);
+                if findings.is_empty() {
+                    continue;
+                }
+                changed = true;
+                if let Some(replacement) = &replacement {
+                    *trait_item = TraitItem::Verbatim(replacement.clone());
+                }
+                reports.extend(findings.report(&options));
+            }
+
+            if !changed {
+                return item;
+            }
+            let mut new_trait_stream = definition.to_token_stream();
+            new_trait_stream.extend(reports);
+            new_trait_stream
+        }
+        Item::ForeignMod(mut block) => {
+            let mut reports = proc_macro2::TokenStream::new();
+            let mut changed = false;
+            for foreign_item in &mut block.items {
+                let ForeignItem::Fn(function) = foreign_item else {
+                    continue;
+                };
+                if function.attrs.iter().any(is_require_lifetimes_attribute) {
+                    continue;
+                }
+
+                let findings = check_signature(&function.sig, &options, None, None);
+                changed |= strip_elided_attributes(&mut function.sig);
+                let note = findings.report_note();
+                changed |= !note.is_empty();
+                reports.extend(note);
+                if findings.is_empty() {
+                    continue;
+                }
+                changed = true;
+                if let Some(replacement) = &replacement {
+                    *foreign_item = ForeignItem::Verbatim(replacement.clone());
+                }
+                reports.extend(findings.report(&options));
+            }
+
+            if !changed {
+                return item;
+            }
+            let mut new_block_stream = block.to_token_stream();
+            new_block_stream.extend(reports);
+            new_block_stream
+        }
+        Item::Struct(ref definition) => definition_stream(
+            item.clone(),
+            &definition.generics,
+            replacement,
+            &options,
+            definition_errors(
+                &definition.ident,
+                &definition.generics,
+                [(None, &definition.fields)],
+                options.allow_static,
+            ),
+        ),
+        Item::Enum(ref definition) => definition_stream(
+            item.clone(),
+            &definition.generics,
+            replacement,
+            &options,
+            definition_errors(
+                &definition.ident,
+                &definition.generics,
+                definition
+                    .variants
+                    .iter()
+                    .map(|variant| (Some(&variant.ident), &variant.fields)),
+                options.allow_static,
+            ),
+        ),
+        Item::Type(ref alias) => definition_stream(
+            item.clone(),
+            &alias.generics,
+            replacement,
+            &options,
+            alias_errors(alias, options.allow_static),
+        ),
+        Item::Mod(mut module) => {
+            let Some((_, items)) = &mut module.content else {
+                return error_to_final_token_stream(
+                    item,
+                    &attr_span,
+                    "Attribute only valid on modules written out in place, as `mod name { ... }`.",
+                );
+            };
+            annotate_module_items(items, &attr, options.include_types);
+            module.to_token_stream()
+        }
+        // This is what syn makes of an item it can parse but not read, like
+        // a function without a body that another macro will fill in.
+        Item::Verbatim(_) => unread(item, attr_span),
+        _ => {
+            error_to_final_token_stream(item, &attr_span, "Attribute not valid on this item type.")
+        }
+    }
+}
+
+/// Given a function's signature, the trait it's a method of (if it is one),
+/// and the generics of the `impl` or `trait` it's in (if any), return what's
+/// wrong with it: an error for every reference in it that's missing a
+/// lifetime (or uses the anonymous one), along with a suggestion of what the
+/// lifetimes could be, then an error for every lifetime parameter it doesn't
+/// use, and with `require_bounds`, for every bound that the return type
+/// relies on without writing.
+fn check_signature(
+    signature: &syn::Signature,
+    options: &Options,
+    trait_method: Option<(String, String)>,
+    outer_generics: Option<&syn::Generics>,
+) -> Findings {
+    let name = match &trait_method {
+        Some((trait_name, method_name)) => format!("{trait_name}::{method_name}"),
+        None => signature.ident.to_string(),
+    };
+    let mut visitor = TypeVisitor {
+        trait_method,
+        deny_wildcard: options.deny_wildcard,
+        deny_static: options.deny_static,
+        ..TypeVisitor::default()
+    };
+    visitor.visit_signature(signature);
+    // Writing out the elided lifetimes doesn't help with a `'static`.
+    let suggestion = visitor
+        .errors
+        .iter()
+        .any(|violation| violation.kind != Kind::StaticLifetime)
+        .then(|| suggestion_error(signature, outer_generics));
+    let location = visitor.location();
+    let mut other_errors = unused_lifetime_errors(signature, &location);
+    if options.require_bounds {
+        other_errors.extend(bound_errors(signature, outer_generics, &location));
+    }
+    if options.descriptive_names {
+        other_errors.extend(short_name_errors(&signature.generics, &location));
+    }
+
+    let note = options.verbose.then(|| {
+        syn::Error::new(
+            signature.ident.span(),
+            elision_note(signature, outer_generics),
+        )
+    });
+
+    Findings {
+        name,
+        #[cfg(feature = "nightly-diagnostics")]
+        name_span: signature.ident.span(),
+        lifetime_errors: visitor.errors,
+        suggestion,
+        other_errors,
+        note,
+    }
+}
+
+/// Given a struct, enum or type alias definition `item`, its generics, what
+/// the attribute on it says to replace it with (if anything), its options,
+/// and what was found wrong with it, return what to replace it with: the
+/// definition itself (or whatever the attribute said to replace it with, if
+/// there were errors), followed by the errors.
+fn definition_stream(
+    item: proc_macro2::TokenStream,
+    generics: &syn::Generics,
+    replacement: Option<proc_macro2::TokenStream>,
+    options: &Options,
+    mut findings: Findings,
+) -> proc_macro2::TokenStream {
+    if options.descriptive_names {
+        let location = format!("`{}`", findings.name);
+        findings
+            .other_errors
+            .extend(short_name_errors(generics, &location));
+    }
+    if findings.is_empty() {
+        return item;
+    }
+
+    let mut new_definition_stream = match replacement {
+        Some(replacement) => replacement,
+        None => item,
+    };
+    new_definition_stream.extend(findings.report(options));
+    new_definition_stream
+}
+
+/// Given a signature, remove the `#[elided]` attributes from its
+/// parameters, since rustc wouldn't know what to make of them. Return
+/// whether there were any.
+fn strip_elided_attributes(signature: &mut syn::Signature) -> bool {
+    let mut stripped = false;
+    for input in &mut signature.inputs {
+        let attrs = match input {
+            syn::FnArg::Receiver(receiver) => &mut receiver.attrs,
+            syn::FnArg::Typed(argument) => &mut argument.attrs,
+        };
+        let before = attrs.len();
+        attrs.retain(|attr| !attr.path.is_ident("elided"));
+        stripped |= attrs.len() != before;
+    }
+    stripped
+}
+
+/// Given an item and the arguments of the `require_lifetimes` being
+/// expanded on it, remove any more `require_lifetimes` attributes on it with
+/// the same arguments, and return whether there were any. A macro that
+/// re-emits items can end up putting the attribute on one twice, and
+/// checking it twice would report everything twice.
+fn strip_repeated_attributes(item: &mut Item, attr: &proc_macro2::TokenStream) -> bool {
+    let attrs = match item {
+        Item::Fn(function) => &mut function.attrs,
+        Item::Impl(block) => &mut block.attrs,
+        Item::Trait(definition) => &mut definition.attrs,
+        Item::ForeignMod(block) => &mut block.attrs,
+        Item::Struct(definition) => &mut definition.attrs,
+        Item::Enum(definition) => &mut definition.attrs,
+        Item::Type(alias) => &mut alias.attrs,
+        Item::Mod(module) => &mut module.attrs,
+        _ => return false,
+    };
+    let arguments = attr.to_string();
+    let before = attrs.len();
+    attrs.retain(|other| {
+        let other_arguments = match other.tokens.clone().into_iter().next() {
+            Some(proc_macro2::TokenTree::Group(group))
+                if group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+            {
+                group.stream().to_string()
+            }
+            _ => String::new(),
+        };
+        !(is_require_lifetimes_attribute(other) && other_arguments == arguments)
+    });
+    attrs.len() != before
+}
+
+/// Given an item, return it without checking anything, which is what the
+/// attribute does with `--cfg require_lifetimes_disabled`. The `#[elided]`
+/// attributes in it still have to go, since rustc wouldn't know what to
+/// make of them.
+fn pass_through(item: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let Ok(mut ast) = syn::parse2::<Item>(item.clone()) else {
+        return item;
+    };
+    let mut strip = StripElided(false);
+    strip.visit_item_mut(&mut ast);
+    if !strip.0 {
+        return item;
+    }
+    ast.to_token_stream()
+}
+
+/// Given an item that isn't Rust yet, which happens when another attribute
+/// macro under this one still has to turn it into Rust, and the attribute's
+/// span, return the item as it is, with a warning at the attribute that it
+/// wasn't checked. Rejecting it would stop the other macro from running.
+fn unread(
+    mut item: proc_macro2::TokenStream,
+    attr_span: proc_macro2::Span,
+) -> proc_macro2::TokenStream {
+    item.extend(report::unread_warning(attr_span));
+    item
+}
+
+/// This removes the `#[elided]` attributes from every signature in what it
+/// visits, and records whether there were any.
+struct StripElided(bool);
+
+impl VisitMut for StripElided {
+    fn visit_signature_mut(&mut self, signature: &mut syn::Signature) {
+        self.0 |= strip_elided_attributes(signature);
+    }
+}
+
+/// Given an attribute, return whether it's `require_lifetimes` (with or
+/// without arguments, and however its path is written).
+fn is_require_lifetimes_attribute(attr: &syn::Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "require_lifetimes")
+}
+
+/// Given a `span` which contains an error, and an error message `message`,
+/// return a `proc_macro2::TokenStream` which encapsulates that error.
+fn error_to_token_stream(
+    span: &proc_macro2::Span,
+    message: &'static str,
+) -> proc_macro2::TokenStream {
+    syn::Error::new(*span, message).to_compile_error()
+}
+
+/// Given an error message, a span, and some `TokenStream` representing
+/// the new code outputted by the macro; append the error to the new code.
+fn error_to_final_token_stream(
+    mut body: proc_macro2::TokenStream,
+    span: &proc_macro2::Span,
+    message: &'static str,
+) -> proc_macro2::TokenStream {
+    let error = error_to_token_stream(span, message);
+    body.extend(error);
+    body
+}
+
+/// These are the options `require_lifetimes` can be given, as a
+/// comma-separated list like `#[require_lifetimes(deny_wildcard)]`.
+#[derive(Default)]
+struct Options {
+    deny_wildcard: bool,
+    deny_static: bool,
+    require_bounds: bool,
+    allow_static: bool,
+    warn: bool,
+    skip_nested: bool,
+    include_types: bool,
+    verbose: bool,
+    descriptive_names: bool,
+}
+
+/// Given an attribute for `require_lifetimes`, get the options in it, or
+/// `None` if it isn't a list of them (so it must be replacement code).
+fn parse_options(attr: &proc_macro2::TokenStream) -> Option<Options> {
+    let mut options = Options::default();
+    for (index, token) in attr.clone().into_iter().enumerate() {
+        match token {
+            proc_macro2::TokenTree::Ident(option) if index % 2 == 0 => {
+                match option.to_string().as_str() {
+                    "deny_wildcard" => options.deny_wildcard = true,
+                    "deny_static" => options.deny_static = true,
+                    "require_bounds" => options.require_bounds = true,
+                    "allow_static" => options.allow_static = true,
+                    "warn" => options.warn = true,
+                    "skip_nested" => options.skip_nested = true,
+                    "include_types" => options.include_types = true,
+                    "verbose" => options.verbose = true,
+                    "descriptive_names" => options.descriptive_names = true,
+                    _ => return None,
+                }
+            }
+            proc_macro2::TokenTree::Punct(comma) if index % 2 == 1 && comma.as_char() == ',' => {}
+            _ => return None,
+        }
+    }
+    Some(options)
+}
+
+/// Given an attribute for `require_lifetimes`, get the body of
+/// the attribute (or an error). This allows the user to replace
+/// broken code with nothing (or another default block of code).
+fn parse_error_case_from_attribute(
+    attr: proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    if parse_options(&attr).is_some() {
+        None
+    } else if attr.to_string() == "!" {
+        Some(proc_macro2::TokenStream::new())
+    } else {
+        Some(attr)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::expand;
+    use quote::quote;
+
+    // These pin down what comes out of expansions that re-emit the item from
+    // its syntax tree, which is where attributes could be lost or reordered.
+    // `ui_tests/attributes_ok.rs` and `attributes_err.rs` check the same
+    // things compile the way they should.
+
+    #[test]
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    fn expanded_functions_keep_their_attributes_in_order() {
+        // Removing the `#[elided]`, and the repeated `#[require_lifetimes]`,
+        // makes it re-emit the function.
+        let expanded = expand(
+            quote!(),
+            quote! {
+                #[deprecated(note = "use `second` instead")]
+                #[cfg(test)]
+                #[require_lifetimes]
+                #[inline]
+                fn second<'a>(
+                    #[cfg(any())] first: &'a str,
+                    #[elided] scratch: &mut Vec<usize>,
+                    #[allow(unused_mut)] mut text: &'a str,
+                ) -> &'a str {
+                    text
+                }
+            },
+        );
+        assert_eq!(
+            expanded.to_string(),
+            "# [deprecated (note = \"use `second` instead\")] # [cfg (test)] # [inline] \
+             fn second < 'a > (# [cfg (any ())] first : & 'a str , \
+             scratch : & mut Vec < usize > , \
+             # [allow (unused_mut)] mut text : & 'a str ,) -> & 'a str { text }"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    fn expanded_methods_keep_their_attributes_in_order() {
+        let expanded = expand(
+            quote!(),
+            quote! {
+                #[cfg(not(test))]
+                impl Wrapper {
+                    /// Stands in for `get` outside of tests.
+                    #[cfg(not(test))]
+                    #[inline]
+                    pub fn get<'a>(&'a self, #[elided] scratch: &mut Vec<usize>) -> &'a str {
+                        "not a test"
+                    }
+                }
+            },
+        );
+        assert_eq!(
+            expanded.to_string(),
+            "# [cfg (not (test))] impl Wrapper { \
+             # [doc = r\" Stands in for `get` outside of tests.\"] # [cfg (not (test))] # [inline] \
+             pub fn get < 'a > (& 'a self , scratch : & mut Vec < usize >) -> & 'a str { \
+             \"not a test\" } }"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    fn functions_with_errors_keep_their_attributes_in_order() {
+        let expanded = expand(
+            quote!(),
+            quote! {
+                #[deprecated(note = "use `second` instead")]
+                #[must_use]
+                fn first(text: &str) -> &str {
+                    text
+                }
+            },
+        )
+        .to_string();
+        let (function, errors) = expanded.split_once(" compile_error !").unwrap();
+        assert_eq!(
+            function,
+            "# [deprecated (note = \"use `second` instead\")] # [must_use] \
+             fn first (text : & str) -> & str { text }"
+        );
+        assert!(errors.contains("all references must be annotated with a lifetime"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    fn ui_tests() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("ui_tests/*_err.rs");
+        t.pass("ui_tests/*_ok.rs");
+    }
+
+    #[test]
+    #[cfg(feature = "nightly-diagnostics")]
+    fn nightly_ui_tests() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("nightly_ui_tests/*_err.rs");
+        t.pass("nightly_ui_tests/*_ok.rs");
+    }
+}
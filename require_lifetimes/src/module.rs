@@ -0,0 +1,48 @@
+use crate::is_require_lifetimes_attribute;
+use syn::visit_mut::VisitMut;
+use syn::Item;
+
+/// Given the items in a module that has `#[require_lifetimes]` on it, and
+/// the attribute's arguments, put the same attribute on each item that's
+/// something that gets checked and doesn't have one of its own. Structs,
+/// enums and type aliases only get it with `include_types`.
+///
+/// The module might not have `require_lifetimes` in scope, so the attribute
+/// is added with its full path, and any the items already have (like
+/// `#[require_lifetimes(skip)]`) are changed to use it too.
+pub fn annotate_module_items(
+    items: &mut [Item],
+    attr: &proc_macro2::TokenStream,
+    include_types: bool,
+) {
+    for item in items.iter_mut() {
+        FullPaths.visit_item_mut(item);
+
+        let attrs = match item {
+            Item::Fn(function) => &mut function.attrs,
+            Item::Impl(block) => &mut block.attrs,
+            Item::Trait(definition) => &mut definition.attrs,
+            Item::ForeignMod(block) => &mut block.attrs,
+            Item::Mod(module) if module.content.is_some() => &mut module.attrs,
+            Item::Struct(definition) if include_types => &mut definition.attrs,
+            Item::Enum(definition) if include_types => &mut definition.attrs,
+            Item::Type(alias) if include_types => &mut alias.attrs,
+            _ => continue,
+        };
+        if !attrs.iter().any(is_require_lifetimes_attribute) {
+            attrs.push(syn::parse_quote!(#[::require_lifetimes::require_lifetimes(#attr)]));
+        }
+    }
+}
+
+/// This changes every `require_lifetimes` attribute it visits to use the
+/// attribute's full path.
+struct FullPaths;
+
+impl VisitMut for FullPaths {
+    fn visit_attribute_mut(&mut self, attr: &mut syn::Attribute) {
+        if is_require_lifetimes_attribute(attr) {
+            attr.path = syn::parse_quote!(::require_lifetimes::require_lifetimes);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::report::{Kind, Violation};
+
+/// Given some generics and how to refer to what they're on, return an error
+/// for every lifetime parameter whose name is a single letter, like `'a`,
+/// at the parameter. This is for `descriptive_names`. (`'static` and `'_`
+/// can't be declared, so they never come up here.)
+pub fn short_name_errors(generics: &syn::Generics, location: &str) -> Vec<Violation> {
+    generics
+        .lifetimes()
+        .map(|definition| &definition.lifetime)
+        .filter(|lifetime| lifetime.ident.to_string().chars().count() == 1)
+        .map(|lifetime| {
+            Violation::new(
+                Kind::ShortName,
+                lifetime,
+                format!(
+                    "In {location}, `{lifetime}` needs a longer name: this exercise wants \
+                     each lifetime named after what it borrows from, like `'text` or \
+                     `'matcher`, rather than a single letter."
+                ),
+            )
+        })
+        .collect()
+}
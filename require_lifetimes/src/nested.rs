@@ -0,0 +1,49 @@
+use crate::report::Findings;
+use crate::{check_signature, is_require_lifetimes_attribute, strip_elided_attributes, Options};
+use syn::visit_mut::VisitMut;
+
+/// This checks the functions defined inside a function's body, and the
+/// methods of `impl` blocks there, the same way as the function itself, and
+/// records what it finds in each. Any with their own `#[require_lifetimes]`
+/// are left to that one.
+pub struct NestedItems<'a> {
+    pub options: &'a Options,
+    pub checked: Vec<Findings>,
+    /// Whether any of them had an `#[elided]` to remove.
+    pub stripped: bool,
+}
+
+impl VisitMut for NestedItems<'_> {
+    fn visit_item_fn_mut(&mut self, function: &mut syn::ItemFn) {
+        if function.attrs.iter().any(is_require_lifetimes_attribute) {
+            return;
+        }
+
+        let findings = check_signature(&function.sig, self.options, None, None);
+        self.stripped |= strip_elided_attributes(&mut function.sig);
+        self.checked.push(findings);
+
+        self.visit_block_mut(&mut function.block);
+    }
+
+    fn visit_item_impl_mut(&mut self, block: &mut syn::ItemImpl) {
+        if block.attrs.iter().any(is_require_lifetimes_attribute) {
+            return;
+        }
+
+        for impl_item in &mut block.items {
+            let syn::ImplItem::Method(method) = impl_item else {
+                continue;
+            };
+            if method.attrs.iter().any(is_require_lifetimes_attribute) {
+                continue;
+            }
+
+            let findings = check_signature(&method.sig, self.options, None, Some(&block.generics));
+            self.stripped |= strip_elided_attributes(&mut method.sig);
+            self.checked.push(findings);
+
+            self.visit_block_mut(&mut method.block);
+        }
+    }
+}
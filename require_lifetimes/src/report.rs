@@ -0,0 +1,300 @@
+#[cfg(not(feature = "nightly-diagnostics"))]
+use crate::warning::{deprecated_item, note_item, warning_item};
+use crate::Options;
+
+/// What sort of problem a violation is. These are what
+/// `REQUIRE_LIFETIMES_JSON` calls them, so a tool can tell them apart
+/// without reading the messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// A reference without a lifetime.
+    MissingLifetime,
+    /// A reference with `'_` as its lifetime.
+    AnonymousLifetime,
+    /// A `'_` anywhere, with `deny_wildcard`.
+    Wildcard,
+    /// A `'static` in a parameter or the return type, with `deny_static`.
+    StaticLifetime,
+    /// An `impl Trait` return type that doesn't say which lifetime it
+    /// captures.
+    UnboundedImplTrait,
+    /// A `dyn Trait` that doesn't say how long it lives.
+    UnboundedDynTrait,
+    /// A lifetime parameter that nothing uses.
+    UnusedLifetime,
+    /// A bound the return type needs that isn't written, with
+    /// `require_bounds`.
+    MissingBound,
+    /// A field that borrows for `'static`, without `allow_static`.
+    StaticField,
+    /// A field with a lifetime that isn't one of the type's parameters.
+    UndeclaredLifetime,
+    /// A lifetime parameter with a one-letter name, with
+    /// `descriptive_names`.
+    ShortName,
+}
+
+impl Kind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::MissingLifetime => "missing_lifetime",
+            Kind::AnonymousLifetime => "anonymous_lifetime",
+            Kind::Wildcard => "wildcard",
+            Kind::StaticLifetime => "static_lifetime",
+            Kind::UnboundedImplTrait => "unbounded_impl_trait",
+            Kind::UnboundedDynTrait => "unbounded_dyn_trait",
+            Kind::UnusedLifetime => "unused_lifetime",
+            Kind::MissingBound => "missing_bound",
+            Kind::StaticField => "static_field",
+            Kind::UndeclaredLifetime => "undeclared_lifetime",
+            Kind::ShortName => "short_name",
+        }
+    }
+}
+
+/// One thing found wrong, and the error that reports it.
+#[derive(Debug)]
+pub struct Violation {
+    pub kind: Kind,
+    pub error: syn::Error,
+}
+
+impl Violation {
+    /// Given what sort of problem it is, the tokens it's about, and a
+    /// message, return a violation with an error covering those tokens.
+    pub fn new(
+        kind: Kind,
+        node: impl quote::ToTokens,
+        message: impl std::fmt::Display,
+    ) -> Violation {
+        Violation {
+            kind,
+            error: syn::Error::new_spanned(node, message),
+        }
+    }
+}
+
+/// This is everything found wrong with one function or type.
+pub struct Findings {
+    /// How warnings should refer to the item, like `first` or `Named::name`.
+    pub name: String,
+    /// Where the item's name is written.
+    #[cfg(feature = "nightly-diagnostics")]
+    pub name_span: proc_macro2::Span,
+    /// An error for every lifetime that's missing, or written in a way
+    /// that isn't allowed.
+    pub lifetime_errors: Vec<Violation>,
+    /// The signature with its lifetimes written out, if it's a function
+    /// with lifetime errors.
+    pub suggestion: Option<syn::Error>,
+    /// Every other error, which the suggestion doesn't help with.
+    pub other_errors: Vec<Violation>,
+    /// With `verbose`, how the elision rules read the signature, at the
+    /// function's name. This isn't a problem, so it doesn't count towards
+    /// `is_empty`.
+    pub note: Option<syn::Error>,
+}
+
+impl Findings {
+    /// Given the name of a type and the errors found in it, return them as
+    /// findings.
+    pub fn for_type(name: &syn::Ident, errors: Vec<Violation>) -> Findings {
+        Findings {
+            name: name.to_string(),
+            #[cfg(feature = "nightly-diagnostics")]
+            name_span: name.span(),
+            lifetime_errors: errors,
+            suggestion: None,
+            other_errors: Vec::new(),
+            note: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lifetime_errors.is_empty() && self.other_errors.is_empty()
+    }
+
+    /// Return every error, with the suggestion right after the lifetime
+    /// errors it goes with.
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    pub fn errors(&self) -> impl Iterator<Item = &syn::Error> {
+        self.lifetime_errors
+            .iter()
+            .map(|violation| &violation.error)
+            .chain(&self.suggestion)
+            .chain(self.other_errors.iter().map(|violation| &violation.error))
+    }
+
+    /// Return what reports these findings: the errors themselves, or with
+    /// `warn`, warnings at the same spans.
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    pub fn report(&self, options: &Options) -> proc_macro2::TokenStream {
+        if self.is_empty() {
+            return proc_macro2::TokenStream::new();
+        }
+        if json_requested() {
+            self.print_json(options);
+        }
+
+        if options.warn {
+            warning_item(self)
+        } else {
+            self.errors().map(syn::Error::to_compile_error).collect()
+        }
+    }
+
+    /// Return what shows the note from `verbose`, if there is one: a
+    /// warning, since that's as close to a note as there is on stable.
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    pub fn report_note(&self) -> proc_macro2::TokenStream {
+        note_item(self)
+    }
+
+    /// Show the note from `verbose` as a diagnostic, if there is one.
+    /// Nothing needs to go in the expansion, so this returns no tokens.
+    #[cfg(feature = "nightly-diagnostics")]
+    pub fn report_note(&self) -> proc_macro2::TokenStream {
+        use proc_macro::{Diagnostic, Level};
+
+        if let Some(note) = &self.note {
+            Diagnostic::spanned(note.span().unwrap(), Level::Note, note.to_string()).emit();
+        }
+        proc_macro2::TokenStream::new()
+    }
+
+    /// Report these findings as diagnostics, which are warnings with `warn`.
+    /// Each error gets a note pointing at the item's name, and the lifetime
+    /// errors get the suggested signature as help. Nothing needs to go in
+    /// the expansion, so this returns no tokens.
+    #[cfg(feature = "nightly-diagnostics")]
+    pub fn report(&self, options: &Options) -> proc_macro2::TokenStream {
+        use proc_macro::{Diagnostic, Level};
+
+        if json_requested() {
+            self.print_json(options);
+        }
+        let level = if options.warn {
+            Level::Warning
+        } else {
+            Level::Error
+        };
+        let help = self
+            .suggestion
+            .as_ref()
+            .map(|suggestion| suggestion.to_string().trim_end().to_string());
+        let errors = self
+            .lifetime_errors
+            .iter()
+            .map(|violation| (&violation.error, help.as_ref()))
+            .chain(
+                self.other_errors
+                    .iter()
+                    .map(|violation| (&violation.error, None)),
+            );
+
+        for (error, help) in errors {
+            for message in error.clone() {
+                let mut diagnostic =
+                    Diagnostic::spanned(message.span().unwrap(), level, message.to_string())
+                        .span_note(self.name_span.unwrap(), format!("in `{}`", self.name));
+                if let Some(help) = help {
+                    diagnostic = diagnostic.help(help.clone());
+                }
+                diagnostic.emit();
+            }
+        }
+
+        proc_macro2::TokenStream::new()
+    }
+
+    /// Print a line of JSON to stderr for each violation (after
+    /// `JSON_PREFIX`), so a tool running the compiler (like an exercise
+    /// runner) can tell what went wrong without reading the messages. The
+    /// lifetime violations come with the suggested signature.
+    fn print_json(&self, options: &Options) {
+        let level = if options.warn { "warning" } else { "error" };
+        let suggestion = self
+            .suggestion
+            .as_ref()
+            .map(|suggestion| suggestion.to_string().trim_end().to_string());
+        let violations = self
+            .lifetime_errors
+            .iter()
+            .map(|violation| (violation, suggestion.as_ref()))
+            .chain(self.other_errors.iter().map(|violation| (violation, None)));
+
+        for (violation, suggestion) in violations {
+            let span = violation.error.span().unwrap();
+            let mut line = format!(
+                "{{\"level\":{},\"item\":{},\"kind\":{},\"message\":{},\"file\":{},\
+                 \"line\":{},\"column\":{}",
+                json_string(level),
+                json_string(&self.name),
+                json_string(violation.kind.name()),
+                json_string(&violation.error.to_string()),
+                json_string(&span.file()),
+                span.line(),
+                span.column(),
+            );
+            if let Some(suggestion) = suggestion {
+                line.push_str(&format!(",\"suggestion\":{}", json_string(suggestion)));
+            }
+            line.push('}');
+            eprintln!("{JSON_PREFIX}{line}");
+        }
+    }
+}
+
+/// What the warning about an item that couldn't be read says.
+const UNREAD_MESSAGE: &str = "`#[require_lifetimes]` couldn't read this item as Rust, so it \
+     wasn't checked. If an attribute macro under it is what makes it Rust, put \
+     `#[require_lifetimes]` under that one instead.";
+
+/// Given the span of an attribute on an item that couldn't be read, return
+/// what warns that it wasn't checked: a deprecation warning there, like the
+/// ones from `warn`.
+#[cfg(not(feature = "nightly-diagnostics"))]
+pub fn unread_warning(span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    deprecated_item("require_lifetimes_unread", UNREAD_MESSAGE, span)
+}
+
+/// Warn that the item with the attribute at the span couldn't be read, and
+/// so wasn't checked. Nothing needs to go in the expansion, so this returns
+/// no tokens.
+#[cfg(feature = "nightly-diagnostics")]
+pub fn unread_warning(span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    use proc_macro::{Diagnostic, Level};
+
+    Diagnostic::spanned(span.unwrap(), Level::Warning, UNREAD_MESSAGE).emit();
+    proc_macro2::TokenStream::new()
+}
+
+/// This starts each line of JSON. Cargo reads any line rustc prints that
+/// starts with `{` as one of rustc's own JSON messages, so the lines can't
+/// start with the JSON itself.
+const JSON_PREFIX: &str = "require_lifetimes: ";
+
+/// Return whether `REQUIRE_LIFETIMES_JSON` is set to `1`. Cargo doesn't know
+/// the macro reads it, so changing it doesn't rebuild crates that compiled.
+fn json_requested() -> bool {
+    std::env::var_os("REQUIRE_LIFETIMES_JSON").is_some_and(|value| value == "1")
+}
+
+/// Given some text, return it as a JSON string, in quotes and escaped.
+fn json_string(text: &str) -> String {
+    let mut escaped = String::from('"');
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            character if character.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", u32::from(character)))
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
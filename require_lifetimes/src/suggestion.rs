@@ -0,0 +1,667 @@
+use crate::binders::{binder_names, Binders};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::visit_mut::VisitMut;
+
+/// Given a signature that's missing some lifetimes, and the generics of the
+/// `impl` or `trait` it's in (if any), return an error at the function's
+/// name that shows the signature with its lifetimes written out the way the
+/// elision rules fill them in. If the rules can't say what the output's
+/// lifetime is, the error says so instead, and when that's because several
+/// references were passed in (like `fn longest(x: &str, y: &str) -> &str`),
+/// it shows the two ways it could be written.
+pub fn suggestion_error(
+    signature: &syn::Signature,
+    outer_generics: Option<&syn::Generics>,
+) -> syn::Error {
+    let (fixed, taken) = prepare(signature, outer_generics);
+    let elided = write_out(&fixed, &taken, Choice::Elision);
+    let output_lifetime_count = elided.input_lifetimes;
+    let message = if elided.unnamed_outputs == 0 && elided.from_self && output_lifetime_count > 1 {
+        format!(
+            "Since one of the inputs is `self`, the elision rules give the output \
+             `self`'s lifetime, so without this attribute the signature would have \
+             compiled as it is. With its lifetimes written out, it is:\n\n    {}\n\n\
+             If the output borrows from one of the other inputs instead, it needs that \
+             one's lifetime.",
+            elided.rendered
+        )
+    } else if elided.unnamed_outputs == 0 {
+        format!(
+            "With its lifetimes written out the way the elision rules fill them in, \
+             this signature is:\n\n    {}\n",
+            elided.rendered
+        )
+    } else if output_lifetime_count == 0 {
+        format!(
+            "None of the inputs borrow anything, so the elision rules can't give the \
+             output's references a lifetime. They may need to be `'static`, or the \
+             function may need to return something it owns instead:\n\n    {}\n",
+            elided.rendered
+        )
+    } else if elided.all_elided {
+        let shared = write_out(&fixed, &taken, Choice::Shared);
+        let first = write_out(&fixed, &taken, Choice::First);
+        let borrower = match &first.first_borrower {
+            Some(name) => format!("`{name}`"),
+            None => "the first reference".to_string(),
+        };
+        format!(
+            "The elision rules can't tell which of the {output_lifetime_count} input \
+             lifetimes the output should have, since none of them belong to `self`. \
+             Which one it borrows from depends on what the body returns, and rustc only \
+             looks at the signature to decide, so it has to be written out. If the \
+             output can borrow from any of the inputs, they need to share a \
+             lifetime:\n\n    {}\n\nIf it only ever borrows from {borrower}, the \
+             others can have lifetimes of their own:\n\n    {}\n",
+            shared.rendered, first.rendered
+        )
+    } else {
+        format!(
+            "The elision rules can't tell which of the {output_lifetime_count} input \
+             lifetimes the output should have, since none of them belong to `self`. \
+             That's your choice to make: with the inputs' lifetimes written out, this \
+             signature is\n\n    {}\n\nand each reference in the output needs \
+             one of them.",
+            elided.rendered
+        )
+    };
+
+    syn::Error::new(signature.ident.span(), message)
+}
+
+/// Given a signature, and the generics of the `impl` or `trait` it's in (if
+/// any), return a note that walks through how the elision rules fill in its
+/// lifetimes: which inputs get lifetimes of their own, and which one the
+/// output gets (or why it can't get one).
+pub fn elision_note(signature: &syn::Signature, outer_generics: Option<&syn::Generics>) -> String {
+    let (fixed, taken) = prepare(signature, outer_generics);
+    let elided = write_out(&fixed, &taken, Choice::Elision);
+
+    let given: Vec<_> = elided
+        .fresh
+        .iter()
+        .map(|(input, lifetimes)| {
+            let lifetimes: Vec<_> = lifetimes
+                .iter()
+                .map(|lifetime| format!("`{lifetime}`"))
+                .collect();
+            format!("`{input}` gets {}", list(&lifetimes))
+        })
+        .collect();
+    let inputs = if given.is_empty() {
+        "None of the lifetimes in the inputs are left out, so there's nothing for the \
+         first rule (each one left out gets a lifetime of its own) to do."
+            .to_string()
+    } else {
+        format!(
+            "Each lifetime left out of the inputs gets a lifetime of its own: {}.",
+            list(&given)
+        )
+    };
+
+    let output = match (&elided.output_lifetime, elided.input_lifetimes) {
+        _ if elided.named_outputs + elided.unnamed_outputs == 0 => {
+            "The output doesn't leave out any lifetimes, so that's all the rules do.".to_string()
+        }
+        (Some(lifetime), _) if elided.from_self => format!(
+            "It's a method that borrows `self`, so the output gets `self`'s lifetime, \
+             `{lifetime}`, whatever else the inputs borrow."
+        ),
+        (Some(lifetime), _) => format!(
+            "There's only one lifetime in the inputs, so the output gets that one, \
+             `{lifetime}`."
+        ),
+        (None, 0) => "There aren't any lifetimes in the inputs, so no rule gives the output \
+                      one, and it has to be written out."
+            .to_string(),
+        (None, count) => format!(
+            "There are {count} lifetimes in the inputs and none of them are `self`'s, so \
+             no rule says which one the output gets, and it has to be written out."
+        ),
+    };
+
+    let written_out = if elided.unnamed_outputs == 0 {
+        "Altogether, that's"
+    } else {
+        "With the inputs' lifetimes written out, that's"
+    };
+    format!(
+        "Here's how the elision rules read this signature. {inputs} {output} \
+         {written_out}:\n\n    {}\n",
+        elided.rendered
+    )
+}
+
+/// Given some items, return them as a list in a sentence, like `a, b and c`.
+fn list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+/// Given a signature, and the generics of the `impl` or `trait` it's in (if
+/// any), return it ready to have its lifetimes written out, along with the
+/// lifetime names already in use around it.
+fn prepare(
+    signature: &syn::Signature,
+    outer_generics: Option<&syn::Generics>,
+) -> (syn::Signature, Vec<String>) {
+    let mut fixed = signature.clone();
+    crate::strip_elided_attributes(&mut fixed);
+    // Signatures split over several lines often end their inputs (and where
+    // clauses) with a comma, which would look out of place once they're on
+    // one.
+    if fixed.inputs.trailing_punct() {
+        if let Some(last) = fixed.inputs.pop() {
+            fixed.inputs.push(last.into_value());
+        }
+    }
+    if let Some(where_clause) = &mut fixed.generics.where_clause {
+        if where_clause.predicates.trailing_punct() {
+            if let Some(last) = where_clause.predicates.pop() {
+                where_clause.predicates.push(last.into_value());
+            }
+        }
+    }
+    let taken = signature
+        .generics
+        .lifetimes()
+        .chain(
+            outer_generics
+                .into_iter()
+                .flat_map(syn::Generics::lifetimes),
+        )
+        .map(|definition| definition.lifetime.ident.to_string())
+        // A fresh name can't be one a `for<...>` declares either, since
+        // that would shadow it.
+        .chain(binder_names(signature))
+        .collect();
+
+    (fixed, taken)
+}
+
+/// This is which lifetime the output gets, when writing out a signature's
+/// lifetimes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    /// Whichever one the elision rules pick, if they pick one.
+    Elision,
+    /// One lifetime that every elided input lifetime shares.
+    Shared,
+    /// The first input lifetime, with the rest getting their own.
+    First,
+}
+
+/// This is a signature with its lifetimes written out, and what was found
+/// while writing them.
+struct WrittenOut {
+    rendered: String,
+    /// How many distinct lifetimes the inputs have.
+    input_lifetimes: usize,
+    /// Whether every input lifetime was elided (or anonymous).
+    all_elided: bool,
+    /// Whether the output got `self`'s lifetime somewhere.
+    from_self: bool,
+    /// The lifetime the output's elided lifetimes get, if there is one.
+    output_lifetime: Option<syn::Lifetime>,
+    /// How many of the output's lifetimes were given one.
+    named_outputs: usize,
+    /// How many of the output's lifetimes couldn't be given one.
+    unnamed_outputs: usize,
+    /// Each input that got fresh lifetimes, and what they are.
+    fresh: Vec<(String, Vec<syn::Lifetime>)>,
+    /// The name of the first parameter that borrows something, if it's
+    /// just a name and it has just the one lifetime.
+    first_borrower: Option<String>,
+}
+
+/// Given a signature (with no `#[elided]` attributes), the lifetime names
+/// already in use around it, and which lifetime its output should get,
+/// return it with its lifetimes written out.
+fn write_out(signature: &syn::Signature, taken: &[String], choice: Choice) -> WrittenOut {
+    let mut fixed = signature.clone();
+    let mut inputs = NameInputs {
+        fresh: FreshLifetimes {
+            taken: taken.to_vec(),
+            next: 0,
+        },
+        shared: None,
+        used: Vec::new(),
+        added: Vec::new(),
+        binders: Binders::default(),
+    };
+    if choice == Choice::Shared {
+        inputs.shared = Some(inputs.fresh.next());
+    }
+
+    let mut self_lifetime = None;
+    let mut first_borrower = None;
+    let mut fresh = Vec::new();
+    for input in &mut fixed.inputs {
+        let borrowed_before = inputs.used.len();
+        let added_before = inputs.added.len();
+        match input {
+            syn::FnArg::Receiver(receiver) => {
+                if let Some((_, lifetime)) = &mut receiver.reference {
+                    inputs.name(lifetime);
+                    self_lifetime = lifetime.clone();
+                }
+            }
+            syn::FnArg::Typed(argument) => {
+                inputs.visit_type_mut(&mut argument.ty);
+                if let syn::Pat::Ident(pattern) = &*argument.pat {
+                    if pattern.ident == "self" {
+                        self_lifetime = self_reference_lifetime(&argument.ty);
+                    } else if borrowed_before == 0 && inputs.used.len() == 1 {
+                        first_borrower = Some(pattern.ident.to_string());
+                    }
+                }
+            }
+        }
+        if inputs.added.len() > added_before {
+            let name = match input {
+                syn::FnArg::Receiver(_) => "self".to_string(),
+                syn::FnArg::Typed(argument) => render(argument.pat.to_token_stream()),
+            };
+            fresh.push((name, inputs.added[added_before..].to_vec()));
+        }
+    }
+
+    let self_borrowed = self_lifetime.is_some();
+    let output_lifetime = self_lifetime.or(match (choice, inputs.used.as_slice()) {
+        (_, [only]) => Some(only.clone()),
+        (Choice::First, [first, ..]) => Some(first.clone()),
+        _ => None,
+    });
+    let mut outputs = NameOutputs {
+        lifetime: output_lifetime.clone(),
+        bound_impl_traits: !inputs.used.is_empty(),
+        named_count: 0,
+        unnamed_count: 0,
+    };
+    if let syn::ReturnType::Type(_, output) = &mut fixed.output {
+        outputs.visit_type_mut(output);
+    }
+
+    let all_elided = inputs.added.len() == inputs.used.len();
+    let generics = &mut fixed.generics;
+    let first_non_lifetime = generics.lifetimes().count();
+    let added = inputs.shared.iter().chain(&inputs.added);
+    for (offset, lifetime) in added.enumerate() {
+        generics.params.insert(
+            first_non_lifetime + offset,
+            syn::GenericParam::Lifetime(syn::LifetimeDef::new(lifetime.clone())),
+        );
+    }
+    if !generics.params.is_empty() {
+        generics.lt_token.get_or_insert_with(Default::default);
+        generics.gt_token.get_or_insert_with(Default::default);
+    }
+
+    WrittenOut {
+        rendered: render(fixed.to_token_stream()),
+        input_lifetimes: inputs.used.len(),
+        all_elided,
+        from_self: self_borrowed && outputs.named_count > 0,
+        output_lifetime,
+        named_outputs: outputs.named_count,
+        unnamed_outputs: outputs.unnamed_count,
+        fresh,
+        first_borrower,
+    }
+}
+
+/// This hands out lifetime names that aren't used yet, in the order `'a`,
+/// `'b`, ..., `'z`, `'a1`, `'b1`, and so on.
+struct FreshLifetimes {
+    taken: Vec<String>,
+    next: usize,
+}
+
+impl FreshLifetimes {
+    fn next(&mut self) -> syn::Lifetime {
+        loop {
+            let letter = char::from(b'a' + (self.next % 26) as u8);
+            let name = match self.next / 26 {
+                0 => letter.to_string(),
+                round => format!("{letter}{round}"),
+            };
+            self.next += 1;
+
+            if !self.taken.contains(&name) {
+                return syn::Lifetime::new(&format!("'{name}"), proc_macro2::Span::call_site());
+            }
+        }
+    }
+}
+
+/// This gives every elided (or anonymous) lifetime in the inputs a fresh
+/// name (or the `shared` one, if there is one), and keeps track of every
+/// distinct lifetime the inputs use.
+struct NameInputs {
+    fresh: FreshLifetimes,
+    shared: Option<syn::Lifetime>,
+    used: Vec<syn::Lifetime>,
+    added: Vec<syn::Lifetime>,
+    binders: Binders,
+}
+
+impl NameInputs {
+    fn name(&mut self, lifetime: &mut Option<syn::Lifetime>) {
+        match lifetime {
+            // The lifetimes a `for<...>` declares aren't inputs either.
+            Some(existing) if self.binders.contains(existing) => {}
+            Some(existing) if existing.ident != "_" => {
+                if !self.used.contains(existing) {
+                    self.used.push(existing.clone());
+                }
+            }
+            _ => {
+                let named = match &self.shared {
+                    Some(shared) => shared.clone(),
+                    None => {
+                        let fresh = self.fresh.next();
+                        self.added.push(fresh.clone());
+                        fresh
+                    }
+                };
+                if !self.used.contains(&named) {
+                    self.used.push(named.clone());
+                }
+                *lifetime = Some(named);
+            }
+        }
+    }
+}
+
+impl VisitMut for NameInputs {
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        self.name(&mut reference.lifetime);
+        match &mut *reference.elem {
+            syn::Type::TraitObject(trait_object) => {
+                syn::visit_mut::visit_type_trait_object_mut(self, trait_object);
+                bound_behind_reference(reference);
+            }
+            elem => self.visit_type_mut(elem),
+        }
+    }
+
+    // Lifetimes in function types don't count as inputs for elision, even
+    // named ones, and elided ones there are left elided.
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+
+    fn visit_bound_lifetimes_mut(&mut self, _: &mut syn::BoundLifetimes) {}
+
+    fn visit_trait_bound_mut(&mut self, bound: &mut syn::TraitBound) {
+        let depth = self.binders.enter(bound.lifetimes.as_ref());
+        syn::visit_mut::visit_trait_bound_mut(self, bound);
+        self.binders.leave(depth);
+    }
+
+    // The default `'static` isn't an input lifetime as far as elision goes,
+    // so it's added after visiting the bounds, to keep it out of `used`.
+    fn visit_type_trait_object_mut(&mut self, trait_object: &mut syn::TypeTraitObject) {
+        syn::visit_mut::visit_type_trait_object_mut(self, trait_object);
+        bound_trait_object(trait_object, static_lifetime());
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        let mut named = Some(lifetime.clone());
+        self.name(&mut named);
+        *lifetime = named.unwrap();
+    }
+}
+
+/// This gives every elided (or anonymous) lifetime in the output the
+/// lifetime the elision rules picked, if they picked one, and otherwise
+/// counts them. If `bound_impl_traits`, an `impl Trait` without a lifetime
+/// bound counts as one of them.
+struct NameOutputs {
+    lifetime: Option<syn::Lifetime>,
+    bound_impl_traits: bool,
+    named_count: usize,
+    unnamed_count: usize,
+}
+
+impl NameOutputs {
+    fn name(&mut self, lifetime: &mut Option<syn::Lifetime>) {
+        if lifetime
+            .as_ref()
+            .is_some_and(|existing| existing.ident != "_")
+        {
+            return;
+        }
+        match &self.lifetime {
+            Some(chosen) => {
+                *lifetime = Some(chosen.clone());
+                self.named_count += 1;
+            }
+            None => self.unnamed_count += 1,
+        }
+    }
+}
+
+impl VisitMut for NameOutputs {
+    fn visit_type_impl_trait_mut(&mut self, impl_trait: &mut syn::TypeImplTrait) {
+        let has_lifetime_bound = impl_trait
+            .bounds
+            .iter()
+            .any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)));
+        if self.bound_impl_traits && !has_lifetime_bound {
+            match &self.lifetime {
+                Some(chosen) => {
+                    impl_trait
+                        .bounds
+                        .push(syn::TypeParamBound::Lifetime(chosen.clone()));
+                    self.named_count += 1;
+                }
+                None => self.unnamed_count += 1,
+            }
+        }
+
+        syn::visit_mut::visit_type_impl_trait_mut(self, impl_trait);
+    }
+
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        self.name(&mut reference.lifetime);
+        match &mut *reference.elem {
+            syn::Type::TraitObject(trait_object) => {
+                syn::visit_mut::visit_type_trait_object_mut(self, trait_object);
+                bound_behind_reference(reference);
+            }
+            elem => self.visit_type_mut(elem),
+        }
+    }
+
+    // Elided lifetimes in function types get their own lifetime for each
+    // call, rather than the one the elision rules picked for the output.
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+
+    fn visit_type_trait_object_mut(&mut self, trait_object: &mut syn::TypeTraitObject) {
+        bound_trait_object(trait_object, static_lifetime());
+        syn::visit_mut::visit_type_trait_object_mut(self, trait_object);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        let mut named = Some(lifetime.clone());
+        self.name(&mut named);
+        *lifetime = named.unwrap();
+    }
+}
+
+/// Given the type of a typed `self`, return the lifetime of the reference
+/// to `Self` in it, like `'a` in `&'a Self` or `Pin<&'a mut Self>`. The
+/// elision rules give that lifetime to the output, however the reference is
+/// wrapped.
+fn self_reference_lifetime(ty: &syn::Type) -> Option<syn::Lifetime> {
+    match ty {
+        syn::Type::Reference(reference) => match &*reference.elem {
+            syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self") => {
+                reference.lifetime.clone()
+            }
+            elem => self_reference_lifetime(elem),
+        },
+        syn::Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                return None;
+            };
+            arguments.args.iter().find_map(|argument| match argument {
+                syn::GenericArgument::Type(ty) => self_reference_lifetime(ty),
+                _ => None,
+            })
+        }
+        syn::Type::Paren(paren) => self_reference_lifetime(&paren.elem),
+        _ => None,
+    }
+}
+
+/// Given a reference to a trait object, give that the reference's lifetime
+/// as its bound (if it has none), which is what it defaults to. The trait
+/// object gets put in brackets, as `&'a (dyn Trait + 'a)` needs.
+fn bound_behind_reference(reference: &mut syn::TypeReference) {
+    let Some(lifetime) = &reference.lifetime else {
+        return;
+    };
+    if let syn::Type::TraitObject(trait_object) = &mut *reference.elem {
+        let bounds_before = trait_object.bounds.len();
+        bound_trait_object(trait_object, lifetime.clone());
+        if trait_object.bounds.len() > bounds_before {
+            let elem = std::mem::replace(
+                &mut *reference.elem,
+                syn::Type::Verbatim(Default::default()),
+            );
+            *reference.elem = syn::Type::Paren(syn::TypeParen {
+                paren_token: Default::default(),
+                elem: Box::new(elem),
+            });
+        }
+    }
+}
+
+/// Given a trait object and a lifetime, give it that lifetime as its bound,
+/// unless it has one already.
+fn bound_trait_object(trait_object: &mut syn::TypeTraitObject, lifetime: syn::Lifetime) {
+    let has_lifetime_bound = trait_object
+        .bounds
+        .iter()
+        .any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)));
+    if !has_lifetime_bound {
+        trait_object
+            .bounds
+            .push(syn::TypeParamBound::Lifetime(lifetime));
+    }
+}
+
+/// Return `'static`, which is what a trait object that isn't behind a
+/// reference lives for unless it says otherwise. (A type's own bounds can
+/// change that, as in `Ref<'a, dyn Trait>`, but those can't be seen here.)
+fn static_lifetime() -> syn::Lifetime {
+    syn::Lifetime::new("'static", proc_macro2::Span::call_site())
+}
+
+/// This is a piece of a signature's tokens, as far as spacing it out goes.
+enum Piece {
+    /// An identifier, literal or lifetime.
+    Word(String),
+    /// An operator, with any punctuation joined onto it (like `->` or `::`).
+    Operator(String),
+    /// A delimited group, already rendered.
+    Group(Delimiter, String),
+}
+
+/// Given some tokens of a signature, return them as text spaced out the way
+/// rustfmt would (at least for the kind of tokens signatures have).
+fn render(tokens: TokenStream) -> String {
+    let mut pieces = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        pieces.push(match token {
+            TokenTree::Ident(ident) => Piece::Word(ident.to_string()),
+            TokenTree::Literal(literal) => Piece::Word(literal.to_string()),
+            TokenTree::Group(group) => Piece::Group(group.delimiter(), render(group.stream())),
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                let name = tokens
+                    .next()
+                    .map(|name| name.to_string())
+                    .unwrap_or_default();
+                Piece::Word(format!("'{name}"))
+            }
+            TokenTree::Punct(punct) => {
+                let mut operator = punct.as_char().to_string();
+                let mut spacing = punct.spacing();
+                while spacing == Spacing::Joint {
+                    match tokens.peek() {
+                        Some(TokenTree::Punct(next)) if next.as_char() != '\'' => {
+                            operator.push(next.as_char());
+                            spacing = next.spacing();
+                            tokens.next();
+                        }
+                        _ => break,
+                    }
+                }
+                Piece::Operator(operator)
+            }
+        });
+    }
+
+    let mut rendered = String::new();
+    for (index, piece) in pieces.iter().enumerate() {
+        if index > 0 && needs_space(&pieces[index - 1], piece) {
+            rendered.push(' ');
+        }
+        match piece {
+            Piece::Word(text) | Piece::Operator(text) => rendered.push_str(text),
+            Piece::Group(delimiter, inner) => {
+                let (open, close) = match delimiter {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                rendered.push_str(open);
+                rendered.push_str(inner);
+                rendered.push_str(close);
+            }
+        }
+    }
+    rendered
+}
+
+/// Given two pieces that come one after the other, return whether there
+/// should be a space between them.
+fn needs_space(before: &Piece, after: &Piece) -> bool {
+    match (before, after) {
+        (_, Piece::Operator(operator))
+            if matches!(operator.as_str(), "," | ";" | ":" | "::" | "<" | ">") =>
+        {
+            false
+        }
+        (Piece::Operator(operator), _)
+            if matches!(operator.as_str(), "&" | "::" | "<" | "#" | "?" | "*") =>
+        {
+            false
+        }
+        // A lifetime is followed by a space, as in `&'a (dyn Trait + 'a)`,
+        // but a name isn't, as in `f(x)`.
+        (Piece::Word(word), Piece::Group(Delimiter::Parenthesis, _)) => word.starts_with('\''),
+        (Piece::Operator(operator), Piece::Group(Delimiter::Parenthesis, _)) => operator != ">",
+        _ => true,
+    }
+}
@@ -0,0 +1,241 @@
+use crate::report::{Kind, Violation};
+use quote::ToTokens;
+
+#[derive(Debug, Default)]
+pub struct TypeVisitor {
+    pub errors: Vec<Violation>,
+    /// The trait and method whose signature is being visited, if it's a
+    /// trait method. The errors name the method so it's clear which one of
+    /// the trait is wrong.
+    pub trait_method: Option<(String, String)>,
+    /// Whether `'_` is rejected everywhere, rather than only on references.
+    pub deny_wildcard: bool,
+    /// Whether `'static` is rejected in the parameters and return type.
+    pub deny_static: bool,
+    /// Whether any parameter is (or contains) a reference, in which case an
+    /// `impl Trait` in the return type has to say which lifetime it captures,
+    /// and every `dyn Trait` has to say how long it lives.
+    pub takes_references: bool,
+    /// Whether a parameter is the part being visited.
+    pub in_inputs: bool,
+    /// Whether the return type is the part being visited.
+    pub in_output: bool,
+}
+
+impl TypeVisitor {
+    /// Return how the errors should refer to the function being checked.
+    pub fn location(&self) -> String {
+        match &self.trait_method {
+            Some((trait_name, method_name)) => format!("the method `{trait_name}::{method_name}`"),
+            None => "this function".to_string(),
+        }
+    }
+
+    /// Given a reference (or receiver) `node` and its lifetime (if any),
+    /// record an error if the lifetime is missing or anonymous. The error
+    /// covers the whole of `node`, so it's clear which reference is wrong.
+    fn check_lifetime(&mut self, node: &dyn ToTokens, lifetime: Option<&syn::Lifetime>) {
+        let location = self.location();
+
+        match lifetime {
+            None => self.errors.push(Violation::new(
+                Kind::MissingLifetime,
+                node,
+                format!("In {location}, all references must be annotated with a lifetime."),
+            )),
+            // With `deny_wildcard`, `visit_lifetime` rejects these.
+            Some(lifetime) if lifetime.ident == "_" && !self.deny_wildcard => {
+                self.errors.push(Violation::new(
+                    Kind::AnonymousLifetime,
+                    lifetime,
+                    format!("In {location}, the anonymous lifetime is not allowed."),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+impl<'ast> syn::visit::Visit<'ast> for TypeVisitor {
+    fn visit_signature(&mut self, signature: &'ast syn::Signature) {
+        let mut references = ReferenceFinder::default();
+        for input in &signature.inputs {
+            references.visit_fn_arg(input);
+        }
+        self.takes_references = references.found;
+
+        syn::visit::visit_signature(self, signature);
+    }
+
+    fn visit_fn_arg(&mut self, input: &'ast syn::FnArg) {
+        self.in_inputs = true;
+        syn::visit::visit_fn_arg(self, input);
+        self.in_inputs = false;
+    }
+
+    fn visit_return_type(&mut self, output: &'ast syn::ReturnType) {
+        self.in_output = true;
+        syn::visit::visit_return_type(self, output);
+        self.in_output = false;
+    }
+
+    fn visit_type_impl_trait(&mut self, impl_trait: &'ast syn::TypeImplTrait) {
+        let has_lifetime_bound = impl_trait
+            .bounds
+            .iter()
+            .any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)));
+        if self.in_output && self.takes_references && !has_lifetime_bound {
+            self.errors.push(Violation::new(
+                Kind::UnboundedImplTrait,
+                impl_trait,
+                format!(
+                    "In {}, an `impl Trait` return type must say which lifetime it captures, \
+                     since the parameters have references in them. Add a bound like `+ 'a`.",
+                    self.location()
+                ),
+            ));
+        }
+
+        syn::visit::visit_type_impl_trait(self, impl_trait);
+    }
+
+    fn visit_type_trait_object(&mut self, trait_object: &'ast syn::TypeTraitObject) {
+        let has_lifetime_bound = trait_object
+            .bounds
+            .iter()
+            .any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)));
+        if self.takes_references && !has_lifetime_bound {
+            self.errors.push(Violation::new(
+                Kind::UnboundedDynTrait,
+                trait_object,
+                format!(
+                    "In {}, a `dyn Trait` must say how long it lives, since the parameters \
+                     have references in them. Without a bound, it's `'static` unless it's \
+                     right behind a reference. Add a bound like `+ 'a` or `+ 'static`.",
+                    self.location()
+                ),
+            ));
+        }
+
+        syn::visit::visit_type_trait_object(self, trait_object);
+    }
+
+    fn visit_type(&mut self, ty: &'ast syn::Type) {
+        if let syn::Type::Reference(ref reference) = ty {
+            self.check_lifetime(reference, reference.lifetime.as_ref());
+        }
+
+        syn::visit::visit_type(self, ty);
+    }
+
+    // References in function pointer types and `Fn(...)` bounds belong to
+    // each call rather than to the function being checked, so they can
+    // leave their lifetimes out.
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments(
+        &mut self,
+        _: &'ast syn::ParenthesizedGenericArguments,
+    ) {
+    }
+
+    fn visit_type_path(&mut self, type_path: &'ast syn::TypePath) {
+        // `PhantomData` only marks what a type acts like it holds, so the
+        // lifetimes in it can be left out.
+        let is_phantom_data = type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData");
+        if is_phantom_data {
+            return;
+        }
+
+        syn::visit::visit_type_path(self, type_path);
+    }
+
+    // Only the type says what a parameter borrows, so it's checked the same
+    // whatever the pattern is: a name (raw or not), `_`, or a destructuring
+    // one like `(&a, b)`, where the `&` is part of the pattern rather than a
+    // reference.
+    fn visit_pat_type(&mut self, argument: &'ast syn::PatType) {
+        if argument.attrs.iter().any(is_elided_attribute) {
+            return;
+        }
+
+        self.visit_type(&argument.ty);
+    }
+
+    fn visit_receiver(&mut self, receiver: &'ast syn::Receiver) {
+        if receiver.attrs.iter().any(is_elided_attribute) {
+            return;
+        }
+        if let Some((_, lifetime)) = &receiver.reference {
+            self.check_lifetime(receiver, lifetime.as_ref());
+        }
+
+        syn::visit::visit_receiver(self, receiver);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        if self.deny_wildcard && lifetime.ident == "_" {
+            self.errors.push(Violation::new(
+                Kind::Wildcard,
+                lifetime,
+                format!(
+                    "In {}, `'_` is not allowed: this exercise wants every \
+                     lifetime to have a name, so that it's clear what each one borrows from.",
+                    self.location()
+                ),
+            ));
+        }
+
+        if self.deny_static && (self.in_inputs || self.in_output) && lifetime.ident == "static" {
+            self.errors.push(Violation::new(
+                Kind::StaticLifetime,
+                lifetime,
+                format!(
+                    "In {}, `'static` is not allowed: this exercise wants every lifetime to \
+                     come from what the function is given, rather than from borrowing for the \
+                     whole program. Note that a `dyn Trait` without a bound, like \
+                     `Box<dyn Trait>`, is `'static` too, so give it one like `+ 'a`.",
+                    self.location()
+                ),
+            ));
+        }
+
+        syn::visit::visit_lifetime(self, lifetime);
+    }
+}
+
+/// Given an attribute on a parameter, return whether it's `#[elided]`, which
+/// means the parameter isn't checked.
+fn is_elided_attribute(attr: &syn::Attribute) -> bool {
+    attr.path.is_ident("elided")
+}
+
+/// This looks for a reference anywhere in what it visits.
+#[derive(Default)]
+struct ReferenceFinder {
+    found: bool,
+}
+
+impl<'ast> syn::visit::Visit<'ast> for ReferenceFinder {
+    fn visit_type_reference(&mut self, _: &'ast syn::TypeReference) {
+        self.found = true;
+    }
+
+    fn visit_receiver(&mut self, receiver: &'ast syn::Receiver) {
+        self.found |= receiver.reference.is_some();
+    }
+
+    // A reference in a function type is borrowed for one call, so there's
+    // nothing the function being checked could hold on to.
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments(
+        &mut self,
+        _: &'ast syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
@@ -0,0 +1,51 @@
+use crate::bounds::LifetimeCollector;
+use crate::report::{Kind, Violation};
+use syn::visit::Visit;
+
+/// Given a function's signature and how to refer to it, return an error for
+/// every lifetime parameter it declares but never uses: not in a parameter,
+/// the return type, or a bound (on another parameter or in the where
+/// clause).
+pub fn unused_lifetime_errors(signature: &syn::Signature, location: &str) -> Vec<Violation> {
+    let mut used = LifetimeCollector::default();
+    for param in &signature.generics.params {
+        match param {
+            // A lifetime's own name isn't a use of it, but a bound like
+            // `'b: 'a` is a use of both.
+            syn::GenericParam::Lifetime(definition) => {
+                if !definition.bounds.is_empty() {
+                    used.visit_lifetime(&definition.lifetime);
+                }
+                for bound in &definition.bounds {
+                    used.visit_lifetime(bound);
+                }
+            }
+            syn::GenericParam::Type(param) => used.visit_type_param(param),
+            syn::GenericParam::Const(param) => used.visit_const_param(param),
+        }
+    }
+    if let Some(where_clause) = &signature.generics.where_clause {
+        used.visit_where_clause(where_clause);
+    }
+    for input in &signature.inputs {
+        used.visit_fn_arg(input);
+    }
+    used.visit_return_type(&signature.output);
+
+    signature
+        .generics
+        .lifetimes()
+        .map(|definition| &definition.lifetime)
+        .filter(|lifetime| !used.lifetimes.contains(lifetime))
+        .map(|lifetime| {
+            Violation::new(
+                Kind::UnusedLifetime,
+                lifetime,
+                format!(
+                    "In {location}, the lifetime parameter `{lifetime}` isn't used by any \
+                     parameter, the return type, or a bound, so it can be removed."
+                ),
+            )
+        })
+        .collect()
+}
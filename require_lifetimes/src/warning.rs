@@ -0,0 +1,63 @@
+use crate::report::Findings;
+use quote::quote;
+
+/// Given what was found wrong with an item, return a `const _` item that
+/// has rustc warn about each error at its span instead. There's no stable
+/// way for a proc-macro to emit a warning, so this uses a deprecated
+/// constant at each span, with the error as the deprecation note.
+pub fn warning_item(findings: &Findings) -> proc_macro2::TokenStream {
+    let name = &findings.name;
+    let uses = findings.errors().flat_map(Clone::clone).map(|error| {
+        let note = format!("{error} (from `#[require_lifetimes(warn)]` on `{name}`)");
+        deprecated_use("require_lifetimes_warning", &note, error.span())
+    });
+
+    quote! {
+        const _: () = {
+            #(#uses)*
+        };
+    }
+}
+
+/// Given an item's findings, return a `const _` item that has rustc show
+/// their note (from `verbose`) as a warning at its span, the same way as
+/// `warning_item` does, or nothing if there isn't a note.
+pub fn note_item(findings: &Findings) -> proc_macro2::TokenStream {
+    let Some(note) = &findings.note else {
+        return proc_macro2::TokenStream::new();
+    };
+    let name = &findings.name;
+    let text = format!("{note} (from `#[require_lifetimes(verbose)]` on `{name}`)");
+    deprecated_item("require_lifetimes_note", &text, note.span())
+}
+
+/// Given a name for a constant, a deprecation note and a span, return a
+/// `const _` item that has rustc warn with the note at the span.
+pub fn deprecated_item(
+    name: &str,
+    note: &str,
+    span: proc_macro2::Span,
+) -> proc_macro2::TokenStream {
+    let usage = deprecated_use(name, note, span);
+
+    quote! {
+        const _: () = {
+            #usage
+        };
+    }
+}
+
+/// Given a name for a constant, a deprecation note and a span, return a
+/// block that declares the constant as deprecated, then uses it at the span.
+fn deprecated_use(name: &str, note: &str, span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    let constant = syn::Ident::new(name, span);
+    let declared = syn::Ident::new(name, proc_macro2::Span::call_site());
+    quote! {
+        {
+            #[deprecated(note = #note)]
+            #[allow(non_upper_case_globals)]
+            const #declared: () = ();
+            let _ = #constant;
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! This sets up crates that use `require_lifetimes`, for the tests that need
+//! to build one of their own.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Given a name for it and the source of its library, set up a crate that
+/// depends on `require_lifetimes`, and return its directory. Each test gets
+/// its own, since they run at once.
+pub fn fixture_crate(name: &str, source: &str) -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let crate_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+
+    std::fs::write(
+        crate_dir.join("Cargo.toml"),
+        format!(
+            "[package]\n\
+             name = {name:?}\n\
+             version = \"0.0.0\"\n\
+             edition = \"2021\"\n\
+             publish = false\n\
+             \n\
+             [dependencies]\n\
+             require_lifetimes = {{ path = {manifest_dir:?} }}\n\
+             \n\
+             [workspace]\n"
+        ),
+    )
+    .unwrap();
+    std::fs::write(crate_dir.join("src/lib.rs"), source).unwrap();
+    // This keeps the fixture on the versions that are already downloaded.
+    let lockfile = manifest_dir.join("../Cargo.lock");
+    if lockfile.exists() {
+        std::fs::copy(lockfile, crate_dir.join("Cargo.lock")).unwrap();
+    }
+
+    crate_dir
+}
+
+/// Given a fixture crate's directory, return a command that checks it, into
+/// a target directory of its own.
+pub fn cargo_check(crate_dir: &Path) -> Command {
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or("cargo".into()));
+    command
+        .arg("check")
+        .arg("--quiet")
+        .current_dir(crate_dir)
+        .env("CARGO_TARGET_DIR", crate_dir.join("target"))
+        .env_remove("REQUIRE_LIFETIMES_JSON");
+    command
+}
@@ -0,0 +1,144 @@
+//! This checks how the attribute gets along with other attribute macros, in
+//! either order, using a crate of small ones that rewrite what they're on.
+
+use std::path::Path;
+
+mod common;
+
+/// `#[with_context]` gives a function a first parameter `context: &str`,
+/// like a macro that passes something along to every function might.
+/// `#[stub]` gives a function without a body (which rustc only allows in
+/// a trait) one that returns the default.
+const MACROS: &str = r#"
+use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
+
+#[proc_macro_attribute]
+pub fn with_context(_: TokenStream, item: TokenStream) -> TokenStream {
+    let mut done = false;
+    item.into_iter()
+        .map(|tree| match tree {
+            TokenTree::Group(group) if !done && group.delimiter() == Delimiter::Parenthesis => {
+                done = true;
+                let mut inputs: TokenStream = "context: &str,".parse().unwrap();
+                inputs.extend(group.stream());
+                TokenTree::Group(Group::new(Delimiter::Parenthesis, inputs))
+            }
+            tree => tree,
+        })
+        .collect()
+}
+
+#[proc_macro_attribute]
+pub fn stub(_: TokenStream, item: TokenStream) -> TokenStream {
+    let mut trees: Vec<TokenTree> = item.into_iter().collect();
+    trees.pop();
+    let body = "Default::default()".parse().unwrap();
+    trees.push(TokenTree::Group(Group::new(Delimiter::Brace, body)));
+    trees.into_iter().collect()
+}
+"#;
+
+/// Given a name for it and the source of its library, set up a crate that
+/// depends on `require_lifetimes` and on the macros above, and return its
+/// directory.
+fn fixture_with_macros(name: &str, source: &str) -> std::path::PathBuf {
+    let crate_dir = common::fixture_crate(name, source);
+    let macros_dir = crate_dir.join("macros");
+    std::fs::create_dir_all(macros_dir.join("src")).unwrap();
+    std::fs::write(
+        macros_dir.join("Cargo.toml"),
+        "[package]\n\
+         name = \"macros\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"2021\"\n\
+         publish = false\n\
+         \n\
+         [lib]\n\
+         proc-macro = true\n",
+    )
+    .unwrap();
+    std::fs::write(macros_dir.join("src/lib.rs"), MACROS).unwrap();
+
+    let manifest = crate_dir.join("Cargo.toml");
+    let mut contents = std::fs::read_to_string(&manifest).unwrap();
+    contents.push_str("\n[dependencies.macros]\npath = \"macros\"\n");
+    std::fs::write(manifest, contents).unwrap();
+
+    crate_dir
+}
+
+/// Given a fixture crate's directory, check it, and return whether that
+/// worked, along with what rustc printed.
+fn check(crate_dir: &Path) -> (bool, String) {
+    let output = common::cargo_check(crate_dir).output().unwrap();
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn checks_what_was_written_when_it_comes_first() {
+    let crate_dir = fixture_with_macros(
+        "composition_first",
+        r#"
+        use macros::with_context;
+        use require_lifetimes::require_lifetimes;
+
+        #[require_lifetimes]
+        #[with_context]
+        pub fn first<'a>(text: &'a str) -> &'a str {
+            let _ = context;
+            text
+        }
+        "#,
+    );
+    let (success, stderr) = check(&crate_dir);
+    assert!(success, "the fixture should compile:\n{stderr}");
+    assert!(!stderr.contains("warning"), "{stderr}");
+}
+
+#[test]
+fn checks_what_the_other_macro_wrote_when_it_comes_second() {
+    let crate_dir = fixture_with_macros(
+        "composition_second",
+        r#"
+        use macros::with_context;
+        use require_lifetimes::require_lifetimes;
+
+        #[with_context]
+        #[require_lifetimes]
+        pub fn first<'a>(text: &'a str) -> &'a str {
+            let _ = context;
+            text
+        }
+        "#,
+    );
+    let (success, stderr) = check(&crate_dir);
+    assert!(!success);
+    assert!(
+        stderr.contains("all references must be annotated with a lifetime"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn passes_on_what_it_cannot_read() {
+    let crate_dir = fixture_with_macros(
+        "composition_unread",
+        r#"
+        use macros::stub;
+        use require_lifetimes::require_lifetimes;
+
+        #[require_lifetimes]
+        #[stub]
+        pub fn answer() -> u32;
+        "#,
+    );
+    let (success, stderr) = check(&crate_dir);
+    assert!(success, "the fixture should compile:\n{stderr}");
+    assert!(
+        stderr.contains("couldn't read this item as Rust"),
+        "{stderr}"
+    );
+}
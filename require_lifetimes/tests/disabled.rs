@@ -0,0 +1,51 @@
+//! This checks that `--cfg require_lifetimes_disabled` turns the attribute
+//! off, by building a crate it would otherwise reject.
+
+mod common;
+
+/// This would fail to compile at every item, if the attribute were on.
+const FIXTURE: &str = r#"
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+pub fn first(text: &str) -> &str {
+    text
+}
+
+#[require_lifetimes]
+pub fn count(text: &str, #[elided] scratch: &mut Vec<usize>) -> usize {
+    scratch.push(text.len());
+    scratch.len()
+}
+
+#[require_lifetimes]
+pub mod words {
+    pub fn count(text: &str, #[elided] skip: &usize) -> usize {
+        text.split(' ').skip(*skip).count()
+    }
+}
+"#;
+
+#[test]
+fn compiles_what_it_would_reject() {
+    let crate_dir = common::fixture_crate("disabled_fixture", FIXTURE);
+    let output = common::cargo_check(&crate_dir)
+        .env("RUSTFLAGS", "--cfg require_lifetimes_disabled")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "the fixture should compile:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn rejects_it_by_default() {
+    let crate_dir = common::fixture_crate("disabled_fixture_off", FIXTURE);
+    let output = common::cargo_check(&crate_dir)
+        .env_remove("RUSTFLAGS")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
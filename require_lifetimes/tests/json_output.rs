@@ -0,0 +1,69 @@
+//! This checks what `require_lifetimes` prints with `REQUIRE_LIFETIMES_JSON`
+//! set, by building `json_tests/fixture.rs` as a crate of its own.
+
+mod common;
+
+/// Given a name for the fixture's crate and the value of
+/// `REQUIRE_LIFETIMES_JSON` (if it should be set), check the fixture, and
+/// return the JSON lines it printed.
+fn json_lines(name: &str, value: Option<&str>) -> Vec<serde_json::Value> {
+    let crate_dir = common::fixture_crate(name, include_str!("../json_tests/fixture.rs"));
+    let mut command = common::cargo_check(&crate_dir);
+    if let Some(value) = value {
+        command.env("REQUIRE_LIFETIMES_JSON", value);
+    }
+    let output = command.output().unwrap();
+    assert!(
+        !output.status.success(),
+        "the fixture should fail to compile"
+    );
+
+    String::from_utf8(output.stderr)
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.strip_prefix("require_lifetimes: "))
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn prints_a_line_for_each_violation() {
+    let lines = json_lines("json_fixture", Some("1"));
+    let summary: Vec<_> = lines
+        .iter()
+        .map(|line| {
+            (
+                line["item"].as_str().unwrap(),
+                line["kind"].as_str().unwrap(),
+                line["line"].as_u64().unwrap(),
+                line["column"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("first", "missing_lifetime", 4, 20),
+            ("first", "missing_lifetime", 4, 29),
+            ("unused", "unused_lifetime", 9, 19),
+        ]
+    );
+
+    for line in &lines {
+        assert_eq!(line["level"], "error");
+        assert!(line["file"].as_str().unwrap().ends_with("lib.rs"));
+        assert!(line["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("In this function"));
+    }
+    let suggestion = lines[0]["suggestion"].as_str().unwrap();
+    assert!(suggestion.contains("fn first<'a>(text: &'a str) -> &'a str"));
+    assert!(lines[2].get("suggestion").is_none());
+}
+
+#[test]
+fn prints_nothing_by_default() {
+    assert!(json_lines("json_fixture_off", None).is_empty());
+    assert!(json_lines("json_fixture_off", Some("0")).is_empty());
+}
@@ -0,0 +1,68 @@
+//! This builds an `impl` block with a couple of hundred methods under
+//! `require_lifetimes`, to keep an eye on how long the macro takes on one.
+//! Run it with `--nocapture` to see the time.
+
+use std::fmt::Write;
+use std::time::{Instant, SystemTime};
+
+mod common;
+
+/// How many methods the generated `impl` has.
+const METHODS: usize = 200;
+
+/// Return the source of a crate with one large `impl` block, whose methods
+/// all have their lifetimes written out.
+fn large_impl() -> String {
+    let mut source = String::from(
+        "use require_lifetimes::require_lifetimes;\n\
+         \n\
+         pub struct Parser<'s> {\n    \
+             text: &'s str,\n    \
+             position: usize,\n\
+         }\n\
+         \n\
+         #[require_lifetimes]\n\
+         impl<'s> Parser<'s> {\n",
+    );
+    for index in 0..METHODS {
+        write!(
+            source,
+            "    pub fn method_{index}<'a, 'b>(&'a mut self, prefix: &'b str) -> Option<&'s str> {{\n        \
+                 let rest = &self.text[self.position..];\n        \
+                 if !rest.starts_with(prefix) {{\n            \
+                     return None;\n        \
+                 }}\n        \
+                 let end = rest.len().min(prefix.len() + {index});\n        \
+                 self.position += end;\n        \
+                 Some(&rest[..end])\n    \
+             }}\n\n"
+        )
+        .unwrap();
+    }
+    source.push_str("}\n");
+    source
+}
+
+#[test]
+fn large_impl_compiles() {
+    let crate_dir = common::fixture_crate("perf_fixture", &large_impl());
+    // Check it once to build the dependencies, then touch it, so the time is
+    // just the fixture's.
+    assert!(common::cargo_check(&crate_dir).status().unwrap().success());
+    std::fs::File::options()
+        .write(true)
+        .open(crate_dir.join("src/lib.rs"))
+        .unwrap()
+        .set_modified(SystemTime::now())
+        .unwrap();
+
+    let start = Instant::now();
+    let output = common::cargo_check(&crate_dir).output().unwrap();
+    let elapsed = start.elapsed();
+    assert!(
+        output.status.success(),
+        "the large impl should compile:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    eprintln!("checking an impl with {METHODS} methods took {elapsed:.2?}");
+}
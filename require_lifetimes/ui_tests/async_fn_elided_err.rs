@@ -0,0 +1,12 @@
+use require_lifetimes::require_lifetimes;
+use std::collections::HashMap;
+
+#[require_lifetimes]
+async fn lookup(map: &HashMap<String, String>, key: &str) -> Option<&String> {
+    map.get(key)
+}
+
+fn main() {
+    let map = HashMap::new();
+    let _ = async { lookup(&map, "key").await };
+}
@@ -0,0 +1,26 @@
+use require_lifetimes::require_lifetimes;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+#[require_lifetimes]
+async fn lookup<'a>(map: &'a HashMap<String, String>, key: &'a str) -> Option<&'a String> {
+    map.get(key)
+}
+
+/// This polls `future` once, which is all it takes here since nothing in
+/// it ever waits, so no async runtime is needed.
+fn poll_once<F: Future>(future: F) -> F::Output {
+    let mut context = Context::from_waker(Waker::noop());
+    match pin!(future).poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("the future should not wait"),
+    }
+}
+
+fn main() {
+    let map = HashMap::from([("key".to_string(), "value".to_string())]);
+    let found = poll_once(async { lookup(&map, "key").await });
+    assert_eq!(found, Some(&"value".to_string()));
+}
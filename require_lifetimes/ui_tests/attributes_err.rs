@@ -0,0 +1,16 @@
+// The attributes on an annotated function have to survive its expansion,
+// which these denied lints show.
+#![deny(deprecated, unused_must_use)]
+
+use require_lifetimes::require_lifetimes;
+
+#[deprecated(note = "use `second` instead")]
+#[require_lifetimes]
+#[must_use]
+fn first<'a>(text: &'a str) -> &'a str {
+    text
+}
+
+fn main() {
+    first("x");
+}
@@ -0,0 +1,66 @@
+//! Every attribute on an annotated item, and on its parameters, has to come
+//! out of the expansion as it went in. This crate denies missing docs, so
+//! the doc comments have to survive too.
+#![deny(missing_docs)]
+
+use require_lifetimes::require_lifetimes;
+
+/// Only compiled in tests, whichever side of the attribute the `cfg` is on.
+#[cfg(test)]
+#[require_lifetimes]
+pub fn only_in_test<'a>(text: &'a str) -> &'a str {
+    text
+}
+
+/// Only compiled in tests, whichever side of the attribute the `cfg` is on.
+#[require_lifetimes]
+#[cfg(test)]
+pub fn also_only_in_test<'a>(text: &'a str) -> &'a str {
+    text
+}
+
+/// Stands in for `only_in_test` outside of tests.
+#[cfg(not(test))]
+pub fn only_in_test(_text: &str) -> &'static str {
+    "not a test"
+}
+
+/// Stands in for `also_only_in_test` outside of tests.
+#[cfg(not(test))]
+pub fn also_only_in_test(_text: &str) -> &'static str {
+    "not a test"
+}
+
+/// Keeps its `#[inline]`, and the parameter that's configured out.
+#[require_lifetimes]
+#[inline]
+pub fn second<'a>(#[cfg(any())] first: &'a str, #[allow(unused_mut)] mut text: &'a str) -> &'a str {
+    text = text.trim();
+    text
+}
+
+/// A type whose methods keep their attributes too.
+pub struct Wrapper(pub String);
+
+#[require_lifetimes]
+impl Wrapper {
+    /// Only compiled in tests.
+    #[cfg(test)]
+    pub fn get<'a>(&'a self) -> &'a str {
+        &self.0
+    }
+
+    /// Stands in for `get` outside of tests.
+    #[cfg(not(test))]
+    #[inline]
+    pub fn get<'a>(&'a self) -> &'a str {
+        "not a test"
+    }
+}
+
+fn main() {
+    assert_eq!(only_in_test("x"), "not a test");
+    assert_eq!(also_only_in_test("x"), "not a test");
+    assert_eq!(second(" y "), "y");
+    assert_eq!(Wrapper("z".to_string()).get(), "not a test");
+}
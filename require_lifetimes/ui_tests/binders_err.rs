@@ -0,0 +1,21 @@
+use require_lifetimes::require_lifetimes;
+
+trait Parse<'text> {
+    fn parse(&self, text: &'text str) -> &'text str;
+}
+
+// The elided references are still caught, and the suggestion doesn't count
+// `'a` from the `for<'a>` as an input, or reuse its name.
+#[require_lifetimes]
+fn parse_with(parser: impl for<'a> Parse<'a>, text: &str) -> &str {
+    parser.parse(text)
+}
+
+// The `for<'a>` shadows the function's `'a`, which the compiler rejects, and
+// using it there doesn't count as using the function's.
+#[require_lifetimes]
+fn shadowed<'a>(function: impl for<'a> Fn(&'a str) -> &'a str) -> usize {
+    function("a").len()
+}
+
+fn main() {}
@@ -0,0 +1,75 @@
+use require_lifetimes::require_lifetimes;
+
+trait Parse<'text> {
+    fn parse(&self, text: &'text str) -> &'text str;
+}
+
+struct Trim;
+
+impl<'text> Parse<'text> for Trim {
+    fn parse(&self, text: &'text str) -> &'text str {
+        text.trim()
+    }
+}
+
+#[require_lifetimes(require_bounds)]
+fn apply<'a, F>(function: F, text: &'a str) -> &'a str
+where
+    F: for<'x> Fn(&'x str) -> &'x str,
+{
+    function(text)
+}
+
+#[require_lifetimes(require_bounds)]
+fn apply_where<'a, F>(function: F, text: &'a str) -> &'a str
+where
+    for<'x> F: Parse<'x>,
+{
+    function.parse(text)
+}
+
+#[require_lifetimes(require_bounds)]
+fn call<'a>(function: &'a (dyn for<'x> Fn(&'x str) + 'a), text: &'a str) {
+    function(text)
+}
+
+#[require_lifetimes(require_bounds)]
+fn nested<'a>(
+    function: &'a (dyn for<'x> Fn(&'x str, &'x dyn for<'y> Fn(&'x str, &'y str) -> bool) -> bool
+              + 'a),
+) -> bool {
+    function("a", &|first, second| first == second)
+}
+
+#[require_lifetimes(require_bounds)]
+fn parser<'a>(parser: &'a (dyn for<'x> Parse<'x> + 'a)) -> &'a (dyn for<'x> Parse<'x> + 'a) {
+    parser
+}
+
+#[require_lifetimes(require_bounds)]
+fn parse_with<'a, 'b>(parser: impl for<'x> Parse<'x> + 'b, text: &'a str) -> &'a str {
+    parser.parse(text)
+}
+
+#[require_lifetimes]
+struct Callbacks<'a> {
+    text: &'a str,
+    check: Box<dyn for<'x> Fn(&'x str) -> bool + 'a>,
+    parse: &'a dyn for<'x> Parse<'x>,
+}
+
+fn main() {
+    assert_eq!(apply(str::trim, " a "), "a");
+    assert_eq!(apply_where(Trim, " a "), "a");
+    call(&|text| assert_eq!(text, "a"), "a");
+    assert!(nested(&|text, check| check(text, "a")));
+    assert_eq!(parser(&Trim).parse(" a "), "a");
+    assert_eq!(parse_with(Trim, " a "), "a");
+
+    let callbacks = Callbacks {
+        text: " a ",
+        check: Box::new(|text| text.is_empty()),
+        parse: &Trim,
+    };
+    assert!(!(callbacks.check)(callbacks.parse.parse(callbacks.text)));
+}
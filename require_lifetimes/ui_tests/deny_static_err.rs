@@ -0,0 +1,26 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(deny_static)]
+fn first_word(text: &'static str) -> &'static str {
+    text.split(' ').next().unwrap()
+}
+
+#[require_lifetimes(deny_static)]
+fn boxed<'a>(text: &'a str, callback: Box<dyn Fn(&str) -> bool + 'static>) -> bool {
+    callback(text)
+}
+
+struct Text(String);
+
+#[require_lifetimes(deny_static)]
+impl Text {
+    fn leaked(self) -> &'static str {
+        self.0.leak()
+    }
+
+    fn trimmed(&self) -> &'static str {
+        "trimmed"
+    }
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+use require_lifetimes::require_lifetimes;
+
+use std::fmt::Display;
+
+#[require_lifetimes]
+fn greeting<'a>(name: &'a str) -> &'static str {
+    if name.is_empty() {
+        "hello"
+    } else {
+        "hi"
+    }
+}
+
+#[require_lifetimes(deny_static)]
+fn first_word<'a>(text: &'a str) -> &'a str {
+    text.split(' ').next().unwrap()
+}
+
+#[require_lifetimes(deny_static)]
+fn show<'a, T: Display + 'static>(value: &'a T) -> String {
+    value.to_string()
+}
+
+#[require_lifetimes(deny_static)]
+fn boxed<'a>(text: &'a str, callback: Box<dyn Fn(&str) -> bool + 'a>) -> bool {
+    callback(text)
+}
+
+fn main() {
+    assert_eq!(greeting("you"), "hi");
+    assert_eq!(first_word("a b"), "a");
+    assert_eq!(show(&1), "1");
+    assert!(boxed("", Box::new(str::is_empty)));
+}
@@ -0,0 +1,21 @@
+use require_lifetimes::require_lifetimes;
+
+struct Token<'a>(&'a str);
+
+#[require_lifetimes(deny_wildcard)]
+fn in_reference<'a>(text: &'a str, _other: &'_ str) -> &'a str {
+    text
+}
+
+#[require_lifetimes(deny_wildcard)]
+fn in_generics<'a>(token: Token<'_>, text: &'a str) -> &'a str {
+    let _ = token;
+    text
+}
+
+#[require_lifetimes(deny_wildcard)]
+fn in_bound<'a>(text: &'a str) -> impl Iterator<Item = char> + '_ {
+    text.chars()
+}
+
+fn main() {}
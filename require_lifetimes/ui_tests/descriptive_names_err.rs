@@ -0,0 +1,25 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(descriptive_names)]
+fn first_word<'a>(text: &'a str) -> &'a str {
+    text.split(' ').next().unwrap()
+}
+
+#[require_lifetimes(descriptive_names)]
+struct Matcher<'m> {
+    text: &'m str,
+}
+
+#[require_lifetimes(descriptive_names)]
+impl<'m> Matcher<'m> {
+    fn text<'s>(&'s self) -> &'m str {
+        self.text
+    }
+}
+
+#[require_lifetimes(descriptive_names)]
+trait Split<'t> {
+    fn split_at<'text>(&'text self, text: &'t str) -> &'t str;
+}
+
+fn main() {}
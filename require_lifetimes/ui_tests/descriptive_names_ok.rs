@@ -0,0 +1,42 @@
+use require_lifetimes::require_lifetimes;
+
+// Without `descriptive_names`, one-letter names are fine. With it, longer
+// ones are, as are `'static` and `'_` (which aren't declared).
+
+#[require_lifetimes]
+fn first_word<'a>(text: &'a str) -> &'a str {
+    text.split(' ').next().unwrap()
+}
+
+#[require_lifetimes(descriptive_names)]
+fn longest<'text>(first: &'text str, second: &'text str) -> &'text str {
+    if first.len() > second.len() {
+        first
+    } else {
+        second
+    }
+}
+
+#[require_lifetimes(descriptive_names)]
+fn greeting<'name>(name: &'name str) -> (&'name str, &'static str) {
+    (name, "hello")
+}
+
+#[require_lifetimes(descriptive_names)]
+struct Matcher<'matcher> {
+    text: &'matcher str,
+}
+
+#[require_lifetimes(descriptive_names)]
+impl<'matcher> Matcher<'matcher> {
+    fn text<'me>(&'me self) -> &'matcher str {
+        self.text
+    }
+}
+
+fn main() {
+    assert_eq!(first_word("a b"), "a");
+    assert_eq!(longest("ab", "c"), "ab");
+    assert_eq!(greeting("you"), ("you", "hello"));
+    assert_eq!(Matcher { text: "m" }.text(), "m");
+}
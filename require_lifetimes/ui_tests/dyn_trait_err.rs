@@ -0,0 +1,30 @@
+use require_lifetimes::require_lifetimes;
+
+use std::fmt::Display;
+
+struct Store {
+    callbacks: Vec<Box<dyn Fn(&str) -> bool>>,
+}
+
+#[require_lifetimes]
+impl Store {
+    fn store<'a>(&'a mut self, callback: Box<dyn for<'x> Fn(&'x str) -> bool>) {
+        self.callbacks.push(callback);
+    }
+
+    fn store_sugar<'a>(&'a mut self, callback: Box<dyn Fn(&str) -> bool>) {
+        self.callbacks.push(callback);
+    }
+}
+
+#[require_lifetimes]
+fn show<'a>(value: &'a dyn Display) -> String {
+    value.to_string()
+}
+
+#[require_lifetimes]
+fn boxed<'a>(text: &'a str) -> Box<dyn Display> {
+    Box::new(text.len())
+}
+
+fn main() {}
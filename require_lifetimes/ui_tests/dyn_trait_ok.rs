@@ -0,0 +1,45 @@
+use require_lifetimes::require_lifetimes;
+
+use std::fmt::Display;
+
+struct Store<'s> {
+    callbacks: Vec<Box<dyn for<'x> Fn(&'x str) -> bool + 's>>,
+}
+
+#[require_lifetimes]
+impl<'s> Store<'s> {
+    fn store<'a>(&'a mut self, callback: Box<dyn for<'x> Fn(&'x str) -> bool + 's>) {
+        self.callbacks.push(callback);
+    }
+
+    fn store_static<'a>(&'a mut self, callback: Box<dyn for<'x> Fn(&'x str) -> bool + 'static>) {
+        self.callbacks.push(callback);
+    }
+}
+
+#[require_lifetimes]
+fn show<'a>(value: &'a (dyn Display + 'a)) -> String {
+    value.to_string()
+}
+
+#[require_lifetimes]
+fn boxed<'a>(text: &'a str) -> Box<dyn Display + 'a> {
+    Box::new(text)
+}
+
+// Without reference inputs, there's nothing else a trait object could
+// borrow from, so it doesn't need a bound.
+#[require_lifetimes]
+fn owned(value: Box<dyn Display>) -> String {
+    value.to_string()
+}
+
+fn main() {
+    let mut store = Store { callbacks: Vec::new() };
+    store.store(Box::new(|text| text.is_empty()));
+    store.store_static(Box::new(|text| text.len() > 1));
+    assert!((store.callbacks[0])(""));
+    assert_eq!(show(&1), "1");
+    assert_eq!(boxed("ab").to_string(), "ab");
+    assert_eq!(owned(Box::new(2)), "2");
+}
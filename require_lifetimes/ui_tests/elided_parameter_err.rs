@@ -0,0 +1,9 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn split_words(text: &str, #[elided] scratch: &mut Vec<usize>, separator: &str) -> &str {
+    scratch.push(separator.len());
+    text
+}
+
+fn main() {}
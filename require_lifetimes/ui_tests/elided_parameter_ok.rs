@@ -0,0 +1,35 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn split_words<'a>(text: &'a str, #[elided] scratch: &mut Vec<&'a str>) -> Option<&'a str> {
+    scratch.extend(text.split(' '));
+    scratch.first().copied()
+}
+
+struct Splitter(char);
+
+#[require_lifetimes]
+impl Splitter {
+    fn split<'a, 'b>(&'a self, text: &'b str, #[elided] scratch: &mut Vec<usize>) -> &'b str {
+        scratch.push(text.len());
+        text.split(self.0).next().unwrap()
+    }
+}
+
+#[require_lifetimes]
+trait Counter {
+    fn count<'a>(#[elided] &self, text: &'a str) -> usize;
+}
+
+impl Counter for Splitter {
+    fn count<'a>(&self, text: &'a str) -> usize {
+        text.split(self.0).count()
+    }
+}
+
+fn main() {
+    let mut scratch = Vec::new();
+    assert_eq!(split_words("a b", &mut scratch), Some("a"));
+    assert_eq!(Splitter(',').split("a,b", &mut Vec::new()), "a");
+    assert_eq!(Splitter(',').count("a,b"), 2);
+}
@@ -0,0 +1,132 @@
+use require_lifetimes::require_lifetimes;
+
+// These signatures are copied from the finale, so the macro has to keep
+// accepting the book's own code.
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MatcherToken<'a> {
+    RawText(&'a str),
+    OneOfText(Box<[&'a str]>),
+    WildCard,
+}
+
+impl<'a> MatcherToken<'a> {
+    #[require_lifetimes]
+    pub fn min_match_len<'b>(&'b self) -> usize {
+        match self {
+            MatcherToken::RawText(text) => text.len(),
+            MatcherToken::OneOfText(options) => {
+                options.iter().map(|option| option.len()).min().unwrap_or(0)
+            }
+            MatcherToken::WildCard => 1,
+        }
+    }
+}
+
+pub struct Matcher<'a> {
+    text: &'a str,
+    tokens: Vec<MatcherToken<'a>>,
+    most_tokens_matched: usize,
+}
+
+pub struct FindIter<'a, 'internal, 'b> {
+    tokens: &'a [MatcherToken<'internal>],
+    most_tokens_matched: &'a mut usize,
+    haystack: &'b str,
+    position: usize,
+}
+
+#[require_lifetimes]
+impl<'internal> Matcher<'internal> {
+    pub fn new(text: &'internal str) -> Option<Matcher<'internal>> {
+        let tokens = text.split('.').map(MatcherToken::RawText).collect();
+        Some(Self::from_tokens(text, tokens))
+    }
+
+    fn from_tokens(
+        text: &'internal str,
+        tokens: Vec<MatcherToken<'internal>>,
+    ) -> Matcher<'internal> {
+        Matcher {
+            text,
+            tokens,
+            most_tokens_matched: 0,
+        }
+    }
+
+    fn find_either<'a>(string: &'a str, first: u8, second: u8) -> Option<usize> {
+        string
+            .bytes()
+            .position(|byte| byte == first || byte == second)
+    }
+
+    pub fn is_match<'a, 'b>(&'a self, string: &'b str) -> bool {
+        self.text == string
+    }
+
+    pub fn match_string<'a, 'b>(
+        &'a mut self,
+        string: &'b str,
+    ) -> Vec<(&'a MatcherToken<'internal>, &'b str)> {
+        let matched_tokens = Self::match_tokens(&self.tokens, string);
+        self.most_tokens_matched = self.most_tokens_matched.max(matched_tokens.len());
+        matched_tokens
+    }
+
+    pub fn find_iter<'a, 'b>(&'a mut self, haystack: &'b str) -> FindIter<'a, 'internal, 'b> {
+        FindIter {
+            tokens: &self.tokens,
+            most_tokens_matched: &mut self.most_tokens_matched,
+            haystack,
+            position: 0,
+        }
+    }
+
+    fn match_tokens<'a, 'b, 'c>(
+        tokens: &'a [MatcherToken<'b>],
+        string: &'c str,
+    ) -> Vec<(&'a MatcherToken<'b>, &'c str)> {
+        tokens.iter().map(|token| (token, string)).collect()
+    }
+
+    fn match_wild_card<'a, 'b, 'c, 'd, 'e>(
+        token: &'a MatcherToken<'b>,
+        matched_tokens: &'c mut Vec<(&'a MatcherToken<'b>, &'d str)>,
+        string: &'e mut &'d str,
+    ) -> bool {
+        match string.chars().next() {
+            Some(next_char) => {
+                let (matched, rest) = string.split_at(next_char.len_utf8());
+                matched_tokens.push((token, matched));
+                *string = rest;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a, 'internal, 'b> FindIter<'a, 'internal, 'b> {
+    #[require_lifetimes]
+    fn next_candidate_position<'c>(&'c self) -> Option<usize> {
+        (self.position <= self.haystack.len()).then_some(self.position)
+    }
+}
+
+fn main() {
+    let mut matcher = Matcher::new("abc").unwrap();
+    assert!(matcher.is_match("abc"));
+    assert_eq!(matcher.match_string("abc").len(), 1);
+    assert_eq!(Matcher::find_either("a.b", b'.', b'('), Some(1));
+    assert_eq!(MatcherToken::WildCard.min_match_len(), 1);
+
+    let mut matched_tokens = Vec::new();
+    let mut rest = "xy";
+    assert!(Matcher::match_wild_card(&MatcherToken::WildCard, &mut matched_tokens, &mut rest));
+    assert_eq!(rest, "y");
+
+    let iter = matcher.find_iter("abc");
+    assert_eq!(iter.next_candidate_position(), Some(0));
+    assert_eq!(iter.tokens.len(), 1);
+    assert_eq!(*iter.most_tokens_matched, 1);
+}
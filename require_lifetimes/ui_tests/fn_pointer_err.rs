@@ -0,0 +1,13 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn apply(function: fn(&str) -> &str, text: &str) -> &str {
+    function(text)
+}
+
+#[require_lifetimes]
+fn call_bound(function: impl for<'x> Fn(&'x str) -> &'x str, text: &str) -> &str {
+    function(text)
+}
+
+fn main() {}
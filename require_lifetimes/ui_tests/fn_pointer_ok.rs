@@ -0,0 +1,49 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn apply<'a>(function: fn(&str) -> &str, text: &'a str) -> &'a str {
+    function(text)
+}
+
+#[require_lifetimes]
+fn apply_bound<'a>(function: for<'x> fn(&'x str) -> &'x str, text: &'a str) -> &'a str {
+    function(text)
+}
+
+#[require_lifetimes]
+fn apply_outer<'a>(function: fn(&'a str) -> &'a str, text: &'a str) -> &'a str {
+    function(text)
+}
+
+#[require_lifetimes]
+fn call<'a>(function: impl Fn(&str) -> &str, text: &'a str) -> &'a str {
+    function(text)
+}
+
+#[require_lifetimes]
+fn call_bound<'a>(function: impl for<'x> Fn(&'x str) -> &'x str, text: &'a str) -> &'a str {
+    function(text)
+}
+
+#[require_lifetimes]
+fn call_outer<'a, F>(function: F, text: &'a str) -> &'a str
+where
+    F: Fn(&'a str) -> &'a str,
+{
+    function(text)
+}
+
+#[require_lifetimes]
+fn trimmer() -> fn(&str) -> &str {
+    str::trim
+}
+
+fn main() {
+    assert_eq!(apply(str::trim, " a "), "a");
+    assert_eq!(apply_bound(str::trim, " a "), "a");
+    assert_eq!(apply_outer(str::trim, " a "), "a");
+    assert_eq!(call(str::trim, " a "), "a");
+    assert_eq!(call_bound(str::trim, " a "), "a");
+    assert_eq!(call_outer(str::trim, " a "), "a");
+    assert_eq!(trimmer()(" a "), "a");
+}
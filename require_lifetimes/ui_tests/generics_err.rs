@@ -0,0 +1,24 @@
+use require_lifetimes::require_lifetimes;
+
+use std::fmt::Display;
+
+#[require_lifetimes]
+fn first<T>(items: &[T]) -> Option<&T> {
+    items.first()
+}
+
+#[require_lifetimes]
+fn show<T: Display>(value: &T) -> String {
+    value.to_string()
+}
+
+struct Stack<T>(Vec<T>);
+
+#[require_lifetimes]
+impl<T> Stack<T> {
+    fn peek(&self) -> Option<&T> {
+        self.0.last()
+    }
+}
+
+fn main() {}
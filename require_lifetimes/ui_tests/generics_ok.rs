@@ -0,0 +1,44 @@
+use require_lifetimes::require_lifetimes;
+
+use std::fmt::Display;
+
+#[require_lifetimes]
+fn first<'a, T>(items: &'a [T]) -> Option<&'a T> {
+    items.first()
+}
+
+#[require_lifetimes]
+fn show<'a, T: Display>(value: &'a T) -> String {
+    value.to_string()
+}
+
+#[require_lifetimes]
+fn longest<'a, T>(items: &'a [T]) -> Option<&'a T>
+where
+    T: AsRef<str> + 'a,
+{
+    items.iter().max_by_key(|item| item.as_ref().len())
+}
+
+struct Stack<T>(Vec<T>);
+
+#[require_lifetimes]
+impl<T: Clone> Stack<T> {
+    fn peek<'a>(&'a self) -> Option<&'a T> {
+        self.0.last()
+    }
+
+    fn push_all<'a, 'b>(&'a mut self, items: &'b [T]) {
+        self.0.extend_from_slice(items);
+    }
+}
+
+fn main() {
+    assert_eq!(first(&[1, 2]), Some(&1));
+    assert_eq!(show(&3), "3");
+    assert_eq!(longest(&["a", "bcd", "ef"]), Some(&"bcd"));
+
+    let mut stack = Stack(vec![1]);
+    stack.push_all(&[2, 3]);
+    assert_eq!(stack.peek(), Some(&3));
+}
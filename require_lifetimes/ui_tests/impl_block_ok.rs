@@ -0,0 +1,26 @@
+use require_lifetimes::require_lifetimes;
+
+struct Pair(i32, i32);
+
+#[require_lifetimes]
+impl Pair {
+    fn first<'a, 'b>(&'a self, _other: &'b i32) -> &'a i32 {
+        &self.0
+    }
+
+    #[require_lifetimes(skip)]
+    fn second(&self, _other: &i32) -> &i32 {
+        &self.1
+    }
+
+    fn sum<'a>(&'a self) -> i32 {
+        self.0 + self.1
+    }
+}
+
+fn main() {
+    let pair = Pair(1, 2);
+    assert_eq!(pair.first(&3), &1);
+    assert_eq!(pair.second(&3), &2);
+    assert_eq!(pair.sum(), 3);
+}
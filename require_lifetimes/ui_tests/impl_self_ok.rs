@@ -0,0 +1,83 @@
+use require_lifetimes::require_lifetimes;
+
+// Inside an `impl`, its own lifetimes are in scope for every method, and
+// `Self` (or `Self::Assoc`) stands for a type whose lifetimes are already
+// written in the `impl` header, so none of these need anything more. Bounds
+// on the `impl`'s lifetimes can be written on a method, too.
+
+pub struct Matcher<'internal> {
+    text: &'internal str,
+}
+
+#[require_lifetimes(require_bounds)]
+impl<'internal> Matcher<'internal> {
+    pub fn new(text: &'internal str) -> Option<Matcher<'internal>> {
+        Some(Matcher { text })
+    }
+
+    pub fn with_text(text: &'internal str) -> Self {
+        Matcher { text }
+    }
+
+    pub fn maybe(text: &'internal str) -> Option<Self> {
+        (!text.is_empty()).then(|| Self::with_text(text))
+    }
+
+    pub fn text<'a>(&'a self) -> &'internal str {
+        self.text
+    }
+
+    pub fn pair<'a, 'b>(&'a self, other: &'b str) -> Vec<(&'a Matcher<'internal>, &'b str)>
+    where
+        'internal: 'a,
+    {
+        vec![(self, other)]
+    }
+
+    pub fn split<'a>(&'a self) -> (Self, Self) {
+        let (left, right) = self.text.split_at(self.text.len() / 2);
+        (Self::with_text(left), Self::with_text(right))
+    }
+}
+
+pub trait Source {
+    type Item;
+    type Part<'a>
+    where
+        Self: 'a;
+
+    fn item<'a>(&'a self) -> Self::Item;
+    fn part<'a>(&'a self) -> Self::Part<'a>;
+}
+
+#[require_lifetimes]
+impl<'internal> Source for Matcher<'internal> {
+    type Item = &'internal str;
+    type Part<'a> = &'a str where Self: 'a;
+
+    fn item<'a>(&'a self) -> Self::Item {
+        self.text
+    }
+
+    fn part<'a>(&'a self) -> Self::Part<'a> {
+        &self.text[..1]
+    }
+}
+
+#[require_lifetimes]
+impl<'internal> Matcher<'internal> {
+    fn first_item<'a>(&'a self) -> <Self as Source>::Item {
+        self.item()
+    }
+}
+
+fn main() {
+    let matcher = Matcher::new("abcd").unwrap();
+    assert_eq!(Matcher::maybe("").map(|matcher| matcher.text), None);
+    assert_eq!(matcher.text(), "abcd");
+    assert_eq!(matcher.pair("x")[0].1, "x");
+    assert_eq!(matcher.split().1.text, "cd");
+    assert_eq!(matcher.item(), "abcd");
+    assert_eq!(matcher.part(), "a");
+    assert_eq!(matcher.first_item(), "abcd");
+}
@@ -0,0 +1,29 @@
+use require_lifetimes::require_lifetimes;
+
+enum MatcherToken<'a> {
+    OneOfText(Vec<&'a str>),
+}
+
+#[require_lifetimes]
+fn match_one_of_text_exhaustive<'a, 'b, 'c>(
+    options: &'a [&'b str],
+    token: &'c MatcherToken<'b>,
+    index: usize,
+    string: &'a str,
+) -> impl DoubleEndedIterator<Item = (usize, &'c MatcherToken<'b>, &'b str)>
+where
+    'c: 'a,
+{
+    options
+        .iter()
+        .filter(|&option| string.starts_with(option))
+        .map(move |&option| (index, token, option))
+}
+
+#[require_lifetimes]
+fn options<'a>(token: &'a MatcherToken<'a>) -> impl Iterator<Item = &str> + 'a {
+    let MatcherToken::OneOfText(options) = token;
+    options.iter().copied()
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use require_lifetimes::require_lifetimes;
+
+enum MatcherToken<'a> {
+    OneOfText(Vec<&'a str>),
+}
+
+#[require_lifetimes]
+fn match_one_of_text_exhaustive<'a, 'b, 'c>(
+    options: &'a [&'b str],
+    token: &'c MatcherToken<'b>,
+    index: usize,
+    string: &'a str,
+) -> impl DoubleEndedIterator<Item = (usize, &'c MatcherToken<'b>, &'b str)> + 'a
+where
+    'c: 'a,
+{
+    options
+        .iter()
+        .filter(|&option| string.starts_with(option))
+        .map(move |&option| (index, token, option))
+}
+
+#[require_lifetimes]
+fn count_up(limit: usize) -> impl Iterator<Item = usize> {
+    0..limit
+}
+
+fn main() {
+    let token = MatcherToken::OneOfText(vec!["a", "ab"]);
+    let MatcherToken::OneOfText(options) = &token;
+    assert_eq!(match_one_of_text_exhaustive(options, &token, 0, "abc").count(), 2);
+    assert_eq!(count_up(3).count(), 3);
+}
@@ -0,0 +1,16 @@
+use require_lifetimes::require_lifetimes;
+
+struct Token<'a>(&'a str);
+
+#[require_lifetimes]
+fn match_one_of_text<'a, 'b, 'c>(
+    options: &'a [&str],
+    token: &'b Token<'c>,
+    matched_tokens: &mut Vec<(&'b Token<'c>, &'a str)>,
+    string: &'a str,
+) -> Option<&'a str> {
+    let _ = (options, token, matched_tokens);
+    Some(string)
+}
+
+fn main() {}
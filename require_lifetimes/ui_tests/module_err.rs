@@ -0,0 +1,28 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(include_types)]
+mod text {
+    pub struct Named {
+        pub name: &'static str,
+    }
+
+    pub fn first(text: &str) -> Option<&str> {
+        text.split(' ').next()
+    }
+
+    pub trait Describe {
+        fn describe<'a>(&self) -> &'a str;
+    }
+
+    pub mod nested {
+        pub struct Counter(pub u32);
+
+        impl Counter {
+            pub fn get(&self) -> u32 {
+                self.0
+            }
+        }
+    }
+}
+
+fn main() {}
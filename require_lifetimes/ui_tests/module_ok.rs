@@ -0,0 +1,67 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(include_types)]
+mod text {
+    pub const SEPARATOR: char = ' ';
+
+    pub struct Words<'a> {
+        pub rest: &'a str,
+    }
+
+    impl<'a> Iterator for Words<'a> {
+        type Item = &'a str;
+
+        fn next<'b>(&'b mut self) -> Option<&'a str> {
+            let rest = self.rest.trim_start_matches(SEPARATOR);
+            if rest.is_empty() {
+                return None;
+            }
+            let end = rest.find(SEPARATOR).unwrap_or(rest.len());
+            self.rest = &rest[end..];
+            Some(&rest[..end])
+        }
+    }
+
+    impl Words<'_> {
+        #[require_lifetimes(skip)]
+        pub fn rest(&self) -> &str {
+            self.rest
+        }
+    }
+
+    pub fn words<'a>(text: &'a str) -> Words<'a> {
+        Words { rest: text }
+    }
+
+    #[require_lifetimes(skip)]
+    pub fn first(text: &str) -> Option<&str> {
+        words(text).next()
+    }
+
+    pub mod nested {
+        pub fn last<'a>(text: &'a str) -> Option<&'a str> {
+            super::words(text).last()
+        }
+    }
+}
+
+// Without `include_types`, only functions, impls and traits are checked.
+#[require_lifetimes]
+mod types_unchecked {
+    pub struct Named {
+        pub name: &'static str,
+    }
+
+    pub fn name<'a>(named: &'a Named) -> &'static str {
+        named.name
+    }
+}
+
+fn main() {
+    assert_eq!(text::words("a bc  d").collect::<Vec<_>>(), ["a", "bc", "d"]);
+    assert_eq!(text::first("x y"), Some("x"));
+    assert_eq!(text::words("x y").rest(), "x y");
+    assert_eq!(text::nested::last("x y"), Some("y"));
+    let named = types_unchecked::Named { name: "n" };
+    assert_eq!(types_unchecked::name(&named), "n");
+}
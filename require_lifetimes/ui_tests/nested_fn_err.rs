@@ -0,0 +1,27 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn outer<'a>(text: &'a str) -> &'a str {
+    fn compliant<'b>(text: &'b str) -> &'b str {
+        text.trim()
+    }
+
+    fn inner(text: &str) -> &str {
+        text
+    }
+
+    struct Wrapper(String);
+
+    impl Wrapper {
+        fn get(&self) -> &str {
+            &self.0
+        }
+    }
+
+    let _ = Wrapper(String::new()).get();
+    inner(compliant(text))
+}
+
+fn main() {
+    assert_eq!(outer(" x "), "x");
+}
@@ -0,0 +1,37 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn outer<'a>(text: &'a str) -> &'a str {
+    fn compliant<'b>(text: &'b str) -> &'b str {
+        text.trim()
+    }
+
+    #[require_lifetimes(skip)]
+    fn skipped(text: &str) -> &str {
+        text
+    }
+
+    struct Wrapper<'w>(&'w str);
+
+    impl<'w> Wrapper<'w> {
+        fn get<'b>(&'b self) -> &'w str {
+            self.0
+        }
+    }
+
+    Wrapper(skipped(compliant(text))).get()
+}
+
+#[require_lifetimes(skip_nested)]
+fn outer_skipping_nested<'a>(text: &'a str) -> &'a str {
+    fn inner(text: &str) -> &str {
+        text
+    }
+
+    inner(text)
+}
+
+fn main() {
+    assert_eq!(outer(" x "), "x");
+    assert_eq!(outer_skipping_nested("y"), "y");
+}
@@ -0,0 +1,23 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn join<'a>(parts: &'a Vec<&str>) -> &'a str {
+    parts[0]
+}
+
+#[require_lifetimes]
+fn first<'a>(parts: Option<&'a Vec<&str>>) -> Option<&'a str> {
+    parts.map(|parts| parts[0])
+}
+
+#[require_lifetimes]
+fn second<'a>(pair: (&str, &'a str)) -> &'a str {
+    pair.1
+}
+
+#[require_lifetimes]
+fn last<'a>(parts: [&'a str; 2]) -> Vec<&str> {
+    parts.to_vec()
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use require_lifetimes::require_lifetimes;
+
+use std::marker::PhantomData;
+
+#[require_lifetimes]
+fn join<'a, 'b>(parts: &'a Vec<&'b str>) -> &'b str {
+    parts[0]
+}
+
+#[require_lifetimes]
+fn first<'a, 'b>(parts: Option<&'a Vec<&'b str>>) -> Option<&'b str> {
+    parts.map(|parts| parts[0])
+}
+
+#[require_lifetimes]
+fn second<'a, 'b>(pair: (&'b str, &'a str)) -> &'a str {
+    pair.1
+}
+
+#[require_lifetimes]
+fn last<'a>(parts: [&'a str; 2]) -> Vec<&'a str> {
+    parts.to_vec()
+}
+
+#[require_lifetimes]
+fn apply<'a>(text: &'a str, function: fn(&str) -> usize, _marker: PhantomData<&u8>) -> usize {
+    function(text)
+}
+
+fn main() {
+    let parts = vec!["a", "b"];
+    assert_eq!(join(&parts), "a");
+    assert_eq!(first(Some(&parts)), Some("a"));
+    assert_eq!(second(("a", "b")), "b");
+    assert_eq!(last(["a", "b"]), parts);
+    assert_eq!(apply("abc", str::len, PhantomData), 3);
+}
@@ -0,0 +1,18 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(!)]
+fn keyword(r#fn: &str) -> &str {
+    r#fn
+}
+
+#[require_lifetimes(!)]
+fn destructure<'x>((&first, second): (&'x u8, &str), _: &str) -> u8 {
+    first
+}
+
+#[require_lifetimes(!)]
+fn r#match(mut r#in: &str) -> &str {
+    r#in
+}
+
+fn main() {}
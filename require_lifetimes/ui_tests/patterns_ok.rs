@@ -0,0 +1,37 @@
+use require_lifetimes::require_lifetimes;
+
+// Whatever the pattern a parameter is bound with, its type is what's
+// checked, and raw identifiers stay raw.
+
+#[require_lifetimes]
+fn keyword<'a>(r#fn: &'a str) -> &'a str {
+    r#fn
+}
+
+#[require_lifetimes]
+fn destructure<'x, 'y>((&first, second): (&'x u8, &'y str), _: &'y str) -> (u8, &'y str) {
+    (first, second)
+}
+
+struct Point<'a> {
+    name: &'a str,
+    x: i32,
+}
+
+#[require_lifetimes]
+fn fields<'a, 'b>(Point { name, x }: Point<'a>, [first, ..]: &'b [i32; 2]) -> (&'a str, i32) {
+    (name, x + first)
+}
+
+#[require_lifetimes]
+fn r#match<'a>(mut r#in: &'a str) -> &'a str {
+    r#in = r#in.trim();
+    r#in
+}
+
+fn main() {
+    assert_eq!(keyword("a"), "a");
+    assert_eq!(destructure((&1, "b"), "c"), (1, "b"));
+    assert_eq!(fields(Point { name: "p", x: 1 }, &[2, 3]), ("p", 3));
+    assert_eq!(r#match(" m "), "m");
+}
@@ -0,0 +1,23 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+const fn first(values: &[i32; 2]) -> &i32 {
+    &values[0]
+}
+
+#[require_lifetimes]
+extern "C" fn sum(values: &[i32; 2]) -> i32 {
+    values[0] + values[1]
+}
+
+#[require_lifetimes]
+pub const unsafe extern "C" fn pick(values: &[i32; 2], index: usize) -> &i32 {
+    &values[index]
+}
+
+#[require_lifetimes]
+extern "C" {
+    fn largest(values: &[i32; 2]) -> &i32;
+}
+
+fn main() {}
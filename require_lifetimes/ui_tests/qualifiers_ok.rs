@@ -0,0 +1,46 @@
+use require_lifetimes::require_lifetimes;
+
+use std::ffi::{c_char, c_int};
+
+#[require_lifetimes]
+const fn first<'a>(values: &'a [i32; 2]) -> &'a i32 {
+    &values[0]
+}
+
+#[require_lifetimes]
+unsafe fn second<'a>(values: &'a [i32; 2]) -> &'a i32 {
+    values.get_unchecked(1)
+}
+
+#[require_lifetimes]
+extern "C" fn sum<'a>(values: &'a [i32; 2]) -> i32 {
+    values[0] + values[1]
+}
+
+#[require_lifetimes]
+pub const unsafe extern "C" fn pick<'a>(values: &'a [i32; 2], index: usize) -> &'a i32 {
+    &values[index]
+}
+
+#[require_lifetimes]
+extern "C" {
+    fn printf(format: *const c_char, ...) -> c_int;
+    fn largest<'a>(values: &'a [i32; 2]) -> &'a i32;
+}
+
+// Each of these only compiles if the qualifier made it through the macro.
+const FIRST: i32 = *first(&[1, 2]);
+const PICKED: i32 = unsafe { *pick(&[1, 2], 1) };
+static SUM: extern "C" fn(&[i32; 2]) -> i32 = sum;
+static PICK: unsafe extern "C" fn(&[i32; 2], usize) -> &i32 = pick;
+static PRINTF: unsafe extern "C" fn(*const c_char, ...) -> c_int = printf;
+static LARGEST: unsafe extern "C" fn(&[i32; 2]) -> &i32 = largest;
+
+fn main() {
+    assert_eq!(FIRST, 1);
+    assert_eq!(PICKED, 2);
+    assert_eq!(unsafe { *second(&[1, 2]) }, 2);
+    assert_eq!(SUM(&[1, 2]), 3);
+    assert_eq!(unsafe { *PICK(&[1, 2], 0) }, 1);
+    let _ = (PRINTF, LARGEST);
+}
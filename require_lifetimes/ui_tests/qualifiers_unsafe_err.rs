@@ -0,0 +1,11 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+unsafe fn second<'a>(values: &'a [i32; 2]) -> &'a i32 {
+    values.get_unchecked(1)
+}
+
+fn main() {
+    // This fails, since `second` is still `unsafe` after the macro.
+    assert_eq!(*second(&[1, 2]), 2);
+}
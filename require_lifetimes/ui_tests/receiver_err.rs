@@ -0,0 +1,31 @@
+use require_lifetimes::require_lifetimes;
+
+use std::pin::Pin;
+
+struct Counter(u32);
+
+#[require_lifetimes]
+impl Counter {
+    fn shared(&self) -> u32 {
+        self.0
+    }
+
+    fn unique(&mut self) -> u32 {
+        self.0 += 1;
+        self.0
+    }
+
+    fn anonymous(&'_ self) -> u32 {
+        self.0
+    }
+
+    fn typed(self: &Self) -> u32 {
+        self.0
+    }
+
+    fn pinned(self: Pin<&mut Self>) -> u32 {
+        self.0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+use require_lifetimes::require_lifetimes;
+
+use std::pin::Pin;
+
+struct Counter(u32);
+
+#[require_lifetimes]
+impl Counter {
+    fn owned(self) -> u32 {
+        self.0
+    }
+
+    fn owned_mut(mut self) -> u32 {
+        self.0 += 1;
+        self.0
+    }
+
+    fn shared<'a>(&'a self) -> u32 {
+        self.0
+    }
+
+    fn unique<'a>(&'a mut self) -> u32 {
+        self.0 += 1;
+        self.0
+    }
+
+    fn typed<'a>(self: &'a Self) -> u32 {
+        self.0
+    }
+
+    fn pinned<'a>(self: Pin<&'a mut Self>) -> u32 {
+        self.0
+    }
+
+    fn boxed(self: Box<Self>) -> u32 {
+        self.0
+    }
+}
+
+fn main() {
+    let mut counter = Counter(0);
+    assert_eq!(counter.unique(), 1);
+    assert_eq!(counter.shared(), 1);
+    assert_eq!(counter.typed(), 1);
+    assert_eq!(Pin::new(&mut counter).pinned(), 1);
+    assert_eq!(Box::new(Counter(2)).boxed(), 2);
+    assert_eq!(Counter(3).owned_mut(), 4);
+    assert_eq!(counter.owned(), 1);
+}
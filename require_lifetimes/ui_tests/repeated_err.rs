@@ -0,0 +1,9 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(!)]
+#[require_lifetimes(!)]
+fn first(text: &str) -> &str {
+    text
+}
+
+fn main() {}
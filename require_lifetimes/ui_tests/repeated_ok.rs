@@ -0,0 +1,25 @@
+use require_lifetimes::require_lifetimes;
+
+// A macro that re-emits items can put the attribute on one twice, which
+// checks it the same as once.
+
+#[require_lifetimes]
+#[require_lifetimes]
+fn first<'a>(text: &'a str) -> &'a str {
+    text
+}
+
+struct Pair(i32, i32);
+
+#[require_lifetimes(deny_wildcard)]
+#[require_lifetimes(deny_wildcard)]
+impl Pair {
+    fn left<'a>(&'a self) -> &'a i32 {
+        &self.0
+    }
+}
+
+fn main() {
+    assert_eq!(first("a"), "a");
+    assert_eq!(*Pair(1, 2).left(), 1);
+}
@@ -0,0 +1,18 @@
+use require_lifetimes::require_lifetimes;
+
+struct Token<'a>(&'a str);
+
+#[require_lifetimes(require_bounds)]
+fn first<'a, 'b>(tokens: &'a [Token<'b>]) -> &'a Token<'b> {
+    &tokens[0]
+}
+
+#[require_lifetimes(require_bounds, deny_wildcard)]
+fn texts<'a, 'b, 'c>(tokens: &'a [&'b Token<'c>]) -> impl Iterator<Item = &'b Token<'c>> + 'a
+where
+    'c: 'b,
+{
+    tokens.iter().copied()
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use require_lifetimes::require_lifetimes;
+
+struct Token<'a>(&'a str);
+
+#[require_lifetimes(require_bounds)]
+fn first<'a, 'b: 'a>(tokens: &'a [Token<'b>]) -> &'a Token<'b> {
+    &tokens[0]
+}
+
+#[require_lifetimes(require_bounds)]
+fn texts<'a, 'b, 'c>(tokens: &'a [&'b Token<'c>]) -> impl Iterator<Item = &'b Token<'c>> + 'a
+where
+    'c: 'b,
+    'b: 'a,
+{
+    tokens.iter().copied()
+}
+
+#[require_lifetimes(require_bounds)]
+fn unrelated<'a, 'b>(text: &'a str, _other: &'b str) -> (&'a str, Token<'b>) {
+    (text, Token("b"))
+}
+
+// Without the option, nothing needs writing down.
+#[require_lifetimes]
+fn implied<'a, 'b>(tokens: &'a [Token<'b>]) -> &'a Token<'b> {
+    &tokens[0]
+}
+
+fn main() {
+    let tokens = [Token("a")];
+    assert_eq!(first(&tokens).0, "a");
+    assert_eq!(implied(&tokens).0, "a");
+    assert_eq!(texts(&[&tokens[0]]).count(), 1);
+    assert_eq!(unrelated("a", "b").0, "a");
+}
@@ -0,0 +1,23 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(skip)]
+fn split_words(text: &str, #[elided] scratch: &mut Vec<usize>) -> usize {
+    scratch.push(text.len());
+    text.split(' ').count()
+}
+
+struct Splitter(char);
+
+#[require_lifetimes]
+impl Splitter {
+    #[require_lifetimes(skip)]
+    fn split<'a>(&self, text: &'a str, #[elided] scratch: &mut Vec<usize>) -> &'a str {
+        scratch.push(text.len());
+        text.split(self.0).next().unwrap()
+    }
+}
+
+fn main() {
+    assert_eq!(split_words("a b", &mut Vec::new()), 2);
+    assert_eq!(Splitter(',').split("a,b", &mut Vec::new()), "a");
+}
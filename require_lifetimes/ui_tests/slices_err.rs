@@ -0,0 +1,18 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(!)]
+fn firsts<'a>(words: &'a [&str]) -> Vec<&str> {
+    words.iter().map(|word| &word[..1]).collect()
+}
+
+#[require_lifetimes(!)]
+fn pair(words: [&str; 2]) -> &str {
+    words[1]
+}
+
+#[require_lifetimes(!)]
+fn split_ref(text: &str, delimiter: &str) -> Vec<&str> {
+    text.split(delimiter).collect()
+}
+
+fn main() {}
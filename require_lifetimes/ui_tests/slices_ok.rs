@@ -0,0 +1,31 @@
+use require_lifetimes::require_lifetimes;
+
+// References inside slices, arrays and `Vec`s need their lifetimes as much
+// as the outer ones do.
+
+#[require_lifetimes]
+fn firsts<'a, 'b>(words: &'a [&'b str]) -> Vec<&'b str> {
+    words.iter().map(|word| &word[..1]).collect()
+}
+
+#[require_lifetimes]
+fn same<'a>(words: &'a [&'a str]) -> &'a str {
+    words[0]
+}
+
+#[require_lifetimes]
+fn pair<'a>(words: [&'a str; 2]) -> &'a str {
+    words[1]
+}
+
+#[require_lifetimes]
+fn split_ref<'a, 'b>(text: &'a str, delimiter: &'b str) -> Vec<&'a str> {
+    text.split(delimiter).collect()
+}
+
+fn main() {
+    assert_eq!(firsts(&["ab", "cd"]), ["a", "c"]);
+    assert_eq!(same(&["a"]), "a");
+    assert_eq!(pair(["a", "b"]), "b");
+    assert_eq!(split_ref("a,b", ","), ["a", "b"]);
+}
@@ -0,0 +1,35 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+struct InputData<'reference, 'matcher_token, 'str_to_match> {
+    tokens: &'reference [Token<'matcher_token>],
+    string: &'str_to_match str,
+    on_match: fn(&str) -> &str,
+    callback: Box<dyn Fn(&str) -> usize + 'reference>,
+}
+
+#[require_lifetimes]
+enum Token<'a> {
+    RawText(&'a str),
+    OneOfText(Box<[&'a str]>),
+    WildCard,
+}
+
+#[require_lifetimes(allow_static)]
+struct Named {
+    name: &'static str,
+}
+
+fn main() {
+    let tokens = [Token::RawText("a"), Token::OneOfText(Box::new(["b"])), Token::WildCard];
+    let data = InputData {
+        tokens: &tokens,
+        string: "ab",
+        on_match: |text| text,
+        callback: Box::new(|text| text.len()),
+    };
+    assert_eq!((data.on_match)(data.string), "ab");
+    assert_eq!((data.callback)("abc"), 3);
+    assert_eq!(data.tokens.len(), 3);
+    assert_eq!(Named { name: "x" }.name, "x");
+}
@@ -0,0 +1,17 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+struct Pair<'a> {
+    first: &'a str,
+    second: &'b str,
+    third: &str,
+    fourth: &'static str,
+}
+
+#[require_lifetimes]
+enum Token<'a> {
+    RawText(&'a str),
+    OneOfText { options: Vec<&'c str> },
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(!)]
+fn first_word(words: &[&str]) -> &str {
+    words[0]
+}
+
+fn main() {}
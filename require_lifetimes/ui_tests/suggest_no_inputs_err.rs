@@ -0,0 +1,8 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(!)]
+fn greeting(name: String) -> Option<&str> {
+    name.is_empty().then_some("hello")
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use require_lifetimes::require_lifetimes;
+
+struct Token<'a>(&'a str);
+
+#[require_lifetimes(!)]
+fn split_token<'a, T: Clone>(token: &'a Token<'_>, extra: &T) -> impl Iterator<Item = &str> + '_ {
+    let _ = extra.clone();
+    token.0.split(' ')
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use require_lifetimes::require_lifetimes;
+
+struct Greeter<'a> {
+    greeting: &'a str,
+}
+
+#[require_lifetimes(!)]
+impl<'a> Greeter<'a> {
+    fn pick(&self, names: &[&str], index: usize) -> (&str, &str) {
+        (self.greeting, names[index])
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use require_lifetimes::require_lifetimes;
+
+struct Text {
+    body: String,
+}
+
+#[require_lifetimes(!)]
+impl Text {
+    fn longest(&self, other: &str) -> &str {
+        if self.body.len() > other.len() {
+            &self.body
+        } else {
+            other
+        }
+    }
+}
+
+fn main() {}
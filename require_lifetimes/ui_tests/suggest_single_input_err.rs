@@ -0,0 +1,8 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(!)]
+fn first_word(text: &str, separators: Vec<char>) -> &str {
+    text.split(&separators[..]).next().unwrap()
+}
+
+fn main() {}
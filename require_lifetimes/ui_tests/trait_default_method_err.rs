@@ -0,0 +1,12 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+trait Named {
+    fn get<'a>(&'a self) -> &'a str;
+
+    fn first_char(&self, _fallback: &'_ str) -> Option<char> {
+        self.get().chars().next()
+    }
+}
+
+fn main() {}
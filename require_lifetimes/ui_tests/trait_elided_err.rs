@@ -0,0 +1,13 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+trait Named {
+    fn get(&self) -> &str;
+
+    fn get_or<'a>(&'a self, default: &'a str) -> &'a str {
+        let _ = default;
+        self.get()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+trait Named {
+    type Name;
+    const PREFIX: &'static str;
+
+    fn get<'a>(&'a self) -> &'a str;
+
+    fn get_or<'a>(&'a self, default: &'a str) -> &'a str {
+        let name = self.get();
+        if name.is_empty() {
+            default
+        } else {
+            name
+        }
+    }
+
+    fn len<'a>(&'a self) -> usize {
+        self.get().len()
+    }
+}
+
+struct Person;
+
+impl Named for Person {
+    type Name = String;
+    const PREFIX: &'static str = "Mx";
+
+    fn get<'a>(&'a self) -> &'a str {
+        "Sam"
+    }
+}
+
+fn main() {
+    assert_eq!(Person.get_or("nobody"), "Sam");
+    assert_eq!(Person.len(), 3);
+}
@@ -0,0 +1,20 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+struct Wrapper<'a>(&'a str, &str);
+
+#[require_lifetimes]
+enum MatcherToken<'a> {
+    RawText(&'a str),
+    OneOfText(Vec<&str>),
+    Pair(&'a str, &str),
+    WildCard,
+}
+
+#[require_lifetimes]
+enum Split<'text, 'delimiter> {
+    Found(&'text str, &'text str),
+    Missing,
+}
+
+fn main() {}
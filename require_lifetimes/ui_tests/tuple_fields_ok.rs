@@ -0,0 +1,32 @@
+use require_lifetimes::require_lifetimes;
+
+// Tuple structs and enum variants have their fields checked the same way as
+// named ones. A lifetime parameter only needs to be used by one variant.
+
+#[require_lifetimes]
+#[derive(Debug, PartialEq, Eq)]
+enum MatcherToken<'a> {
+    RawText(&'a str),
+    OneOfText(Vec<&'a str>),
+    WildCard,
+}
+
+#[require_lifetimes]
+struct Wrapper<'a, 'b>(&'a str, &'b str);
+
+#[require_lifetimes]
+enum Split<'text, 'delimiter> {
+    Found(&'text str, &'text str),
+    Missing { delimiter: &'delimiter str },
+}
+
+fn main() {
+    assert_eq!(MatcherToken::RawText("a"), MatcherToken::RawText("a"));
+    assert_ne!(MatcherToken::OneOfText(vec!["a"]), MatcherToken::WildCard);
+    let wrapper = Wrapper("a", "b");
+    assert_eq!((wrapper.0, wrapper.1), ("a", "b"));
+    let found = Split::Found("a", "b");
+    let missing = Split::Missing { delimiter: "," };
+    assert!(matches!(found, Split::Found("a", "b")));
+    assert!(matches!(missing, Split::Missing { delimiter: "," }));
+}
@@ -0,0 +1,12 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(!)]
+type Pair<'a> = (&'a str, &str);
+
+#[require_lifetimes]
+type Unused<'a, 'b, T> = Option<&'a T>;
+
+#[require_lifetimes]
+type Forever = &'static str;
+
+fn main() {}
@@ -0,0 +1,32 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+type Pair<'a> = (&'a str, &'a str);
+
+#[require_lifetimes]
+type Split<'a, 'b> = (&'a str, Vec<&'b str>);
+
+#[require_lifetimes]
+type Lookup<'a, T> = Option<&'a T>;
+
+#[require_lifetimes]
+type Callback<'a> = Box<dyn for<'x> Fn(&'x str) -> bool + 'a>;
+
+#[require_lifetimes]
+fn first<'a>(pair: Pair<'a>) -> &'a str {
+    pair.0
+}
+
+#[require_lifetimes]
+fn find<'a, 'b>(items: &'a [i32], target: &'b i32) -> Lookup<'a, i32> {
+    items.iter().find(|item| *item == target)
+}
+
+fn main() {
+    assert_eq!(first(("a", "b")), "a");
+    assert_eq!(find(&[1, 2], &2), Some(&2));
+    let split: Split = ("a", vec!["b"]);
+    assert_eq!(split.1, ["b"]);
+    let callback: Callback = Box::new(|text| text.is_empty());
+    assert!(callback(""));
+}
@@ -0,0 +1,28 @@
+use require_lifetimes::require_lifetimes;
+
+use std::fmt::Debug;
+
+// `'b` only appears in bounds, which still counts as using it.
+#[require_lifetimes]
+fn in_generics<'a, 'b: 'a>(x: &'a str) -> &'a str {
+    x
+}
+
+#[require_lifetimes]
+fn in_where_clause<'a, 'b>(x: &'a str) -> &'a str
+where
+    'a: 'b,
+{
+    x
+}
+
+#[require_lifetimes]
+fn in_type_bound<'a, 'b, T: Debug + 'b>(x: &'a T) -> &'a T {
+    x
+}
+
+fn main() {
+    assert_eq!(in_generics("x"), "x");
+    assert_eq!(in_where_clause("y"), "y");
+    assert_eq!(in_type_bound(&1), &1);
+}
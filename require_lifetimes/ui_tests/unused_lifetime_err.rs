@@ -0,0 +1,20 @@
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes]
+fn first<'a, 'b>(x: &'a str) -> &'a str {
+    x
+}
+
+struct Wrapper(String);
+
+#[require_lifetimes]
+impl Wrapper {
+    fn text<'a, 'b, 'c>(&'a self, _other: &'c str) -> &'a str {
+        &self.0
+    }
+}
+
+fn main() {
+    assert_eq!(first("x"), "x");
+    assert_eq!(Wrapper("y".to_string()).text("z"), "y");
+}
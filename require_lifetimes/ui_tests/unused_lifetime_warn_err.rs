@@ -0,0 +1,14 @@
+// Warnings can't be checked on their own, so this denies them to see
+// what they say.
+#![deny(deprecated)]
+
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(warn)]
+fn first<'a, 'b>(x: &'a str) -> &'a str {
+    x
+}
+
+fn main() {
+    assert_eq!(first("x"), "x");
+}
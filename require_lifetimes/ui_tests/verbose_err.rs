@@ -0,0 +1,35 @@
+use require_lifetimes::require_lifetimes;
+
+// `verbose` explains how the elision rules read each signature, as a
+// warning at its name, alongside the usual errors.
+
+#[require_lifetimes(verbose)]
+fn first_word(text: &str) -> &str {
+    text.split(' ').next().unwrap_or("")
+}
+
+struct Text {
+    body: String,
+}
+
+impl Text {
+    #[require_lifetimes(verbose)]
+    fn longest(&self, other: &str) -> &str {
+        if self.body.len() > other.len() {
+            &self.body
+        } else {
+            ""
+        }
+    }
+}
+
+#[require_lifetimes(verbose)]
+fn longest(x: &str, y: &str) -> &str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+fn main() {}
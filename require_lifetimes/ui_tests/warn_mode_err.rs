@@ -0,0 +1,14 @@
+// Warnings can't be checked on their own, so this denies them to see
+// what they say.
+#![deny(deprecated)]
+
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(warn)]
+fn identity(a: &i32) -> &i32 {
+    a
+}
+
+fn main() {
+    assert_eq!(identity(&3), &3);
+}
@@ -0,0 +1,40 @@
+use require_lifetimes::require_lifetimes;
+
+struct Pair(i32, i32);
+
+#[require_lifetimes(warn)]
+fn identity(a: &i32) -> &i32 {
+    a
+}
+
+#[require_lifetimes(warn)]
+impl Pair {
+    fn first(&self, _other: &i32) -> &i32 {
+        &self.0
+    }
+}
+
+impl Pair {
+    #[require_lifetimes(warn, deny_wildcard)]
+    fn second(&self, _other: &'_ i32) -> &i32 {
+        &self.1
+    }
+}
+
+#[require_lifetimes(warn)]
+trait Named {
+    fn name(&self) -> &str;
+}
+
+#[require_lifetimes(warn)]
+struct Label {
+    name: &'static str,
+}
+
+fn main() {
+    let pair = Pair(1, 2);
+    assert_eq!(identity(&3), &3);
+    assert_eq!(pair.first(&3), &1);
+    assert_eq!(pair.second(&3), &2);
+    assert_eq!(Label { name: "x" }.name, "x");
+}
@@ -0,0 +1,26 @@
+use require_lifetimes::require_lifetimes;
+
+struct Token<'a>(&'a str);
+
+#[require_lifetimes]
+fn in_generics<'a>(token: Token<'_>, text: &'a str) -> &'a str {
+    let _ = token;
+    text
+}
+
+#[require_lifetimes]
+fn in_bound<'a>(text: &'a str) -> impl Iterator<Item = char> + '_ {
+    text.chars()
+}
+
+#[require_lifetimes(deny_wildcard)]
+fn named<'a, 'b>(token: Token<'b>, text: &'a str) -> impl Iterator<Item = char> + 'a {
+    let _ = token;
+    text.chars()
+}
+
+fn main() {
+    assert_eq!(in_generics(Token("a"), "b"), "b");
+    assert_eq!(in_bound("ab").count(), 2);
+    assert_eq!(named(Token("a"), "bc").count(), 2);
+}
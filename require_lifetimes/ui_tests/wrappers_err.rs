@@ -0,0 +1,23 @@
+use require_lifetimes::require_lifetimes;
+
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::Rc;
+
+struct Parser {
+    text: String,
+}
+
+#[require_lifetimes]
+impl Parser {
+    fn rest(self: Pin<&mut Self>, _label: &str) -> &str {
+        &self.get_mut().text
+    }
+}
+
+#[require_lifetimes]
+fn shared(cell: Rc<RefCell<&str>>) -> usize {
+    cell.borrow().len()
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+use require_lifetimes::require_lifetimes;
+
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::Rc;
+
+// References are checked wherever they are in a parameter's type, including
+// inside `Box`, `Rc`, `RefCell` and `Pin`. A `self` that's a wrapped
+// reference, like `Pin<&mut Self>`, still counts as `self` for elision.
+
+struct Parser {
+    text: String,
+}
+
+#[require_lifetimes]
+impl Parser {
+    fn rest<'a, 'b>(self: Pin<&'a mut Self>, _label: &'b str) -> &'a str {
+        &self.get_mut().text
+    }
+
+    fn boxed<'a>(self: Box<&'a Self>) -> &'a str {
+        &self.text
+    }
+}
+
+#[require_lifetimes]
+fn shared<'a>(cell: Rc<RefCell<&'a str>>) -> &'a str {
+    *cell.borrow()
+}
+
+#[require_lifetimes]
+fn unboxed<'a>(text: Box<&'a str>) -> &'a str {
+    *text
+}
+
+fn main() {
+    let mut parser = Parser {
+        text: "rest".to_string(),
+    };
+    assert_eq!(Pin::new(&mut parser).rest("label"), "rest");
+    assert_eq!(Box::new(&parser).boxed(), "rest");
+    assert_eq!(shared(Rc::new(RefCell::new("cell"))), "cell");
+    assert_eq!(unboxed(Box::new("box")), "box");
+}
@@ -0,0 +1,310 @@
+//! This shows how an exercise differs from its solution, for when someone's
+//! stuck on it.
+
+use crate::manifest::Manifest;
+use crate::progress;
+use crate::verify::{check_unless_done, Outcome};
+use similar::{Algorithm, DiffTag};
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// How many unchanged lines to show around each change.
+const CONTEXT: usize = 3;
+
+/// Given the root of the repository, the manifest, and the arguments after
+/// `compare` (a chapter's number, and `--force`), show how the chapter's
+/// exercise differs from its solution. Unless it's forced, that's only done
+/// for an exercise that doesn't pass.
+pub fn command(root: &Path, manifest: &Manifest, args: &[String]) -> Result<bool, String> {
+    let force = args.iter().any(|arg| arg == "--force");
+    let Some(number) = args.iter().find(|arg| *arg != "--force") else {
+        return Err("say which chapter to compare, like `compare 5`".to_string());
+    };
+    let chapter = manifest.numbered(number)?;
+    let Some(package) = &chapter.package else {
+        return Err(format!("{} doesn't have an exercise", chapter.name));
+    };
+
+    if !force {
+        let mut progress = progress::read(&progress::path(root))?;
+        if let Outcome::Passed = check_unless_done(root, chapter, package, &mut progress)? {
+            println!("{} passes already, so yours is as good", chapter.name);
+            println!("as the solution. To see how they differ anyway, run");
+            println!("`compare {number} --force`.");
+            return Ok(false);
+        }
+    }
+
+    let differences = compare(
+        &root.join(chapter.exercise()).join("src"),
+        &root.join(chapter.solution()).join("src"),
+        std::io::stdout().is_terminal(),
+    )?;
+    if differences.is_empty() {
+        println!("Yours is the same as the solution, apart from whitespace.");
+    } else {
+        print!("{differences}");
+    }
+    Ok(true)
+}
+
+/// Given an exercise's `src`, its solution's, and whether to show the
+/// differences in colour, return them, file by file.
+fn compare(exercise: &Path, solution: &Path, color: bool) -> Result<String, String> {
+    let yours: BTreeMap<String, PathBuf> = progress::files(exercise)?.into_iter().collect();
+    let theirs: BTreeMap<String, PathBuf> = progress::files(solution)?.into_iter().collect();
+    let mut names: Vec<&String> = yours.keys().chain(theirs.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let mut differences = Vec::new();
+    for name in names {
+        match (yours.get(name), theirs.get(name)) {
+            (Some(yours), Some(theirs)) => {
+                let yours = read(yours)?;
+                let theirs = read(theirs)?;
+                differences.extend(diff(name, &yours, &theirs, color));
+            }
+            (Some(_), None) => differences.push(format!("Only yours has {name}.\n")),
+            (None, _) => differences.push(format!("Only the solution has {name}.\n")),
+        }
+    }
+    Ok(differences.join("\n"))
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|error| format!("couldn't read {}: {error}", path.display()))
+}
+
+/// Given a file's name, what it is in the exercise and in the solution, and
+/// whether to use colour, return how they differ, or nothing if it's only
+/// in whitespace. Unchanged lines are left out, apart from a few around
+/// each change, and lifetimes that differ are marked underneath.
+fn diff(name: &str, yours: &str, theirs: &str, color: bool) -> Option<String> {
+    let yours: Vec<&str> = yours.lines().collect();
+    let theirs: Vec<&str> = theirs.lines().collect();
+    // Blank lines, and how much whitespace there is, don't count, so the
+    // lines are compared without them, and these map back to the file.
+    let (your_lines, your_keys) = significant(&yours);
+    let (their_lines, their_keys) = significant(&theirs);
+
+    let ops = similar::capture_diff_slices(Algorithm::Myers, &your_keys, &their_keys);
+    if ops.iter().all(|op| op.tag() == DiffTag::Equal) {
+        return None;
+    }
+
+    let mut out = format!("--- {name} (yours)\n+++ {name} (the solution)\n");
+    for group in similar::group_diff_ops(ops, CONTEXT) {
+        let (_, old, new) = group[0].as_tag_tuple();
+        let line_number = |lines: &[usize], index: usize, all: &[&str]| {
+            lines.get(index).map_or(all.len(), |line| *line) + 1
+        };
+        out.push_str(&format!(
+            "@@ line {} of yours, {} of the solution @@\n",
+            line_number(&your_lines, old.start, &yours),
+            line_number(&their_lines, new.start, &theirs),
+        ));
+
+        for op in group {
+            let (tag, old, new) = op.as_tag_tuple();
+            let removed: Vec<&str> = old.map(|index| yours[your_lines[index]]).collect();
+            let added: Vec<&str> = new.map(|index| theirs[their_lines[index]]).collect();
+            if tag == DiffTag::Equal {
+                for line in removed {
+                    push_line(&mut out, ' ', line, None, color);
+                }
+                continue;
+            }
+
+            let lifetimes_in = |lines: &[&str]| -> Vec<String> {
+                let found = lines.iter().flat_map(|line| lifetimes(line));
+                found.map(|(_, lifetime)| lifetime).collect()
+            };
+            let (old_lifetimes, new_lifetimes) = (lifetimes_in(&removed), lifetimes_in(&added));
+            for line in removed {
+                push_line(&mut out, '-', line, Some(&new_lifetimes), color);
+            }
+            for line in added {
+                push_line(&mut out, '+', line, Some(&old_lifetimes), color);
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Given a file's lines, return which of them aren't blank, and what they
+/// are with their whitespace evened out.
+fn significant(lines: &[&str]) -> (Vec<usize>, Vec<String>) {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index, line.split_whitespace().collect::<Vec<_>>().join(" ")))
+        .unzip()
+}
+
+/// Given the differences so far, a line and what it is (`-` for removed,
+/// `+` for added, or a space), add it to them. Unless it's unchanged, the
+/// lifetimes in it that aren't among `others` (the ones on the other side)
+/// are marked underneath.
+fn push_line(out: &mut String, sign: char, line: &str, others: Option<&[String]>, color: bool) {
+    let (start, end) = match (color, sign) {
+        (true, '-') => ("\x1b[31m", "\x1b[0m"),
+        (true, '+') => ("\x1b[32m", "\x1b[0m"),
+        _ => ("", ""),
+    };
+    out.push_str(&format!("{start}{sign} {line}{end}\n"));
+
+    let Some(others) = others else {
+        return;
+    };
+    let mut marks = String::new();
+    for (column, lifetime) in lifetimes(line) {
+        if others.contains(&lifetime) {
+            continue;
+        }
+        let padding = 2 + column - marks.chars().count();
+        marks.extend(std::iter::repeat_n(' ', padding));
+        marks.extend(std::iter::repeat_n('^', lifetime.chars().count()));
+    }
+    if !marks.is_empty() {
+        out.push_str(&marks);
+        out.push('\n');
+    }
+}
+
+/// Given a line of Rust, return the lifetimes in it (leaving out any in a
+/// comment), with the column each one starts at.
+fn lifetimes(line: &str) -> Vec<(usize, String)> {
+    let code = line.split("//").next().unwrap_or(line);
+    let chars: Vec<char> = code.chars().collect();
+    let is_name = |c: char| c.is_alphanumeric() || c == '_';
+
+    let mut found = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] != '\'' {
+            index += 1;
+            continue;
+        }
+        let end = (index + 1..chars.len())
+            .find(|&after| !is_name(chars[after]))
+            .unwrap_or(chars.len());
+        if chars.get(end) == Some(&'\'') {
+            // That's a character, like `'a'`, not a lifetime.
+            index = end + 1;
+            continue;
+        }
+        if end > index + 1 && !chars[index + 1].is_ascii_digit() {
+            found.push((index, chars[index..end].iter().collect()));
+        }
+        index = end.max(index + 1);
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{scratch, write};
+
+    /// Given a name for a test, and the files in an exercise's `src` and its
+    /// solution's, set them up, and return how they differ.
+    fn compare_files(name: &str, yours: &[(&str, &str)], theirs: &[(&str, &str)]) -> String {
+        let root = scratch(module_path!(), name);
+        write(&root.join("exercise/src"), yours);
+        write(&root.join("solutions/src"), theirs);
+        compare(
+            &root.join("exercise/src"),
+            &root.join("solutions/src"),
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn marks_the_lifetimes_that_differ() {
+        let yours = "pub struct Pair<'a> {\n    first: &'a str,\n    second: &'a str,\n}\n";
+        let theirs = "pub struct Pair<'a, 'b> {\n    first: &'a str,\n    second: &'b str,\n}\n";
+        let differences = compare_files("marks", &[("lib.rs", yours)], &[("lib.rs", theirs)]);
+        assert_eq!(
+            differences,
+            "--- lib.rs (yours)\n\
+             +++ lib.rs (the solution)\n\
+             @@ line 1 of yours, 1 of the solution @@\n\
+             - pub struct Pair<'a> {\n\
+             + pub struct Pair<'a, 'b> {\n\
+             \x20                     ^^\n\
+             \x20     first: &'a str,\n\
+             -     second: &'a str,\n\
+             \x20              ^^\n\
+             +     second: &'b str,\n\
+             \x20              ^^\n\
+             \x20 }\n"
+        );
+    }
+
+    #[test]
+    fn ignores_whitespace() {
+        let yours = "fn first<'a>(x: &'a str)   -> &'a str {\n\n\n  x\n}\n";
+        let theirs = "fn first<'a>(x: &'a str) -> &'a str {\n    x\n}\n";
+        let differences = compare_files("whitespace", &[("lib.rs", yours)], &[("lib.rs", theirs)]);
+        assert_eq!(differences, "");
+    }
+
+    #[test]
+    fn leaves_out_unchanged_lines() {
+        let lines: Vec<String> = (1..=20)
+            .map(|n| format!("const N{n}: u32 = {n};"))
+            .collect();
+        let yours = lines.join("\n");
+        let mut theirs = lines.clone();
+        theirs[1] = "const N2: &'static str = \"2\";".to_string();
+        theirs[18] = "const N19: &'static str = \"19\";".to_string();
+        let differences = compare_files(
+            "unchanged",
+            &[("lib.rs", &yours)],
+            &[("lib.rs", &theirs.join("\n"))],
+        );
+
+        assert_eq!(differences.matches("@@ line").count(), 2);
+        assert!(differences.contains("@@ line 16 of yours, 16 of the solution @@\n"));
+        assert!(differences.contains("+ const N2: &'static str = \"2\";\n"));
+        assert!(differences.contains("  const N5: u32 = 5;\n"));
+        assert!(!differences.contains("N6:"));
+        assert!(!differences.contains("N15:"));
+    }
+
+    #[test]
+    fn says_which_files_only_one_has() {
+        let differences = compare_files(
+            "files",
+            &[("lib.rs", "fn f() {}\n"), ("scratch.rs", "")],
+            &[("lib.rs", "fn f() {}\n"), ("tests/extra.rs", "")],
+        );
+        assert_eq!(
+            differences,
+            "Only yours has scratch.rs.\n\nOnly the solution has tests/extra.rs.\n"
+        );
+    }
+
+    #[test]
+    fn finds_lifetimes() {
+        let found = |line: &str| -> Vec<(usize, String)> { lifetimes(line) };
+        assert_eq!(
+            found("fn f<'a>(x: &'a str) -> &'static str"),
+            vec![
+                (5, "'a".to_string()),
+                (13, "'a".to_string()),
+                (25, "'static".to_string()),
+            ]
+        );
+        assert_eq!(found("let c = 'a'; let d = '\\''; let e = ' ';"), vec![]);
+        assert_eq!(
+            found("let x: &'_ u8 = y; // it's 'b"),
+            vec![(8, "'_".to_string())]
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! This shows a chapter's hints one at a time, keeping track of how many
+//! have been shown in the progress file.
+
+use crate::manifest::{Chapter, Manifest};
+use crate::progress::{self, Progress};
+use crate::verify::{check_unless_done, Outcome};
+use serde_derive::Deserialize;
+use std::path::Path;
+
+/// A chapter's `hints.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hints {
+    pub hint: Vec<Hint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hint {
+    pub text: String,
+}
+
+/// What asking for a hint got.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Reveal {
+    /// The hint with this index, which is newly shown.
+    Hint(usize),
+    /// Every hint has been shown already.
+    NoneLeft,
+}
+
+impl Progress {
+    /// Given a chapter's path and how many hints it has, return which one to
+    /// show next, and record that it has been.
+    pub fn reveal(&mut self, chapter: &str, available: usize) -> Reveal {
+        let shown = self.hints.entry(chapter.to_string()).or_default();
+        if *shown >= available {
+            return Reveal::NoneLeft;
+        }
+        *shown += 1;
+        Reveal::Hint(*shown - 1)
+    }
+
+    /// Given a chapter's path, forget which of its hints have been shown.
+    pub fn reset(&mut self, chapter: &str) {
+        self.hints.remove(chapter);
+    }
+}
+
+/// Given the root of the repository and a chapter, read its hints, or
+/// return none if it doesn't have any.
+pub fn read_hints(root: &Path, chapter: &Chapter) -> Result<Vec<Hint>, String> {
+    let path = chapter.hints();
+    match std::fs::read_to_string(root.join(&path)) {
+        Ok(text) => basic_toml::from_str::<Hints>(&text)
+            .map(|hints| hints.hint)
+            .map_err(|error| format!("couldn't parse {}: {error}", path.display())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(format!("couldn't read {}: {error}", path.display())),
+    }
+}
+
+/// Given the root of the repository, the manifest, and the arguments after
+/// `hint` (`--reset`, and a chapter's number), show the next hint for that
+/// chapter, or for the first exercise that doesn't pass.
+pub fn command(root: &Path, manifest: &Manifest, args: &[String]) -> Result<bool, String> {
+    let reset = args.iter().any(|arg| arg == "--reset");
+    let number = args.iter().find(|arg| *arg != "--reset");
+    let chapter = match number {
+        Some(number) => manifest.numbered(number)?,
+        None => match current(root, manifest)? {
+            Some(chapter) => chapter,
+            None => {
+                println!("Every exercise passes, so there's nothing to give a hint for.");
+                return Ok(true);
+            }
+        },
+    };
+
+    hint(root, chapter, reset)?;
+    Ok(true)
+}
+
+/// Given the root of the repository and the manifest, return the first
+/// chapter whose exercise doesn't pass, if there is one.
+fn current<'manifest>(
+    root: &Path,
+    manifest: &'manifest Manifest,
+) -> Result<Option<&'manifest Chapter>, String> {
+    let mut progress = progress::read(&progress::path(root))?;
+    for chapter in &manifest.chapter {
+        let Some(package) = &chapter.package else {
+            continue;
+        };
+        if let Outcome::Failed { .. } = check_unless_done(root, chapter, package, &mut progress)? {
+            return Ok(Some(chapter));
+        }
+    }
+    Ok(None)
+}
+
+/// Given the root of the repository and a chapter, show its next hint, or
+/// with `reset`, start its hints again.
+fn hint(root: &Path, chapter: &Chapter, reset: bool) -> Result<(), String> {
+    let path = progress::path(root);
+    let mut progress = progress::read(&path)?;
+    if reset {
+        progress.reset(&chapter.path);
+        println!("The hints for {} will start again.", chapter.name);
+        return progress::write(&path, &progress);
+    }
+
+    let hints = read_hints(root, chapter)?;
+    if hints.is_empty() {
+        println!("{} doesn't have any hints.", chapter.name);
+        return Ok(());
+    }
+    match progress.reveal(&chapter.path, hints.len()) {
+        Reveal::Hint(index) => {
+            println!(
+                "Hint {} of {} for {}:",
+                index + 1,
+                hints.len(),
+                chapter.name
+            );
+            println!();
+            println!("{}", hints[index].text.trim());
+        }
+        Reveal::NoneLeft => {
+            println!("That's every hint for {}.", chapter.name);
+            if chapter.package.is_some() {
+                println!("The solution is in {}.", chapter.solution().display());
+            }
+            println!("Run `hint --reset` to see them again.");
+        }
+    }
+    progress::write(&path, &progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reveals_hints_in_order() {
+        let mut progress = Progress::default();
+        assert_eq!(progress.reveal("exercises/03", 3), Reveal::Hint(0));
+        assert_eq!(progress.reveal("exercises/03", 3), Reveal::Hint(1));
+        // Each chapter's hints are counted on their own.
+        assert_eq!(progress.reveal("exercises/04", 3), Reveal::Hint(0));
+        assert_eq!(progress.reveal("exercises/03", 3), Reveal::Hint(2));
+    }
+
+    #[test]
+    fn runs_out_of_hints() {
+        let mut progress = Progress::default();
+        assert_eq!(progress.reveal("exercises/03", 1), Reveal::Hint(0));
+        assert_eq!(progress.reveal("exercises/03", 1), Reveal::NoneLeft);
+        assert_eq!(progress.reveal("exercises/03", 1), Reveal::NoneLeft);
+        assert_eq!(progress.reveal("exercises/04", 0), Reveal::NoneLeft);
+    }
+
+    #[test]
+    fn starts_again_after_a_reset() {
+        let mut progress = Progress::default();
+        progress.reveal("exercises/03", 2);
+        progress.reveal("exercises/03", 2);
+        progress.reveal("exercises/04", 2);
+        progress.reset("exercises/03");
+        assert_eq!(progress.reveal("exercises/03", 2), Reveal::Hint(0));
+        assert_eq!(progress.reveal("exercises/04", 2), Reveal::Hint(1));
+    }
+
+    #[test]
+    fn keeps_progress_between_runs() {
+        let mut progress = Progress::default();
+        progress.reveal("exercises/03", 2);
+        let text = basic_toml::to_string(&progress).unwrap();
+        let mut progress: Progress = basic_toml::from_str(&text).unwrap();
+        assert_eq!(progress.reveal("exercises/03", 2), Reveal::Hint(1));
+    }
+
+    /// Every chapter from 1 to 9 has a reading or an exercise, so each has
+    /// a few hints, and they have to parse.
+    #[test]
+    fn chapters_have_hints() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let manifest = crate::manifest::read(root).unwrap();
+        for chapter in &manifest.chapter[1..10] {
+            let hints = read_hints(root, chapter).unwrap();
+            assert!(
+                (2..=3).contains(&hints.len()),
+                "{} should have 2 or 3 hints",
+                chapter.path
+            );
+        }
+    }
+}
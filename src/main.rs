@@ -1,3 +1,98 @@
+//! The kata's runner. `cargo run -- verify` checks the exercises in order,
+//! and says which one to work on next, and `cargo run -- watch` does that
+//! again each time the exercise is saved. `cargo run -- hint` gives a hint
+//! for that exercise, `cargo run -- compare N` shows how it differs from
+//! the solution, `cargo run -- reset N` puts it back how the repository has
+//! it, and `cargo run -- list` shows where each one is up to.
+//! `cargo run -- report --format json` (or `markdown`) summarises that, with
+//! how many times each was checked, for collecting from a workshop.
+//! `cargo run -- new-exercise` sets up a new chapter, for adding to the kata,
+//! and `cargo run -- validate` checks `exercises/info.toml` still matches
+//! the exercises. `cargo run -- matcher-repl` starts a prompt for trying
+//! patterns out on the finale's `Matcher`.
+
+mod compare;
+mod hints;
+mod manifest;
+mod matcher_repl;
+mod progress;
+mod report;
+mod reset;
+mod scaffold;
+#[cfg(test)]
+mod test_support;
+mod validate;
+mod verify;
+mod watch;
+
+use std::env;
+use std::path::PathBuf;
+use std::process::exit;
+
 fn main() {
-    println!("Hello, world!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = args.first().map(String::as_str);
+    // `LIFETIMEKATA_ROOT` points the runner at another copy of the kata.
+    let root = env::var_os("LIFETIMEKATA_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+    let root = root.as_path();
+
+    let result = match command {
+        Some("verify") => manifest::read(root).and_then(|manifest| verify::verify(root, &manifest)),
+        Some("watch") => manifest::read(root).and_then(|manifest| watch::watch(root, &manifest)),
+        Some("compare") => {
+            manifest::read(root).and_then(|manifest| compare::command(root, &manifest, &args[1..]))
+        }
+        Some("reset") => {
+            manifest::read(root).and_then(|manifest| reset::command(root, &manifest, &args[1..]))
+        }
+        Some("new-exercise") => {
+            manifest::read(root).and_then(|manifest| scaffold::command(root, &manifest, &args[1..]))
+        }
+        Some("validate") => {
+            manifest::read(root).and_then(|manifest| validate::command(root, &manifest, &args[1..]))
+        }
+        Some("list") => manifest::read(root).and_then(|manifest| progress::list(root, &manifest)),
+        Some("report") => {
+            manifest::read(root).and_then(|manifest| report::command(root, &manifest, &args[1..]))
+        }
+        Some("hint") => {
+            manifest::read(root).and_then(|manifest| hints::command(root, &manifest, &args[1..]))
+        }
+        Some("matcher-repl") => matcher_repl::command(root),
+        _ => {
+            eprintln!("usage: lifetimekata <command>");
+            eprintln!();
+            eprintln!("commands:");
+            eprintln!("    verify    check the exercises in order, and say which one is next");
+            eprintln!("    watch     verify, then again each time the next exercise is saved");
+            eprintln!("    list      show which exercises are done, without checking them");
+            eprintln!("    report    summarise where each exercise is up to, with");
+            eprintln!("              `report --format json` or `report --format markdown`");
+            eprintln!("    hint      show the next hint for the exercise (or chapter N, with");
+            eprintln!("              `hint N`); `hint --reset` starts the hints again");
+            eprintln!("    compare   show how chapter N's exercise differs from its solution,");
+            eprintln!("              with `compare N` (and `--force` if it passes already)");
+            eprintln!("    reset     put chapter N's exercise back how the repository has it,");
+            eprintln!("              with `reset N` (and `--yes` not to be asked first)");
+            eprintln!("    new-exercise NN_NAME [--template basic|matcher]");
+            eprintln!("              set up a new chapter, with an exercise and its solution");
+            eprintln!("    validate  check exercises/info.toml against the exercises, and that");
+            eprintln!("              each goes as expected (`--quick` doesn't run cargo)");
+            eprintln!("    matcher-repl");
+            eprintln!("              try patterns out on the finale's Matcher, greedily and");
+            eprintln!("              exhaustively, at a prompt");
+            exit(2);
+        }
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => exit(1),
+        Err(message) => {
+            eprintln!("error: {message}");
+            exit(1);
+        }
+    }
 }
@@ -0,0 +1,223 @@
+//! This reads `exercises/info.toml`, which lists the chapters in order.
+//! `validate` checks that it matches what's really there.
+
+use serde_derive::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Where the manifest is, from the root of the repository.
+pub const MANIFEST: &str = "exercises/info.toml";
+
+/// Where a snapshot of each exercise's `src`, as the repository has it, is
+/// kept for `reset`, from the root of the repository, in a directory named
+/// after its chapter's. `cargo xtask snapshots` writes them.
+pub const SNAPSHOTS: &str = "exercises/.snapshots";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub chapter: Vec<Chapter>,
+}
+
+/// One chapter of the kata, and its exercise, if it has one.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Chapter {
+    /// What the book calls it, like `Chapter 3: Lifetime Elision`.
+    pub name: String,
+    /// Its directory, from the root of the repository. The chapter itself is
+    /// `README.md` in there, and its exercise is in `exercise`.
+    pub path: String,
+    /// The package of its exercise, like `ex03`.
+    pub package: Option<String>,
+    /// Where the solution to its exercise is, from the root of the
+    /// repository, if it isn't `solutions` in its directory.
+    pub solution: Option<String>,
+    /// Where its hints are, from the root of the repository, if they aren't
+    /// in `hints.toml` in its directory.
+    pub hints: Option<String>,
+    #[serde(default)]
+    pub mode: Mode,
+    /// How its exercise goes before anyone has worked on it. `validate` and
+    /// `cargo xtask check-all` check it.
+    pub expected: Option<Expected>,
+    /// Whether the book links to its exercise in the Rust Playground. The
+    /// runner doesn't need this; `cargo xtask playground-links` reads it.
+    #[serde(default)]
+    #[allow(dead_code)]
+    pub playground: bool,
+}
+
+impl Manifest {
+    /// Given a chapter's number, as it was typed (like `5`, `05` or `9b`),
+    /// return the chapter.
+    pub fn numbered(&self, number: &str) -> Result<&Chapter, String> {
+        let wanted = label(number).ok_or_else(|| format!("`{number}` isn't a chapter number"))?;
+        self.chapter
+            .iter()
+            .find(|chapter| chapter.label() == Some(wanted))
+            .ok_or_else(|| format!("there's no chapter {number}"))
+    }
+}
+
+/// Given a chapter's number, like `05` or `9b`, return the number, and any
+/// letters after it.
+pub fn label(number: &str) -> Option<(u32, &str)> {
+    let letters = number.trim_start_matches(|c: char| c.is_ascii_digit());
+    let digits = &number[..number.len() - letters.len()];
+    let is_letters = letters.chars().all(|c| c.is_ascii_lowercase());
+    Some((digits.parse().ok()?, letters)).filter(|_| is_letters)
+}
+
+impl Chapter {
+    /// Return its number, from the start of its directory's name (like `3`
+    /// for `exercises/03_lifetime_elision`).
+    pub fn number(&self) -> Option<u32> {
+        let directory = Path::new(&self.path).file_name()?.to_str()?;
+        directory.split('_').next()?.parse().ok()
+    }
+
+    /// Return its number, and any letters after it, from the start of its
+    /// directory's name (like `9` and `b` for `exercises/09b_finale_extended`).
+    pub fn label(&self) -> Option<(u32, &str)> {
+        let directory = Path::new(&self.path).file_name()?.to_str()?;
+        label(directory.split('_').next()?)
+    }
+
+    /// Return where its exercise is, from the root of the repository.
+    pub fn exercise(&self) -> PathBuf {
+        Path::new(&self.path).join("exercise")
+    }
+
+    /// Return where the snapshot of its exercise's `src`, which `reset` puts
+    /// back, is kept, from the root of the repository. That isn't its
+    /// `starter/`, if it has one.
+    pub fn snapshot(&self) -> PathBuf {
+        let directory = Path::new(&self.path).file_name().unwrap_or_default();
+        Path::new(SNAPSHOTS).join(directory)
+    }
+
+    /// Return where the solution to its exercise is, from the root of the
+    /// repository.
+    pub fn solution(&self) -> PathBuf {
+        match &self.solution {
+            Some(solution) => PathBuf::from(solution),
+            None => Path::new(&self.path).join("solutions"),
+        }
+    }
+
+    /// Return where its hints are (if it has any), from the root of the
+    /// repository.
+    pub fn hints(&self) -> PathBuf {
+        match &self.hints {
+            Some(hints) => PathBuf::from(hints),
+            None => Path::new(&self.path).join("hints.toml"),
+        }
+    }
+}
+
+/// What an exercise has to do to pass.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Compile, and pass its tests.
+    #[default]
+    Test,
+    /// Compile.
+    Check,
+}
+
+/// How an exercise goes as it's handed out.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Expected {
+    CompileError,
+    TestFailure,
+    Passes,
+}
+
+impl Mode {
+    /// Return the cargo command that tells whether an exercise passes.
+    pub fn command(self) -> &'static str {
+        match self {
+            Mode::Test => "test",
+            Mode::Check => "check",
+        }
+    }
+}
+
+/// Given the root of the repository, read the manifest in it.
+pub fn read(root: &Path) -> Result<Manifest, String> {
+    let path = root.join(MANIFEST);
+    let text = std::fs::read_to_string(&path)
+        .map_err(|error| format!("couldn't read {}: {error}", path.display()))?;
+    basic_toml::from_str(&text).map_err(|error| format!("couldn't parse {MANIFEST}: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every chapter in `exercises/` should be listed, in the order of its
+    /// number, and every listed one should be there.
+    #[test]
+    fn lists_every_chapter_in_order() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let manifest = read(root).unwrap();
+
+        let mut directories: Vec<String> = std::fs::read_dir(root.join("exercises"))
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .filter(|entry| entry.path().join("README.md").exists())
+            .map(|entry| format!("exercises/{}", entry.file_name().to_string_lossy()))
+            .collect();
+        directories.sort();
+        let listed: Vec<&str> = manifest.chapter.iter().map(|c| c.path.as_str()).collect();
+
+        assert_eq!(listed, directories);
+    }
+
+    #[test]
+    fn finds_chapters_by_number() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let manifest = read(root).unwrap();
+        let path = |number: &str| manifest.numbered(number).map(|c| c.path.as_str());
+        assert_eq!(path("5"), Ok("exercises/05_lifetimes_on_types"));
+        assert_eq!(path("05"), Ok("exercises/05_lifetimes_on_types"));
+        assert_eq!(path("9"), Ok("exercises/09_further_reading"));
+        assert_eq!(path("9b"), Ok("exercises/09b_finale_extended"));
+        assert!(path("9c").is_err());
+        assert!(path("9B").is_err());
+        assert!(path("b").is_err());
+    }
+
+    #[test]
+    fn every_exercise_says_how_it_goes() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for chapter in read(root).unwrap().chapter {
+            assert_eq!(
+                chapter.package.is_some(),
+                chapter.expected.is_some(),
+                "{} should have an `expected` in {MANIFEST} if, and only if, it has a package",
+                chapter.path
+            );
+            // An exercise that only has to compile can't fail a test.
+            assert!(
+                !(chapter.mode == Mode::Check && chapter.expected == Some(Expected::TestFailure))
+            );
+        }
+    }
+
+    #[test]
+    fn every_exercise_has_a_package() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for chapter in read(root).unwrap().chapter {
+            let has_exercise = root.join(&chapter.path).join("exercise").exists();
+            assert_eq!(
+                has_exercise,
+                chapter.package.is_some(),
+                "{} has an exercise, but no package in {MANIFEST} (or the other way round)",
+                chapter.path
+            );
+        }
+    }
+}
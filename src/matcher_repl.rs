@@ -0,0 +1,21 @@
+//! This starts the finale's prompt for trying patterns out, which is a
+//! binary in the finale's own package, so it uses the learner's `Matcher`.
+
+use std::path::Path;
+use std::process::Command;
+
+/// The finale's package, and the prompt's binary in it.
+const PACKAGE: &str = "ex08";
+const BINARY: &str = "matcher-repl";
+
+/// Given the root of the repository, run the prompt until it's quit, with
+/// the terminal handed over to it.
+pub fn command(root: &Path) -> Result<bool, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .args(["run", "--quiet", "--package", PACKAGE, "--bin", BINARY])
+        .current_dir(root)
+        .status()
+        .map_err(|error| format!("couldn't run cargo: {error}"))?;
+    Ok(status.success())
+}
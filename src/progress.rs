@@ -0,0 +1,373 @@
+//! This keeps track of what's been done between runs: which hints have been
+//! shown, and how each exercise went when it was last checked, so exercises
+//! that passed aren't checked again until they're changed. It also counts
+//! how many times each one has been checked, for `report`.
+
+use crate::manifest::{Chapter, Manifest};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Where the progress file is, from the root of the repository, unless
+/// `LIFETIMEKATA_PROGRESS` says otherwise.
+pub const PROGRESS: &str = ".lifetimekata-progress.toml";
+
+/// What's been done so far.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Progress {
+    /// How many hints have been shown for each chapter, by its path.
+    #[serde(default)]
+    pub hints: BTreeMap<String, usize>,
+    /// How each exercise went when it was last checked, by its chapter's
+    /// path.
+    #[serde(default)]
+    pub exercises: BTreeMap<String, Exercise>,
+}
+
+/// How an exercise went when it was last checked.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Exercise {
+    pub passed: bool,
+    /// The hash of its `src` directory when it was checked.
+    pub hash: String,
+    /// How many times it's been checked. Progress files from before this
+    /// was counted start from none.
+    #[serde(default)]
+    pub attempts: u32,
+    /// If it didn't compile, the code of the first error the compiler gave
+    /// for it, like `E0106`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Where a chapter is up to, as far as the progress file knows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// It doesn't have an exercise, just something to read.
+    Reading,
+    /// Its exercise hasn't been checked, or has changed since it was.
+    Pending,
+    /// Its exercise didn't pass, and hasn't changed since.
+    Failed,
+    /// Its exercise passed, and hasn't changed since.
+    Done,
+}
+
+impl Status {
+    /// Return how `list` shows it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Reading => "reading",
+            Status::Pending => "pending",
+            Status::Failed => "failed",
+            Status::Done => "done",
+        }
+    }
+}
+
+impl Progress {
+    /// Given a chapter and the hash of its exercise's `src` now (if it has
+    /// an exercise), return where it's up to.
+    pub fn status(&self, chapter: &Chapter, hash: Option<&str>) -> Status {
+        let Some(hash) = hash else {
+            return Status::Reading;
+        };
+        match self.exercises.get(&chapter.path) {
+            Some(exercise) if exercise.hash != hash => Status::Pending,
+            Some(Exercise { passed: true, .. }) => Status::Done,
+            Some(Exercise { passed: false, .. }) => Status::Failed,
+            None => Status::Pending,
+        }
+    }
+
+    /// Given a chapter's path, the hash of its exercise's `src` when it was
+    /// checked, whether it passed, and the code of the first error the
+    /// compiler gave (if it gave one), record how it went.
+    pub fn record(&mut self, chapter: &str, hash: String, passed: bool, error: Option<String>) {
+        let attempts = self
+            .exercises
+            .get(chapter)
+            .map_or(0, |exercise| exercise.attempts);
+        let exercise = Exercise {
+            passed,
+            hash,
+            attempts: attempts + 1,
+            error,
+        };
+        self.exercises.insert(chapter.to_string(), exercise);
+    }
+}
+
+/// Given the root of the repository and the manifest, show where each
+/// chapter is up to, without checking any of them.
+pub fn list(root: &Path, manifest: &Manifest) -> Result<bool, String> {
+    let progress = read(&path(root))?;
+    println!("{:<9}chapter", "status");
+    for chapter in &manifest.chapter {
+        let hash = match chapter.package {
+            Some(_) => Some(exercise_hash(root, chapter)?),
+            None => None,
+        };
+        let status = progress.status(chapter, hash.as_deref());
+        println!("{:<9}{}", status.name(), chapter.name);
+    }
+    Ok(true)
+}
+
+/// Given the root of the repository, return where its progress file is.
+pub fn path(root: &Path) -> PathBuf {
+    std::env::var_os("LIFETIMEKATA_PROGRESS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join(PROGRESS))
+}
+
+/// Given the path of the progress file, read it, or return no progress if
+/// there isn't one yet.
+pub fn read(path: &Path) -> Result<Progress, String> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => basic_toml::from_str(&text)
+            .map_err(|error| format!("couldn't parse {}: {error}", path.display())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Progress::default()),
+        Err(error) => Err(format!("couldn't read {}: {error}", path.display())),
+    }
+}
+
+/// Given the path of the progress file and the progress so far, save it.
+pub fn write(path: &Path, progress: &Progress) -> Result<(), String> {
+    let text = basic_toml::to_string(progress)
+        .map_err(|error| format!("couldn't write {}: {error}", path.display()))?;
+    std::fs::write(path, text)
+        .map_err(|error| format!("couldn't write {}: {error}", path.display()))
+}
+
+/// Given the root of the repository and a chapter with an exercise, return
+/// the hash of its exercise's `src`.
+pub fn exercise_hash(root: &Path, chapter: &Chapter) -> Result<String, String> {
+    hash(&root.join(chapter.exercise()).join("src"))
+}
+
+/// Given a directory, return a hash of the names and contents of every file
+/// in it, which changes whenever one of them does, or one is added or
+/// removed. It's FNV-1a, since it has to be the same from one run (and one
+/// version of Rust) to the next.
+pub fn hash(directory: &Path) -> Result<String, String> {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut add = |bytes: &[u8]| {
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    };
+    for (name, path) in files(directory)? {
+        let contents = std::fs::read(&path)
+            .map_err(|error| format!("couldn't read {}: {error}", path.display()))?;
+        add(name.as_bytes());
+        add(&[0]);
+        add(&(contents.len() as u64).to_le_bytes());
+        add(&contents);
+    }
+    Ok(format!("{hash:016x}"))
+}
+
+/// Given a directory, return every file in it (and in the directories in
+/// it), by name from there, sorted by name.
+pub fn files(directory: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let mut files = Vec::new();
+    files_in(directory, directory, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+/// Given the directory being listed and one in it, add every file in the
+/// latter to `files`, with its name from the former.
+fn files_in(
+    base: &Path,
+    directory: &Path,
+    files: &mut Vec<(String, PathBuf)>,
+) -> Result<(), String> {
+    let entries = std::fs::read_dir(directory)
+        .map_err(|error| format!("couldn't read {}: {error}", directory.display()))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|error| format!("couldn't read {}: {error}", directory.display()))?
+            .path();
+        if path.is_dir() {
+            files_in(base, &path, files)?;
+        } else {
+            let name = path.strip_prefix(base).unwrap_or(&path);
+            files.push((name.to_string_lossy().replace('\\', "/"), path));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::Mode;
+    use crate::test_support::scratch;
+
+    /// Given the root of a kata, set up a chapter in it with an exercise.
+    fn chapter(root: &Path) -> Chapter {
+        let src = root.join("exercises/01_smoke/exercise/src");
+        std::fs::create_dir_all(src.join("bin")).unwrap();
+        std::fs::write(src.join("lib.rs"), "pub fn answer() -> u32 { 42 }\n").unwrap();
+        std::fs::write(src.join("bin/main.rs"), "fn main() {}\n").unwrap();
+        Chapter {
+            name: "Chapter 1: Smoke".to_string(),
+            path: "exercises/01_smoke".to_string(),
+            package: Some("smoke".to_string()),
+            solution: None,
+            hints: None,
+            mode: Mode::Check,
+            expected: None,
+            playground: false,
+        }
+    }
+
+    /// Given a kata, one of its chapters and the progress on it, return
+    /// where the chapter is up to now.
+    fn status(root: &Path, chapter: &Chapter, progress: &Progress) -> Status {
+        let hash = exercise_hash(root, chapter).unwrap();
+        progress.status(chapter, Some(&hash))
+    }
+
+    #[test]
+    fn exercises_start_pending() {
+        let root = scratch(module_path!(), "start");
+        let chapter = chapter(&root);
+        let progress = Progress::default();
+        assert_eq!(status(&root, &chapter, &progress), Status::Pending);
+        assert_eq!(progress.status(&chapter, None), Status::Reading);
+    }
+
+    #[test]
+    fn editing_a_done_exercise_makes_it_pending() {
+        let root = scratch(module_path!(), "edit");
+        let chapter = chapter(&root);
+        let mut progress = Progress::default();
+        progress.record(
+            &chapter.path,
+            exercise_hash(&root, &chapter).unwrap(),
+            true,
+            None,
+        );
+        assert_eq!(status(&root, &chapter, &progress), Status::Done);
+
+        let lib = root.join("exercises/01_smoke/exercise/src/lib.rs");
+        std::fs::write(&lib, "pub fn answer() -> u32 { 43 }\n").unwrap();
+        assert_eq!(status(&root, &chapter, &progress), Status::Pending);
+        // Changing it back is the same as never having changed it.
+        std::fs::write(&lib, "pub fn answer() -> u32 { 42 }\n").unwrap();
+        assert_eq!(status(&root, &chapter, &progress), Status::Done);
+    }
+
+    #[test]
+    fn adding_or_removing_a_file_makes_it_pending() {
+        let root = scratch(module_path!(), "files");
+        let chapter = chapter(&root);
+        let src = root.join("exercises/01_smoke/exercise/src");
+        let mut progress = Progress::default();
+        progress.record(
+            &chapter.path,
+            exercise_hash(&root, &chapter).unwrap(),
+            true,
+            None,
+        );
+
+        std::fs::write(src.join("extra.rs"), "").unwrap();
+        assert_eq!(status(&root, &chapter, &progress), Status::Pending);
+        std::fs::remove_file(src.join("extra.rs")).unwrap();
+        assert_eq!(status(&root, &chapter, &progress), Status::Done);
+        std::fs::remove_file(src.join("bin/main.rs")).unwrap();
+        assert_eq!(status(&root, &chapter, &progress), Status::Pending);
+    }
+
+    #[test]
+    fn failures_are_remembered_until_an_edit() {
+        let root = scratch(module_path!(), "failed");
+        let chapter = chapter(&root);
+        let mut progress = Progress::default();
+        progress.record(
+            &chapter.path,
+            exercise_hash(&root, &chapter).unwrap(),
+            false,
+            Some("E0106".to_string()),
+        );
+        assert_eq!(status(&root, &chapter, &progress), Status::Failed);
+
+        let lib = root.join("exercises/01_smoke/exercise/src/lib.rs");
+        std::fs::write(&lib, "pub fn answer() -> u32 { 43 }\n").unwrap();
+        assert_eq!(status(&root, &chapter, &progress), Status::Pending);
+    }
+
+    #[test]
+    fn counts_attempts() {
+        let root = scratch(module_path!(), "attempts");
+        let chapter = chapter(&root);
+        let hash = exercise_hash(&root, &chapter).unwrap();
+        let mut progress = Progress::default();
+        progress.record(
+            &chapter.path,
+            hash.clone(),
+            false,
+            Some("E0106".to_string()),
+        );
+        progress.record(
+            &chapter.path,
+            hash.clone(),
+            false,
+            Some("E0499".to_string()),
+        );
+        assert_eq!(progress.exercises[&chapter.path].attempts, 2);
+        assert_eq!(
+            progress.exercises[&chapter.path].error.as_deref(),
+            Some("E0499")
+        );
+
+        progress.record(&chapter.path, hash, true, None);
+        assert_eq!(progress.exercises[&chapter.path].attempts, 3);
+        assert_eq!(progress.exercises[&chapter.path].error, None);
+    }
+
+    #[test]
+    fn reads_progress_from_before_attempts_were_counted() {
+        let file = scratch(module_path!(), "old").join(PROGRESS);
+        std::fs::write(
+            &file,
+            "[exercises.\"exercises/01_smoke\"]\npassed = true\nhash = \"0123\"\n",
+        )
+        .unwrap();
+        let progress = read(&file).unwrap();
+        assert_eq!(progress.exercises["exercises/01_smoke"].attempts, 0);
+    }
+
+    #[test]
+    fn keeps_progress_between_runs() {
+        let root = scratch(module_path!(), "saved");
+        let chapter = chapter(&root);
+        let mut progress = Progress::default();
+        progress.record(
+            &chapter.path,
+            exercise_hash(&root, &chapter).unwrap(),
+            true,
+            None,
+        );
+        progress.hints.insert(chapter.path.clone(), 2);
+        let other = "exercises/02_other";
+        progress.record(other, "0123".to_string(), false, Some("E0106".to_string()));
+
+        let file = root.join(PROGRESS);
+        write(&file, &progress).unwrap();
+        let progress = read(&file).unwrap();
+        assert_eq!(status(&root, &chapter, &progress), Status::Done);
+        assert_eq!(progress.hints[&chapter.path], 2);
+        assert_eq!(progress.exercises[other].error.as_deref(), Some("E0106"));
+        assert_eq!(progress.exercises[other].attempts, 1);
+        // With no file, nothing has been done yet.
+        let progress = read(&root.join("missing.toml")).unwrap();
+        assert_eq!(status(&root, &chapter, &progress), Status::Pending);
+    }
+}
@@ -0,0 +1,235 @@
+//! This summarises where each exercise is up to, from the progress file, for
+//! whoever's running a workshop to collect from everyone in it. It can be
+//! JSON, for reading into something else, or Markdown, for reading.
+
+use crate::manifest::Manifest;
+use crate::progress::{self, Progress, Status};
+use serde_derive::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The version of the JSON report's schema. This goes up whenever a field
+/// is renamed or removed, or changes what it means.
+pub const VERSION: u32 = 1;
+
+/// A summary of every exercise, as it's written out in JSON.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Report {
+    /// Which version of this schema it is; see `VERSION`.
+    pub version: u32,
+    pub exercises: Vec<Entry>,
+}
+
+/// Where one exercise is up to.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+    /// The name of its chapter, like `Chapter 2: Lifetimes Explained`.
+    pub chapter: String,
+    /// Its chapter's path, like `exercises/02_lifetimes_explained`.
+    pub path: String,
+    /// `pending`, `failed` or `done`.
+    pub status: Status,
+    /// How many times it's been checked.
+    pub attempts: u32,
+    /// If it failed because it didn't compile, the code of the first error
+    /// the compiler gave for it.
+    pub error: Option<String>,
+}
+
+/// How to write out the report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Json,
+    Markdown,
+}
+
+/// Given the root of the repository, the manifest, and the arguments after
+/// `report` (`--format json` or `--format markdown`), print where each
+/// exercise is up to, without checking any of them.
+pub fn command(root: &Path, manifest: &Manifest, args: &[String]) -> Result<bool, String> {
+    let format = match args {
+        [flag, format] if flag == "--format" => match format.as_str() {
+            "json" => Format::Json,
+            "markdown" => Format::Markdown,
+            _ => {
+                return Err(format!(
+                    "there's no `{format}` format; try json or markdown"
+                ))
+            }
+        },
+        _ => return Err("say which format, like `report --format markdown`".to_string()),
+    };
+
+    let progress = progress::read(&progress::path(root))?;
+    let report = report(root, manifest, &progress)?;
+    match format {
+        Format::Json => {
+            let json = serde_json::to_string_pretty(&report)
+                .map_err(|error| format!("couldn't write the report: {error}"))?;
+            println!("{json}");
+        }
+        Format::Markdown => print!("{}", markdown(&report)),
+    }
+    Ok(true)
+}
+
+/// Given the root of the repository, the manifest and the progress so far,
+/// return where each chapter with an exercise is up to.
+fn report(root: &Path, manifest: &Manifest, progress: &Progress) -> Result<Report, String> {
+    let mut exercises = Vec::new();
+    for chapter in &manifest.chapter {
+        if chapter.package.is_none() {
+            continue;
+        }
+        let hash = progress::exercise_hash(root, chapter)?;
+        let status = progress.status(chapter, Some(&hash));
+        let exercise = progress.exercises.get(&chapter.path);
+        exercises.push(Entry {
+            chapter: chapter.name.clone(),
+            path: chapter.path.clone(),
+            status,
+            attempts: exercise.map_or(0, |exercise| exercise.attempts),
+            // An error from before the exercise was changed isn't what's
+            // wrong with it now.
+            error: match status {
+                Status::Failed => exercise.and_then(|exercise| exercise.error.clone()),
+                _ => None,
+            },
+        });
+    }
+    Ok(Report {
+        version: VERSION,
+        exercises,
+    })
+}
+
+/// Given a report, return it as a Markdown table, with how many exercises
+/// are done under it.
+fn markdown(report: &Report) -> String {
+    let mut markdown = String::new();
+    markdown.push_str("| Chapter | Status | Attempts | First error |\n");
+    markdown.push_str("| --- | --- | --- | --- |\n");
+    for entry in &report.exercises {
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            entry.chapter.replace('|', "\\|"),
+            entry.status.name(),
+            entry.attempts,
+            entry.error.as_deref().unwrap_or(""),
+        ));
+    }
+    let done = report
+        .exercises
+        .iter()
+        .filter(|entry| entry.status == Status::Done)
+        .count();
+    markdown.push_str(&format!(
+        "\n{done} of {} exercises done.\n",
+        report.exercises.len()
+    ));
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return a report with an exercise in each state.
+    fn example() -> Report {
+        let entry = |chapter: &str, path: &str, status, attempts, error: Option<&str>| Entry {
+            chapter: chapter.to_string(),
+            path: path.to_string(),
+            status,
+            attempts,
+            error: error.map(str::to_string),
+        };
+        Report {
+            version: VERSION,
+            exercises: vec![
+                entry(
+                    "Chapter 2: Lifetimes Explained",
+                    "exercises/02_lifetimes_explained",
+                    Status::Done,
+                    3,
+                    None,
+                ),
+                entry(
+                    "Chapter 3: Lifetime Elision",
+                    "exercises/03_lifetime_elision",
+                    Status::Failed,
+                    2,
+                    Some("E0106"),
+                ),
+                entry(
+                    "Chapter 4: Mutable References | Containers",
+                    "exercises/04_mutable_references_and_containers",
+                    Status::Pending,
+                    0,
+                    None,
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let report = example();
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn json_schema_is_versioned() {
+        let json = serde_json::to_value(example()).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(
+            json["exercises"][1],
+            serde_json::json!({
+                "chapter": "Chapter 3: Lifetime Elision",
+                "path": "exercises/03_lifetime_elision",
+                "status": "failed",
+                "attempts": 2,
+                "error": "E0106",
+            })
+        );
+        // A report with a field this version doesn't know about is from
+        // another version, and isn't read as this one.
+        let mut json = json;
+        json["exercises"][0]["hints"] = 2.into();
+        assert!(serde_json::from_value::<Report>(json).is_err());
+    }
+
+    #[test]
+    fn markdown_snapshot() {
+        assert_eq!(
+            markdown(&example()),
+            concat!(
+                "| Chapter | Status | Attempts | First error |\n",
+                "| --- | --- | --- | --- |\n",
+                "| Chapter 2: Lifetimes Explained | done | 3 |  |\n",
+                "| Chapter 3: Lifetime Elision | failed | 2 | E0106 |\n",
+                "| Chapter 4: Mutable References \\| Containers | pending | 0 |  |\n",
+                "\n",
+                "1 of 3 exercises done.\n",
+            )
+        );
+    }
+
+    #[test]
+    fn markdown_snapshot_with_no_exercises() {
+        let report = Report {
+            version: VERSION,
+            exercises: Vec::new(),
+        };
+        assert_eq!(
+            markdown(&report),
+            concat!(
+                "| Chapter | Status | Attempts | First error |\n",
+                "| --- | --- | --- | --- |\n",
+                "\n",
+                "0 of 0 exercises done.\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! This puts an exercise back how the repository has it, from the snapshot of
+//! its `src` in `exercises/.snapshots`, for when it's easier to start it
+//! again than to untangle it. That's the exercise as it's committed, which
+//! isn't the `starter/` some chapters keep for checking rustc's errors.
+
+use crate::manifest::Manifest;
+use crate::progress;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+/// Given the root of the repository, the manifest, and the arguments after
+/// `reset` (a chapter's number, and `--yes`), put the chapter's exercise
+/// back how the repository has it. Unless it's `--yes`, that's asked about
+/// first, since whatever's in the exercise now is lost.
+pub fn command(root: &Path, manifest: &Manifest, args: &[String]) -> Result<bool, String> {
+    let yes = args.iter().any(|arg| arg == "--yes");
+    let Some(number) = args.iter().find(|arg| *arg != "--yes") else {
+        return Err("say which chapter to reset, like `reset 5`".to_string());
+    };
+    let chapter = manifest.numbered(number)?;
+    if chapter.package.is_none() {
+        return Err(format!("{} doesn't have an exercise", chapter.name));
+    }
+    let src = root.join(chapter.exercise()).join("src");
+
+    if !yes {
+        print!(
+            "This puts {} back how the repository has it, and what's in it now is lost.\n\
+             Carry on? [y/N] ",
+            src.display()
+        );
+        let _ = std::io::stdout().flush();
+        let mut answer = String::new();
+        std::io::stdin()
+            .lock()
+            .read_line(&mut answer)
+            .map_err(|error| format!("couldn't read the answer: {error}"))?;
+        if !confirmed(&answer) {
+            println!("Left it as it was.");
+            return Ok(false);
+        }
+    }
+
+    reset(&root.join(chapter.snapshot()), &src)?;
+    println!("{} is back how the repository has it.", chapter.name);
+    Ok(true)
+}
+
+/// Given an answer to "Carry on?", return whether it's a yes.
+fn confirmed(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Given a snapshot and an exercise's `src`, make the latter a copy of the
+/// former, taking out any files that have been added to it. The snapshot is
+/// read first, so if it can't be, the exercise is left alone.
+pub fn reset(snapshot: &Path, src: &Path) -> Result<(), String> {
+    if !snapshot.is_dir() {
+        return Err(format!("there's no snapshot in {}", snapshot.display()));
+    }
+    let mut files = Vec::new();
+    for (name, path) in progress::files(snapshot)? {
+        let contents = std::fs::read(&path)
+            .map_err(|error| format!("couldn't read {}: {error}", path.display()))?;
+        files.push((name, contents));
+    }
+    if files.is_empty() {
+        return Err(format!("{} is empty", snapshot.display()));
+    }
+
+    if src.exists() {
+        std::fs::remove_dir_all(src)
+            .map_err(|error| format!("couldn't remove {}: {error}", src.display()))?;
+    }
+    for (name, contents) in files {
+        let file = src.join(name);
+        std::fs::create_dir_all(file.parent().unwrap_or(src))
+            .and_then(|()| std::fs::write(&file, contents))
+            .map_err(|error| format!("couldn't write {}: {error}", file.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest;
+    use crate::test_support::{scratch, write};
+
+    fn read(directory: &Path) -> Vec<(String, String)> {
+        progress::files(directory)
+            .unwrap()
+            .into_iter()
+            .map(|(name, path)| (name, std::fs::read_to_string(path).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn puts_the_exercise_back() {
+        let directory = scratch(module_path!(), "reset");
+        let snapshot = directory.join("snapshot");
+        let src = directory.join("src");
+        write(
+            &snapshot,
+            &[
+                ("lib.rs", "mod engine;\n"),
+                ("engine/mod.rs", "fn run() {}\n"),
+            ],
+        );
+        write(
+            &src,
+            &[
+                ("lib.rs", "mod engine;\nmod mine;\n"),
+                ("mine.rs", "fn mine() {}\n"),
+            ],
+        );
+
+        reset(&snapshot, &src).unwrap();
+        assert_eq!(
+            read(&src),
+            [
+                ("engine/mod.rs".to_string(), "fn run() {}\n".to_string()),
+                ("lib.rs".to_string(), "mod engine;\n".to_string()),
+            ]
+        );
+        // The snapshot is still there, to reset from again.
+        assert_eq!(read(&snapshot), read(&src));
+    }
+
+    #[test]
+    fn leaves_the_exercise_without_a_snapshot() {
+        let directory = scratch(module_path!(), "reset-missing");
+        let src = directory.join("src");
+        write(&src, &[("lib.rs", "fn mine() {}\n")]);
+
+        let missing = directory.join("snapshot");
+        assert_eq!(
+            reset(&missing, &src),
+            Err(format!("there's no snapshot in {}", missing.display()))
+        );
+        std::fs::create_dir_all(&missing).unwrap();
+        assert!(reset(&missing, &src).is_err());
+        assert_eq!(
+            read(&src),
+            [("lib.rs".to_string(), "fn mine() {}\n".to_string())]
+        );
+    }
+
+    #[test]
+    fn only_yes_confirms() {
+        for yes in ["y", "Y", "yes\n", " YES \r\n"] {
+            assert!(confirmed(yes), "{yes:?}");
+        }
+        for no in ["", "\n", "n", "no", "yeah", "y y"] {
+            assert!(!confirmed(no), "{no:?}");
+        }
+    }
+
+    #[test]
+    fn every_exercise_has_a_snapshot() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let manifest = manifest::read(root).unwrap();
+        for chapter in manifest.chapter.iter().filter(|c| c.package.is_some()) {
+            let snapshot = root.join(chapter.snapshot());
+            assert!(
+                snapshot.join("lib.rs").is_file() || snapshot.join("main.rs").is_file(),
+                "{} has no snapshot in {}; run `cargo xtask snapshots`",
+                chapter.name,
+                snapshot.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! This sets up a new chapter, with an exercise and its solution, from the
+//! templates in `src/templates`, and adds it to the manifest and the
+//! workspace.
+
+use crate::manifest::{Manifest, MANIFEST, SNAPSHOTS};
+use std::path::Path;
+
+/// What an exercise can start out as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Template {
+    /// A function that takes a reference and returns one.
+    Basic,
+    /// A struct that borrows a pattern, and finds it in text, like the
+    /// finale's `Matcher`.
+    Matcher,
+}
+
+impl Template {
+    fn parse(name: &str) -> Result<Template, String> {
+        match name {
+            "basic" => Ok(Template::Basic),
+            "matcher" => Ok(Template::Matcher),
+            _ => Err(format!(
+                "there's no template `{name}`; it's `basic` or `matcher`"
+            )),
+        }
+    }
+
+    /// Return its `src/lib.rs` and its test, before they're filled in.
+    fn files(self) -> (&'static str, &'static str) {
+        match self {
+            Template::Basic => (
+                include_str!("templates/basic.rs.in"),
+                include_str!("templates/basic_test.rs.in"),
+            ),
+            Template::Matcher => (
+                include_str!("templates/matcher.rs.in"),
+                include_str!("templates/matcher_test.rs.in"),
+            ),
+        }
+    }
+}
+
+/// What a new chapter is called.
+#[derive(Debug, PartialEq, Eq)]
+struct Chapter {
+    number: u32,
+    /// Its directory's name, like `03_lifetime_elision`.
+    directory: String,
+    /// The rest of that, like `lifetime_elision`.
+    name: String,
+}
+
+impl Chapter {
+    /// Given a directory's name, like `03_lifetime_elision`, return the
+    /// chapter it's for.
+    fn parse(directory: &str) -> Result<Chapter, String> {
+        let wrong =
+            || format!("`{directory}` should be a number and a name, like `11_static_lifetimes`");
+        let (number, name) = directory.split_once('_').ok_or_else(wrong)?;
+        let is_name = name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if number.len() != 2 || !is_name {
+            return Err(wrong());
+        }
+        Ok(Chapter {
+            number: number.parse().map_err(|_| wrong())?,
+            directory: directory.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    /// Return what the book calls it, like `Chapter 3: Lifetime Elision`.
+    fn title(&self) -> String {
+        let words: Vec<String> = self
+            .name
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(|word| word[..1].to_uppercase() + &word[1..])
+            .collect();
+        format!("Chapter {}: {}", self.number, words.join(" "))
+    }
+
+    /// Given a template, fill it in for this chapter.
+    fn fill(&self, template: &str, package: &str) -> String {
+        template
+            .replace("{{title}}", &self.title())
+            .replace("{{package}}", package)
+            .replace("{{name}}", &self.name)
+    }
+}
+
+/// Given the root of the repository, the manifest, and the arguments after
+/// `new-exercise` (the new chapter's directory, and `--template` with which
+/// one to use), set the chapter up.
+pub fn command(root: &Path, manifest: &Manifest, args: &[String]) -> Result<bool, String> {
+    let mut directory = None;
+    let mut template = Template::Basic;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--template" => {
+                let name = args.next().ok_or("say which template to use")?;
+                template = Template::parse(name)?;
+            }
+            _ if directory.is_none() => directory = Some(arg),
+            _ => return Err(format!("didn't expect `{arg}`")),
+        }
+    }
+    let directory =
+        directory.ok_or("say what the new chapter is called, like `11_static_lifetimes`")?;
+    let chapter = Chapter::parse(directory)?;
+
+    scaffold(root, manifest, &chapter, template)?;
+    println!(
+        "Set up {}, in exercises/{}.",
+        chapter.title(),
+        chapter.directory
+    );
+    println!("Write its README.md, then its exercise, and the solution to it.");
+    Ok(true)
+}
+
+/// Given the root of the repository, the manifest, a new chapter and the
+/// template for its exercise, write the chapter out, and add it to the
+/// manifest and the workspace.
+fn scaffold(
+    root: &Path,
+    manifest: &Manifest,
+    chapter: &Chapter,
+    template: Template,
+) -> Result<(), String> {
+    let path = format!("exercises/{}", chapter.directory);
+    if root.join(&path).exists() {
+        return Err(format!("{path} is there already"));
+    }
+    if let Some(existing) = manifest
+        .chapter
+        .iter()
+        .find(|existing| existing.number() == Some(chapter.number))
+    {
+        return Err(format!(
+            "{} is there already, in {}",
+            existing.name, existing.path
+        ));
+    }
+
+    let exercise = format!("ex{:02}", chapter.number);
+    let solution = format!("soln{:02}", chapter.number);
+    let (lib, test) = template.files();
+    let files = [
+        (
+            "README.md".to_string(),
+            chapter.fill(include_str!("templates/README.md.in"), ""),
+        ),
+        (
+            "exercise/Cargo.toml".to_string(),
+            chapter.fill(include_str!("templates/Cargo.toml.in"), &exercise),
+        ),
+        (
+            "exercise/src/lib.rs".to_string(),
+            chapter.fill(lib, &exercise),
+        ),
+        (
+            format!("exercise/tests/{}.rs", chapter.name),
+            chapter.fill(test, &exercise),
+        ),
+        (
+            "solutions/Cargo.toml".to_string(),
+            chapter.fill(include_str!("templates/Cargo.toml.in"), &solution),
+        ),
+        (
+            "solutions/src/lib.rs".to_string(),
+            chapter.fill(lib, &solution),
+        ),
+    ];
+    for (file, text) in files {
+        let file = root.join(&path).join(file);
+        std::fs::create_dir_all(file.parent().unwrap())
+            .and_then(|()| std::fs::write(&file, text))
+            .map_err(|error| format!("couldn't write {}: {error}", file.display()))?;
+    }
+    // The exercise is committed as the template left it, so that's what
+    // `reset` puts it back to.
+    let snapshot = root.join(SNAPSHOTS).join(&chapter.directory).join("lib.rs");
+    std::fs::create_dir_all(snapshot.parent().unwrap())
+        .and_then(|()| std::fs::write(&snapshot, chapter.fill(lib, &exercise)))
+        .map_err(|error| format!("couldn't write {}: {error}", snapshot.display()))?;
+
+    let entry = format!(
+        "[[chapter]]\nname = \"{}\"\npath = \"{path}\"\npackage = \"{exercise}\"\nexpected = \"test_failure\"\n",
+        chapter.title()
+    );
+    edit(&root.join(MANIFEST), |text| {
+        add_chapter(text, manifest, chapter.number, &entry)
+    })?;
+    edit(&root.join("Cargo.toml"), |text| {
+        add_members(
+            text,
+            &[format!("{path}/exercise"), format!("{path}/solutions")],
+        )
+    })
+}
+
+/// Given a file and a change to make to what's in it, make it.
+fn edit(file: &Path, change: impl FnOnce(&str) -> Result<String, String>) -> Result<(), String> {
+    let text = std::fs::read_to_string(file)
+        .map_err(|error| format!("couldn't read {}: {error}", file.display()))?;
+    std::fs::write(file, change(&text)?)
+        .map_err(|error| format!("couldn't write {}: {error}", file.display()))
+}
+
+/// Given the manifest, as text and as it was read, a new chapter's number
+/// and its entry, return the manifest with the entry before the first
+/// chapter with a bigger number.
+fn add_chapter(
+    text: &str,
+    manifest: &Manifest,
+    number: u32,
+    entry: &str,
+) -> Result<String, String> {
+    let next = manifest
+        .chapter
+        .iter()
+        .find(|chapter| chapter.number().is_some_and(|other| other > number));
+    let Some(next) = next else {
+        return Ok(format!("{}\n\n{entry}", text.trim_end()));
+    };
+
+    // That's the `[[chapter]]` before that chapter's path.
+    let path = format!("path = \"{}\"", next.path);
+    let at = text
+        .find(&path)
+        .and_then(|at| text[..at].rfind("[[chapter]]"))
+        .ok_or_else(|| format!("couldn't find where {} is in {MANIFEST}", next.name))?;
+    Ok(format!("{}{entry}\n{}", &text[..at], &text[at..]))
+}
+
+/// Given the root `Cargo.toml` and some directories, return it with them
+/// added to the workspace's members, in order among the other exercises.
+fn add_members(text: &str, members: &[String]) -> Result<String, String> {
+    let start = text
+        .find("members = [")
+        .ok_or("couldn't find the workspace's members in Cargo.toml")?;
+    let end = start
+        + text[start..]
+            .find(']')
+            .ok_or("couldn't find the end of the members")?;
+    let mut lines: Vec<String> = text[start..end].split('\n').map(str::to_string).collect();
+
+    for member in members {
+        let line = format!("        \"{member}\",");
+        // It goes before the first exercise that sorts after it, or after
+        // the last one.
+        let exercises = || {
+            lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| line.trim_start().starts_with("\"exercises/"))
+        };
+        let at = exercises()
+            .find(|(_, existing)| existing.trim() > line.trim())
+            .map(|(index, _)| index)
+            .or_else(|| exercises().next_back().map(|(index, _)| index + 1))
+            .unwrap_or(1);
+        lines.insert(at, line);
+    }
+    Ok(format!(
+        "{}{}{}",
+        &text[..start],
+        lines.join("\n"),
+        &text[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_chapter() {
+        let chapter = Chapter::parse("11_static_lifetimes").unwrap();
+        assert_eq!(chapter.number, 11);
+        assert_eq!(chapter.name, "static_lifetimes");
+        assert_eq!(chapter.title(), "Chapter 11: Static Lifetimes");
+        for wrong in [
+            "static_lifetimes",
+            "1_static",
+            "11_",
+            "11_Static",
+            "11_static-ish",
+        ] {
+            assert!(Chapter::parse(wrong).is_err(), "{wrong}");
+        }
+    }
+
+    #[test]
+    fn adds_the_chapter_in_order() {
+        let text = "# The chapters.\n\n\
+                    [[chapter]]\nname = \"Chapter 2: Two\"\npath = \"exercises/02_two\"\n\n\
+                    [[chapter]]\nname = \"Chapter 5: Five\"\npath = \"exercises/05_five\"\n";
+        let manifest: Manifest = basic_toml::from_str(text).unwrap();
+        let entry = "[[chapter]]\nname = \"Chapter 3: Three\"\npath = \"exercises/03_three\"\n";
+
+        let added = add_chapter(text, &manifest, 3, entry).unwrap();
+        let added: Manifest = basic_toml::from_str(&added).unwrap();
+        let paths: Vec<&str> = added.chapter.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "exercises/02_two",
+                "exercises/03_three",
+                "exercises/05_five"
+            ]
+        );
+
+        let last = entry.replace("03_three", "07_seven");
+        let added = add_chapter(text, &manifest, 7, &last).unwrap();
+        assert!(added.ends_with(
+            "\n\n[[chapter]]\nname = \"Chapter 3: Three\"\npath = \"exercises/07_seven\"\n"
+        ));
+    }
+
+    #[test]
+    fn adds_the_members_in_order() {
+        let text = "[workspace]\nmembers = [\n        \"exercises/02_two/exercise\",\n        \"exercises/05_five/exercise\",\n        \"xtask\",\n]\n";
+        let added = add_members(
+            text,
+            &[
+                "exercises/03_three/exercise".to_string(),
+                "exercises/09_nine/exercise".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            added,
+            "[workspace]\nmembers = [\n        \"exercises/02_two/exercise\",\n        \"exercises/03_three/exercise\",\n        \"exercises/05_five/exercise\",\n        \"exercises/09_nine/exercise\",\n        \"xtask\",\n]\n"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Helpers for the runner's tests, which set up kata-shaped directories to
+//! run against.
+
+use std::path::{Path, PathBuf};
+
+/// Given the module a test is in (its `module_path!()`) and a name for the
+/// test, make an empty directory for it to use. The module's in the name,
+/// so tests with the same name in different modules don't share one.
+pub fn scratch(module: &str, name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "lifetimekata-{}-{name}-{}",
+        module.replace("::", "-"),
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+/// Given a directory and some files, as paths from it and what's in them,
+/// write them.
+pub fn write(root: &Path, files: &[(&str, &str)]) {
+    for (path, contents) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+}
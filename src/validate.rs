@@ -0,0 +1,483 @@
+//! This checks the manifest against what's really in `exercises/`: that
+//! every chapter it lists is there (with its exercise, solution and hints),
+//! that every chapter there is listed, in order, and that each exercise
+//! still goes the way its `expected` says, which takes running cargo.
+
+use crate::hints;
+use crate::manifest::{self, Chapter, Expected, Manifest, Mode, MANIFEST};
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Given the root of the repository, the manifest, and the arguments after
+/// `validate` (`--quick`, to leave out running cargo), say what's wrong
+/// with the manifest. Return whether anything was.
+pub fn command(root: &Path, manifest: &Manifest, args: &[String]) -> Result<bool, String> {
+    let quick = match args {
+        [] => false,
+        [flag] if flag == "--quick" => true,
+        _ => return Err(format!("didn't expect `{}`", args.join(" "))),
+    };
+
+    let mut problems = problems(root, manifest);
+    // There's no point building exercises that aren't where they should be.
+    if problems.is_empty() && !quick {
+        problems = states(root, manifest)?;
+    }
+    if problems.is_empty() {
+        println!("{MANIFEST} matches the exercises.");
+        return Ok(true);
+    }
+    for problem in &problems {
+        println!("{problem}");
+    }
+    Ok(false)
+}
+
+/// Given the root of the repository and the manifest, return everything
+/// wrong with it that can be found without running cargo.
+pub fn problems(root: &Path, manifest: &Manifest) -> Vec<String> {
+    let mut problems = Vec::new();
+    for chapter in &manifest.chapter {
+        if !root.join(&chapter.path).join("README.md").is_file() {
+            problems.push(format!(
+                "{} is listed in {MANIFEST}, but {}/README.md doesn't exist",
+                chapter.name, chapter.path
+            ));
+        }
+        match &chapter.package {
+            Some(package) => problems.extend(exercise_problems(root, chapter, package)),
+            None => problems.extend(reading_problems(root, chapter)),
+        }
+        problems.extend(hints_problems(root, chapter));
+    }
+    problems.extend(order_problems(manifest));
+    problems.extend(unlisted(root, manifest));
+    problems
+}
+
+/// Given the root of the repository, a chapter and its exercise's package,
+/// return what's wrong with its exercise and solution.
+fn exercise_problems(root: &Path, chapter: &Chapter, package: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    let exercise = chapter.exercise();
+    match package_name(root, &exercise) {
+        Ok(name) if name != package => problems.push(format!(
+            "{} is the package {name}, not {package}",
+            exercise.display()
+        )),
+        Ok(_) => {}
+        Err(problem) => problems.push(problem),
+    }
+    if let Err(problem) = package_name(root, &chapter.solution()) {
+        problems.push(problem);
+    }
+    match chapter.expected {
+        None => problems.push(format!(
+            "{} has a package, but no `expected` in {MANIFEST}",
+            chapter.name
+        )),
+        Some(Expected::TestFailure) if chapter.mode == Mode::Check => problems.push(format!(
+            "{} only has to compile, so it can't be expected to fail its tests",
+            chapter.name
+        )),
+        Some(_) => {}
+    }
+    problems
+}
+
+/// Given the root of the repository and a chapter without an exercise,
+/// return anything it has that only an exercise should.
+fn reading_problems(root: &Path, chapter: &Chapter) -> Vec<String> {
+    let mut problems = Vec::new();
+    if root.join(chapter.exercise()).exists() {
+        problems.push(format!(
+            "{} exists, but {} has no package in {MANIFEST}",
+            chapter.exercise().display(),
+            chapter.name
+        ));
+    }
+    for (field, set) in [
+        ("expected", chapter.expected.is_some()),
+        ("solution", chapter.solution.is_some()),
+    ] {
+        if set {
+            problems.push(format!(
+                "{} sets `{field}`, but has no package in {MANIFEST}",
+                chapter.name
+            ));
+        }
+    }
+    problems
+}
+
+/// Given the root of the repository and a chapter, return what's wrong with
+/// its hints: they don't have to be there, unless `hints` says where they
+/// are, but if they are, they have to parse.
+fn hints_problems(root: &Path, chapter: &Chapter) -> Option<String> {
+    if chapter.hints.is_some() && !root.join(chapter.hints()).is_file() {
+        return Some(format!(
+            "{} has `hints = \"{}\"`, but that doesn't exist",
+            chapter.name,
+            chapter.hints().display()
+        ));
+    }
+    hints::read_hints(root, chapter).err()
+}
+
+/// Given the manifest, return where its chapters aren't in the order of
+/// their numbers, and where two have the same number.
+fn order_problems(manifest: &Manifest) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut numbered: BTreeMap<(u32, &str), &str> = BTreeMap::new();
+    let mut previous: Option<((u32, &str), &str)> = None;
+    for chapter in &manifest.chapter {
+        let Some(label) = chapter.label() else {
+            problems.push(format!(
+                "{} doesn't start with a chapter number, like `05_`",
+                chapter.path
+            ));
+            continue;
+        };
+        if let Some(other) = numbered.insert(label, &chapter.path) {
+            problems.push(format!(
+                "{other} and {} are both chapter {}{}",
+                chapter.path, label.0, label.1
+            ));
+        }
+        if let Some((previous_label, previous_path)) = previous {
+            if label < previous_label {
+                problems.push(format!(
+                    "{} is listed after {previous_path}, but comes before it",
+                    chapter.path
+                ));
+            }
+        }
+        previous = Some((label, &chapter.path));
+    }
+    problems
+}
+
+/// Given the root of the repository and the manifest, return each chapter
+/// in `exercises/` (each directory starting with a number) that isn't
+/// listed.
+fn unlisted(root: &Path, manifest: &Manifest) -> Vec<String> {
+    let exercises = root.join("exercises");
+    let entries = match std::fs::read_dir(&exercises) {
+        Ok(entries) => entries,
+        Err(error) => return vec![format!("couldn't read {}: {error}", exercises.display())],
+    };
+    let mut unlisted: Vec<String> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_dir())
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .filter(|name| manifest::label(name.split('_').next().unwrap_or_default()).is_some())
+        .map(|name| format!("exercises/{name}"))
+        .filter(|path| !manifest.chapter.iter().any(|chapter| chapter.path == *path))
+        .collect();
+    unlisted.sort();
+    unlisted
+        .into_iter()
+        .map(|path| format!("{path} isn't listed in {MANIFEST}"))
+        .collect()
+}
+
+/// Given the root of the repository and a package's directory, return the
+/// package's name, or what's wrong if it doesn't have one.
+fn package_name(root: &Path, directory: &Path) -> Result<String, String> {
+    #[derive(Deserialize)]
+    struct CargoToml {
+        package: Package,
+    }
+    #[derive(Deserialize)]
+    struct Package {
+        name: String,
+    }
+
+    let path = directory.join("Cargo.toml");
+    let text = std::fs::read_to_string(root.join(&path))
+        .map_err(|_| format!("{} doesn't exist", path.display()))?;
+    let cargo_toml: CargoToml = basic_toml::from_str(&text)
+        .map_err(|error| format!("couldn't parse {}: {error}", path.display()))?;
+    Ok(cargo_toml.package.name)
+}
+
+/// Given the root of the repository and the manifest, build (and test) each
+/// exercise, and return each one that doesn't go the way it's expected to.
+fn states(root: &Path, manifest: &Manifest) -> Result<Vec<String>, String> {
+    let mut problems = Vec::new();
+    for chapter in &manifest.chapter {
+        let (Some(package), Some(expected)) = (&chapter.package, chapter.expected) else {
+            continue;
+        };
+        problems.extend(mismatch(package, expected, state(root, chapter, package)?));
+    }
+    Ok(problems)
+}
+
+/// Given the root of the repository, a chapter and its exercise's package,
+/// build the exercise, then (if it builds, and has to pass tests) test it,
+/// and return how it went.
+fn state(root: &Path, chapter: &Chapter, package: &str) -> Result<Expected, String> {
+    let build: &[&str] = match chapter.mode {
+        Mode::Test => &["test", "--no-run"],
+        Mode::Check => &["check"],
+    };
+    if !cargo(root, build, package)? {
+        return Ok(Expected::CompileError);
+    }
+    if chapter.mode == Mode::Check || cargo(root, &["test"], package)? {
+        Ok(Expected::Passes)
+    } else {
+        Ok(Expected::TestFailure)
+    }
+}
+
+/// Given the root of the repository, a cargo command and a package, run it
+/// on the package quietly, and return whether it succeeded.
+fn cargo(root: &Path, command: &[&str], package: &str) -> Result<bool, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(command)
+        .args(["--quiet", "--package", package])
+        .current_dir(root)
+        .output()
+        .map_err(|error| format!("couldn't run cargo: {error}"))?;
+    Ok(output.status.success())
+}
+
+/// Given an exercise's package, how it should go and how it went, return
+/// what's wrong, if anything.
+fn mismatch(package: &str, expected: Expected, actual: Expected) -> Option<String> {
+    let should = |state| match state {
+        Expected::CompileError => "fail to compile",
+        Expected::TestFailure => "fail its tests",
+        Expected::Passes => "pass",
+    };
+    let did = |state| match state {
+        Expected::CompileError => "fails to compile",
+        Expected::TestFailure => "fails its tests",
+        Expected::Passes => "passes",
+    };
+    (expected != actual).then(|| {
+        format!(
+            "{package} should {} (as {MANIFEST} says), but it {}",
+            should(expected),
+            did(actual)
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{scratch, write};
+
+    /// Given a directory, set up a kata in it with a chapter to read, a
+    /// chapter with an exercise, and a directory that isn't a chapter.
+    fn fixture(root: &Path) {
+        write(
+            root,
+            &[
+                ("exercises/01_reading/README.md", "# Reading\n"),
+                ("exercises/02_exercise/README.md", "# Exercise\n"),
+                (
+                    "exercises/02_exercise/exercise/Cargo.toml",
+                    "[package]\nname = \"ex02\"\n",
+                ),
+                (
+                    "exercises/02_exercise/solutions/Cargo.toml",
+                    "[package]\nname = \"soln02\"\n",
+                ),
+                (
+                    "exercises/02_exercise/hints.toml",
+                    "[[hint]]\ntext = \"Try harder.\"\n",
+                ),
+                ("exercises/examples/example.rs", "fn main() {}\n"),
+            ],
+        );
+    }
+
+    /// The manifest for the fixture, which matches it.
+    const VALID: &str = "\
+[[chapter]]
+name = \"Chapter 1: Reading\"
+path = \"exercises/01_reading\"
+
+[[chapter]]
+name = \"Chapter 2: Exercise\"
+path = \"exercises/02_exercise\"
+package = \"ex02\"
+expected = \"test_failure\"
+";
+
+    /// Given a name for a test, a manifest, and some files to add to (or
+    /// change in) the fixture, return what's wrong with the manifest.
+    fn problems_with(name: &str, manifest: &str, files: &[(&str, &str)]) -> Vec<String> {
+        let root = scratch(module_path!(), name);
+        fixture(&root);
+        write(&root, files);
+        let manifest: Manifest = basic_toml::from_str(manifest).unwrap();
+        problems(&root, &manifest)
+    }
+
+    #[test]
+    fn valid_manifest_has_no_problems() {
+        assert_eq!(problems_with("valid", VALID, &[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn the_kata_is_valid() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let manifest = manifest::read(root).unwrap();
+        assert_eq!(problems(root, &manifest), Vec::<String>::new());
+    }
+
+    #[test]
+    fn listed_chapters_have_to_be_there() {
+        let manifest = format!(
+            "{VALID}\n[[chapter]]\nname = \"Chapter 3: Missing\"\npath = \"exercises/03_missing\"\n"
+        );
+        assert_eq!(
+            problems_with("missing", &manifest, &[]),
+            ["Chapter 3: Missing is listed in exercises/info.toml, but \
+              exercises/03_missing/README.md doesn't exist"]
+        );
+    }
+
+    #[test]
+    fn chapters_there_have_to_be_listed() {
+        let manifest = VALID.replacen(
+            "[[chapter]]\nname = \"Chapter 1: Reading\"\npath = \"exercises/01_reading\"\n",
+            "",
+            1,
+        );
+        assert_eq!(
+            problems_with("unlisted", &manifest, &[]),
+            ["exercises/01_reading isn't listed in exercises/info.toml"]
+        );
+    }
+
+    #[test]
+    fn chapter_numbers_have_to_be_unique() {
+        let manifest = format!(
+            "{VALID}\n[[chapter]]\nname = \"Chapter 2: Again\"\npath = \"exercises/02_again\"\n"
+        );
+        assert_eq!(
+            problems_with(
+                "unique",
+                &manifest,
+                &[("exercises/02_again/README.md", "# Again\n")]
+            ),
+            ["exercises/02_exercise and exercises/02_again are both chapter 2"]
+        );
+    }
+
+    #[test]
+    fn chapters_have_to_be_in_order() {
+        let (first, second) = VALID.split_at(VALID.rfind("[[chapter]]").unwrap());
+        let manifest = format!("{second}\n{first}");
+        assert_eq!(
+            problems_with("order", &manifest, &[]),
+            ["exercises/01_reading is listed after exercises/02_exercise, but comes before it"]
+        );
+    }
+
+    #[test]
+    fn exercises_need_their_package_and_solution() {
+        let manifest = VALID.replace(
+            "package = \"ex02\"",
+            "package = \"ex2\"\nsolution = \"exercises/02_exercise/answers\"",
+        );
+        assert_eq!(
+            problems_with("packages", &manifest, &[]),
+            [
+                "exercises/02_exercise/exercise is the package ex02, not ex2",
+                "exercises/02_exercise/answers/Cargo.toml doesn't exist",
+            ]
+        );
+    }
+
+    #[test]
+    fn exercises_say_how_they_go() {
+        let manifest = VALID.replace("expected = \"test_failure\"\n", "");
+        assert_eq!(
+            problems_with("expected", &manifest, &[]),
+            ["Chapter 2: Exercise has a package, but no `expected` in exercises/info.toml"]
+        );
+
+        let manifest = VALID.replace("package = \"ex02\"", "package = \"ex02\"\nmode = \"check\"");
+        assert_eq!(
+            problems_with("check", &manifest, &[]),
+            ["Chapter 2: Exercise only has to compile, so it can't be expected to fail its tests"]
+        );
+    }
+
+    #[test]
+    fn readings_have_no_exercise() {
+        let manifest = VALID.replace(
+            "path = \"exercises/01_reading\"",
+            "path = \"exercises/01_reading\"\nexpected = \"passes\"",
+        );
+        assert_eq!(
+            problems_with(
+                "reading",
+                &manifest,
+                &[("exercises/01_reading/exercise/Cargo.toml", "")]
+            ),
+            [
+                "exercises/01_reading/exercise exists, but Chapter 1: Reading has no package in \
+                 exercises/info.toml",
+                "Chapter 1: Reading sets `expected`, but has no package in exercises/info.toml",
+            ]
+        );
+    }
+
+    #[test]
+    fn hints_have_to_be_there_and_parse() {
+        let manifest = VALID.replace(
+            "path = \"exercises/01_reading\"",
+            "path = \"exercises/01_reading\"\nhints = \"exercises/01_reading/clues.toml\"",
+        );
+        assert_eq!(
+            problems_with("hints", &manifest, &[]),
+            [
+                "Chapter 1: Reading has `hints = \"exercises/01_reading/clues.toml\"`, but that \
+              doesn't exist"
+            ]
+        );
+
+        let problems = problems_with(
+            "bad_hints",
+            VALID,
+            &[("exercises/02_exercise/hints.toml", "[[hint]]\nwords = 1\n")],
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].starts_with("couldn't parse exercises/02_exercise/hints.toml"),
+            "{}",
+            problems[0]
+        );
+    }
+
+    #[test]
+    fn names_how_an_exercise_went() {
+        assert_eq!(
+            mismatch("ex03", Expected::CompileError, Expected::Passes),
+            Some(
+                "ex03 should fail to compile (as exercises/info.toml says), but it passes"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            mismatch("ex04", Expected::TestFailure, Expected::CompileError),
+            Some(
+                "ex04 should fail its tests (as exercises/info.toml says), but it fails to \
+                 compile"
+                    .to_string()
+            )
+        );
+        assert_eq!(mismatch("ex05", Expected::Passes, Expected::Passes), None);
+    }
+}
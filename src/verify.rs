@@ -0,0 +1,182 @@
+//! This goes through the exercises in order, and says which one to work on.
+
+use crate::manifest::{Chapter, Manifest};
+use crate::progress::{self, Progress, Status};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::Command;
+
+/// How an exercise went.
+pub enum Outcome {
+    Passed,
+    /// It didn't pass, with what the compiler (or the tests) said about it,
+    /// and the code of the first error the compiler gave, if it gave one.
+    Failed {
+        output: String,
+        code: Option<String>,
+    },
+}
+
+/// Given the root of the repository and the manifest, check each exercise
+/// in order until one doesn't pass, then say where it is. Return whether
+/// they all passed.
+pub fn verify(root: &Path, manifest: &Manifest) -> Result<bool, String> {
+    let mut progress = progress::read(&progress::path(root))?;
+    for chapter in &manifest.chapter {
+        let Some(package) = &chapter.package else {
+            println!("read:    {}", chapter.name);
+            continue;
+        };
+
+        match check_unless_done(root, chapter, package, &mut progress)? {
+            Outcome::Passed => println!("passed:  {}", chapter.name),
+            Outcome::Failed { output, .. } => {
+                println!("failed:  {}", chapter.name);
+                println!();
+                println!("{}", output.trim_end());
+                println!();
+                println!("Next up is {}.", chapter.name);
+                println!("Read {}/README.md, then work on", chapter.path);
+                println!("{}/exercise, and verify again.", chapter.path);
+                return Ok(false);
+            }
+        }
+    }
+
+    println!();
+    println!("Every exercise passes. Well done!");
+    Ok(true)
+}
+
+/// Given the root of the repository, a chapter, its exercise's package and
+/// the progress so far, check the exercise, unless it passed last time and
+/// hasn't changed since. Record how it went in the progress file.
+pub fn check_unless_done(
+    root: &Path,
+    chapter: &Chapter,
+    package: &str,
+    progress: &mut Progress,
+) -> Result<Outcome, String> {
+    // This is hashed first, so a change made during the check means it's
+    // checked again next time.
+    let hash = progress::exercise_hash(root, chapter)?;
+    if progress.status(chapter, Some(&hash)) == Status::Done {
+        return Ok(Outcome::Passed);
+    }
+    let outcome = check(root, chapter, package)?;
+    match &outcome {
+        Outcome::Passed => progress.record(&chapter.path, hash, true, None),
+        Outcome::Failed { code, .. } => progress.record(&chapter.path, hash, false, code.clone()),
+    }
+    progress::write(&progress::path(root), progress)?;
+    Ok(outcome)
+}
+
+/// Given the root of the repository, a chapter and its exercise's package,
+/// run cargo on the package the way the chapter's mode says to.
+pub fn check(root: &Path, chapter: &Chapter, package: &str) -> Result<Outcome, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    // The errors are shown in colour, the way cargo would, if they're going
+    // to a terminal.
+    let message_format = if std::io::stdout().is_terminal() {
+        "--message-format=json-diagnostic-rendered-ansi"
+    } else {
+        "--message-format=json"
+    };
+    let output = Command::new(cargo)
+        .args([chapter.mode.command(), "--quiet", "--package", package])
+        .arg(message_format)
+        .current_dir(root)
+        .output()
+        .map_err(|error| format!("couldn't run cargo: {error}"))?;
+    if output.status.success() {
+        return Ok(Outcome::Passed);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let errors = compiler_errors(&stdout);
+    if !errors.is_empty() {
+        return Ok(Outcome::Failed {
+            output: errors,
+            code: first_error_code(&stdout),
+        });
+    }
+    // It compiled, so the tests must have failed, and what they printed is
+    // everything that wasn't one of cargo's messages.
+    let tests: String = stdout
+        .lines()
+        .filter(|line| !line.starts_with('{'))
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(Outcome::Failed {
+        output: tests + &stderr,
+        code: None,
+    })
+}
+
+/// Given what cargo printed with `--message-format=json`, return the code of
+/// the first error the compiler reported that had one.
+fn first_error_code(stdout: &str) -> Option<String> {
+    compiler_diagnostics(stdout)
+        .filter_map(|diagnostic| diagnostic["code"]["code"].as_str().map(str::to_string))
+        .next()
+}
+
+/// Given what cargo printed with `--message-format=json`, return every error
+/// the compiler reported, the way it would have shown them.
+fn compiler_errors(stdout: &str) -> String {
+    compiler_diagnostics(stdout)
+        .filter_map(|diagnostic| diagnostic["rendered"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// Given what cargo printed with `--message-format=json`, return each error
+/// the compiler reported, as it was in the JSON.
+fn compiler_diagnostics(stdout: &str) -> impl Iterator<Item = serde_json::Value> + '_ {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-message")
+        .map(|message| message["message"].clone())
+        .filter(|diagnostic| diagnostic["level"] == "error")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_only_the_errors() {
+        let stdout = concat!(
+            r#"{"reason":"compiler-artifact","target":{"name":"ex02"}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"warning","rendered":"warning: unused\n"}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","rendered":"error: missing lifetime\n"}}"#,
+            "\n",
+            "running 1 test\n",
+            r#"{"reason":"build-finished","success":false}"#,
+            "\n",
+        );
+
+        assert_eq!(compiler_errors(stdout), "error: missing lifetime\n");
+        assert_eq!(first_error_code(stdout), None);
+    }
+
+    #[test]
+    fn finds_the_first_error_code() {
+        let stdout = concat!(
+            r#"{"reason":"compiler-message","message":{"level":"warning","code":{"code":"unused_variables"},"rendered":"warning: unused\n"}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","code":null,"rendered":"error: aborting\n"}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","code":{"code":"E0106"},"rendered":"error[E0106]: missing lifetime specifier\n"}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","code":{"code":"E0499"},"rendered":"error[E0499]: cannot borrow\n"}}"#,
+            "\n",
+        );
+
+        assert_eq!(first_error_code(stdout).as_deref(), Some("E0106"));
+    }
+}
@@ -0,0 +1,232 @@
+//! This checks the exercise being worked on each time it's saved, and moves
+//! on to the next one when it passes.
+
+use crate::manifest::{Chapter, Manifest};
+use crate::progress;
+use crate::verify::{check_unless_done, Outcome};
+use notify::{RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long to wait after a change for any more, before checking. Editors
+/// often write a file in several steps, and "save all" saves several files.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// How often to look at whether it's time to check, between changes.
+const TICK: Duration = Duration::from_millis(50);
+
+/// Something that happened while watching, and when (from the start).
+#[derive(Debug)]
+pub struct Input {
+    pub at: Duration,
+    pub kind: InputKind,
+}
+
+#[derive(Debug)]
+pub enum InputKind {
+    /// A file was created, changed or removed.
+    Changed(PathBuf),
+    /// Nothing happened, but time passed.
+    Tick,
+}
+
+/// What to do about what happened.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Check the exercise again.
+    Verify,
+}
+
+/// Given what happened while watching, in order, and how long changes have
+/// to settle for, return what to do about it: a `Verify` once the changes
+/// to source files have stopped for that long. Changes to anything else
+/// (like an editor's swap and backup files) are ignored.
+pub fn actions(
+    inputs: impl IntoIterator<Item = Input>,
+    settle: Duration,
+) -> impl Iterator<Item = Action> {
+    let mut due: Option<Duration> = None;
+    inputs.into_iter().filter_map(move |input| {
+        if let InputKind::Changed(path) = &input.kind {
+            if is_source(path) {
+                due = Some(input.at + settle);
+            }
+        }
+        match due {
+            Some(time) if input.at >= time => {
+                due = None;
+                Some(Action::Verify)
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Given the path of a file that changed, return whether it's Rust source,
+/// rather than a file an editor keeps next to one, like `.lib.rs.swp`,
+/// `lib.rs~`, or `.#lib.rs`.
+fn is_source(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    name.ends_with(".rs") && !name.starts_with('.') && !name.starts_with('#')
+}
+
+/// Given the root of the repository and the manifest, check the exercises
+/// in order, then watch the first one that doesn't pass, checking it again
+/// each time it's saved. Return once every exercise passes.
+pub fn watch(root: &Path, manifest: &Manifest) -> Result<bool, String> {
+    let exercises = manifest
+        .chapter
+        .iter()
+        .filter_map(|chapter| Some((chapter, chapter.package.as_deref()?)));
+    // This is the exercise that was just made to pass, if there is one, so
+    // the next screen can say so.
+    let mut solved: Option<&Chapter> = None;
+    let mut progress = progress::read(&progress::path(root))?;
+
+    for (chapter, package) in exercises {
+        let mut failed = false;
+        loop {
+            let outcome = check_unless_done(root, chapter, package, &mut progress)?;
+            let Outcome::Failed { output, .. } = outcome else {
+                break;
+            };
+
+            clear_screen();
+            if let Some(previous) = solved.take() {
+                println!("{} passes!", previous.name);
+                println!();
+            }
+            println!("{}", output.trim_end());
+            println!();
+            println!("Next up is {}.", chapter.name);
+            println!("Read {}/README.md, then work on", chapter.path);
+            println!("{}/exercise. It's checked again", chapter.path);
+            println!("each time it's saved.");
+            wait_for_save(&root.join(chapter.exercise()).join("src"))?;
+            failed = true;
+        }
+        if failed {
+            solved = Some(chapter);
+        }
+    }
+
+    if let Some(previous) = solved {
+        clear_screen();
+        println!("{} passes!", previous.name);
+        println!();
+    }
+    println!("Every exercise passes. Well done!");
+    Ok(true)
+}
+
+/// Given a directory, return once a source file in it has been saved.
+fn wait_for_save(directory: &Path) -> Result<(), String> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)
+        .map_err(|error| format!("couldn't watch for changes: {error}"))?;
+    watcher
+        .watch(directory, RecursiveMode::Recursive)
+        .map_err(|error| format!("couldn't watch {}: {error}", directory.display()))?;
+
+    let start = Instant::now();
+    let mut pending = Vec::new();
+    let inputs = std::iter::from_fn(|| {
+        if pending.is_empty() {
+            match receiver.recv_timeout(TICK) {
+                Ok(Ok(event)) if !event.kind.is_access() => {
+                    pending.extend(event.paths.into_iter().rev().map(|path| Input {
+                        at: start.elapsed(),
+                        kind: InputKind::Changed(path),
+                    }));
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => return None,
+                _ => {}
+            }
+        }
+        Some(pending.pop().unwrap_or(Input {
+            at: start.elapsed(),
+            kind: InputKind::Tick,
+        }))
+    });
+
+    let action = actions(inputs, SETTLE).next();
+    match action {
+        Some(Action::Verify) => Ok(()),
+        None => Err("stopped getting changes from the watcher".to_string()),
+    }
+}
+
+/// Clear the terminal, so each check starts at the top.
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed(millis: u64, path: &str) -> Input {
+        Input {
+            at: Duration::from_millis(millis),
+            kind: InputKind::Changed(PathBuf::from(path)),
+        }
+    }
+
+    fn tick(millis: u64) -> Input {
+        Input {
+            at: Duration::from_millis(millis),
+            kind: InputKind::Tick,
+        }
+    }
+
+    fn count(inputs: Vec<Input>) -> usize {
+        actions(inputs, Duration::from_millis(100)).count()
+    }
+
+    #[test]
+    fn verifies_once_a_save_settles() {
+        assert_eq!(count(vec![changed(0, "src/lib.rs"), tick(50)]), 0);
+        assert_eq!(count(vec![changed(0, "src/lib.rs"), tick(100)]), 1);
+    }
+
+    #[test]
+    fn verifies_once_for_a_burst_of_saves() {
+        let inputs = vec![
+            changed(0, "src/lib.rs"),
+            changed(60, "src/main.rs"),
+            tick(120),
+            changed(150, "src/lib.rs"),
+            tick(200),
+            tick(250),
+            tick(300),
+        ];
+        assert_eq!(count(inputs), 1);
+    }
+
+    #[test]
+    fn verifies_again_after_another_save() {
+        let inputs = vec![
+            changed(0, "src/lib.rs"),
+            tick(100),
+            changed(500, "src/lib.rs"),
+            tick(600),
+        ];
+        assert_eq!(count(inputs), 2);
+    }
+
+    #[test]
+    fn ignores_editor_files() {
+        let inputs = vec![
+            changed(0, "src/.lib.rs.swp"),
+            changed(0, "src/lib.rs~"),
+            changed(0, "src/.#lib.rs"),
+            changed(0, "src/#lib.rs#"),
+            changed(0, "src/4913"),
+            tick(500),
+        ];
+        assert_eq!(count(inputs), 0);
+    }
+}
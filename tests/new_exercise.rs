@@ -0,0 +1,146 @@
+//! This runs `lifetimekata new-exercise` on a kata with no exercises yet,
+//! and checks that what it sets up builds, fails its tests until it's
+//! written, and can be put back with `lifetimekata reset`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Given a directory, copy everything in it to another one.
+fn copy_dir(from: &Path, to: &Path) {
+    std::fs::create_dir_all(to).unwrap();
+    for entry in std::fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &to.join(entry.file_name()));
+        } else {
+            std::fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+        }
+    }
+}
+
+/// Given a directory, set up a kata in it with two chapters, to read. New
+/// exercises depend on `require_lifetimes` by its path in the kata, so the
+/// kata gets a copy of it.
+fn fixture(root: &Path) {
+    std::fs::create_dir_all(root.join("exercises")).unwrap();
+    let require_lifetimes = Path::new(env!("CARGO_MANIFEST_DIR")).join("require_lifetimes");
+    std::fs::create_dir_all(root.join("require_lifetimes")).unwrap();
+    std::fs::copy(
+        require_lifetimes.join("Cargo.toml"),
+        root.join("require_lifetimes/Cargo.toml"),
+    )
+    .unwrap();
+    copy_dir(
+        &require_lifetimes.join("src"),
+        &root.join("require_lifetimes/src"),
+    );
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\n\
+         resolver = \"2\"\n\
+         \n\
+         members = [\n\
+         ]\n\
+         \n\
+         [workspace.lints.clippy]\n\
+         needless_lifetimes = \"allow\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("exercises/info.toml"),
+        "# The chapters.\n\
+         \n\
+         [[chapter]]\n\
+         name = \"Chapter 1: One\"\n\
+         path = \"exercises/01_one\"\n\
+         \n\
+         [[chapter]]\n\
+         name = \"Chapter 5: Five\"\n\
+         path = \"exercises/05_five\"\n",
+    )
+    .unwrap();
+}
+
+/// Given the kata's root, run the runner in it with some arguments.
+fn lifetimekata(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_lifetimekata"))
+        .args(args)
+        .env("LIFETIMEKATA_ROOT", root)
+        .output()
+        .unwrap()
+}
+
+/// Given the kata's root, run cargo in it with some arguments.
+fn cargo(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO"))
+        .args(args)
+        .current_dir(root)
+        .env("CARGO_TARGET_DIR", root.join("target"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn sets_up_exercises_that_build() {
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("new_exercise_fixture");
+    let _ = std::fs::remove_dir_all(&root);
+    fixture(&root);
+
+    for (directory, template) in [("07_find_pattern", "matcher"), ("03_first_word", "basic")] {
+        let output = lifetimekata(&root, &["new-exercise", directory, "--template", template]);
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    let manifest = std::fs::read_to_string(root.join("exercises/info.toml")).unwrap();
+    let at = |path: &str| manifest.find(path).unwrap();
+    assert!(at("01_one") < at("03_first_word"));
+    assert!(at("03_first_word") < at("05_five"));
+    assert!(at("05_five") < at("07_find_pattern"));
+    assert!(manifest.contains("name = \"Chapter 3: First Word\"\n"));
+    assert!(manifest.contains("package = \"ex03\"\nexpected = \"test_failure\"\n"));
+
+    // `reset` puts an exercise back how it was set up.
+    let lib = root.join("exercises/03_first_word/exercise/src/lib.rs");
+    let set_up = std::fs::read_to_string(&lib).unwrap();
+    std::fs::write(&lib, "mangled").unwrap();
+    let output = lifetimekata(&root, &["reset", "3", "--yes"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(std::fs::read_to_string(&lib).unwrap(), set_up);
+
+    let build = cargo(&root, &["build", "--workspace", "--all-targets"]);
+    assert!(
+        build.status.success(),
+        "{}",
+        String::from_utf8_lossy(&build.stderr)
+    );
+    // The tests are there to fail until the exercise is written.
+    for package in ["ex03", "ex07"] {
+        let test = cargo(&root, &["test", "--package", package]);
+        assert!(!test.status.success(), "{package}'s tests passed");
+        assert!(String::from_utf8_lossy(&test.stdout).contains("not implemented"));
+    }
+}
+
+#[test]
+fn refuses_to_overwrite() {
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("new_exercise_overwrite");
+    let _ = std::fs::remove_dir_all(&root);
+    fixture(&root);
+    std::fs::create_dir_all(root.join("exercises/03_first_word")).unwrap();
+    let cargo_toml = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+
+    let output = lifetimekata(&root, &["new-exercise", "03_first_word"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is there already"));
+    // Another name for a chapter that's there is refused too.
+    let output = lifetimekata(&root, &["new-exercise", "05_another_five"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Chapter 5: Five is there already"));
+
+    assert!(!root.join("exercises/03_first_word/exercise").exists());
+    assert!(!root.join("exercises/05_another_five").exists());
+    assert_eq!(
+        std::fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+        cargo_toml
+    );
+}
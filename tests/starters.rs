@@ -0,0 +1,13 @@
+//! Each exercise that's meant not to compile as it's handed out keeps a copy
+//! of how it starts, in its chapter's `starter`, with the errors it's meant
+//! to fail with. This checks they still fail, with the same errors, so a new
+//! compiler that starts accepting one (or says something else about it) is
+//! noticed. A chapter can keep the wrong way to go about its exercise there
+//! too, as chapter 11 does. To accept new errors, run this with
+//! `TRYBUILD=overwrite`.
+
+#[test]
+fn starters_fail_to_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("exercises/*/starter/*.rs");
+}
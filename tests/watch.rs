@@ -0,0 +1,107 @@
+//! This runs `lifetimekata watch` on a kata with one exercise, which starts
+//! out broken, then fixes it the way someone working on it would.
+
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+const BROKEN: &str = "pub fn answer() -> u32 {\n    \"forty-two\"\n}\n";
+const FIXED: &str = "pub fn answer() -> u32 {\n    42\n}\n";
+
+/// Given a directory, set up a kata in it with one exercise, `smoke`.
+fn fixture(root: &Path) {
+    let exercise = root.join("exercises/01_smoke/exercise");
+    std::fs::create_dir_all(exercise.join("src")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nresolver = \"2\"\nmembers = [\"exercises/01_smoke/exercise\"]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("exercises/info.toml"),
+        "[[chapter]]\n\
+         name = \"Chapter 1: Smoke\"\n\
+         path = \"exercises/01_smoke\"\n\
+         package = \"smoke\"\n\
+         mode = \"check\"\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("exercises/01_smoke/README.md"), "# Smoke\n").unwrap();
+    std::fs::write(
+        exercise.join("Cargo.toml"),
+        "[package]\nname = \"smoke\"\nversion = \"0.0.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(exercise.join("src/lib.rs"), BROKEN).unwrap();
+}
+
+#[test]
+fn checks_again_when_the_exercise_is_saved() {
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("watch_fixture");
+    let _ = std::fs::remove_dir_all(&root);
+    fixture(&root);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lifetimekata"))
+        .arg("watch")
+        .env("LIFETIMEKATA_ROOT", &root)
+        .env("CARGO_TARGET_DIR", root.join("target"))
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let (sender, lines) = mpsc::channel();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    std::thread::spawn(move || {
+        for line in stdout.lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(120);
+    let mut seen = String::new();
+    wait_for(
+        &lines,
+        &mut seen,
+        "Next up is Chapter 1: Smoke.",
+        deadline,
+        || {},
+    );
+    assert!(seen.contains("mismatched types"));
+    // The watcher starts after the message is printed, so this saves the
+    // fix until it's noticed.
+    let lib = root.join("exercises/01_smoke/exercise/src/lib.rs");
+    wait_for(
+        &lines,
+        &mut seen,
+        "Every exercise passes.",
+        deadline,
+        || std::fs::write(&lib, FIXED).unwrap(),
+    );
+    assert!(seen.contains("Chapter 1: Smoke passes!"));
+    assert!(child.wait().unwrap().success());
+}
+
+/// Given the lines the runner prints, what it's printed so far, some text
+/// and a deadline, wait until the runner prints the text, calling
+/// `meanwhile` whenever it's quiet for a bit.
+fn wait_for(
+    lines: &mpsc::Receiver<String>,
+    seen: &mut String,
+    text: &str,
+    deadline: Instant,
+    mut meanwhile: impl FnMut(),
+) {
+    while !seen.contains(text) {
+        assert!(Instant::now() < deadline, "never saw {text:?} in:\n{seen}");
+        match lines.recv_timeout(Duration::from_secs(2)) {
+            Ok(line) => {
+                seen.push_str(&line);
+                seen.push('\n');
+            }
+            Err(_) => meanwhile(),
+        }
+    }
+}
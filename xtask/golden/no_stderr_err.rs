@@ -0,0 +1 @@
+// This has no .stderr next to it, so it doesn't get a snippet.
@@ -0,0 +1 @@
+// Only tests that fail to compile get snippets.
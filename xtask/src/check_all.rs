@@ -0,0 +1,277 @@
+//! This checks every chapter's exercise and solution: each solution has to
+//! pass, and each exercise has to go the way the manifest's `expected` says
+//! it does as it's handed out. That catches an exercise that starts to
+//! compile (or to pass) by accident, like after a new toolchain.
+
+use serde_derive::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Where the manifest is, from the workspace's root.
+pub const MANIFEST: &str = "exercises/info.toml";
+
+/// The parts of the manifest this needs; the runner reads the rest.
+#[derive(Deserialize)]
+struct Manifest {
+    chapter: Vec<Chapter>,
+}
+
+#[derive(Deserialize)]
+struct Chapter {
+    name: String,
+    path: String,
+    package: Option<String>,
+    solution: Option<String>,
+    #[serde(default)]
+    mode: Mode,
+    expected: Option<Outcome>,
+}
+
+/// What an exercise has to do to pass.
+#[derive(Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    #[default]
+    Test,
+    Check,
+}
+
+/// How an exercise goes.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    CompileError,
+    TestFailure,
+    Passes,
+}
+
+impl Outcome {
+    /// Return what it is, as what something should do.
+    fn expected(self) -> &'static str {
+        match self {
+            Outcome::CompileError => "fail to compile",
+            Outcome::TestFailure => "fail its tests",
+            Outcome::Passes => "pass",
+        }
+    }
+
+    /// Return what it is, as what something did.
+    fn actual(self) -> &'static str {
+        match self {
+            Outcome::CompileError => "fails to compile",
+            Outcome::TestFailure => "fails its tests",
+            Outcome::Passes => "passes",
+        }
+    }
+}
+
+/// Given the workspace's root, test every solution, and check every
+/// exercise goes the way it's expected to. Fail with everything that
+/// doesn't.
+pub fn check_all(root: &Path) -> Result<(), String> {
+    let text = std::fs::read_to_string(root.join(MANIFEST))
+        .map_err(|error| format!("couldn't read {MANIFEST}: {error}"))?;
+    let manifest: Manifest = basic_toml::from_str(&text)
+        .map_err(|error| format!("couldn't parse {MANIFEST}: {error}"))?;
+
+    let mut problems = Vec::new();
+    for chapter in &manifest.chapter {
+        let Some(package) = &chapter.package else {
+            continue;
+        };
+        let expected = chapter
+            .expected
+            .ok_or_else(|| format!("{} has no `expected` in {MANIFEST}", chapter.name))?;
+
+        let solution = solution_package(root, chapter)?;
+        if crate::cargo(&["test", "--package", &solution]).is_err() {
+            problems.push(format!(
+                "{solution}, the solution to {package}, doesn't pass"
+            ));
+        }
+
+        let outcome = exercise_outcome(root, chapter, package)?;
+        eprintln!(
+            "{package} {}, and should {}",
+            outcome.actual(),
+            expected.expected()
+        );
+        problems.extend(mismatch(package, expected, outcome));
+    }
+
+    if problems.is_empty() {
+        eprintln!("every solution passes, and every exercise goes as expected");
+        Ok(())
+    } else {
+        Err(problems.join("\n"))
+    }
+}
+
+/// Given the workspace's root and a chapter, return the name of the package
+/// in its `solutions`.
+fn solution_package(root: &Path, chapter: &Chapter) -> Result<String, String> {
+    #[derive(Deserialize)]
+    struct CargoToml {
+        package: Package,
+    }
+    #[derive(Deserialize)]
+    struct Package {
+        name: String,
+    }
+
+    let path = match &chapter.solution {
+        Some(solution) => Path::new(solution).join("Cargo.toml"),
+        None => Path::new(&chapter.path).join("solutions/Cargo.toml"),
+    };
+    let text = std::fs::read_to_string(root.join(&path))
+        .map_err(|error| format!("couldn't read {}: {error}", path.display()))?;
+    let cargo_toml: CargoToml = basic_toml::from_str(&text)
+        .map_err(|error| format!("couldn't parse {}: {error}", path.display()))?;
+    Ok(cargo_toml.package.name)
+}
+
+/// Given the workspace's root, a chapter and its exercise's package, build
+/// the exercise, then (if it builds, and has to pass tests) test it, and
+/// return how it went.
+fn exercise_outcome(root: &Path, chapter: &Chapter, package: &str) -> Result<Outcome, String> {
+    let build: &[&str] = match chapter.mode {
+        Mode::Test => &["test", "--no-run"],
+        Mode::Check => &["check"],
+    };
+    let mut args = build.to_vec();
+    args.extend_from_slice(&["--package", package, "--message-format=json"]);
+    let output = cargo_output(root, &args)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let built = built(&stdout).map_err(|error| format!("{package}: {error}\n{stderr}"))?;
+
+    let tests_passed = if built && chapter.mode == Mode::Test {
+        let output = cargo_output(root, &["test", "--package", package])?;
+        Some(output.status.success())
+    } else {
+        None
+    };
+    outcome(&stdout, tests_passed).map_err(|error| format!("{package}: {error}"))
+}
+
+/// Given what cargo printed building a package with `--message-format=json`,
+/// return whether it built.
+fn built(stdout: &str) -> Result<bool, String> {
+    let mut errors = false;
+    for message in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        if message["reason"] == "compiler-message" && message["message"]["level"] == "error" {
+            errors = true;
+        }
+        if message["reason"] == "build-finished" {
+            return Ok(message["success"] == true && !errors);
+        }
+    }
+    Err("cargo stopped before it finished building".to_string())
+}
+
+/// Given what cargo printed building an exercise with
+/// `--message-format=json`, and whether its tests passed (if they were
+/// run), return how it went.
+fn outcome(stdout: &str, tests_passed: Option<bool>) -> Result<Outcome, String> {
+    Ok(match (built(stdout)?, tests_passed) {
+        (false, _) => Outcome::CompileError,
+        (true, Some(false)) => Outcome::TestFailure,
+        (true, _) => Outcome::Passes,
+    })
+}
+
+/// Given an exercise's package, how it should go and how it went, return
+/// what's wrong, if anything.
+fn mismatch(package: &str, expected: Outcome, outcome: Outcome) -> Option<String> {
+    (expected != outcome).then(|| {
+        format!(
+            "{package} should {}, but it {}",
+            expected.expected(),
+            outcome.actual()
+        )
+    })
+}
+
+fn cargo_output(root: &Path, args: &[&str]) -> Result<Output, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    eprintln!("running: cargo {}", args.join(" "));
+    Command::new(cargo)
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|error| format!("couldn't run cargo: {error}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::snippets::workspace_root;
+
+    const ARTIFACT: &str = r#"{"reason":"compiler-artifact","target":{"name":"ex03"}}"#;
+    const WARNING: &str = r#"{"reason":"compiler-message","message":{"level":"warning","rendered":"warning: unused\n"}}"#;
+    const ERROR: &str = r#"{"reason":"compiler-message","message":{"level":"error","rendered":"error[E0106]: missing lifetime specifier\n"}}"#;
+    const BUILT: &str = r#"{"reason":"build-finished","success":true}"#;
+    const NOT_BUILT: &str = r#"{"reason":"build-finished","success":false}"#;
+
+    fn stdout(lines: &[&str]) -> String {
+        lines.iter().flat_map(|line| [*line, "\n"]).collect()
+    }
+
+    #[test]
+    fn compile_errors_fail_to_compile() {
+        let stdout = stdout(&[WARNING, ERROR, NOT_BUILT]);
+        assert_eq!(outcome(&stdout, None), Ok(Outcome::CompileError));
+        // An error anywhere means it didn't compile, whatever cargo says.
+        let stdout = self::stdout(&[ERROR, BUILT]);
+        assert_eq!(outcome(&stdout, None), Ok(Outcome::CompileError));
+    }
+
+    #[test]
+    fn failing_tests_fail_the_tests() {
+        let stdout = stdout(&[ARTIFACT, WARNING, BUILT]);
+        assert_eq!(outcome(&stdout, Some(false)), Ok(Outcome::TestFailure));
+    }
+
+    #[test]
+    fn passes_when_it_builds_and_its_tests_pass() {
+        let stdout = stdout(&[ARTIFACT, "   Compiling ex03", WARNING, BUILT]);
+        assert_eq!(outcome(&stdout, Some(true)), Ok(Outcome::Passes));
+        // Something that only has to compile passes by compiling.
+        assert_eq!(outcome(&stdout, None), Ok(Outcome::Passes));
+    }
+
+    #[test]
+    fn needs_cargo_to_finish() {
+        let stdout = stdout(&[ARTIFACT, WARNING]);
+        assert!(outcome(&stdout, Some(true)).is_err());
+        assert!(outcome("", None).is_err());
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        assert_eq!(
+            mismatch("ex03", Outcome::CompileError, Outcome::Passes),
+            Some("ex03 should fail to compile, but it passes".to_string())
+        );
+        assert_eq!(
+            mismatch("ex04", Outcome::TestFailure, Outcome::CompileError),
+            Some("ex04 should fail its tests, but it fails to compile".to_string())
+        );
+        assert_eq!(mismatch("ex05", Outcome::Passes, Outcome::Passes), None);
+    }
+
+    #[test]
+    fn reads_the_manifest() {
+        let root = workspace_root();
+        let text = std::fs::read_to_string(root.join(MANIFEST)).unwrap();
+        let manifest: Manifest = basic_toml::from_str(&text).unwrap();
+        for chapter in manifest.chapter.iter().filter(|c| c.package.is_some()) {
+            assert!(chapter.expected.is_some(), "{}", chapter.name);
+            solution_package(&root, chapter).unwrap();
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! This writes the C header for the finale matcher's `ffi` module with
+//! cbindgen, so that it's only ever changed along with the functions it
+//! declares.
+
+use std::fs;
+use std::path::Path;
+
+/// The module the header declares, from the workspace's root.
+pub const SOURCE: &str = "exercises/08_finale/exercise/src/ffi.rs";
+
+/// Where the header goes, from the workspace's root.
+pub const HEADER: &str = "exercises/08_finale/exercise/include/lk_matcher.h";
+
+/// Given the workspace's root, return the header, as cbindgen writes it.
+fn generate(root: &Path) -> Result<String, String> {
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        header: Some("/* The finale matcher, for C. See src/ffi.rs. */".to_string()),
+        autogen_warning: Some(
+            "/* Written by `cargo xtask ffi-header`; don't change it by hand. */".to_string(),
+        ),
+        include_guard: Some("LK_MATCHER_H".to_string()),
+        sys_includes: vec!["stddef.h".to_string()],
+        usize_is_size_t: true,
+        no_includes: true,
+        documentation: true,
+        ..Default::default()
+    };
+    let mut header = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root.join(SOURCE))
+        .generate()
+        .map_err(|error| format!("cbindgen couldn't read {SOURCE}: {error}"))?
+        .write(&mut header);
+    String::from_utf8(header).map_err(|error| format!("cbindgen wrote something odd: {error}"))
+}
+
+/// Given the workspace's root, write the header.
+pub fn write(root: &Path) -> Result<(), String> {
+    let header = generate(root)?;
+    let path = root.join(HEADER);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("couldn't create {}: {error}", parent.display()))?;
+    }
+    fs::write(&path, header).map_err(|error| format!("couldn't write {HEADER}: {error}"))?;
+    eprintln!("wrote {HEADER}");
+    Ok(())
+}
+
+/// Given the workspace's root, fail if the header isn't what cbindgen would
+/// write now.
+pub fn check(root: &Path) -> Result<(), String> {
+    let written = fs::read_to_string(root.join(HEADER))
+        .map_err(|error| format!("couldn't read {HEADER}: {error}"))?;
+    if written == generate(root)? {
+        eprintln!("{HEADER} is up to date");
+        Ok(())
+    } else {
+        Err(format!(
+            "{HEADER} doesn't match {SOURCE}; run `cargo xtask ffi-header`"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::snippets::workspace_root;
+
+    #[test]
+    fn header_is_up_to_date() {
+        check(&workspace_root()).unwrap();
+    }
+
+    #[test]
+    fn header_declares_every_function() {
+        let header = generate(&workspace_root()).unwrap();
+        for function in [
+            "struct LkMatcher *lk_matcher_new(const char *pattern);",
+            "struct LkMatcher *lk_matcher_parse(const char *pattern, struct LkParseError *error);",
+            "void lk_matcher_free(struct LkMatcher *matcher);",
+            "int lk_matcher_match(",
+            "void lk_match_result_free(struct LkMatchResult *result);",
+        ] {
+            assert!(header.contains(function), "{function} isn't in:\n{header}");
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Helper tasks for working on the repository, run with `cargo xtask <task>`.
+
+mod check_all;
+mod ffi_header;
+mod playground;
+mod shared_tests;
+mod snapshots;
+mod snippets;
+
+use std::env;
+use std::process::{exit, Command};
+
+/// These are the feature sets of the finale matcher that have to keep
+/// building: without the exhaustive engine, the default, and everything.
+const FEATURE_MATRIX: &[&[&str]] = &[&["--no-default-features"], &[], &["--all-features"]];
+
+/// A target without an operating system, for checking that the finale
+/// matcher builds without `std`.
+const NO_STD_TARGET: &str = "thumbv7m-none-eabi";
+
+/// These are the feature sets of the finale matcher that have to build
+/// without `std`: greedy matching, and the exhaustive engine.
+const NO_STD_FEATURES: &[&[&str]] = &[
+    &["--no-default-features"],
+    &["--no-default-features", "--features", "exhaustive"],
+];
+
+fn main() {
+    let task = env::args().nth(1);
+
+    let result = match task.as_deref() {
+        Some("feature-matrix") => feature_matrix(),
+        Some("no-std") => no_std(),
+        Some("book-snippets") => {
+            let root = snippets::workspace_root();
+            snippets::write(&root.join(snippets::CORPUS), &root.join(snippets::OUTPUT))
+        }
+        Some("playground-links") => playground::write(&snippets::workspace_root()),
+        Some("validate") => cargo(&[
+            "run",
+            "--quiet",
+            "--package",
+            "lifetimekata",
+            "--",
+            "validate",
+        ]),
+        Some("snapshots") => {
+            let root = snippets::workspace_root();
+            if env::args().nth(2).as_deref() == Some("--check") {
+                snapshots::check(&root)
+            } else {
+                snapshots::write(&root)
+            }
+        }
+        Some("ffi-header") => {
+            let root = snippets::workspace_root();
+            if env::args().nth(2).as_deref() == Some("--check") {
+                ffi_header::check(&root)
+            } else {
+                ffi_header::write(&root)
+            }
+        }
+        Some("check-all") => check_all::check_all(&snippets::workspace_root()),
+        Some("shared-tests") => {
+            shared_tests::check(&snippets::workspace_root().join(shared_tests::EXERCISES))
+        }
+        _ => {
+            eprintln!("usage: cargo xtask <task>");
+            eprintln!();
+            eprintln!("tasks:");
+            eprintln!("    feature-matrix    build, lint and test ex08 with each set of features");
+            eprintln!("    no-std            check ex08 builds without std, for {NO_STD_TARGET}");
+            eprintln!("    book-snippets     write the book's error snippets from the UI tests");
+            eprintln!("    playground-links  write the book's links to exercises in the Rust");
+            eprintln!("                      Playground, for chapters with `playground = true`");
+            eprintln!("    check-all         test every solution, and check every exercise goes");
+            eprintln!("                      the way info.toml expects");
+            eprintln!("    validate          check exercises/info.toml against the exercises,");
+            eprintln!("                      with `cargo run -- validate`");
+            eprintln!("    snapshots         copy each exercise, as git has it, to");
+            eprintln!("                      exercises/.snapshots for `cargo run -- reset`;");
+            eprintln!("                      `--check` fails if they've drifted instead");
+            eprintln!("    ffi-header        write ex08's C header from its `ffi` module, with");
+            eprintln!("                      cbindgen; `--check` fails if it's out of date");
+            eprintln!("    shared-tests      check each exercise and its solution include their");
+            eprintln!("                      chapter's shared_tests.rs");
+            exit(2);
+        }
+    };
+
+    if let Err(message) = result {
+        eprintln!("error: {message}");
+        exit(1);
+    }
+}
+
+fn feature_matrix() -> Result<(), String> {
+    for features in FEATURE_MATRIX {
+        for command in [
+            &["build", "--package", "ex08", "--all-targets"][..],
+            &["clippy", "--package", "ex08", "--all-targets"],
+            &["test", "--package", "ex08"],
+        ] {
+            let mut args = command.to_vec();
+            args.extend_from_slice(features);
+            if command[0] == "clippy" {
+                args.extend_from_slice(&["--", "-D", "warnings"]);
+            }
+
+            cargo(&args)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn no_std() -> Result<(), String> {
+    for features in NO_STD_FEATURES {
+        let mut args = vec![
+            "check",
+            "--package",
+            "ex08",
+            "--lib",
+            "--target",
+            NO_STD_TARGET,
+        ];
+        args.extend_from_slice(features);
+        cargo(&args).map_err(|error| {
+            format!("{error} (it needs `rustup target add {NO_STD_TARGET}` first)")
+        })?;
+    }
+
+    Ok(())
+}
+
+fn cargo(args: &[&str]) -> Result<(), String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    eprintln!("running: cargo {}", args.join(" "));
+
+    let status = Command::new(cargo)
+        .args(args)
+        .status()
+        .map_err(|error| format!("couldn't run cargo: {error}"))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("`cargo {}` failed", args.join(" ")))
+    }
+}
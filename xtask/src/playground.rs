@@ -0,0 +1,314 @@
+//! This writes a page of links that open exercises in the Rust Playground,
+//! for each chapter with `playground = true` in the manifest. The playground
+//! doesn't have `require_lifetimes`, so it's taken out of the source first;
+//! the compiler still checks the lifetimes, it just doesn't insist they're
+//! written out.
+
+use crate::check_all::MANIFEST;
+use serde_derive::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Where the page goes, from the workspace's root.
+pub const OUTPUT: &str = "book/src/links.md";
+
+/// How the links start; the code goes on the end.
+const PLAYGROUND: &str = "https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&code=";
+
+/// How long a link can be, in bytes. Some browsers and servers cut off
+/// longer ones, so an exercise that needs a longer link is reported instead.
+pub const LIMIT: usize = 8 * 1024;
+
+/// The parts of the manifest this needs; the runner reads the rest.
+#[derive(Deserialize)]
+struct Manifest {
+    chapter: Vec<Chapter>,
+}
+
+#[derive(Deserialize)]
+struct Chapter {
+    name: String,
+    path: String,
+    #[serde(default)]
+    playground: bool,
+}
+
+/// Given an exercise's source, take out `require_lifetimes`: its `use`, and
+/// each `#[require_lifetimes]` attribute (with or without arguments). A line
+/// that only had those on it goes too.
+pub fn strip_attributes(source: &str) -> String {
+    let mut stripped = String::new();
+    for line in source.lines() {
+        if line.trim_start().starts_with("use require_lifetimes") {
+            continue;
+        }
+        let without = strip_attributes_from(line);
+        if without.trim().is_empty() && !line.trim().is_empty() {
+            continue;
+        }
+        stripped.push_str(&without);
+        stripped.push('\n');
+    }
+    stripped
+}
+
+/// Given a line, take out each `#[require_lifetimes ...]` in it, along with
+/// the space after it.
+fn strip_attributes_from(line: &str) -> String {
+    let mut stripped = String::new();
+    let mut rest = line;
+    // This finds `#[require_lifetimes::require_lifetimes]` too.
+    while let Some(start) = rest.find("#[require_lifetimes") {
+        stripped.push_str(&rest[..start]);
+        // The attribute ends at the `]` that closes its `#[`, which might
+        // have brackets of its own inside it.
+        let mut depth = 0;
+        let end = rest[start + 1..].find(|c| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        });
+        match end {
+            Some(end) => rest = rest[start + 1 + end + 1..].trim_start(),
+            // It carries on onto the next line, which this doesn't handle,
+            // so it's left as it is.
+            None => {
+                stripped.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// Given an exercise's source and the directory it's in, replace each line
+/// like `include!("../../shared_tests.rs");` with that file, indented the
+/// same as the line was.
+pub fn inline_includes(source: &str, directory: &Path) -> Result<String, String> {
+    let mut inlined = String::new();
+    for line in source.lines() {
+        let included = line
+            .trim()
+            .strip_prefix("include!(\"")
+            .and_then(|rest| rest.strip_suffix("\");"));
+        let Some(included) = included else {
+            inlined.push_str(line);
+            inlined.push('\n');
+            continue;
+        };
+        let indent = &line[..line.len() - line.trim_start().len()];
+        for included_line in read(&directory.join(included))?.lines() {
+            if !included_line.is_empty() {
+                inlined.push_str(indent);
+            }
+            inlined.push_str(included_line);
+            inlined.push('\n');
+        }
+    }
+    Ok(inlined)
+}
+
+/// Given some text, percent-encode everything in it but letters, digits,
+/// and `-`, `.`, `_` and `~`, so it can go in a URL's query.
+pub fn encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Given some code, return a link that opens it in the playground, or how
+/// long that link would be, if it's longer than `LIMIT`.
+pub fn link(code: &str) -> Result<String, usize> {
+    let link = format!("{PLAYGROUND}{}", encode(code));
+    if link.len() > LIMIT {
+        Err(link.len())
+    } else {
+        Ok(link)
+    }
+}
+
+/// Given the workspace's root and a chapter, return its exercise's source,
+/// ready for the playground. It has to be a single file.
+fn source(root: &Path, chapter: &Chapter) -> Result<String, String> {
+    let src = root.join(&chapter.path).join("exercise/src");
+    let files: Vec<_> = fs::read_dir(&src)
+        .map_err(|error| format!("couldn't read {}: {error}", src.display()))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect();
+    let [file] = files.as_slice() else {
+        return Err(format!(
+            "{} has `playground = true`, but its exercise isn't a single file",
+            chapter.name
+        ));
+    };
+    let source = inline_includes(&read(file)?, &src)?;
+    Ok(strip_attributes(&source))
+}
+
+/// Given the workspace's root, return the page of links, and the name of
+/// each chapter whose exercise is too big for one. Those are listed on the
+/// page too, saying so.
+pub fn page(root: &Path) -> Result<(String, Vec<String>), String> {
+    let text = read(&root.join(MANIFEST))?;
+    let manifest: Manifest = basic_toml::from_str(&text)
+        .map_err(|error| format!("couldn't parse {MANIFEST}: {error}"))?;
+
+    let mut page = format!(
+        "<!-- Generated by `cargo xtask playground-links` from {MANIFEST}; \
+         change that, or the exercises, rather than this file. -->\n\
+         \n\
+         # Playground Links\n\
+         \n\
+         These open an exercise in the Rust Playground, to try it out without\n\
+         downloading anything. The playground doesn't have `require_lifetimes`,\n\
+         so it's been taken out: the compiler still checks your lifetimes, but\n\
+         it won't insist that you write them out.\n\
+         \n"
+    );
+    let mut too_big = Vec::new();
+    for chapter in manifest.chapter.iter().filter(|chapter| chapter.playground) {
+        match link(&source(root, chapter)?) {
+            Ok(link) => page.push_str(&format!(" - [{}]({link})\n", chapter.name)),
+            Err(length) => {
+                page.push_str(&format!(
+                    " - {} is too big for a link ({length} bytes, over {LIMIT}), so it has\n   \
+                     to be worked on locally.\n",
+                    chapter.name
+                ));
+                too_big.push(chapter.name.clone());
+            }
+        }
+    }
+    Ok((page, too_big))
+}
+
+/// Given the workspace's root, write the page of links, and report each
+/// exercise that was too big for one.
+pub fn write(root: &Path) -> Result<(), String> {
+    let (page, too_big) = page(root)?;
+    let path = root.join(OUTPUT);
+    fs::write(&path, page)
+        .map_err(|error| format!("couldn't write {}: {error}", path.display()))?;
+
+    for name in too_big {
+        eprintln!("{name} is too big for a playground link, so it's not linked to");
+    }
+    eprintln!("wrote {}", path.display());
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|error| format!("couldn't read {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::snippets::workspace_root;
+
+    #[test]
+    fn strips_the_attribute() {
+        let source = "\
+use require_lifetimes::require_lifetimes;
+
+#[require_lifetimes(!)]
+pub fn first<'a>(x: &'a str) -> &'a str {
+    x
+}
+
+#[derive(Debug)] #[require_lifetimes] pub struct Wrapper<'a>(&'a str);
+
+    #[require_lifetimes::require_lifetimes(descriptive_names)]
+    fn nested() {}
+";
+        assert_eq!(
+            strip_attributes(source),
+            concat!(
+                "\n",
+                "pub fn first<'a>(x: &'a str) -> &'a str {\n",
+                "    x\n",
+                "}\n",
+                "\n",
+                "#[derive(Debug)] pub struct Wrapper<'a>(&'a str);\n",
+                "\n",
+                "    fn nested() {}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_other_attributes() {
+        let source = "#[derive(Debug, PartialEq)]\n#[allow(dead_code)]\nstruct Unit;\n";
+        assert_eq!(strip_attributes(source), source);
+        // The brackets of an array inside it don't end it early.
+        assert_eq!(
+            strip_attributes_from("#[require_lifetimes([1])] fn f() {}"),
+            "fn f() {}"
+        );
+    }
+
+    #[test]
+    fn inlines_includes() {
+        let directory =
+            std::env::temp_dir().join(format!("lifetimekata-playground-{}", std::process::id()));
+        fs::create_dir_all(directory.join("src")).unwrap();
+        fs::write(
+            directory.join("shared.rs"),
+            "use super::*;\n\n#[test]\nfn t() {}\n",
+        )
+        .unwrap();
+
+        let source = "fn f() {}\n\nmod tests {\n    include!(\"../shared.rs\");\n}\n";
+        assert_eq!(
+            inline_includes(source, &directory.join("src")).unwrap(),
+            "fn f() {}\n\nmod tests {\n    use super::*;\n\n    #[test]\n    fn t() {}\n}\n"
+        );
+        assert!(inline_includes("include!(\"missing.rs\");", &directory).is_err());
+    }
+
+    #[test]
+    fn encodes_for_a_url() {
+        assert_eq!(encode("fn f<'a>() {}"), "fn%20f%3C%27a%3E%28%29%20%7B%7D");
+        assert_eq!(encode("a-b.c_d~e\n&="), "a-b.c_d~e%0A%26%3D");
+        assert_eq!(encode("é"), "%C3%A9");
+    }
+
+    #[test]
+    fn links_have_a_limit() {
+        let code = "fn main() {}";
+        assert_eq!(
+            link(code),
+            Ok(format!("{PLAYGROUND}fn%20main%28%29%20%7B%7D"))
+        );
+
+        // Each space is three bytes once it's encoded.
+        let spaces = (LIMIT - PLAYGROUND.len()) / 3;
+        assert!(link(&" ".repeat(spaces)).is_ok());
+        assert_eq!(
+            link(&" ".repeat(spaces + 1)),
+            Err(PLAYGROUND.len() + (spaces + 1) * 3)
+        );
+    }
+
+    /// The links have to be to the exercises as they are. If this fails,
+    /// run `cargo xtask playground-links`.
+    #[test]
+    fn book_is_up_to_date() {
+        let root = workspace_root();
+        let written = read(&root.join(OUTPUT)).unwrap_or_default();
+        assert!(
+            written == page(&root).unwrap().0,
+            "{OUTPUT} is out of date; run `cargo xtask playground-links`"
+        );
+    }
+}
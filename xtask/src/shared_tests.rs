@@ -0,0 +1,176 @@
+//! This checks that each chapter's shared tests are included by both its
+//! exercise and its solution, so the two really do run the same tests.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the chapters are, from the workspace's root.
+pub const EXERCISES: &str = "exercises";
+
+/// What a chapter's shared tests are called, in its directory.
+pub const SHARED: &str = "shared_tests.rs";
+
+/// Given the directory of chapters, return what's wrong: for each chapter
+/// with shared tests, whether its exercise or its solution doesn't include
+/// them.
+pub fn problems(exercises: &Path) -> Result<Vec<String>, String> {
+    let mut chapters: Vec<PathBuf> = fs::read_dir(exercises)
+        .map_err(|error| format!("couldn't read {}: {error}", exercises.display()))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|chapter| chapter.join(SHARED).is_file())
+        .collect();
+    chapters.sort();
+
+    let mut problems = Vec::new();
+    for chapter in chapters {
+        let shared = chapter.join(SHARED);
+        for side in ["exercise", "solutions"] {
+            if !includes(&chapter.join(side).join("src"), &shared)? {
+                problems.push(format!(
+                    "{}/src doesn't include {}",
+                    chapter.join(side).display(),
+                    shared.display()
+                ));
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// Given the directory of chapters, fail if any of them has shared tests
+/// that its exercise or its solution doesn't include.
+pub fn check(exercises: &Path) -> Result<(), String> {
+    let problems = problems(exercises)?;
+    if problems.is_empty() {
+        eprintln!("every exercise and solution includes its shared tests");
+        Ok(())
+    } else {
+        Err(problems.join("\n"))
+    }
+}
+
+/// Given a crate's `src` and a file, return whether any of the crate's
+/// source files includes the file with `include!`.
+fn includes(src: &Path, file: &Path) -> Result<bool, String> {
+    let Ok(file) = file.canonicalize() else {
+        return Ok(false);
+    };
+    let Ok(entries) = fs::read_dir(src) else {
+        return Ok(false);
+    };
+
+    for entry in entries {
+        let path = entry
+            .map_err(|error| format!("couldn't read {}: {error}", src.display()))?
+            .path();
+        if path.is_dir() {
+            if includes(&path, &file)? {
+                return Ok(true);
+            }
+            continue;
+        }
+        if path.extension().is_none_or(|extension| extension != "rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&path)
+            .map_err(|error| format!("couldn't read {}: {error}", path.display()))?;
+        // `include!` paths are from the file they're in.
+        let mut included = included(&source)
+            .into_iter()
+            .filter_map(|included| path.parent()?.join(included).canonicalize().ok());
+        if included.any(|included| included == file) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Given some source, return the path of everything it includes.
+fn included(source: &str) -> Vec<&str> {
+    source
+        .split("include!(")
+        .skip(1)
+        .filter_map(|after| after.trim_start().strip_prefix('"')?.split('"').next())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::snippets::workspace_root;
+
+    /// Given a name for a test, and some files, write them into a directory
+    /// of chapters for it, and return the directory.
+    fn fixture(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let exercises = std::env::temp_dir().join(format!("xtask-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&exercises);
+        for (file, contents) in files {
+            let path = exercises.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        exercises
+    }
+
+    const INCLUDE: &str =
+        "#[cfg(test)]\nmod tests {\n    include!(\"../../shared_tests.rs\");\n}\n";
+
+    #[test]
+    fn finds_the_includes() {
+        assert_eq!(
+            included("mod a { include!(\"../x.rs\"); }\ninclude!( \"y.rs\" );\ninclude!(concat!(\"z\"));"),
+            ["../x.rs", "y.rs"]
+        );
+    }
+
+    #[test]
+    fn passes_when_both_include_them() {
+        let exercises = fixture(
+            "both",
+            &[
+                ("01_one/shared_tests.rs", "#[test]\nfn one() {}\n"),
+                ("01_one/exercise/src/lib.rs", INCLUDE),
+                ("01_one/solutions/src/main.rs", INCLUDE),
+                // A chapter without shared tests doesn't need to include any.
+                ("02_two/exercise/src/lib.rs", ""),
+            ],
+        );
+        assert_eq!(problems(&exercises).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn fails_when_one_side_does_not() {
+        let exercises = fixture(
+            "one_side",
+            &[
+                ("01_one/shared_tests.rs", "#[test]\nfn one() {}\n"),
+                ("01_one/exercise/src/lib.rs", INCLUDE),
+                (
+                    "01_one/solutions/src/main.rs",
+                    "#[cfg(test)]\nmod tests {}\n",
+                ),
+                ("02_two/shared_tests.rs", "#[test]\nfn two() {}\n"),
+                // This includes the first chapter's tests, not its own.
+                (
+                    "02_two/exercise/src/lib.rs",
+                    "include!(\"../../../01_one/shared_tests.rs\");\n",
+                ),
+                (
+                    "02_two/solutions/src/deep/mod.rs",
+                    "include!(\"../../../shared_tests.rs\");\n",
+                ),
+            ],
+        );
+        let problems = problems(&exercises).unwrap();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].contains("01_one/solutions/src"), "{problems:?}");
+        assert!(problems[1].contains("02_two/exercise/src"), "{problems:?}");
+    }
+
+    #[test]
+    fn kata_includes_its_shared_tests() {
+        let exercises = workspace_root().join(EXERCISES);
+        assert_eq!(problems(&exercises).unwrap(), Vec::<String>::new());
+        assert!(exercises.join("08_finale").join(SHARED).is_file());
+    }
+}
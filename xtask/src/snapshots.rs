@@ -0,0 +1,263 @@
+//! This keeps a snapshot of each exercise's `src` as the repository has it,
+//! in `exercises/.snapshots`, for `cargo run -- reset` to put it back to. The
+//! snapshots are of what's in git (its index, so what's committed along with
+//! anything staged), rather than what's on disk, which is where someone
+//! working through the kata makes their changes. They're not the `starter/`s
+//! some chapters have, which are only there for their errors.
+
+use crate::check_all::MANIFEST;
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Where the copies go, from the workspace's root, each in a directory named
+/// after its chapter's.
+pub const SNAPSHOTS: &str = "exercises/.snapshots";
+
+/// The parts of the manifest this needs; the runner reads the rest.
+#[derive(Deserialize)]
+struct Manifest {
+    chapter: Vec<Chapter>,
+}
+
+#[derive(Deserialize)]
+struct Chapter {
+    path: String,
+    package: Option<String>,
+}
+
+/// Some files, by name from the directory they're in.
+type Files = BTreeMap<String, Vec<u8>>;
+
+/// Given the workspace's root, return each chapter with an exercise, by its
+/// directory's name, and where its `src` is, from the root.
+fn exercises(root: &Path) -> Result<Vec<(String, String)>, String> {
+    let text = fs::read_to_string(root.join(MANIFEST))
+        .map_err(|error| format!("couldn't read {MANIFEST}: {error}"))?;
+    let manifest: Manifest = basic_toml::from_str(&text)
+        .map_err(|error| format!("couldn't parse {MANIFEST}: {error}"))?;
+    Ok(manifest
+        .chapter
+        .into_iter()
+        .filter(|chapter| chapter.package.is_some())
+        .map(|chapter| {
+            let directory = chapter.path.rsplit('/').next().unwrap_or_default();
+            (
+                directory.to_string(),
+                format!("{}/exercise/src", chapter.path),
+            )
+        })
+        .collect())
+}
+
+/// Given the workspace's root and a directory in it, return the files in it
+/// as git's index has them.
+fn committed(root: &Path, directory: &str) -> Result<Files, String> {
+    let listed = git(root, &["ls-files", "-z", "--", directory])?;
+    let mut files = Files::new();
+    for path in String::from_utf8_lossy(&listed).split_terminator('\0') {
+        let contents = git(root, &["show", &format!(":{path}")])?;
+        let name = path[directory.len()..].trim_start_matches('/');
+        files.insert(name.to_string(), contents);
+    }
+    if files.is_empty() {
+        return Err(format!("git doesn't have anything in {directory}"));
+    }
+    Ok(files)
+}
+
+/// Given a directory, return the files in it (and in the directories in it),
+/// or none, if it isn't there.
+fn on_disk(directory: &Path) -> Result<Files, String> {
+    let mut files = Files::new();
+    if directory.is_dir() {
+        add_files(directory, directory, &mut files)?;
+    }
+    Ok(files)
+}
+
+/// Given the directory being read and one in it, add every file in the
+/// latter to `files`, by name from the former.
+fn add_files(base: &Path, directory: &Path, files: &mut Files) -> Result<(), String> {
+    let entries = fs::read_dir(directory)
+        .map_err(|error| format!("couldn't read {}: {error}", directory.display()))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|error| format!("couldn't read {}: {error}", directory.display()))?
+            .path();
+        if path.is_dir() {
+            add_files(base, &path, files)?;
+        } else {
+            let contents = fs::read(&path)
+                .map_err(|error| format!("couldn't read {}: {error}", path.display()))?;
+            let name = path.strip_prefix(base).unwrap_or(&path);
+            files.insert(name.to_string_lossy().replace('\\', "/"), contents);
+        }
+    }
+    Ok(())
+}
+
+/// Given a snapshot's name, what its exercise has in git, and what the
+/// snapshot has, return how they differ, one file to a line.
+fn differences(name: &str, committed: &Files, snapshot: &Files) -> Vec<String> {
+    let mut differences = Vec::new();
+    for (file, contents) in committed {
+        match snapshot.get(file) {
+            None => differences.push(format!("{name} is missing {file}")),
+            Some(copy) if copy != contents => {
+                differences.push(format!("{name}'s {file} differs from the exercise's"))
+            }
+            Some(_) => {}
+        }
+    }
+    for file in snapshot
+        .keys()
+        .filter(|file| !committed.contains_key(*file))
+    {
+        differences.push(format!("{name} has {file}, which the exercise doesn't"));
+    }
+    differences
+}
+
+/// Given the workspace's root, write every exercise's snapshot, from what git
+/// has of it, replacing whatever was there before.
+pub fn write(root: &Path) -> Result<(), String> {
+    let exercises = exercises(root)?;
+    let snapshots = root.join(SNAPSHOTS);
+    if snapshots.exists() {
+        fs::remove_dir_all(&snapshots)
+            .map_err(|error| format!("couldn't remove {}: {error}", snapshots.display()))?;
+    }
+    for (name, src) in &exercises {
+        for (file, contents) in committed(root, src)? {
+            let path = snapshots.join(name).join(file);
+            fs::create_dir_all(path.parent().unwrap())
+                .and_then(|()| fs::write(&path, contents))
+                .map_err(|error| format!("couldn't write {}: {error}", path.display()))?;
+        }
+    }
+    eprintln!("wrote {} snapshots in {SNAPSHOTS}", exercises.len());
+    Ok(())
+}
+
+/// Given the workspace's root, return how the snapshots differ from what git
+/// has of the exercises, including any snapshot with no exercise.
+pub fn problems(root: &Path) -> Result<Vec<String>, String> {
+    let exercises = exercises(root)?;
+    let mut problems = Vec::new();
+    for (name, src) in &exercises {
+        let snapshot = on_disk(&root.join(SNAPSHOTS).join(name))?;
+        if snapshot.is_empty() {
+            problems.push(format!("{name} has no snapshot"));
+        } else {
+            problems.extend(differences(name, &committed(root, src)?, &snapshot));
+        }
+    }
+
+    if let Ok(entries) = fs::read_dir(root.join(SNAPSHOTS)) {
+        let mut strays: Vec<String> = entries
+            .filter_map(|entry| Some(entry.ok()?.file_name().to_string_lossy().into_owned()))
+            .filter(|name| !exercises.iter().any(|(exercise, _)| exercise == name))
+            .collect();
+        strays.sort();
+        for stray in strays {
+            problems.push(format!("{SNAPSHOTS}/{stray} isn't any exercise's snapshot"));
+        }
+    }
+    Ok(problems)
+}
+
+/// Given the workspace's root, fail if any snapshot has drifted from its
+/// exercise.
+pub fn check(root: &Path) -> Result<(), String> {
+    let problems = problems(root)?;
+    if problems.is_empty() {
+        eprintln!("every snapshot matches its exercise");
+        Ok(())
+    } else {
+        Err(format!(
+            "{}\nrun `cargo xtask snapshots` once the exercises are as they should be committed",
+            problems.join("\n")
+        ))
+    }
+}
+
+/// Given the workspace's root, run git in it, and return what it printed.
+fn git(root: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|error| format!("couldn't run git: {error}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::snippets::workspace_root;
+
+    fn files(files: &[(&str, &str)]) -> Files {
+        files
+            .iter()
+            .map(|(name, contents)| (name.to_string(), contents.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn names_each_difference() {
+        let committed = files(&[
+            ("lib.rs", "mod a;\n"),
+            ("a.rs", "fn a() {}\n"),
+            ("b.rs", ""),
+        ]);
+        assert!(differences("02_two", &committed, &committed).is_empty());
+
+        let snapshot = files(&[
+            ("lib.rs", "mod a;\n"),
+            ("a.rs", "fn b() {}\n"),
+            ("c.rs", ""),
+        ]);
+        assert_eq!(
+            differences("02_two", &committed, &snapshot),
+            [
+                "02_two's a.rs differs from the exercise's",
+                "02_two is missing b.rs",
+                "02_two has c.rs, which the exercise doesn't",
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_nested_files() {
+        let directory =
+            std::env::temp_dir().join(format!("lifetimekata-snapshots-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(directory.join("engine")).unwrap();
+        fs::write(directory.join("lib.rs"), "mod engine;\n").unwrap();
+        fs::write(directory.join("engine/mod.rs"), "").unwrap();
+
+        assert_eq!(
+            on_disk(&directory).unwrap(),
+            files(&[("engine/mod.rs", ""), ("lib.rs", "mod engine;\n")])
+        );
+        assert!(on_disk(&directory.join("missing")).unwrap().is_empty());
+    }
+
+    /// The snapshots have to be the exercises as git has them. If this fails,
+    /// run `cargo xtask snapshots`.
+    #[test]
+    fn snapshots_are_up_to_date() {
+        let problems = problems(&workspace_root()).unwrap();
+        assert!(problems.is_empty(), "{}", problems.join("\n"));
+    }
+}
@@ -0,0 +1,146 @@
+//! This turns `require_lifetimes`' UI tests into markdown snippets for the
+//! book: each test that fails to compile, with the errors it expects. The
+//! book includes them, so what it shows is what the macro really says.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the UI tests are, from the workspace's root.
+pub const CORPUS: &str = "require_lifetimes/ui_tests";
+
+/// Where the snippets go, from the workspace's root.
+pub const OUTPUT: &str = "book/src/generated";
+
+/// Return the workspace's root, which is the directory above this crate.
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask is in the workspace")
+        .to_path_buf()
+}
+
+/// Given a directory of UI tests, return a snippet for each `_err.rs` test
+/// that has a `.stderr` next to it, as its file name and contents, in order
+/// of name.
+pub fn snippets(corpus: &Path) -> Result<Vec<(String, String)>, String> {
+    let entries = fs::read_dir(corpus)
+        .map_err(|error| format!("couldn't read {}: {error}", corpus.display()))?;
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry =
+            entry.map_err(|error| format!("couldn't read {}: {error}", corpus.display()))?;
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if let Some(name) = file_name.strip_suffix("_err.rs") {
+            names.push(format!("{name}_err"));
+        }
+    }
+    names.sort();
+
+    let mut snippets = Vec::new();
+    for name in names {
+        let stderr_path = corpus.join(format!("{name}.stderr"));
+        if !stderr_path.exists() {
+            continue;
+        }
+        let source = read(&corpus.join(format!("{name}.rs")))?;
+        let stderr = read(&stderr_path)?;
+        snippets.push((format!("{name}.md"), snippet(&name, &source, &stderr)));
+    }
+    Ok(snippets)
+}
+
+/// Given a test's name, its source and the errors it expects, return the
+/// snippet for it: the source, then the errors, with the test directory
+/// taken out of their paths.
+pub fn snippet(name: &str, source: &str, stderr: &str) -> String {
+    let errors: Vec<_> = stderr
+        .trim_end()
+        .lines()
+        .map(|line| line.replacen("--> ui_tests/", "--> ", 1))
+        .collect();
+
+    format!(
+        "<!-- Generated by `cargo xtask book-snippets` from {CORPUS}/{name}.rs; \
+         change that test rather than this file. -->\n\
+         \n\
+         ```rust,ignore\n\
+         {}\n\
+         ```\n\
+         \n\
+         ```text\n\
+         {}\n\
+         ```\n",
+        source.trim_end(),
+        errors.join("\n"),
+    )
+}
+
+/// Given a directory of UI tests and one for the snippets, write a snippet
+/// for each test, and remove any snippet whose test is gone.
+pub fn write(corpus: &Path, output: &Path) -> Result<(), String> {
+    let snippets = snippets(corpus)?;
+    fs::create_dir_all(output)
+        .map_err(|error| format!("couldn't create {}: {error}", output.display()))?;
+
+    for entry in fs::read_dir(output)
+        .map_err(|error| format!("couldn't read {}: {error}", output.display()))?
+    {
+        let path = entry
+            .map_err(|error| format!("couldn't read {}: {error}", output.display()))?
+            .path();
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let stale = path.extension().is_some_and(|extension| extension == "md")
+            && !snippets.iter().any(|(name, _)| *name == file_name);
+        if stale {
+            fs::remove_file(&path)
+                .map_err(|error| format!("couldn't remove {}: {error}", path.display()))?;
+        }
+    }
+    for (name, contents) in &snippets {
+        let path = output.join(name);
+        fs::write(&path, contents)
+            .map_err(|error| format!("couldn't write {}: {error}", path.display()))?;
+    }
+
+    eprintln!("wrote {} snippets to {}", snippets.len(), output.display());
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|error| format!("couldn't read {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_golden_file() {
+        let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("golden");
+        let snippets = snippets(&golden).unwrap();
+        let names: Vec<_> = snippets.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["longest_err.md"]);
+        assert_eq!(snippets[0].1, read(&golden.join("longest_err.md")).unwrap());
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let corpus = workspace_root().join(CORPUS);
+        assert_eq!(snippets(&corpus).unwrap(), snippets(&corpus).unwrap());
+    }
+
+    /// The snippets in the book have to match the tests, so they can't drift
+    /// from what the macro says. If this fails, run `cargo xtask
+    /// book-snippets`.
+    #[test]
+    fn book_is_up_to_date() {
+        let root = workspace_root();
+        for (name, contents) in snippets(&root.join(CORPUS)).unwrap() {
+            let written = read(&root.join(OUTPUT).join(&name)).unwrap_or_default();
+            assert!(
+                written == contents,
+                "{OUTPUT}/{name} is out of date; run `cargo xtask book-snippets`"
+            );
+        }
+    }
+}